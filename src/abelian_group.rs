@@ -0,0 +1,147 @@
+//
+// Finitely generated abelian groups given by generators and relations, decomposed into cyclic
+// groups by the Smith normal form
+//
+
+use std::fmt::Display;
+
+use crate::matrix::Matrix;
+use crate::std_impls::factor;
+
+// MARK: Decomposition
+
+/// The abelian group with generators g_1, ..., g_m subject to relations r_1 g_1 + ... + r_m g_m = 0,
+/// decomposed as Z/(d_1) x ... x Z/(d_k) x Z^rank with 1 < d_1 | d_2 | ... | d_k, the invariant
+/// factors, together with the isomorphism to that product.
+///
+/// Elements are written as integer coordinates, x_1 g_1 + ... + x_m g_m in the presentation, and
+/// as a residue modulo each d_i followed by rank integers in the product.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FinitelyGeneratedAbelianGroup {
+	invariant_factors: Vec<i64>,
+	rank: usize,
+
+	/// The change of coordinates from the generators to the cyclic factors
+	to_cyclic: Matrix<i64>,
+
+	/// The change of coordinates from the cyclic factors back to the generators
+	from_cyclic: Matrix<i64>
+}
+
+impl FinitelyGeneratedAbelianGroup {
+
+	/// The group generated by one generator for each column of the matrix, with a relation for
+	/// each row. This is also the quotient of Z^m by the lattice spanned by the rows.
+	///
+	/// With D = U R V the Smith normal form of the relations, the relations in the coordinates
+	/// x V are the rows of D, so those coordinates modulo the diagonal of D are the isomorphism,
+	/// leaving out the trivial factors Z/(1).
+	pub fn from_relations(relations: &Matrix<i64>) -> FinitelyGeneratedAbelianGroup {
+		let m = relations.cols();
+		let (_, d, v) = relations.smith_normal_form();
+
+		// V is unimodular, so its Hermite normal form is the identity and the transformation reaching
+		// it is the inverse
+		let (v_inverse, _) = v.hermite_normal_form();
+
+		let diagonal: Vec<i64> = (0..m).map(|i| if i < d.rows() { d[(i, i)] } else { 0 }).collect();
+		let kept: Vec<usize> = (0..m).filter(|&i| diagonal[i] != 1).collect();
+
+		FinitelyGeneratedAbelianGroup {
+			invariant_factors: kept.iter().map(|&i| diagonal[i]).filter(|&d| d != 0).collect(),
+			rank: kept.iter().filter(|&&i| diagonal[i] == 0).count(),
+			to_cyclic: Matrix::from_fn(m, kept.len(), |i, j| v[(i, kept[j])]),
+			from_cyclic: Matrix::from_fn(kept.len(), m, |i, j| v_inverse[(kept[i], j)])
+		}
+	}
+
+	/// The product Z/(n_1) x ... x Z/(n_k) of cyclic groups of the given orders, where an order of
+	/// zero gives a factor Z
+	pub fn cyclic_product(orders: &[i64]) -> FinitelyGeneratedAbelianGroup {
+		let n = orders.len();
+		FinitelyGeneratedAbelianGroup::from_relations(&Matrix::from_fn(n, n, |i, j| if i == j { orders[i] } else { 0 }))
+	}
+
+	/// The invariant factors d_1 | d_2 | ... | d_k, all greater than one
+	pub fn invariant_factors(&self) -> &[i64] {
+		&self.invariant_factors
+	}
+
+	/// The prime powers p^e with the group the product of the Z/(p^e) and a free part, in
+	/// increasing order, found by factoring the invariant factors
+	pub fn elementary_divisors(&self) -> Vec<i64> {
+		let mut divisors: Vec<i64> = self.invariant_factors.iter()
+			.flat_map(|&d| factor(d).into_iter().map(|(p, e)| p.pow(e)))
+			.collect();
+		divisors.sort();
+		divisors
+	}
+
+	/// The rank of the free part
+	pub fn rank(&self) -> usize {
+		self.rank
+	}
+
+	/// The number of elements, or None if the group is infinite
+	pub fn order(&self) -> Option<u128> {
+		if self.rank > 0 {
+			return None
+		}
+		Some(self.invariant_factors.iter().map(|&d| d as u128).product())
+	}
+
+	/// Whether or not the group is cyclic, meaning it has at most one factor
+	pub fn is_cyclic(&self) -> bool {
+		self.invariant_factors.len() + self.rank <= 1
+	}
+
+	/// The image of x_1 g_1 + ... + x_m g_m in the product of cyclic groups, with each residue
+	/// modulo d_i in [0, d_i)
+	pub fn to_cyclic(&self, x: &[i64]) -> Vec<i64> {
+		if x.len() != self.to_cyclic.rows() {
+			panic!("Expected {} coordinates, got {}", self.to_cyclic.rows(), x.len())
+		}
+
+		(0..self.to_cyclic.cols()).map(|j| {
+			let y = (0..x.len()).map(|i| x[i] * self.to_cyclic[(i, j)]).sum::<i64>();
+			match self.invariant_factors.get(j) {
+				Some(d) => y.rem_euclid(*d),
+				None => y
+			}
+		}).collect()
+	}
+
+	/// Coordinates in the generators of an element given in the product of cyclic groups, so
+	/// that `to_cyclic` takes them back to y
+	pub fn from_cyclic(&self, y: &[i64]) -> Vec<i64> {
+		if y.len() != self.from_cyclic.rows() {
+			panic!("Expected {} coordinates, got {}", self.from_cyclic.rows(), y.len())
+		}
+
+		(0..self.from_cyclic.cols()).map(|j| (0..y.len()).map(|i| y[i] * self.from_cyclic[(i, j)]).sum()).collect()
+	}
+
+	/// Whether or not x_1 g_1 + ... + x_m g_m is zero, meaning x is in the span of the relations
+	pub fn is_zero(&self, x: &[i64]) -> bool {
+		self.to_cyclic(x).iter().all(|&y| y == 0)
+	}
+
+}
+
+/// Writes the group like Z/(2) x Z/(12) x Z^2, or 0 when it is trivial
+impl Display for FinitelyGeneratedAbelianGroup {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut factors: Vec<String> = self.invariant_factors.iter().map(|d| format!("Z/({})", d)).collect();
+		match self.rank {
+			0 => (),
+			1 => factors.push("Z".to_string()),
+			r => factors.push(format!("Z^{}", r))
+		}
+
+		if factors.is_empty() {
+			write!(f, "0")
+		} else {
+			write!(f, "{}", factors.join(" x "))
+		}
+	}
+}
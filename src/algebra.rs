@@ -2,41 +2,279 @@
 // A collection of primitive algebraic structures
 //
 
-use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
 
-// MARK: Group
+use crate::error::AlgebraError;
+use crate::finite_set::FiniteSet;
+
+pub mod laws;
+
+// MARK: Monoid
 
-pub trait Group: Debug + Clone + PartialEq + Sized + Mul<Self> + MulAssign<Self> + Mul<Output = Self> + Div<Self> + DivAssign<Self> + Div<Output = Self> {
-	
-	/// The group identity element
+/// A set with an associative multiplication and an identity element
+pub trait Monoid: Debug + Clone + PartialEq + Sized + Mul<Self> + MulAssign<Self> + Mul<Output = Self> {
+
+	/// The identity element
 	fn identity() -> Self;
 
+}
+
+// MARK: Group
+
+pub trait Group: Monoid + Div<Self> + DivAssign<Self> + Div<Output = Self> {
+
 	/// The inverse of a group element
 	fn inverse(&self) -> Self;
 
+	/// A group element raised to an integer power
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
+
+		let mut power = Self::identity();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base.clone()
+			}
+			base *= base.clone();
+			e >>= 1;
+		}
+
+		power
+	}
+
 }
 
-// MARK: Ring
+/// A group with finitely many elements
+pub trait FiniteGroup: Group {
 
-/// An algebraic Ring
-pub trait Ring: Debug + Clone + PartialEq + Sized + Add<Self> + AddAssign<Self> + Neg + Sub<Self> + SubAssign<Self> + Mul<Self> + MulAssign<Self> + Mul<Output = Self> + Add<Output = Self> + Neg<Output = Self> + Sub<Output = Self> {
+	/// The number of elements in this group
+	fn order() -> usize;
+
+	/// Every element of this group, each exactly once
+	fn elements() -> impl Iterator<Item = Self>;
+
+	/// The order of this element: the smallest k > 0 with self^k = identity
+	fn element_order(&self) -> usize {
+		let mut k = 1;
+		let mut power = self.clone();
+
+		while power != Self::identity() {
+			power *= self.clone();
+			k += 1;
+		}
 
-	/// The multiplicative identity of this ring
+		k
+	}
+
+	/// The cyclic subgroup generated by this element, as its powers 1, g, g^2, ..., g^(k-1) for k
+	/// the order of g
+	fn cyclic_subgroup(&self) -> Vec<Self> {
+		let mut subgroup = vec![Self::identity()];
+		let mut power = self.clone();
+
+		while power != Self::identity() {
+			subgroup.push(power.clone());
+			power *= self.clone();
+		}
+
+		subgroup
+	}
+
+	/// Every cyclic subgroup of the group exactly once, each given by `cyclic_subgroup` of the first
+	/// of its generators among `elements`, starting with the trivial subgroup if the identity
+	/// comes first
+	fn cyclic_subgroups() -> Vec<Vec<Self>> {
+		let mut subgroups: Vec<Vec<Self>> = Vec::new();
+
+		for g in Self::elements() {
+			// <g> is contained in any subgroup holding g, so it is one of those of the same size
+			let subgroup = g.cyclic_subgroup();
+			if !subgroups.iter().any(|h| h.len() == subgroup.len() && h.contains(&g)) {
+				subgroups.push(subgroup);
+			}
+		}
+
+		subgroups
+	}
+
+	/// Every element which commutes with this one
+	fn centralizer(&self) -> Vec<Self> {
+		Self::elements().filter(|g| g.clone() * self.clone() == self.clone() * g.clone()).collect()
+	}
+
+	/// Every element which commutes with every element of the group
+	fn center() -> Vec<Self> {
+		let elements: Vec<Self> = Self::elements().collect();
+		elements.iter().filter(|z| {
+			elements.iter().all(|g| g.clone() * (*z).clone() == (*z).clone() * g.clone())
+		}).cloned().collect()
+	}
+
+	/// The conjugacy classes of the group, starting with the class of the identity
+	fn conjugacy_classes() -> Vec<Vec<Self>> {
+		let elements: Vec<Self> = Self::elements().collect();
+		let mut classes: Vec<Vec<Self>> = vec![vec![Self::identity()]];
+
+		for g in elements.iter() {
+			if classes.iter().any(|class| class.contains(g)) {
+				continue
+			}
+
+			let mut class = Vec::new();
+			for x in elements.iter() {
+				let conjugate = x.clone() * g.clone() * x.inverse();
+				if !class.contains(&conjugate) {
+					class.push(conjugate);
+				}
+			}
+			classes.push(class);
+		}
+
+		classes
+	}
+
+	/// The sizes of the conjugacy classes in increasing order, which sum to the order of the group.
+	///
+	/// The classes of size 1 are exactly the elements of the center.
+	fn class_equation() -> Vec<usize> {
+		let mut sizes: Vec<usize> = Self::conjugacy_classes().iter().map(|class| class.len()).collect();
+		sizes.sort();
+		sizes
+	}
+
+}
+
+/// A group whose multiplication is commutative
+pub trait AbelianGroup: Group { /* :) */ }
+
+// MARK: Semiring
+
+/// A semiring, which has addition and multiplication but not necessarily subtraction
+pub trait Semiring: Debug + Clone + PartialEq + Sized + Add<Self> + AddAssign<Self> + Mul<Self> + MulAssign<Self> + Add<Output = Self> + Mul<Output = Self> {
+
+	/// The multiplicative identity of this semiring
 	fn one() -> Self;
 
-	/// The additive identity of this ring/
+	/// The additive identity of this semiring
 	fn zero() -> Self;
 
-	/// Whether or not this ring element is zero
+	/// Whether or not this element is zero
 	fn is_zero(&self) -> bool;
 
+	/// Adds a borrowed element to this one in place.
+	/// 
+	/// The default clones `rhs`; types that are expensive to clone should override this.
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		*self += rhs.clone()
+	}
+
+	/// Multiplies this element by a borrowed element in place.
+	/// 
+	/// The default clones `rhs`; types that are expensive to clone should override this.
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self *= rhs.clone()
+	}
+
+	/// The sum of two borrowed elements
+	fn add_ref(&self, rhs: &Self) -> Self {
+		let mut sum = self.clone();
+		sum.add_assign_ref(rhs);
+		sum
+	}
+
+	/// The product of two borrowed elements
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		let mut product = self.clone();
+		product.mul_assign_ref(rhs);
+		product
+	}
+}
+
+// MARK: Ring
+
+/// An algebraic Ring
+pub trait Ring: Semiring + Neg + Sub<Self> + SubAssign<Self> + Neg<Output = Self> + Sub<Output = Self> {
+
 	/// A ring element raised to a power/
    	fn power(&self, n: i64) -> Self;
+
+	/// Subtracts a borrowed element from this one in place.
+	/// 
+	/// The default clones `rhs`; types that are expensive to clone should override this.
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		*self -= rhs.clone()
+	}
+
+	/// The difference of two borrowed elements
+	fn sub_ref(&self, rhs: &Self) -> Self {
+		let mut difference = self.clone();
+		difference.sub_assign_ref(rhs);
+		difference
+	}
+}
+
+/// A ring whose multiplication is commutative
+pub trait CommutativeRing: Ring { /* :) */ }
+
+/// A nonzero commutative ring with no zero divisors
+pub trait IntegralDomain: CommutativeRing {
+
+	/// The q with self = divisor * q, which is unique when it exists since there are no zero
+	/// divisors, or None if divisor does not divide self, or is zero
+	fn divide_exact(&self, divisor: &Self) -> Option<Self>;
+
 }
 
+/// An integral domain where every nonzero nonunit factors uniquely into irreducibles,
+/// so any two elements have a greatest common divisor
+pub trait UniqueFactorizationDomain: IntegralDomain {
+
+	/// A greatest common divisor of self and other, defined up to multiplication by a unit
+	fn gcd(&self, other: &Self) -> Self;
+
+}
+
+/// An integral domain where every ideal is generated by a single element
+pub trait PrincipalIdealDomain: UniqueFactorizationDomain { /* :) */ }
+
 /// A partially ordered ring
-pub trait PoRing: Ring + PartialOrd { /* :) */ }
+pub trait PoRing: Ring + PartialOrd {
+
+	/// Whether or not this element is greater than zero
+	fn is_positive(&self) -> bool {
+		*self > Self::zero()
+	}
+
+	/// Whether or not this element is less than zero
+	fn is_negative(&self) -> bool {
+		*self < Self::zero()
+	}
+
+	/// The absolute value, this element or its negative, whichever is not negative
+	fn abs(&self) -> Self {
+		if self.is_negative() { -self.clone() } else { self.clone() }
+	}
+
+	/// One, minus one, or zero, as this element is positive, negative, or neither
+	fn signum(&self) -> Self {
+		if self.is_positive() {
+			Self::one()
+		} else if self.is_negative() {
+			-Self::one()
+		} else {
+			Self::zero()
+		}
+	}
+
+}
 
 /// A totally ordered ring
 pub trait OrderedRing: PoRing + Ord { /* :) */ }
@@ -47,14 +285,178 @@ pub trait OrderedRing: PoRing + Ord { /* :) */ }
  * A field, which is a ring where every nonzero element has a multiplicative inverse
  */
 pub trait Field: Ring + Div + DivAssign + Div<Output = Self> {
+
+	/// The multiplicative inverse of this element, which may panic if there is none
 	fn inverse(&self) -> Self;
+
+	/// The multiplicative inverse of this element, or None if it has none
+	fn try_inverse(&self) -> Option<Self> {
+		if self.is_zero() {
+			None
+		} else {
+			Some(self.inverse())
+		}
+	}
+
+	/// Divides this element by a borrowed element in place.
+	///
+	/// The default clones `rhs`; types that are expensive to clone should override this.
+	fn div_assign_ref(&mut self, rhs: &Self) {
+		*self /= rhs.clone()
+	}
+
+	/// The quotient of two borrowed elements
+	fn div_ref(&self, rhs: &Self) -> Self {
+		let mut quotient = self.clone();
+		quotient.div_assign_ref(rhs);
+		quotient
+	}
+
+	/// self / rhs, or None if rhs has no inverse
+	fn checked_div(&self, rhs: &Self) -> Option<Self> {
+		rhs.try_inverse().map(|inverse| self.mul_ref(&inverse))
+	}
+
+	/// self / rhs, or an error saying why rhs has no inverse
+	fn try_div(&self, rhs: &Self) -> Result<Self, AlgebraError> {
+		if rhs.is_zero() {
+			return Err(AlgebraError::DivisionByZero)
+		}
+
+		self.checked_div(rhs).ok_or(AlgebraError::NotInvertible)
+	}
+
 }
 
 /// A partially ordered field
-pub trait PoField: Field + PartialOrd { /* :) */}
+pub trait PoField: Field + PoRing {
+
+	/// The largest integer at most this element
+	fn floor(&self) -> Self;
+
+	/// The smallest integer at least this element
+	fn ceil(&self) -> Self {
+		-(-self.clone()).floor()
+	}
+
+	/// The nearest integer, with halves rounded up
+	fn round(&self) -> Self {
+		let half = (Self::one() + Self::one()).inverse();
+		self.add_ref(&half).floor()
+	}
+
+	/// The floor of self / divisor, the quotient of floor division
+	fn floor_div(&self, divisor: &Self) -> Self {
+		self.div_ref(divisor).floor()
+	}
+
+}
 
 /// A totally ordered field
-pub trait OrderedField: PoField + Ord { /* :) */}
+pub trait OrderedField: PoField + OrderedRing { /* :) */ }
+
+// Every field is an integral domain
+impl<F: Field> CommutativeRing for F { }
+impl<F: Field> IntegralDomain for F {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		self.checked_div(divisor)
+	}
+}
+
+/// Replaces every element of a slice by its inverse, using a single call to `inverse` and about 3n
+/// multiplications, by Montgomery's trick. Panics if any element is zero.
+///
+/// The prefix products a_0 a_1 ... a_i are inverted all at once, and walking back down the slice
+/// peels one inverse off the end at a time.
+pub fn batch_inverse<F: Field>(elements: &mut [F]) {
+	if elements.iter().any(F::is_zero) {
+		panic!("Cannot invert zero")
+	}
+
+	let mut prefixes = Vec::with_capacity(elements.len());
+	let mut product = F::one();
+	for x in elements.iter() {
+		prefixes.push(product.clone());
+		product.mul_assign_ref(x);
+	}
+
+	// After each step this is the inverse of the product of the elements not yet visited
+	let mut inverse = product.inverse();
+	for (x, prefix) in elements.iter_mut().zip(prefixes.iter()).rev() {
+		let next = inverse.mul_ref(x);
+		*x = inverse.mul_ref(prefix);
+		inverse = next;
+	}
+}
+
+// MARK: Finite Rings
+
+/// A ring with finitely many elements
+pub trait FiniteRing: Ring + FiniteSet {
+
+	/// The smallest n > 0 with 1 + 1 + ... + 1 = 0, n times, which exists since the multiples of
+	/// one cannot all be distinct. The default adds up ones until reaching zero.
+	fn characteristic() -> u128 {
+		let mut multiple = Self::one();
+		let mut n = 1;
+		while !multiple.is_zero() {
+			multiple += Self::one();
+			n += 1;
+		}
+		n
+	}
+
+	/// The number of elements
+	fn cardinality() -> u128 {
+		Self::size()
+	}
+
+	/// Every element, in order of index
+	fn elements() -> impl Iterator<Item = Self> {
+		Self::all_elements()
+	}
+
+}
+
+/// A finite field, which has p^k elements for its characteristic p, a prime
+pub trait FiniteField: FiniteRing + Field {
+
+	/// The degree k of this field over its prime field, so that it has p^k elements
+	fn degree() -> u32 {
+		Self::cardinality().ilog(Self::characteristic())
+	}
+
+	/// The image of this element under the Frobenius automorphism x -> x^p, which generates the
+	/// automorphisms of the field and fixes exactly the prime field
+	fn frobenius(&self) -> Self {
+		self.power(Self::characteristic() as i64)
+	}
+
+}
+
+// MARK: Modules
+
+/// A module over a ring R, which is an abelian group under addition together with a
+/// multiplication by elements of R distributing over both additions. When R is not commutative
+/// the scalars act on the right.
+pub trait Module<R: Ring>: Debug + Clone + PartialEq + Sized + Add<Output = Self> + AddAssign + Neg<Output = Self> + Sub<Output = Self> + SubAssign {
+
+	/// This element multiplied by the scalar r
+	fn scale(&self, r: &R) -> Self;
+
+}
+
+/// A module over a field
+pub trait VectorSpace<F: Field>: Module<F> { /* :) */ }
+
+impl<F: Field, V: Module<F>> VectorSpace<F> for V { }
+
+/// Every ring is a module over itself
+impl<R: Ring> Module<R> for R {
+	fn scale(&self, r: &R) -> R {
+		self.mul_ref(r)
+	}
+}
 
 // MARK: Inner Product Space
 pub trait InnerProductSpace<R: Ring> {
@@ -70,7 +472,7 @@ pub trait NormSpace {
 
 // MARK: Euclidean Domain
 
-pub trait EuclideanDomain: Ring + Div + DivAssign + Rem + RemAssign {
+pub trait EuclideanDomain: PrincipalIdealDomain + Div + DivAssign + Rem + RemAssign {
 
 	type SizeType: Ord;
 
@@ -79,42 +481,163 @@ pub trait EuclideanDomain: Ring + Div + DivAssign + Rem + RemAssign {
 	/// because often that function name is already used for other properties.
 	fn euc_size(&self) -> Self::SizeType;
 
-	/**
-	 * Finds q and r such that 
-	 *
-	 * self = divisor * q + r
-	 */
+	/// Finds q and r such that 
+	///
+	/// self = divisor * q + r
 	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self);
 }
 
 /// Returns (g, x, y) so that 
 /// - g = gcd(a, b)
-/// ax + by = gcd(a, b)
-pub fn ext_gcd<R: EuclideanDomain>(a: R, b: R) -> (R, R, R) {
+/// - ax + by = gcd(a, b)
+pub fn ext_gcd<R: EuclideanDomain>(a: &R, b: &R) -> (R, R, R) {
+	ext_gcd_with_trace(a, b, &mut |_| { })
+}
+
+/// One division step of the extended Euclidean algorithm on a and b, which found the next
+/// remainder of the chain, remainder = s a + t b, as the previous remainder minus quotient times
+/// the one before
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GcdStep<'a, R> {
+	pub quotient: &'a R,
+	pub remainder: &'a R,
+	pub s: &'a R,
+	pub t: &'a R
+}
+
+/// The extended Euclidean algorithm as in `ext_gcd`, calling trace with every step of the
+/// remainder sequence, so that the whole chain of remainders and cofactors can be watched
+pub fn ext_gcd_with_trace<R: EuclideanDomain>(a: &R, b: &R, trace: &mut impl FnMut(GcdStep<'_, R>)) -> (R, R, R) {
+
+	// Invariants: a = a0 * s + b0 * t and b = a0 * u + b0 * v
+	let (mut a, mut b) = (a.clone(), b.clone());
+	let (mut s, mut t) = (R::one(), R::zero());
+	let (mut u, mut v) = (R::zero(), R::one());
+
+	while !a.is_zero() {
+		let (q, r) = b.quotient_and_remainder(&a);
+
+		// (a, b) <- (b - q * a, a)
+		let mut next_s = u;
+		next_s.sub_assign_ref(&q.mul_ref(&s));
+		let mut next_t = v;
+		next_t.sub_assign_ref(&q.mul_ref(&t));
 
-	if a == R::zero() {
-		return (b, R::zero(), R::one())
+		b = core::mem::replace(&mut a, r);
+		u = core::mem::replace(&mut s, next_s);
+		v = core::mem::replace(&mut t, next_t);
+
+		trace(GcdStep { quotient: &q, remainder: &a, s: &s, t: &t });
 	}
 
-	let (q, r) = b.quotient_and_remainder(&a);
+	(b, u, v)
+}
 
-	let (g, x1, y1) = ext_gcd(r, a);
+/// The inverse of x modulo m, the y with xy = 1 mod m, or None if x and m are not coprime.
+///
+/// The result is reduced modulo m, so over the integers with m > 0 it lies in [0, m).
+pub fn mod_inverse<R: EuclideanDomain>(x: &R, m: &R) -> Option<R> {
+	if m.is_zero() {
+		panic!("Cannot invert modulo zero")
+	}
 
-	let x = y1 - q * x1.clone();
+	// xs + mt = g, so x has an inverse exactly when g is a unit, and then it is s / g
+	let (g, s, _) = ext_gcd(x, m);
+	let (g_inverse, r) = R::one().quotient_and_remainder(&g);
+	if !r.is_zero() {
+		return None
+	}
 
-	(g, x, x1)
+	let (_, y) = s.mul_ref(&g_inverse).quotient_and_remainder(m);
+	Some(y.add_ref(m).quotient_and_remainder(m).1)
 }
 
-/// The Euclidean Algorithm to find the GCD of two elements in a Euclidean Domain
-pub fn gcd<R: EuclideanDomain>(a: &R, b: &R) -> R {
-	if a.is_zero() {
-		b.clone()
-	} else if b.is_zero() {
-		a.clone()
-	} else if a.euc_size() < b.euc_size() {
-		gcd(b, a)
+/// Exact division in a Euclidean domain, the quotient when the remainder is zero, which is how
+/// Euclidean domains typically implement `IntegralDomain::divide_exact`
+pub fn euclidean_divide_exact<R: EuclideanDomain>(a: &R, divisor: &R) -> Option<R> {
+	if divisor.is_zero() {
+		return None
+	}
+
+	let (q, r) = a.quotient_and_remainder(divisor);
+	r.is_zero().then_some(q)
+}
+
+/// The GCD of two elements in a Unique Factorization Domain.
+/// 
+/// Euclidean domains typically implement this with `euclidean_gcd`, and machine integers
+/// use binary GCD, which always gives a nonnegative result. The one exception is when that
+/// result is 2^(k - 1) for a k bit type, as for gcd(i64::MIN, 0), which panics since it does
+/// not fit.
+pub fn gcd<R: UniqueFactorizationDomain>(a: &R, b: &R) -> R {
+	a.gcd(b)
+}
+
+/// The floor of a / b in an ordered Euclidean domain like the integers, whose quotient and
+/// remainder may round toward zero instead
+pub fn floor_div<R: OrderedRing + EuclideanDomain>(a: &R, b: &R) -> R {
+	let (q, r) = a.quotient_and_remainder(b);
+	if !r.is_zero() && (r.is_negative() != b.is_negative()) {
+		q - R::one()
 	} else {
+		q
+	}
+}
+
+/// Replaces a with gcd(a, b)
+pub fn gcd_assign<R: UniqueFactorizationDomain>(a: &mut R, b: &R) {
+	*a = a.gcd(b)
+}
+
+/// A gcd-free basis of the given elements: pairwise coprime nonunits such that each nonzero
+/// input is a unit times a product of powers of them, found without factoring anything.
+///
+/// Whenever a new element a shares a nontrivial gcd g with a basis element b, the pieces g, a / g
+/// and b / g replace both of them, which terminates because each such step lowers the total
+/// number of prime factors. Zeros and units contribute nothing, and each basis element is the
+/// associate gcd(x, 0) picks, so over the integers they are positive.
+pub fn gcd_free_basis<R: UniqueFactorizationDomain>(elements: &[R]) -> Vec<R> {
+	let is_unit = |x: &R| R::one().divide_exact(x).is_some();
+	let mut basis: Vec<R> = Vec::new();
+	let mut pending: Vec<R> = elements.iter().filter(|x| !x.is_zero()).cloned().collect();
+
+	while let Some(a) = pending.pop() {
+		if is_unit(&a) {
+			continue
+		}
+
+		match basis.iter().position(|b| !is_unit(&a.gcd(b))) {
+			Some(i) => {
+				let b = basis.remove(i);
+				let g = a.gcd(&b);
+				pending.push(a.divide_exact(&g).unwrap());
+				pending.push(b.divide_exact(&g).unwrap());
+				pending.push(g);
+			},
+			None => basis.push(a.gcd(&R::zero()))
+		}
+	}
+
+	basis
+}
+
+/// The Euclidean Algorithm to find the GCD of two elements in a Euclidean Domain
+pub fn euclidean_gcd<R: EuclideanDomain>(a: &R, b: &R) -> R {
+	let mut a = a.clone();
+	let mut b = b.clone();
+	euclidean_gcd_assign(&mut a, &mut b);
+	a
+}
+
+/// The Euclidean Algorithm run in place, without cloning: leaves gcd(a, b) in a and zero in b
+pub fn euclidean_gcd_assign<R: EuclideanDomain>(a: &mut R, b: &mut R) {
+	while !b.is_zero() {
+		if a.is_zero() || a.euc_size() < b.euc_size() {
+			core::mem::swap(a, b);
+			continue
+		}
+
 		let (_, r) = a.quotient_and_remainder(b);
-		gcd(b, &r)
+		*a = core::mem::replace(b, r);
 	}
-}
\ No newline at end of file
+}
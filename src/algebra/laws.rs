@@ -0,0 +1,209 @@
+//
+// Checks that an implementation of the algebra traits satisfies the axioms
+//
+// Each check tries every pair or triple drawn from the given samples, so it is exhaustive when the
+// samples are every element, like `G::elements()` for a finite group, and a spot check otherwise,
+// for example with samples from `RandomElement::random`. Triples make the cost cubic in the number
+// of samples, so a few dozen random samples is usually plenty.
+//
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+
+use crate::algebra::*;
+
+// MARK: Violations
+
+/// A law which fails for some particular elements
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LawViolation {
+	/// The name of the law, like "associativity of +"
+	pub law: &'static str,
+
+	/// The elements the law fails for, written with `Debug`
+	pub witnesses: Vec<String>
+}
+
+impl Display for LawViolation {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{} fails for {}", self.law, self.witnesses.join(", "))
+	}
+}
+
+impl core::error::Error for LawViolation { }
+
+/// Ok if the law holds, otherwise a violation naming the witnesses
+fn holds<T: Debug>(law: &'static str, holds: bool, witnesses: &[&T]) -> Result<(), LawViolation> {
+	if holds {
+		Ok(())
+	} else {
+		Err(LawViolation { law, witnesses: witnesses.iter().map(|w| format!("{:?}", w)).collect() })
+	}
+}
+
+// MARK: Groups
+
+/// Checks associativity, the identity, and that `*=` agrees with `*`
+pub fn check_monoid_axioms<M: Monoid>(samples: &[M]) -> Result<(), LawViolation> {
+	let e = M::identity();
+
+	for a in samples {
+		holds("left identity", e.clone() * a.clone() == *a, &[a])?;
+		holds("right identity", a.clone() * e.clone() == *a, &[a])?;
+
+		for b in samples {
+			let mut c = a.clone();
+			c *= b.clone();
+			holds("*= agrees with *", c == a.clone() * b.clone(), &[a, b])?;
+
+			for c in samples {
+				let left = (a.clone() * b.clone()) * c.clone();
+				let right = a.clone() * (b.clone() * c.clone());
+				holds("associativity of *", left == right, &[a, b, c])?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the monoid axioms, inverses, and that division is multiplication by the inverse
+pub fn check_group_axioms<G: Group>(samples: &[G]) -> Result<(), LawViolation> {
+	check_monoid_axioms(samples)?;
+
+	for a in samples {
+		holds("left inverse", a.inverse() * a.clone() == G::identity(), &[a])?;
+		holds("right inverse", a.clone() * a.inverse() == G::identity(), &[a])?;
+
+		for b in samples {
+			holds("a / b = a * b^(-1)", a.clone() / b.clone() == a.clone() * b.inverse(), &[a, b])?;
+
+			let mut c = a.clone();
+			c /= b.clone();
+			holds("/= agrees with /", c == a.clone() / b.clone(), &[a, b])?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the group axioms and commutativity
+pub fn check_abelian_group_axioms<G: AbelianGroup>(samples: &[G]) -> Result<(), LawViolation> {
+	check_group_axioms(samples)?;
+
+	for a in samples {
+		for b in samples {
+			holds("commutativity of *", a.clone() * b.clone() == b.clone() * a.clone(), &[a, b])?;
+		}
+	}
+
+	Ok(())
+}
+
+// MARK: Rings
+
+/// Checks that + is a commutative monoid with identity zero, that * is a monoid with identity one
+/// which distributes over + and is annihilated by zero, and that the assigning and borrowing
+/// forms of the operations agree with the operators
+pub fn check_semiring_axioms<R: Semiring>(samples: &[R]) -> Result<(), LawViolation> {
+	let (zero, one) = (R::zero(), R::one());
+	holds("zero is zero", zero.is_zero(), &[&zero])?;
+
+	for a in samples {
+		holds("additive identity", a.clone() + zero.clone() == *a, &[a])?;
+		holds("multiplicative identity", a.clone() * one.clone() == *a && one.clone() * a.clone() == *a, &[a])?;
+		holds("zero annihilates", (a.clone() * zero.clone()).is_zero() && (zero.clone() * a.clone()).is_zero(), &[a])?;
+		holds("is_zero agrees with ==", a.is_zero() == (*a == zero), &[a])?;
+
+		for b in samples {
+			holds("commutativity of +", a.clone() + b.clone() == b.clone() + a.clone(), &[a, b])?;
+			holds("add_ref agrees with +", a.add_ref(b) == a.clone() + b.clone(), &[a, b])?;
+			holds("mul_ref agrees with *", a.mul_ref(b) == a.clone() * b.clone(), &[a, b])?;
+
+			let mut c = a.clone();
+			c += b.clone();
+			holds("+= agrees with +", c == a.clone() + b.clone(), &[a, b])?;
+
+			let mut c = a.clone();
+			c *= b.clone();
+			holds("*= agrees with *", c == a.clone() * b.clone(), &[a, b])?;
+
+			for c in samples {
+				let sum = (a.clone() + b.clone()) + c.clone();
+				holds("associativity of +", sum == a.clone() + (b.clone() + c.clone()), &[a, b, c])?;
+
+				let product = (a.clone() * b.clone()) * c.clone();
+				holds("associativity of *", product == a.clone() * (b.clone() * c.clone()), &[a, b, c])?;
+
+				let left = a.clone() * (b.clone() + c.clone());
+				holds("left distributivity", left == a.clone() * b.clone() + a.clone() * c.clone(), &[a, b, c])?;
+
+				let right = (a.clone() + b.clone()) * c.clone();
+				holds("right distributivity", right == a.clone() * c.clone() + b.clone() * c.clone(), &[a, b, c])?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the semiring axioms, additive inverses, and that subtraction is adding the negative
+pub fn check_ring_axioms<R: Ring>(samples: &[R]) -> Result<(), LawViolation> {
+	check_semiring_axioms(samples)?;
+
+	for a in samples {
+		holds("additive inverse", (a.clone() + (-a.clone())).is_zero(), &[a])?;
+
+		for b in samples {
+			holds("a - b = a + (-b)", a.clone() - b.clone() == a.clone() + (-b.clone()), &[a, b])?;
+			holds("sub_ref agrees with -", a.sub_ref(b) == a.clone() - b.clone(), &[a, b])?;
+
+			let mut c = a.clone();
+			c -= b.clone();
+			holds("-= agrees with -", c == a.clone() - b.clone(), &[a, b])?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the ring axioms and commutativity of multiplication
+pub fn check_commutative_ring_axioms<R: CommutativeRing>(samples: &[R]) -> Result<(), LawViolation> {
+	check_ring_axioms(samples)?;
+
+	for a in samples {
+		for b in samples {
+			holds("commutativity of *", a.clone() * b.clone() == b.clone() * a.clone(), &[a, b])?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Checks the commutative ring axioms, that one is not zero, that every nonzero element has an
+/// inverse, and that division is multiplication by the inverse
+pub fn check_field_axioms<F: Field>(samples: &[F]) -> Result<(), LawViolation> {
+	check_commutative_ring_axioms(samples)?;
+	holds("one is not zero", !F::one().is_zero(), &[&F::one()])?;
+
+	for a in samples.iter().filter(|a| !a.is_zero()) {
+		// Check try_inverse first, so that a missing inverse is reported rather than panicking
+		holds("nonzero elements are invertible", a.try_inverse().is_some(), &[a])?;
+		holds("multiplicative inverse", a.clone() * a.inverse() == F::one(), &[a])?;
+		holds("try_inverse agrees with inverse", a.try_inverse() == Some(a.inverse()), &[a])?;
+
+		for b in samples {
+			holds("b / a = b * a^(-1)", b.clone() / a.clone() == b.clone() * a.inverse(), &[b, a])?;
+
+			let mut c = b.clone();
+			c /= a.clone();
+			holds("/= agrees with /", c == b.clone() / a.clone(), &[b, a])?;
+		}
+	}
+
+	holds("zero has no inverse", F::zero().try_inverse().is_none(), &[&F::zero()])?;
+
+	Ok(())
+}
@@ -0,0 +1,187 @@
+//
+// Floating point numbers compared up to a tolerance, so that generic algorithms deciding whether
+// something is zero, like pivot selection, are robust to rounding error
+//
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+
+// MARK: Approximate Numbers
+
+/// A floating point number whose comparisons allow an error of 10^-DIGITS.
+///
+/// Two numbers are equal when they differ by at most 10^-DIGITS times the larger of 1 and their
+/// absolute values, so the tolerance is absolute for small numbers and relative for large ones,
+/// and a number is zero when its absolute value is at most 10^-DIGITS. Arithmetic is that of the
+/// underlying floats. Running a generic algorithm over `Approx<f64>` instead of f64 makes its
+/// zero tests and comparisons tolerant, without changing the algorithm.
+///
+/// Approximate equality is not transitive, so this is only a partial order in spirit, and the
+/// tolerance should be chosen well above the rounding error expected of the computation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Approx<T, const DIGITS: u32 = 9>(pub T);
+
+impl<T, const DIGITS: u32> Approx<T, DIGITS> {
+
+	/// The number x, compared up to 10^-DIGITS
+	pub fn new(x: T) -> Approx<T, DIGITS> {
+		Approx(x)
+	}
+
+	/// The underlying number
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+
+}
+
+impl<T, const DIGITS: u32> From<T> for Approx<T, DIGITS> {
+	fn from(x: T) -> Self {
+		Approx(x)
+	}
+}
+
+impl<T: Display, const DIGITS: u32> Display for Approx<T, DIGITS> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+macro_rules! impl_approx {
+	($($t:ty),*) => {$(
+		impl<const DIGITS: u32> Approx<$t, DIGITS> {
+
+			/// The tolerance 10^-DIGITS
+			pub fn epsilon() -> $t {
+				<$t>::powi(10.0, -(DIGITS as i32))
+			}
+
+		}
+
+		impl<const DIGITS: u32> PartialEq for Approx<$t, DIGITS> {
+			fn eq(&self, other: &Self) -> bool {
+				let scale = self.0.abs().max(other.0.abs()).max(1.0);
+				(self.0 - other.0).abs() <= Self::epsilon() * scale
+			}
+		}
+
+		impl<const DIGITS: u32> PartialOrd for Approx<$t, DIGITS> {
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+				if self == other {
+					Some(Ordering::Equal)
+				} else {
+					self.0.partial_cmp(&other.0)
+				}
+			}
+		}
+
+		impl<const DIGITS: u32> Add for Approx<$t, DIGITS> {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self::Output {
+				Approx(self.0 + rhs.0)
+			}
+		}
+
+		impl<const DIGITS: u32> AddAssign for Approx<$t, DIGITS> {
+			fn add_assign(&mut self, rhs: Self) {
+				self.0 += rhs.0
+			}
+		}
+
+		impl<const DIGITS: u32> Sub for Approx<$t, DIGITS> {
+			type Output = Self;
+
+			fn sub(self, rhs: Self) -> Self::Output {
+				Approx(self.0 - rhs.0)
+			}
+		}
+
+		impl<const DIGITS: u32> SubAssign for Approx<$t, DIGITS> {
+			fn sub_assign(&mut self, rhs: Self) {
+				self.0 -= rhs.0
+			}
+		}
+
+		impl<const DIGITS: u32> Neg for Approx<$t, DIGITS> {
+			type Output = Self;
+
+			fn neg(self) -> Self::Output {
+				Approx(-self.0)
+			}
+		}
+
+		impl<const DIGITS: u32> Mul for Approx<$t, DIGITS> {
+			type Output = Self;
+
+			fn mul(self, rhs: Self) -> Self::Output {
+				Approx(self.0 * rhs.0)
+			}
+		}
+
+		impl<const DIGITS: u32> MulAssign for Approx<$t, DIGITS> {
+			fn mul_assign(&mut self, rhs: Self) {
+				self.0 *= rhs.0
+			}
+		}
+
+		impl<const DIGITS: u32> Div for Approx<$t, DIGITS> {
+			type Output = Self;
+
+			fn div(self, rhs: Self) -> Self::Output {
+				Approx(self.0 / rhs.0)
+			}
+		}
+
+		impl<const DIGITS: u32> DivAssign for Approx<$t, DIGITS> {
+			fn div_assign(&mut self, rhs: Self) {
+				self.0 /= rhs.0
+			}
+		}
+
+		impl<const DIGITS: u32> Semiring for Approx<$t, DIGITS> {
+			fn one() -> Self {
+				Approx(1.0)
+			}
+
+			fn zero() -> Self {
+				Approx(0.0)
+			}
+
+			fn is_zero(&self) -> bool {
+				self.0.abs() <= Self::epsilon()
+			}
+		}
+
+		impl<const DIGITS: u32> Ring for Approx<$t, DIGITS> {
+			fn power(&self, n: i64) -> Self {
+				Approx(self.0.powf(n as $t))
+			}
+		}
+
+		/// Numbers within the tolerance of zero have no inverse, rather than a huge one
+		impl<const DIGITS: u32> Field for Approx<$t, DIGITS> {
+			fn inverse(&self) -> Self {
+				if self.is_zero() {
+					panic!("Cannot divide by {}, which is approximately zero", self.0)
+				}
+				Approx(1.0 / self.0)
+			}
+		}
+
+		impl<const DIGITS: u32> PoRing for Approx<$t, DIGITS> { }
+
+		/// A number approximately equal to an integer has that integer as its floor
+		impl<const DIGITS: u32> PoField for Approx<$t, DIGITS> {
+			fn floor(&self) -> Self {
+				let nearest = Approx(<$t>::round(self.0));
+				if nearest == *self { nearest } else { Approx(<$t>::floor(self.0)) }
+			}
+		}
+	)*};
+}
+
+impl_approx!(f64, f32);
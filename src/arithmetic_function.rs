@@ -0,0 +1,164 @@
+//
+// Multiplicative arithmetic functions given by their values on prime powers, with Dirichlet
+// convolution, Dirichlet inverses, and summatory functions
+//
+
+use std::rc::Rc;
+
+use crate::algebra::*;
+use crate::sieve::smallest_prime_factors;
+use crate::std_impls::factor;
+
+/// A rule giving f(p^k) from a prime p and an exponent k >= 1
+type PrimePowerRule<R> = Rc<dyn Fn(i64, u32) -> R>;
+
+// MARK: Multiplicative Functions
+
+/// A multiplicative function f on the positive integers, with f(1) = 1 and f(mn) = f(m) f(n)
+/// whenever m and n are coprime, so it is determined by its values on prime powers.
+///
+/// Cloning a function is cheap, since the clone shares its rule.
+#[derive(Clone)]
+pub struct MultiplicativeFunction<R: CommutativeRing> {
+	rule: PrimePowerRule<R>
+}
+
+impl<R: CommutativeRing + 'static> MultiplicativeFunction<R> {
+
+	/// The multiplicative function with f(p^k) given by a rule taking p and k >= 1
+	pub fn from_prime_powers<F: Fn(i64, u32) -> R + 'static>(rule: F) -> MultiplicativeFunction<R> {
+		MultiplicativeFunction { rule: Rc::new(rule) }
+	}
+
+	/// f(p^k), which is 1 when k = 0
+	pub fn at_prime_power(&self, p: i64, k: u32) -> R {
+		if k == 0 { R::one() } else { (self.rule)(p, k) }
+	}
+
+	/// f(n) for a positive integer n, the product of f(p^k) over the prime powers exactly dividing n
+	pub fn evaluate(&self, n: i64) -> R {
+		factor(n).into_iter().fold(R::one(), |product, (p, k)| product.mul_ref(&(self.rule)(p, k)))
+	}
+
+	/// The values f(0), f(1), ..., f(n), with f(0) = 0 as a placeholder, by sieving out smallest
+	/// prime factors rather than factoring each number
+	pub fn values_up_to(&self, n: usize) -> Vec<R> {
+		let smallest_prime = smallest_prime_factors(n);
+		let mut values = vec![R::zero(); n + 1];
+		if n >= 1 {
+			values[1] = R::one();
+		}
+		for m in 2..=n {
+			// m = p^k r with r coprime to p
+			let p = smallest_prime[m];
+			let (mut r, mut k) = (m, 0);
+			while r.is_multiple_of(p) {
+				r /= p;
+				k += 1;
+			}
+			values[m] = (self.rule)(p as i64, k).mul_ref(&values[r]);
+		}
+
+		values
+	}
+
+	/// The summatory function f(1) + f(2) + ... + f(n)
+	pub fn summatory(&self, n: usize) -> R {
+		self.values_up_to(n).iter().fold(R::zero(), |sum, x| sum.add_ref(x))
+	}
+
+	/// The Dirichlet convolution (f * g)(n), the sum of f(d) g(n / d) over the divisors d of n,
+	/// which is again multiplicative, with (f * g)(p^k) the sum of f(p^i) g(p^(k-i))
+	pub fn dirichlet_convolution(&self, other: &MultiplicativeFunction<R>) -> MultiplicativeFunction<R> {
+		let (f, g) = (self.clone(), other.clone());
+		MultiplicativeFunction::from_prime_powers(move |p, k| {
+			(0..=k).fold(R::zero(), |sum, i| sum.add_ref(&f.at_prime_power(p, i).mul_ref(&g.at_prime_power(p, k - i))))
+		})
+	}
+
+	/// The Dirichlet inverse, the g with f * g = epsilon, found on each prime power from
+	/// g(p^k) = -(f(p) g(p^(k-1)) + f(p^2) g(p^(k-2)) + ... + f(p^k))
+	pub fn dirichlet_inverse(&self) -> MultiplicativeFunction<R> {
+		let f = self.clone();
+		MultiplicativeFunction::from_prime_powers(move |p, k| {
+			let mut g = vec![R::one()];
+			for j in 1..=k {
+				let sum = (1..=j).fold(R::zero(), |sum, i| sum.add_ref(&f.at_prime_power(p, i).mul_ref(&g[(j - i) as usize])));
+				g.push(-sum);
+			}
+			g.pop().unwrap()
+		})
+	}
+
+	/// The pointwise product n -> f(n) g(n), which is again multiplicative
+	pub fn pointwise_product(&self, other: &MultiplicativeFunction<R>) -> MultiplicativeFunction<R> {
+		let (f, g) = (self.clone(), other.clone());
+		MultiplicativeFunction::from_prime_powers(move |p, k| (f.rule)(p, k).mul_ref(&(g.rule)(p, k)))
+	}
+
+	/// The identity of Dirichlet convolution, epsilon(1) = 1 and epsilon(n) = 0 for n > 1
+	pub fn epsilon() -> MultiplicativeFunction<R> {
+		MultiplicativeFunction::from_prime_powers(|_, _| R::zero())
+	}
+
+	/// The constant function 1
+	pub fn one() -> MultiplicativeFunction<R> {
+		MultiplicativeFunction::from_prime_powers(|_, _| R::one())
+	}
+
+}
+
+// MARK: Classical Functions
+
+impl MultiplicativeFunction<i64> {
+
+	/// The power function n -> n^e
+	pub fn power(e: u32) -> MultiplicativeFunction<i64> {
+		MultiplicativeFunction::from_prime_powers(move |p, k| p.pow(k * e))
+	}
+
+	/// The Möbius function, (-1)^r on a product of r distinct primes and 0 on anything else, which
+	/// is the Dirichlet inverse of the constant function 1
+	pub fn mobius() -> MultiplicativeFunction<i64> {
+		MultiplicativeFunction::from_prime_powers(|_, k| if k == 1 { -1 } else { 0 })
+	}
+
+	/// The Liouville function (-1)^Omega(n), for Omega(n) the number of prime factors of n with
+	/// multiplicity
+	pub fn liouville() -> MultiplicativeFunction<i64> {
+		MultiplicativeFunction::from_prime_powers(|_, k| if k % 2 == 0 { 1 } else { -1 })
+	}
+
+	/// Euler's totient function, with phi(p^k) = p^(k-1) (p - 1)
+	pub fn totient() -> MultiplicativeFunction<i64> {
+		MultiplicativeFunction::from_prime_powers(|p, k| p.pow(k - 1) * (p - 1))
+	}
+
+	/// The divisor function sigma_e(n), the sum of d^e over the divisors d of n, so sigma_0 counts
+	/// the divisors and sigma_1 adds them up
+	pub fn divisor_sigma(e: u32) -> MultiplicativeFunction<i64> {
+		MultiplicativeFunction::from_prime_powers(move |p, k| (0..=k).map(|i| p.pow(i * e)).sum())
+	}
+
+}
+
+// MARK: Dirichlet Convolution of Sequences
+
+/// The Dirichlet convolution of two sequences given by their values f(0), f(1), ..., f(n) with the
+/// value at 0 ignored, as the values of f * g up to n, in O(n log n) ring operations. Unlike
+/// `MultiplicativeFunction::dirichlet_convolution`, this works for any arithmetic functions.
+pub fn dirichlet_convolution<R: Semiring>(f: &[R], g: &[R]) -> Vec<R> {
+	if f.len() != g.len() {
+		panic!("Cannot convolve tables of {} and {} values", f.len(), g.len())
+	}
+
+	let n = f.len().saturating_sub(1);
+	let mut h = vec![R::zero(); n + 1];
+	for (d, fd) in f.iter().enumerate().skip(1) {
+		for (m, e) in (d..=n).step_by(d).zip(1..) {
+			h[m].add_assign_ref(&fd.mul_ref(&g[e]));
+		}
+	}
+
+	h
+}
@@ -0,0 +1,427 @@
+//
+// Prime fields with multi-limb moduli, for cryptographic sizes
+//
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+
+// MARK: Moduli
+
+/// An odd prime modulus with LIMBS 64-bit limbs, given at the type level. The top limb must be
+/// nonzero, and using a BigZM with an even modulus or a zero top limb fails to compile.
+pub trait BigModulus<const LIMBS: usize> {
+
+	/// The modulus, least significant limb first
+	const MODULUS: [u64; LIMBS];
+
+}
+
+/// The prime 2^256 - 2^224 + 2^192 + 2^96 - 1 of the NIST P-256 curve
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct P256;
+
+impl BigModulus<4> for P256 {
+	const MODULUS: [u64; 4] = [0xFFFFFFFFFFFFFFFF, 0x00000000FFFFFFFF, 0x0000000000000000, 0xFFFFFFFF00000001];
+}
+
+/// The prime 2^256 - 2^32 - 977 of the secp256k1 curve
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Secp256k1;
+
+impl BigModulus<4> for Secp256k1 {
+	const MODULUS: [u64; 4] = [0xFFFFFFFEFFFFFC2F, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF];
+}
+
+/// The prime 2^384 - 2^128 - 2^96 + 2^32 - 1 of the NIST P-384 curve
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct P384;
+
+impl BigModulus<6> for P384 {
+	const MODULUS: [u64; 6] = [
+		0x00000000FFFFFFFF, 0xFFFFFFFF00000000, 0xFFFFFFFFFFFFFFFE,
+		0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF, 0xFFFFFFFFFFFFFFFF
+	];
+}
+
+// MARK: Field Elements
+
+/// An element of the field of integers modulo the prime M::MODULUS.
+///
+/// Elements are kept in Montgomery form, and addition, subtraction, multiplication,
+/// comparison, and inversion all run in time independent of the values involved.
+pub struct BigZM<M: BigModulus<LIMBS>, const LIMBS: usize> {
+	/// The value times 2^(64 LIMBS), modulo the modulus
+	montgomery: [u64; LIMBS],
+	_modulus: PhantomData<M>
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> BigZM<M, LIMBS> {
+
+	/// Evaluating this fails to compile unless the modulus is odd, which Montgomery reduction
+	/// needs, and its top limb is nonzero, so that every u64 is already reduced when LIMBS > 1
+	const ASSERT_MODULUS: () = assert!(
+		M::MODULUS[0] & 1 == 1 && M::MODULUS[LIMBS - 1] != 0,
+		"The modulus of BigZM must be odd with a nonzero top limb"
+	);
+
+	/// -p^(-1) mod 2^64, for Montgomery reduction
+	const P_INV: u64 = negated_inverse(M::MODULUS[0]);
+
+	/// 2^(128 LIMBS) mod p, for converting into Montgomery form
+	const R2: [u64; LIMBS] = power_of_two_mod(128 * LIMBS, &M::MODULUS);
+
+	/// Every element is built here, so this is where the modulus is checked
+	fn from_montgomery(montgomery: [u64; LIMBS]) -> Self {
+		let () = Self::ASSERT_MODULUS;
+		BigZM { montgomery, _modulus: PhantomData }
+	}
+
+	/// The integer with the given limbs, least significant first, if it is less than the modulus
+	pub fn from_limbs(limbs: [u64; LIMBS]) -> Option<Self> {
+		let (_, borrow) = sub_limbs(&limbs, &M::MODULUS);
+		if borrow == 0 {
+			return None
+		}
+
+		Some(Self::from_montgomery(montgomery_mul::<LIMBS>(&limbs, &Self::R2, &M::MODULUS, Self::P_INV)))
+	}
+
+	/// The residue of a machine integer
+	pub fn from_u64(x: u64) -> Self {
+		let mut limbs = [0; LIMBS];
+
+		// x is already reduced unless the modulus fits in one limb, since the top limb is nonzero
+		limbs[0] = if LIMBS == 1 { x % M::MODULUS[0] } else { x };
+
+		Self::from_montgomery(montgomery_mul::<LIMBS>(&limbs, &Self::R2, &M::MODULUS, Self::P_INV))
+	}
+
+	/// The canonical representative in [0, p), least significant limb first
+	pub fn to_limbs(&self) -> [u64; LIMBS] {
+		let mut one = [0; LIMBS];
+		one[0] = 1;
+		montgomery_mul::<LIMBS>(&self.montgomery, &one, &M::MODULUS, Self::P_INV)
+	}
+
+	/// The modulus p, least significant limb first
+	pub fn modulus() -> [u64; LIMBS] {
+		M::MODULUS
+	}
+
+	/// This element raised to a nonnegative power, given as limbs least significant first.
+	///
+	/// The running time depends on the exponent but not on this element.
+	pub fn pow_limbs(&self, exponent: &[u64]) -> Self {
+		let mut power = Self::one();
+		for limb in exponent.iter().rev() {
+			for bit in (0..64).rev() {
+				power = power * power;
+				if (limb >> bit) & 1 == 1 {
+					power *= *self;
+				}
+			}
+		}
+		power
+	}
+
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> RandomElement for BigZM<M, LIMBS> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		let top_bits = 64 - M::MODULUS[LIMBS - 1].leading_zeros();
+		let top_mask = if top_bits == 64 { u64::MAX } else { (1 << top_bits) - 1 };
+
+		// Rejection sampling from the smallest power of two above the modulus
+		loop {
+			let mut limbs: [u64; LIMBS] = std::array::from_fn(|_| rng.gen());
+			limbs[LIMBS - 1] &= top_mask;
+			if let Some(x) = Self::from_limbs(limbs) {
+				return x
+			}
+		}
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Distribution<BigZM<M, LIMBS>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BigZM<M, LIMBS> {
+		BigZM::random(rng)
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Clone for BigZM<M, LIMBS> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Copy for BigZM<M, LIMBS> { }
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Debug for BigZM<M, LIMBS> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "0x")?;
+		for limb in self.to_limbs().iter().rev() {
+			write!(f, "{:016x}", limb)?;
+		}
+		Ok(())
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> PartialEq for BigZM<M, LIMBS> {
+	fn eq(&self, other: &Self) -> bool {
+		let difference = self.montgomery.iter().zip(other.montgomery.iter()).fold(0, |acc, (a, b)| acc | (a ^ b));
+		difference == 0
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Eq for BigZM<M, LIMBS> { }
+
+/// Hashes the Montgomery form, which is unique to each element
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Hash for BigZM<M, LIMBS> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.montgomery.hash(state)
+	}
+}
+
+/// Ordered by representative in [0, p), which is not compatible with the arithmetic
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> PartialOrd for BigZM<M, LIMBS> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Ord for BigZM<M, LIMBS> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.to_limbs().iter().rev().cmp(other.to_limbs().iter().rev())
+	}
+}
+
+// MARK: Arithmetic
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Add for BigZM<M, LIMBS> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let (sum, carry) = add_limbs(&self.montgomery, &rhs.montgomery);
+		let (reduced, borrow) = sub_limbs(&sum, &M::MODULUS);
+
+		// Keep the unreduced sum only if it didn't overflow and was already below p
+		Self::from_montgomery(select(&reduced, &sum, borrow & !carry & 1))
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> AddAssign for BigZM<M, LIMBS> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Sub for BigZM<M, LIMBS> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		let (difference, borrow) = sub_limbs(&self.montgomery, &rhs.montgomery);
+		let (wrapped, _) = add_limbs(&difference, &M::MODULUS);
+		Self::from_montgomery(select(&difference, &wrapped, borrow))
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> SubAssign for BigZM<M, LIMBS> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Neg for BigZM<M, LIMBS> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::zero() - self
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Mul for BigZM<M, LIMBS> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Self::from_montgomery(montgomery_mul::<LIMBS>(&self.montgomery, &rhs.montgomery, &M::MODULUS, Self::P_INV))
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> MulAssign for BigZM<M, LIMBS> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Div for BigZM<M, LIMBS> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> DivAssign for BigZM<M, LIMBS> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Semiring for BigZM<M, LIMBS> {
+	fn one() -> Self {
+		Self::from_u64(1)
+	}
+
+	fn zero() -> Self {
+		Self::from_montgomery([0; LIMBS])
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == Self::zero()
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Ring for BigZM<M, LIMBS> {
+	fn power(&self, n: i64) -> Self {
+		let power = self.pow_limbs(&[n.unsigned_abs()]);
+		if n < 0 { power.inverse() } else { power }
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Field for BigZM<M, LIMBS> {
+	/// The inverse by Fermat's little theorem, x^(p - 2), which takes the same time for every x.
+	/// Zero is sent to zero.
+	fn inverse(&self) -> Self {
+		let mut two = [0; LIMBS];
+		two[0] = 2;
+		let (exponent, _) = sub_limbs(&M::MODULUS, &two);
+		self.pow_limbs(&exponent)
+	}
+
+	fn try_inverse(&self) -> Option<Self> {
+		if self.is_zero() {
+			None
+		} else {
+			Some(self.inverse())
+		}
+	}
+}
+
+// MARK: Limb Arithmetic
+
+/// a + b, and the carry out
+fn add_limbs<const LIMBS: usize>(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> ([u64; LIMBS], u64) {
+	let mut sum = [0; LIMBS];
+	let mut carry = 0;
+	for i in 0..LIMBS {
+		let s = a[i] as u128 + b[i] as u128 + carry as u128;
+		sum[i] = s as u64;
+		carry = (s >> 64) as u64;
+	}
+	(sum, carry)
+}
+
+/// a - b, and 1 if it borrowed (meaning a < b) or 0 if not
+const fn sub_limbs<const LIMBS: usize>(a: &[u64; LIMBS], b: &[u64; LIMBS]) -> ([u64; LIMBS], u64) {
+	let mut difference = [0; LIMBS];
+	let mut borrow = 0;
+	let mut i = 0;
+	while i < LIMBS {
+		let d = (a[i] as u128).wrapping_sub(b[i] as u128).wrapping_sub(borrow as u128);
+		difference[i] = d as u64;
+		borrow = ((d >> 64) as u64) & 1;
+		i += 1;
+	}
+	(difference, borrow)
+}
+
+/// if_one when choice is 1 and if_zero when it is 0, without branching
+fn select<const LIMBS: usize>(if_zero: &[u64; LIMBS], if_one: &[u64; LIMBS], choice: u64) -> [u64; LIMBS] {
+	let mask = choice.wrapping_neg();
+	std::array::from_fn(|i| (if_zero[i] & !mask) | (if_one[i] & mask))
+}
+
+/// a b 2^(-64 LIMBS) mod p by coarsely integrated operand scanning, for a, b < p
+fn montgomery_mul<const LIMBS: usize>(a: &[u64; LIMBS], b: &[u64; LIMBS], p: &[u64; LIMBS], p_inv: u64) -> [u64; LIMBS] {
+	let mut t = [0u64; LIMBS];
+	let mut t_high = 0u64;
+
+	for b_i in b.iter() {
+		// t += a * b_i
+		let mut carry = 0u64;
+		for j in 0..LIMBS {
+			let s = t[j] as u128 + (a[j] as u128) * (*b_i as u128) + carry as u128;
+			t[j] = s as u64;
+			carry = (s >> 64) as u64;
+		}
+		let s = t_high as u128 + carry as u128;
+		t_high = s as u64;
+		let t_higher = (s >> 64) as u64;
+
+		// t = (t + m p) / 2^64, where m is chosen to clear the low limb
+		let m = t[0].wrapping_mul(p_inv);
+		let s = t[0] as u128 + (m as u128) * (p[0] as u128);
+		let mut carry = (s >> 64) as u64;
+		for j in 1..LIMBS {
+			let s = t[j] as u128 + (m as u128) * (p[j] as u128) + carry as u128;
+			t[j - 1] = s as u64;
+			carry = (s >> 64) as u64;
+		}
+		let s = t_high as u128 + carry as u128;
+		t[LIMBS - 1] = s as u64;
+		t_high = t_higher + (s >> 64) as u64;
+	}
+
+	// Now t < 2p, so one conditional subtraction finishes the reduction
+	let (reduced, borrow) = sub_limbs(&t, p);
+	select(&reduced, &t, borrow & !t_high & 1)
+}
+
+/// -x^(-1) mod 2^64 for odd x, by Newton's iteration
+const fn negated_inverse(x: u64) -> u64 {
+	let mut inverse = 1u64;
+	let mut i = 0;
+	while i < 6 {
+		inverse = inverse.wrapping_mul(2u64.wrapping_sub(x.wrapping_mul(inverse)));
+		i += 1;
+	}
+	inverse.wrapping_neg()
+}
+
+/// 2^k mod p, by doubling k times
+const fn power_of_two_mod<const LIMBS: usize>(k: usize, p: &[u64; LIMBS]) -> [u64; LIMBS] {
+	let mut x = [0u64; LIMBS];
+	x[0] = 1;
+
+	let mut step = 0;
+	while step < k {
+		// x = 2x
+		let mut carry = 0;
+		let mut i = 0;
+		while i < LIMBS {
+			let next_carry = x[i] >> 63;
+			x[i] = (x[i] << 1) | carry;
+			carry = next_carry;
+			i += 1;
+		}
+
+		// Subtract p if 2x overflowed or is at least p
+		let (reduced, borrow) = sub_limbs(&x, p);
+		if carry == 1 || borrow == 0 {
+			x = reduced;
+		}
+		step += 1;
+	}
+
+	x
+}
@@ -0,0 +1,222 @@
+//
+// Arbitrary precision integers and rationals from num-bigint and num-rational
+//
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Pow, ToPrimitive, Zero};
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+use crate::std_impls::factor;
+
+/// The largest coefficient we will factor when looking for rational roots
+pub const RATIONAL_ROOT_LIMIT: i64 = 1 << 40;
+
+// MARK: Integers
+
+impl Semiring for BigInt {
+	fn one() -> Self {
+		One::one()
+	}
+
+	fn zero() -> Self {
+		Zero::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		Zero::is_zero(self)
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		*self += rhs
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self *= rhs
+	}
+
+	fn add_ref(&self, rhs: &Self) -> Self {
+		self + rhs
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		self * rhs
+	}
+}
+
+impl Ring for BigInt {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+		Pow::pow(self, n as u64)
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		*self -= rhs
+	}
+
+	fn sub_ref(&self, rhs: &Self) -> Self {
+		self - rhs
+	}
+}
+
+impl PoRing for BigInt { }
+impl OrderedRing for BigInt { }
+
+impl CommutativeRing for BigInt { }
+impl IntegralDomain for BigInt {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		euclidean_divide_exact(self, divisor)
+	}
+}
+
+impl PrincipalIdealDomain for BigInt { }
+
+impl UniqueFactorizationDomain for BigInt {
+	/// The nonnegative GCD
+	fn gcd(&self, other: &Self) -> Self {
+		Integer::gcd(self, other)
+	}
+}
+
+impl EuclideanDomain for BigInt {
+	type SizeType = BigUint;
+
+	fn euc_size(&self) -> BigUint {
+		self.magnitude().clone()
+	}
+
+	/// Truncating division, so the remainder has the sign of self
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if Zero::is_zero(divisor) {
+			panic!("Cannot divide by zero")
+		}
+		self.div_rem(divisor)
+	}
+}
+
+// MARK: Rationals
+
+impl Semiring for BigRational {
+	fn one() -> Self {
+		One::one()
+	}
+
+	fn zero() -> Self {
+		Zero::zero()
+	}
+
+	fn is_zero(&self) -> bool {
+		Zero::is_zero(self)
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		*self += rhs
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self *= rhs
+	}
+
+	fn add_ref(&self, rhs: &Self) -> Self {
+		self + rhs
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		self * rhs
+	}
+}
+
+impl Ring for BigRational {
+	fn power(&self, n: i64) -> Self {
+		let magnitude = Pow::pow(self, n.unsigned_abs());
+		if n < 0 { magnitude.inverse() } else { magnitude }
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		*self -= rhs
+	}
+
+	fn sub_ref(&self, rhs: &Self) -> Self {
+		self - rhs
+	}
+}
+
+impl Field for BigRational {
+	fn inverse(&self) -> Self {
+		if Zero::is_zero(self) {
+			panic!("Cannot divide by zero")
+		}
+		self.recip()
+	}
+
+	fn div_assign_ref(&mut self, rhs: &Self) {
+		*self /= rhs
+	}
+
+	fn div_ref(&self, rhs: &Self) -> Self {
+		self / rhs
+	}
+}
+
+impl PoRing for BigRational { }
+impl OrderedRing for BigRational { }
+impl PoField for BigRational {
+	fn floor(&self) -> Self {
+		BigRational::floor(self)
+	}
+}
+impl OrderedField for BigRational { }
+
+// MARK: Rational Roots
+
+/// The positive divisors of a positive integer
+fn divisors(n: i64) -> Vec<i64> {
+	factor(n).into_iter().fold(vec![1], |divisors, (p, e)| {
+		divisors.iter().flat_map(|&d| (0..=e).map(move |k| d * p.pow(k))).collect()
+	})
+}
+
+impl Polynomial<BigInt> {
+
+	/// The distinct rational roots of this nonzero integer polynomial, in increasing order, or None
+	/// if its lowest nonzero or leading coefficient is larger than `RATIONAL_ROOT_LIMIT` and too
+	/// large to factor.
+	///
+	/// By the rational root theorem, a root p/q in lowest terms other than 0 has p dividing the
+	/// lowest nonzero coefficient and q dividing the leading one, so each such candidate is tried
+	/// exactly.
+	pub fn rational_roots(&self) -> Option<Vec<BigRational>> {
+		if self.is_zero() {
+			panic!("Every number is a root of the zero polynomial")
+		}
+
+		let lowest = self.coefficients().iter().position(|c| !Zero::is_zero(c)).unwrap();
+		let constant = self.coefficient(lowest).abs().to_i64().filter(|&c| c <= RATIONAL_ROOT_LIMIT)?;
+		let leading = self.leading_coefficient().abs().to_i64().filter(|&c| c <= RATIONAL_ROOT_LIMIT)?;
+
+		let f = Polynomial::new(self.coefficients().iter().map(|c| BigRational::from_integer(c.clone())).collect());
+		let mut roots = Vec::new();
+		if lowest > 0 {
+			roots.push(<BigRational as Zero>::zero());
+		}
+
+		for p in divisors(constant) {
+			for q in divisors(leading).into_iter().filter(|&q| Integer::gcd(&p, &q) == 1) {
+				for sign in [1, -1] {
+					let root = BigRational::new(BigInt::from(sign * p), BigInt::from(q));
+					if Zero::is_zero(&f.evaluate(&root)) {
+						roots.push(root);
+					}
+				}
+			}
+		}
+
+		roots.sort();
+		Some(roots)
+	}
+
+}
@@ -0,0 +1,400 @@
+//
+// The binary fields GF(2) and GF(2^8), with arithmetic on bits and bytes directly rather than
+// through polynomials over ZM<2>
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::extension_field::{Rijndael, GF};
+use crate::finite_set::{check_index, FiniteSet};
+use crate::random::RandomElement;
+use crate::std_impls::ZM;
+
+// MARK: GF(2)
+
+/// The field with two elements, stored as a bool, where addition is exclusive or and
+/// multiplication is and
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GF2(pub bool);
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF2 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		GF2(self.0 ^ rhs.0)
+	}
+}
+
+impl AddAssign for GF2 {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+/// Subtraction is addition, since every element is its own negative
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for GF2 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+impl SubAssign for GF2 {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl Neg for GF2 {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		self
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for GF2 {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		GF2(self.0 && rhs.0)
+	}
+}
+
+impl MulAssign for GF2 {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Div for GF2 {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl DivAssign for GF2 {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl Semiring for GF2 {
+	fn one() -> Self {
+		GF2(true)
+	}
+
+	fn zero() -> Self {
+		GF2(false)
+	}
+
+	fn is_zero(&self) -> bool {
+		!self.0
+	}
+}
+
+impl Ring for GF2 {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse()
+		}
+		GF2(self.0 || n == 0)
+	}
+}
+
+impl Field for GF2 {
+	fn inverse(&self) -> Self {
+		if self.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+		*self
+	}
+}
+
+impl RandomElement for GF2 {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		GF2(rng.gen())
+	}
+}
+
+impl Distribution<GF2> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GF2 {
+		GF2::random(rng)
+	}
+}
+
+impl FiniteSet for GF2 {
+	fn size() -> u128 {
+		2
+	}
+
+	fn to_index(&self) -> u128 {
+		self.0 as u128
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		GF2(index == 1)
+	}
+}
+
+impl FiniteRing for GF2 {
+	fn characteristic() -> u128 {
+		2
+	}
+}
+
+impl FiniteField for GF2 {
+	fn degree() -> u32 {
+		1
+	}
+
+	fn frobenius(&self) -> Self {
+		*self
+	}
+}
+
+impl From<ZM<2>> for GF2 {
+	fn from(x: ZM<2>) -> Self {
+		GF2(x.val == 1)
+	}
+}
+
+impl From<GF2> for ZM<2> {
+	fn from(x: GF2) -> Self {
+		ZM::from_int(x.0 as i64)
+	}
+}
+
+// MARK: GF(2^8)
+
+/// The exponential and logarithm tables of GF(2^8) for the generator x + 1. The exponential
+/// table is written out twice, so a product can look up the sum of two logarithms without
+/// reducing it modulo 255.
+const TABLES: ([u8; 512], [u8; 256]) = {
+	let mut exp = [0; 512];
+	let mut log = [0; 256];
+	let mut x: u16 = 1;
+	let mut i = 0;
+
+	while i < 255 {
+		exp[i] = x as u8;
+		exp[i + 255] = x as u8;
+		log[x as usize] = i as u8;
+
+		// Multiply by x + 1, reducing by the Rijndael modulus x^8 + x^4 + x^3 + x + 1
+		x ^= x << 1;
+		if x & 0x100 != 0 {
+			x ^= 0x11B;
+		}
+		i += 1;
+	}
+
+	(exp, log)
+};
+
+static EXP: [u8; 512] = TABLES.0;
+static LOG: [u8; 256] = TABLES.1;
+
+/// The field with 256 elements, stored as a byte, whose bits are the coefficients of a
+/// polynomial over GF(2) modulo the Rijndael polynomial x^8 + x^4 + x^3 + x + 1, the lowest bit
+/// holding the constant term. This is the same field, with the same bytes, as
+/// `GF<Rijndael, 2, 8>`.
+///
+/// Addition is exclusive or, and multiplication and inversion are looked up in tables of
+/// logarithms to the base x + 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GF256(pub u8);
+
+impl GF256 {
+
+	/// The generator x + 1 of the multiplicative group that the tables are built from
+	pub fn generator() -> GF256 {
+		GF256(3)
+	}
+
+	/// The discrete logarithm of this element to the base x + 1, in [0, 255), or None for zero
+	pub fn log(&self) -> Option<u8> {
+		(!self.is_zero()).then_some(LOG[self.0 as usize])
+	}
+
+	/// (x + 1)^k
+	pub fn exp(k: u8) -> GF256 {
+		GF256(EXP[k as usize])
+	}
+
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for GF256 {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		GF256(self.0 ^ rhs.0)
+	}
+}
+
+impl AddAssign for GF256 {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+/// Subtraction is addition, since every element is its own negative
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for GF256 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+impl SubAssign for GF256 {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl Neg for GF256 {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		self
+	}
+}
+
+impl Mul for GF256 {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		if self.is_zero() || rhs.is_zero() {
+			return GF256(0)
+		}
+		GF256(EXP[LOG[self.0 as usize] as usize + LOG[rhs.0 as usize] as usize])
+	}
+}
+
+impl MulAssign for GF256 {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+impl Div for GF256 {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		if rhs.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+		if self.is_zero() {
+			return GF256(0)
+		}
+		GF256(EXP[LOG[self.0 as usize] as usize + 255 - LOG[rhs.0 as usize] as usize])
+	}
+}
+
+impl DivAssign for GF256 {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl Semiring for GF256 {
+	fn one() -> Self {
+		GF256(1)
+	}
+
+	fn zero() -> Self {
+		GF256(0)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.0 == 0
+	}
+}
+
+impl Ring for GF256 {
+	fn power(&self, n: i64) -> Self {
+		if self.is_zero() {
+			return match n {
+				0 => GF256(1),
+				n if n > 0 => GF256(0),
+				_ => panic!("Cannot divide by zero")
+			}
+		}
+
+		let log = (LOG[self.0 as usize] as i64 * n).rem_euclid(255);
+		GF256(EXP[log as usize])
+	}
+}
+
+impl Field for GF256 {
+	fn inverse(&self) -> Self {
+		GF256(1) / *self
+	}
+}
+
+impl RandomElement for GF256 {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		GF256(rng.gen())
+	}
+}
+
+impl Distribution<GF256> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GF256 {
+		GF256::random(rng)
+	}
+}
+
+/// Each element is numbered by its byte, the same as for `GF<Rijndael, 2, 8>`
+impl FiniteSet for GF256 {
+	fn size() -> u128 {
+		256
+	}
+
+	fn to_index(&self) -> u128 {
+		self.0 as u128
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		GF256(index as u8)
+	}
+}
+
+impl FiniteRing for GF256 {
+	fn characteristic() -> u128 {
+		2
+	}
+}
+
+impl FiniteField for GF256 {
+	fn degree() -> u32 {
+		8
+	}
+}
+
+impl From<GF<Rijndael, 2, 8>> for GF256 {
+	fn from(x: GF<Rijndael, 2, 8>) -> Self {
+		GF256(x.to_byte())
+	}
+}
+
+impl From<GF256> for GF<Rijndael, 2, 8> {
+	fn from(x: GF256) -> Self {
+		GF::from_byte(x.0)
+	}
+}
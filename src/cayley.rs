@@ -0,0 +1,220 @@
+//
+// Cayley tables of finite groups, and isomorphism testing between small groups
+//
+
+use crate::algebra::*;
+
+// MARK: Cayley Tables
+
+/// The full multiplication table of a finite group, with elements referred to by index
+#[derive(Clone, Debug)]
+pub struct CayleyTable<G: Group> {
+	elements: Vec<G>,
+	table: Vec<Vec<usize>>,
+	identity: usize
+}
+
+/// The Cayley table of the finite group G
+pub fn cayley_table<G: FiniteGroup>() -> CayleyTable<G> {
+	CayleyTable::new(G::elements().collect())
+}
+
+impl<G: Group> CayleyTable<G> {
+
+	/// The Cayley table of the group made up of the given elements, which must be closed under
+	/// multiplication and list each element once
+	pub fn new(elements: Vec<G>) -> CayleyTable<G> {
+		let table = elements.iter().map(|a| {
+			elements.iter().map(|b| {
+				let product = a.clone() * b.clone();
+				elements.iter().position(|c| *c == product).expect("The elements are not closed under multiplication")
+			}).collect()
+		}).collect();
+
+		let identity = elements.iter().position(|g| *g == G::identity()).expect("The elements do not contain the identity");
+
+		CayleyTable { elements, table, identity }
+	}
+
+	/// The elements of the group, in table order
+	pub fn elements(&self) -> &[G] {
+		&self.elements
+	}
+
+	/// The number of elements of the group
+	pub fn order(&self) -> usize {
+		self.elements.len()
+	}
+
+	/// The index of the product of the elements with indices i and j
+	pub fn product(&self, i: usize, j: usize) -> usize {
+		self.table[i][j]
+	}
+
+	/// The rows of the table, where entry (i, j) is the index of the product of elements i and j
+	pub fn rows(&self) -> &[Vec<usize>] {
+		&self.table
+	}
+
+	/// The index of the given element, if it is in the group
+	pub fn index_of(&self, g: &G) -> Option<usize> {
+		self.elements.iter().position(|h| h == g)
+	}
+
+	/// The index of the identity element
+	pub fn identity(&self) -> usize {
+		self.identity
+	}
+
+	/// Whether or not the group is abelian
+	pub fn is_abelian(&self) -> bool {
+		(0..self.order()).all(|i| (0..i).all(|j| self.table[i][j] == self.table[j][i]))
+	}
+
+	/// The order of every element, in table order
+	pub fn element_orders(&self) -> Vec<usize> {
+		(0..self.order()).map(|i| {
+			let mut k = 1;
+			let mut power = i;
+			while power != self.identity {
+				power = self.table[power][i];
+				k += 1;
+			}
+			k
+		}).collect()
+	}
+
+	/// A small set of generators of the group, chosen greedily starting from elements of large order
+	pub fn generating_set(&self) -> Vec<usize> {
+		let orders = self.element_orders();
+		let mut candidates: Vec<usize> = (0..self.order()).collect();
+		candidates.sort_by_key(|&i| std::cmp::Reverse(orders[i]));
+
+		let mut generators = Vec::new();
+		let mut generated = vec![false; self.order()];
+		generated[self.identity] = true;
+
+		for g in candidates {
+			if generated[g] {
+				continue
+			}
+			generators.push(g);
+			generated = self.closure(&generators);
+		}
+
+		generators
+	}
+
+	/// Which elements lie in the subgroup generated by the given elements
+	fn closure(&self, generators: &[usize]) -> Vec<bool> {
+		let mut generated = vec![false; self.order()];
+		generated[self.identity] = true;
+		let mut frontier = vec![self.identity];
+
+		while let Some(x) = frontier.pop() {
+			for &g in generators {
+				let y = self.table[x][g];
+				if !generated[y] {
+					generated[y] = true;
+					frontier.push(y);
+				}
+			}
+		}
+
+		generated
+	}
+
+	/// An isomorphism from this group to another, as a list sending each element index here
+	/// to an element index there, if the groups are isomorphic.
+	///
+	/// This searches over images of a generating set, pruned by element orders, so it is meant
+	/// for groups with at most a few hundred elements.
+	pub fn isomorphism_to<H: Group>(&self, other: &CayleyTable<H>) -> Option<Vec<usize>> {
+		if self.order() != other.order() || self.is_abelian() != other.is_abelian() {
+			return None
+		}
+
+		let orders = self.element_orders();
+		let other_orders = other.element_orders();
+
+		let mut sorted = orders.clone();
+		let mut other_sorted = other_orders.clone();
+		sorted.sort();
+		other_sorted.sort();
+		if sorted != other_sorted {
+			return None
+		}
+
+		let generators = self.generating_set();
+		let candidates: Vec<Vec<usize>> = generators.iter().map(|&g| {
+			(0..other.order()).filter(|&h| other_orders[h] == orders[g]).collect()
+		}).collect();
+
+		let mut images = Vec::with_capacity(generators.len());
+		self.search(other, &generators, &candidates, &mut images)
+	}
+
+	/// Whether or not this group is isomorphic to another
+	pub fn is_isomorphic_to<H: Group>(&self, other: &CayleyTable<H>) -> bool {
+		self.isomorphism_to(other).is_some()
+	}
+
+	/// Backtracks over choices of images of the generators
+	fn search<H: Group>(&self, other: &CayleyTable<H>, generators: &[usize], candidates: &[Vec<usize>], images: &mut Vec<usize>) -> Option<Vec<usize>> {
+		if images.len() == generators.len() {
+			return self.extend_to_isomorphism(other, generators, images)
+		}
+
+		for &h in candidates[images.len()].iter() {
+			if images.contains(&h) {
+				continue
+			}
+			images.push(h);
+			if let Some(map) = self.search(other, generators, candidates, images) {
+				return Some(map)
+			}
+			images.pop();
+		}
+
+		None
+	}
+
+	/// Extends an assignment of generator images to the whole group, if that gives an isomorphism
+	fn extend_to_isomorphism<H: Group>(&self, other: &CayleyTable<H>, generators: &[usize], images: &[usize]) -> Option<Vec<usize>> {
+		let n = self.order();
+		let mut map = vec![usize::MAX; n];
+		let mut used = vec![false; n];
+
+		map[self.identity] = other.identity;
+		used[other.identity] = true;
+		let mut frontier = vec![self.identity];
+
+		while let Some(x) = frontier.pop() {
+			for (&g, &h) in generators.iter().zip(images) {
+				let y = self.table[x][g];
+				let image = other.table[map[x]][h];
+
+				if map[y] == usize::MAX {
+					if used[image] {
+						return None
+					}
+					map[y] = image;
+					used[image] = true;
+					frontier.push(y);
+				} else if map[y] != image {
+					return None
+				}
+			}
+		}
+
+		let is_homomorphism = (0..n).all(|a| (0..n).all(|b| map[self.table[a][b]] == other.table[map[a]][map[b]]));
+
+		if is_homomorphism { Some(map) } else { None }
+	}
+
+}
+
+/// Whether or not the finite groups G and H are isomorphic
+pub fn are_isomorphic<G: FiniteGroup, H: FiniteGroup>() -> bool {
+	cayley_table::<G>().is_isomorphic_to(&cayley_table::<H>())
+}
@@ -0,0 +1,139 @@
+//
+// Machine integers whose arithmetic checks for overflow, so generic algorithms can run on them
+// without wrapping around silently
+//
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::error::AlgebraError;
+
+// MARK: Checked Integers
+
+/// A machine integer whose arithmetic checks for overflow.
+///
+/// An operation which overflows gives an overflowed value instead of panicking or wrapping, and
+/// any operation involving an overflowed value overflows too, much like NaN. So a whole
+/// computation can run through the generic traits and be checked once at the end with `value`.
+/// For unsigned types, going below zero counts as overflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Checked<T>(Option<T>);
+
+impl<T: Copy> Checked<T> {
+
+	/// The integer x, which has not overflowed
+	pub fn new(x: T) -> Checked<T> {
+		Checked(Some(x))
+	}
+
+	/// The value of this integer, or an error if some operation leading to it overflowed
+	pub fn value(&self) -> Result<T, AlgebraError> {
+		self.0.ok_or(AlgebraError::Overflow)
+	}
+
+	/// Whether or not some operation leading to this integer overflowed
+	pub fn is_overflow(&self) -> bool {
+		self.0.is_none()
+	}
+
+}
+
+impl<T: Copy> From<T> for Checked<T> {
+	fn from(x: T) -> Self {
+		Checked::new(x)
+	}
+}
+
+macro_rules! impl_checked {
+	($($t:ty),*) => {$(
+		impl Add for Checked<$t> {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self::Output {
+				Checked(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_add(b)))
+			}
+		}
+
+		impl AddAssign for Checked<$t> {
+			fn add_assign(&mut self, rhs: Self) {
+				*self = *self + rhs
+			}
+		}
+
+		impl Sub for Checked<$t> {
+			type Output = Self;
+
+			fn sub(self, rhs: Self) -> Self::Output {
+				Checked(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_sub(b)))
+			}
+		}
+
+		impl SubAssign for Checked<$t> {
+			fn sub_assign(&mut self, rhs: Self) {
+				*self = *self - rhs
+			}
+		}
+
+		impl Mul for Checked<$t> {
+			type Output = Self;
+
+			fn mul(self, rhs: Self) -> Self::Output {
+				Checked(self.0.zip(rhs.0).and_then(|(a, b)| a.checked_mul(b)))
+			}
+		}
+
+		impl MulAssign for Checked<$t> {
+			fn mul_assign(&mut self, rhs: Self) {
+				*self = *self * rhs
+			}
+		}
+
+		impl Neg for Checked<$t> {
+			type Output = Self;
+
+			fn neg(self) -> Self::Output {
+				Checked(self.0.and_then(|a| a.checked_neg()))
+			}
+		}
+
+		impl Semiring for Checked<$t> {
+			fn one() -> Self {
+				Checked(Some(1))
+			}
+
+			fn zero() -> Self {
+				Checked(Some(0))
+			}
+
+			fn is_zero(&self) -> bool {
+				self.0 == Some(0)
+			}
+		}
+
+		impl Ring for Checked<$t> {
+			fn power(&self, n: i64) -> Self {
+				if n < 0 {
+					panic!("Cannot invert ring element")
+				}
+				Checked(self.0.and_then(|a| u32::try_from(n).ok().and_then(|n| a.checked_pow(n))))
+			}
+		}
+
+		impl CommutativeRing for Checked<$t> { }
+
+		/// An overflowed value stays overflowed, rather than counting as not divisible
+		impl IntegralDomain for Checked<$t> {
+			fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+				match (self.0, divisor.0) {
+					(Some(a), Some(b)) => {
+						let q = a.checked_div(b)?;
+						if q.checked_mul(b) == Some(a) { Some(Checked(Some(q))) } else { None }
+					},
+					_ => Some(Checked(None))
+				}
+			}
+		}
+	)*};
+}
+
+impl_checked!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
@@ -0,0 +1,113 @@
+//
+// A toy polynomial commitment scheme, showing how evaluation proofs come from polynomial division
+//
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+
+// MARK: Quotients
+
+/// The quotient (f(x) - f(a)) / (x - a), which divides exactly because a is a root of f(x) - f(a)
+pub fn evaluation_quotient<F: Field>(f: &Polynomial<F>, a: &F) -> Polynomial<F> {
+	let shifted = f.sub_ref(&Polynomial::constant(f.evaluate(a)));
+	shifted / Polynomial::new(vec![-a.clone(), F::one()])
+}
+
+/// The vanishing polynomial (x - a_1)(x - a_2)...(x - a_k) of a set of points
+pub fn vanishing_polynomial<F: Field>(points: &[F]) -> Polynomial<F> {
+	points.iter().fold(Polynomial::one(), |z, a| z * Polynomial::new(vec![-a.clone(), F::one()]))
+}
+
+// MARK: Openings
+
+/// A claim that a committed polynomial takes the given value at a point, with its proof
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opening<F: Field> {
+	pub point: F,
+	pub value: F,
+	pub proof: F
+}
+
+/// A claim that a committed polynomial takes the given values at several points, with a single proof
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchOpening<F: Field> {
+	pub points: Vec<F>,
+	pub values: Vec<F>,
+	pub proof: F
+}
+
+// MARK: Commitment Key
+
+/// The trusted setup of a KZG-style commitment scheme, stripped of its elliptic curve.
+///
+/// A polynomial is committed to by evaluating it at a secret point s. In the real scheme the
+/// commitment is g^f(s) and s is known to nobody, so verifying needs a pairing; here the verifier
+/// simply holds s, which keeps the algebra visible but of course hides nothing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommitmentKey<F: Field> {
+	secret: F
+}
+
+impl<F: Field> CommitmentKey<F> {
+
+	/// A setup with the given secret point
+	pub fn new(secret: F) -> CommitmentKey<F> {
+		CommitmentKey { secret }
+	}
+
+	/// The secret point s
+	pub fn secret(&self) -> &F {
+		&self.secret
+	}
+
+	/// The commitment f(s) to f
+	pub fn commit(&self, f: &Polynomial<F>) -> F {
+		f.evaluate(&self.secret)
+	}
+
+	/// Opens f at a, proving the value with q(s) where q is the evaluation quotient of f at a
+	pub fn open(&self, f: &Polynomial<F>, a: &F) -> Opening<F> {
+		Opening {
+			point: a.clone(),
+			value: f.evaluate(a),
+			proof: evaluation_quotient(f, a).evaluate(&self.secret)
+		}
+	}
+
+	/// Checks an opening against a commitment, using f(s) - f(a) = q(s)(s - a)
+	pub fn verify(&self, commitment: &F, opening: &Opening<F>) -> bool {
+		commitment.sub_ref(&opening.value) == opening.proof.mul_ref(&self.secret.sub_ref(&opening.point))
+	}
+
+	/// Opens f at every point at once.
+	///
+	/// Dividing f by the vanishing polynomial Z of the points gives f = qZ + r, where r agrees with f
+	/// on every point, so the values all come from the small remainder r and the proof is q(s).
+	pub fn open_batch(&self, f: &Polynomial<F>, points: &[F]) -> BatchOpening<F> {
+		let (quotient, remainder) = f.quotient_and_remainder(&vanishing_polynomial(points));
+
+		BatchOpening {
+			points: points.to_vec(),
+			values: points.iter().map(|a| remainder.evaluate(a)).collect(),
+			proof: quotient.evaluate(&self.secret)
+		}
+	}
+
+	/// Checks a batch opening against a commitment, using f(s) - r(s) = q(s)Z(s) where r is the
+	/// polynomial interpolating the claimed values
+	pub fn verify_batch(&self, commitment: &F, opening: &BatchOpening<F>) -> bool {
+		if opening.points.len() != opening.values.len() {
+			return false
+		}
+
+		let points: Vec<(F, F)> = opening.points.iter().cloned().zip(opening.values.iter().cloned()).collect();
+		let remainder = match Polynomial::interpolate(&points) {
+			Some(r) => r,
+			None => return false
+		};
+
+		let vanishing = vanishing_polynomial(&opening.points);
+		commitment.sub_ref(&remainder.evaluate(&self.secret)) == opening.proof.mul_ref(&vanishing.evaluate(&self.secret))
+	}
+
+}
@@ -0,0 +1,307 @@
+//
+// Complex numbers a + bi over any field, where i^2 = -1
+//
+
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+use crate::std_impls::{is_prime, ZM};
+
+// MARK: Complex Numbers
+
+/// The number re + im i in F[i] = F[x]/(x^2 + 1).
+///
+/// This is a field exactly when -1 is not a square in F, as for the reals or for ZM<Q> with
+/// Q = 3 mod 4, where it is GF(Q^2). Otherwise some nonzero elements have norm zero, and have
+/// no inverse, so the `Field` impl requires -1 to be a non-square, and for ZM<Q> inverting
+/// fails to compile unless Q is a prime with Q = 3 mod 4.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Complex<F: Field> {
+	pub re: F,
+	pub im: F
+}
+
+impl<F: Field> Complex<F> {
+
+	/// The number re + im i
+	pub fn new(re: F, im: F) -> Complex<F> {
+		Complex { re, im }
+	}
+
+	/// The image of x under the inclusion F -> F[i]
+	pub fn from_real(x: F) -> Complex<F> {
+		Complex::new(x, F::zero())
+	}
+
+	/// The imaginary unit i
+	pub fn i() -> Complex<F> {
+		Complex::new(F::zero(), F::one())
+	}
+
+	/// The complex conjugate re - im i
+	pub fn conj(&self) -> Complex<F> {
+		Complex::new(self.re.clone(), -self.im.clone())
+	}
+
+	/// The field norm z * conj(z) = re^2 + im^2
+	pub fn norm(&self) -> F {
+		self.re.mul_ref(&self.re) + self.im.mul_ref(&self.im)
+	}
+
+	/// Multiplies both parts by c
+	pub fn scale(&self, c: &F) -> Complex<F> {
+		Complex::new(self.re.mul_ref(c), self.im.mul_ref(c))
+	}
+
+	/// Whether or not the imaginary part is zero
+	pub fn is_real(&self) -> bool {
+		self.im.is_zero()
+	}
+
+}
+
+impl Complex<f64> {
+
+	/// The absolute value |z|, the square root of the norm
+	pub fn abs(&self) -> f64 {
+		self.re.hypot(self.im)
+	}
+
+	/// The argument of z, in (-pi, pi]
+	pub fn arg(&self) -> f64 {
+		self.im.atan2(self.re)
+	}
+
+	/// The number r e^(i theta)
+	pub fn from_polar(r: f64, theta: f64) -> Complex<f64> {
+		Complex::new(r * theta.cos(), r * theta.sin())
+	}
+
+	/// The n-th roots of unity e^(2 pi i k / n), for k = 0, 1, ..., n - 1, counterclockwise from 1
+	pub fn roots_of_unity(n: usize) -> Vec<Complex<f64>> {
+		(0..n).map(|k| Complex::from_polar(1.0, std::f64::consts::TAU * k as f64 / n as f64)).collect()
+	}
+
+	/// The primitive n-th roots of unity e^(2 pi i k / n), for k coprime to n, counterclockwise
+	/// from 1, which are the roots of the n-th cyclotomic polynomial
+	pub fn primitive_roots_of_unity(n: usize) -> Vec<Complex<f64>> {
+		Complex::roots_of_unity(n).into_iter().enumerate()
+			.filter(|&(k, _)| gcd(&(k as i64), &(n as i64)) == 1)
+			.map(|(_, z)| z)
+			.collect()
+	}
+
+}
+
+/// Written as re + im i, or re - |im| i when the imaginary part is written with a minus sign
+impl<F: Field + Display> Display for Complex<F> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let im = self.im.to_string();
+		match im.strip_prefix('-') {
+			Some(magnitude) => write!(f, "{} - {}i", self.re, magnitude),
+			None => write!(f, "{} + {}i", self.re, im)
+		}
+	}
+}
+
+impl<F: Field> From<F> for Complex<F> {
+	fn from(value: F) -> Self {
+		Complex::from_real(value)
+	}
+}
+
+impl<F: Field + RandomElement> RandomElement for Complex<F> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		Complex::new(F::random(rng), F::random(rng))
+	}
+}
+
+impl<F: Field + RandomElement> Distribution<Complex<F>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Complex<F> {
+		Complex::random(rng)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<F: Field> Add for Complex<F> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Complex::new(self.re + rhs.re, self.im + rhs.im)
+	}
+}
+
+impl<F: Field> AddAssign for Complex<F> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.re += rhs.re;
+		self.im += rhs.im;
+	}
+}
+
+impl<F: Field> Neg for Complex<F> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Complex::new(-self.re, -self.im)
+	}
+}
+
+impl<F: Field> Sub for Complex<F> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Complex::new(self.re - rhs.re, self.im - rhs.im)
+	}
+}
+
+impl<F: Field> SubAssign for Complex<F> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.re -= rhs.re;
+		self.im -= rhs.im;
+	}
+}
+
+impl<F: Field> Mul for Complex<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<F: Field> MulAssign for Complex<F> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<F: Field> Div for Complex<F> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<F: Field> DivAssign for Complex<F> {
+	fn div_assign(&mut self, rhs: Self) {
+		self.div_assign_ref(&rhs)
+	}
+}
+
+impl<F: Field> Semiring for Complex<F> {
+	fn one() -> Self {
+		Complex::from_real(F::one())
+	}
+
+	fn zero() -> Self {
+		Complex::from_real(F::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.re.is_zero() && self.im.is_zero()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		self.re.add_assign_ref(&rhs.re);
+		self.im.add_assign_ref(&rhs.im);
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		Complex::new(
+			self.re.mul_ref(&rhs.re) - self.im.mul_ref(&rhs.im),
+			self.re.mul_ref(&rhs.im) + self.im.mul_ref(&rhs.re)
+		)
+	}
+}
+
+impl<F: Field> Ring for Complex<F> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
+
+		let mut power = Complex::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base)
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		self.re.sub_assign_ref(&rhs.re);
+		self.im.sub_assign_ref(&rhs.im);
+	}
+}
+
+/// How a field checks that -1 is not a square in it, specialized for ZM<Q> where that can be
+/// checked at compile time
+trait ComplexBase: Field {
+	fn assert_minus_one_nonsquare();
+}
+
+impl<F: Field> ComplexBase for F {
+	default fn assert_minus_one_nonsquare() { }
+}
+
+impl<const Q: i64> ComplexBase for ZM<Q> {
+	fn assert_minus_one_nonsquare() {
+		let () = MinusOneNonsquare::<Q>::ASSERT;
+	}
+}
+
+struct MinusOneNonsquare<const Q: i64>;
+
+impl<const Q: i64> MinusOneNonsquare<Q> {
+	/// -1 is a square modulo 2 and modulo the primes which are 1 mod 4
+	const ASSERT: () = assert!(is_prime(Q) && Q % 4 == 3, "Complex<ZM<Q>> is only a field for a prime Q = 3 mod 4");
+}
+
+/// A field only when -1 is not a square in F, see `Complex`
+impl<F: Field> Field for Complex<F> {
+	fn inverse(&self) -> Self {
+		match self.try_inverse() {
+			Some(inverse) => inverse,
+			None => panic!("{:?} has norm zero, so it is not invertible", self)
+		}
+	}
+
+	/// conj(z) / N(z), which exists exactly when the norm is invertible
+	fn try_inverse(&self) -> Option<Self> {
+		F::assert_minus_one_nonsquare();
+		self.norm().try_inverse().map(|n| self.conj().scale(&n))
+	}
+
+	fn div_assign_ref(&mut self, rhs: &Self) {
+		self.mul_assign_ref(&rhs.inverse())
+	}
+}
+
+// MARK: Inner Products
+
+/// The Hermitian inner product sum a_i conj(b_i), which is linear in the first argument
+impl<F: Field, const N: usize> InnerProductSpace<Complex<F>> for [Complex<F>; N] {
+	fn inner_product(&self, other: Self) -> Complex<F> {
+		self.iter().zip(other.iter()).fold(Complex::zero(), |sum, (a, b)| sum + a.mul_ref(&b.conj()))
+	}
+}
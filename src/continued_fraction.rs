@@ -0,0 +1,99 @@
+//
+// Continued fractions over a Euclidean domain, their convergents, and rational reconstruction,
+// which recovers a fraction n / d from its residue modulo m
+//
+
+use crate::algebra::*;
+
+// MARK: Continued Fractions
+
+/// The partial quotients [q_0; q_1, q_2, ...] of the continued fraction of a / b, the quotients
+/// of the Euclidean algorithm on a and b, so that a / b = q_0 + 1 / (q_1 + 1 / (q_2 + ...)).
+///
+/// Over the integers with a >= 0 and b > 0 this is the usual simple continued fraction, and for
+/// polynomials over a field the quotients all have positive degree after the first.
+pub fn continued_fraction<R: EuclideanDomain>(a: &R, b: &R) -> Vec<R> {
+	let (mut a, mut b) = (a.clone(), b.clone());
+	let mut quotients = Vec::new();
+
+	while !b.is_zero() {
+		let (q, r) = a.quotient_and_remainder(&b);
+		quotients.push(q);
+		a = std::mem::replace(&mut b, r);
+	}
+
+	quotients
+}
+
+/// The convergents p_k / q_k of the continued fraction with the given partial quotients, as pairs
+/// (p_k, q_k), from p_k = a_k p_(k-1) + p_(k-2) and q_k = a_k q_(k-1) + q_(k-2).
+///
+/// Consecutive convergents satisfy p_k q_(k-1) - p_(k-1) q_k = (-1)^(k-1), so each is in lowest
+/// terms, and the last convergent of `continued_fraction(a, b)` is a / b in lowest terms.
+pub fn convergents<R: EuclideanDomain>(quotients: &[R]) -> Vec<(R, R)> {
+	let (mut p, mut p_previous) = (R::one(), R::zero());
+	let (mut q, mut q_previous) = (R::zero(), R::one());
+
+	quotients.iter().map(|a| {
+		let next_p = a.mul_ref(&p).add_ref(&p_previous);
+		let next_q = a.mul_ref(&q).add_ref(&q_previous);
+		p_previous = std::mem::replace(&mut p, next_p);
+		q_previous = std::mem::replace(&mut q, next_q);
+		(p.clone(), q.clone())
+	}).collect()
+}
+
+// MARK: Rational Reconstruction
+
+/// A fraction n / d with x = n / d modulo m, size(n) <= numerator_bound, size(d) <=
+/// denominator_bound, and d invertible modulo m, or None if there is no such fraction in the
+/// sequence the extended Euclidean algorithm on m and x passes through.
+///
+/// Each remainder r_i of that algorithm is s_i m + t_i x, so r_i / t_i is x modulo m, and the
+/// first remainder within the numerator bound has the smallest denominator. When
+/// 2 numerator_bound denominator_bound < m over the integers, or the bounds on degrees sum to less
+/// than the degree of m for polynomials, a fraction within the bounds is unique if it exists, so
+/// this always finds it. The result is defined up to multiplying n and d by a unit.
+pub fn rational_reconstruct_with_bounds<R: EuclideanDomain>(x: &R, m: &R, numerator_bound: R::SizeType, denominator_bound: R::SizeType) -> Option<(R, R)> {
+	let (mut r_previous, mut r) = (m.clone(), x.quotient_and_remainder(m).1);
+	let (mut t_previous, mut t) = (R::zero(), R::one());
+
+	while r.euc_size() > numerator_bound {
+		if r.is_zero() {
+			return None
+		}
+
+		let (q, next_r) = r_previous.quotient_and_remainder(&r);
+		let mut next_t = t_previous;
+		next_t.sub_assign_ref(&q.mul_ref(&t));
+
+		r_previous = std::mem::replace(&mut r, next_r);
+		t_previous = std::mem::replace(&mut t, next_t);
+	}
+
+	if t.euc_size() > denominator_bound || mod_inverse(&t, m).is_none() {
+		return None
+	}
+	Some((r, t))
+}
+
+/// The fraction n / d with x = n / d modulo m, |n| and 0 < d at most sqrt(m / 2), and d coprime
+/// to m, or None if there is none. Such a fraction is unique when it exists, so a rational number
+/// computed modulo a large enough m, say as the solution of a linear system, is recovered exactly.
+pub fn rational_reconstruct(x: i64, m: i64) -> Option<(i64, i64)> {
+	if m <= 1 {
+		panic!("Rational reconstruction needs a modulus greater than 1, got {}", m)
+	}
+
+	// The largest b with 2 b^2 <= m
+	let mut bound = ((m / 2) as f64).sqrt() as i64;
+	while 2 * (bound as i128 + 1).pow(2) <= m as i128 {
+		bound += 1;
+	}
+	while 2 * (bound as i128).pow(2) > m as i128 {
+		bound -= 1;
+	}
+
+	let (n, d) = rational_reconstruct_with_bounds(&x.rem_euclid(m), &m, bound as u64, bound as u64)?;
+	if d < 0 { Some((-n, -d)) } else { Some((n, d)) }
+}
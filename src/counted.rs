@@ -0,0 +1,302 @@
+//
+// A wrapper ring that counts the arithmetic done through it, for measuring how many operations
+// an algorithm really performs on a given input
+//
+
+use std::cell::Cell;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+
+// MARK: Counters
+
+/// The number of operations performed on `Counted` elements. Subtractions count as additions, and
+/// divisions, including Euclidean and exact division, count as inversions. Negations and
+/// comparisons are not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OperationCounts {
+	pub additions: u64,
+	pub multiplications: u64,
+	pub inversions: u64
+}
+
+thread_local! {
+	static COUNTS: Cell<OperationCounts> = Cell::new(OperationCounts::default());
+}
+
+/// Applies a change to this thread's counts
+fn record(update: impl FnOnce(&mut OperationCounts)) {
+	COUNTS.with(|counts| {
+		let mut current = counts.get();
+		update(&mut current);
+		counts.set(current);
+	})
+}
+
+fn count_addition() {
+	record(|c| c.additions += 1)
+}
+
+fn count_multiplication() {
+	record(|c| c.multiplications += 1)
+}
+
+fn count_inversion() {
+	record(|c| c.inversions += 1)
+}
+
+/// The operations performed on `Counted` elements by this thread since the last reset. Work done
+/// on other threads, such as by the rayon kernels, is counted on those threads.
+pub fn counts() -> OperationCounts {
+	COUNTS.with(Cell::get)
+}
+
+/// Sets this thread's counts back to zero
+pub fn reset_counts() {
+	COUNTS.with(|counts| counts.set(OperationCounts::default()))
+}
+
+/// The result of f, with the operations performed on `Counted` elements while running it. The
+/// running counts are left alone, so measurements can be nested.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, OperationCounts) {
+	let before = counts();
+	let result = f();
+	let after = counts();
+
+	(result, OperationCounts {
+		additions: after.additions - before.additions,
+		multiplications: after.multiplications - before.multiplications,
+		inversions: after.inversions - before.inversions
+	})
+}
+
+// MARK: Counted Elements
+
+/// An element of R which counts every addition, multiplication, and inversion performed on it,
+/// in thread local counters read by `counts` or `measure`.
+///
+/// Running a generic algorithm over `Counted<R>` instead of R, or over polynomials and matrices
+/// of `Counted<R>`, measures its cost on a particular input without changing its result.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Counted<R>(pub R);
+
+impl<R> Counted<R> {
+
+	/// The element x, wrapped to count operations on it
+	pub fn new(x: R) -> Counted<R> {
+		Counted(x)
+	}
+
+	/// The wrapped element
+	pub fn into_inner(self) -> R {
+		self.0
+	}
+
+}
+
+impl<R> From<R> for Counted<R> {
+	fn from(x: R) -> Self {
+		Counted(x)
+	}
+}
+
+impl<R: Ring> Add for Counted<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		count_addition();
+		Counted(self.0 + rhs.0)
+	}
+}
+
+impl<R: Ring> AddAssign for Counted<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Sub for Counted<R> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		count_addition();
+		Counted(self.0 - rhs.0)
+	}
+}
+
+impl<R: Ring> SubAssign for Counted<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.sub_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Neg for Counted<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Counted(-self.0)
+	}
+}
+
+impl<R: Ring> Mul for Counted<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		count_multiplication();
+		Counted(self.0 * rhs.0)
+	}
+}
+
+impl<R: Ring> MulAssign for Counted<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.mul_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring + Div<Output = R>> Div for Counted<R> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		count_inversion();
+		Counted(self.0 / rhs.0)
+	}
+}
+
+impl<R: Ring + Div<Output = R>> DivAssign for Counted<R> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<R: Ring + Rem<Output = R>> Rem for Counted<R> {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		count_inversion();
+		Counted(self.0 % rhs.0)
+	}
+}
+
+impl<R: Ring + Rem<Output = R>> RemAssign for Counted<R> {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = self.clone() % rhs
+	}
+}
+
+impl<R: Ring> Semiring for Counted<R> {
+	fn one() -> Self {
+		Counted(R::one())
+	}
+
+	fn zero() -> Self {
+		Counted(R::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		count_addition();
+		self.0.add_assign_ref(&rhs.0)
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		count_multiplication();
+		self.0.mul_assign_ref(&rhs.0)
+	}
+}
+
+/// Powers are computed by repeated squaring through the counted multiplication, so they count
+/// the multiplications an algorithm calling `power` really pays for. A negative power counts one
+/// inversion.
+impl<R: Ring> Ring for Counted<R> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			count_inversion();
+			return Counted(self.0.power(-1)).power(-n)
+		}
+
+		let mut result = Counted::one();
+		let mut base = self.clone();
+		let mut n = n;
+		while n > 0 {
+			if n & 1 == 1 {
+				result.mul_assign_ref(&base);
+			}
+			n >>= 1;
+			if n > 0 {
+				base = base.mul_ref(&base);
+			}
+		}
+		result
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		count_addition();
+		self.0.sub_assign_ref(&rhs.0)
+	}
+}
+
+impl<F: Field> Field for Counted<F> {
+	fn inverse(&self) -> Self {
+		count_inversion();
+		Counted(self.0.inverse())
+	}
+}
+
+impl<R: PoRing> PoRing for Counted<R> { }
+impl<R: OrderedRing> OrderedRing for Counted<R> { }
+impl<F: PoField> PoField for Counted<F> {
+	fn floor(&self) -> Self {
+		Counted(self.0.floor())
+	}
+}
+impl<F: OrderedField> OrderedField for Counted<F> { }
+
+impl<R: RandomElement> RandomElement for Counted<R> {
+	fn random<G: Rng + ?Sized>(rng: &mut G) -> Self {
+		Counted(R::random(rng))
+	}
+}
+
+/// Counted machine integers are Euclidean domains like the integers they wrap, with GCDs found by
+/// the Euclidean algorithm so that its divisions are counted
+macro_rules! impl_counted_integer_domain {
+	($($t:ty),*) => {$(
+		impl CommutativeRing for Counted<$t> { }
+
+		impl IntegralDomain for Counted<$t> {
+			fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+				euclidean_divide_exact(self, divisor)
+			}
+		}
+
+		impl UniqueFactorizationDomain for Counted<$t> {
+			fn gcd(&self, other: &Self) -> Self {
+				euclidean_gcd(self, other)
+			}
+		}
+
+		impl PrincipalIdealDomain for Counted<$t> { }
+
+		impl EuclideanDomain for Counted<$t> {
+			type SizeType = <$t as EuclideanDomain>::SizeType;
+
+			fn euc_size(&self) -> Self::SizeType {
+				self.0.euc_size()
+			}
+
+			fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+				count_inversion();
+				let (q, r) = self.0.quotient_and_remainder(&divisor.0);
+				(Counted(q), Counted(r))
+			}
+		}
+	)*};
+}
+
+impl_counted_integer_domain!(i8, i16, i32, i64, i128);
@@ -0,0 +1,250 @@
+//
+// The Chinese remainder theorem over a Euclidean domain, for converting whole vectors and
+// polynomials between a composite modulus and its coprime factors, as in residue number systems,
+// and for splitting a finite ring into local rings
+//
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+use crate::std_impls::{factor, ZM};
+
+// MARK: CRT
+
+/// The isomorphism R/(m) -> R/(m_1) x ... x R/(m_k) for pairwise coprime moduli m_i with product
+/// m, together with its inverse, with the constants for both directions computed once up front.
+///
+/// Going back uses Garner's algorithm, which finds the mixed-radix digits v_i of
+/// x = v_0 + v_1 m_0 + v_2 m_0 m_1 + ... one modulus at a time, so every intermediate product is
+/// taken modulo a single m_i. Over the integers this means a product of several 30 bit primes can
+/// be recovered as an i128 or `BigInt` while the residues are handled as i64s would be.
+#[derive(Clone, Debug)]
+pub struct CRT<R: EuclideanDomain> {
+	moduli: Vec<R>,
+	modulus: R,
+
+	/// radices[j] = m_0 m_1 ... m_(j-1)
+	radices: Vec<R>,
+
+	/// radix_residues[i][j] = radices[j] mod m_i, for j < i
+	radix_residues: Vec<Vec<R>>,
+
+	/// radix_inverses[i] = radices[i]^(-1) mod m_i
+	radix_inverses: Vec<R>
+}
+
+/// x mod m, reduced the same way as `mod_inverse`, so over the integers with m > 0 it is in [0, m)
+fn reduce<R: EuclideanDomain>(x: &R, m: &R) -> R {
+	x.quotient_and_remainder(m).1.add_ref(m).quotient_and_remainder(m).1
+}
+
+impl<R: EuclideanDomain> CRT<R> {
+
+	/// Sets up the isomorphism for the given nonzero moduli, or None if they are not pairwise coprime
+	pub fn new(moduli: Vec<R>) -> Option<CRT<R>> {
+		if moduli.iter().any(|m| m.is_zero()) {
+			panic!("The moduli of a CRT must be nonzero")
+		}
+
+		let mut radices = vec![R::one()];
+		let mut radix_residues = Vec::with_capacity(moduli.len());
+		let mut radix_inverses = Vec::with_capacity(moduli.len());
+
+		for (i, m) in moduli.iter().enumerate() {
+			radix_residues.push(radices[..i].iter().map(|r| reduce(r, m)).collect());
+
+			// The product of the earlier moduli is invertible mod m exactly when m is coprime to each
+			radix_inverses.push(mod_inverse(&radices[i], m)?);
+			radices.push(radices[i].mul_ref(m));
+		}
+
+		let modulus = radices.pop().unwrap();
+		Some(CRT { moduli, modulus, radices, radix_residues, radix_inverses })
+	}
+
+	/// The moduli m_i
+	pub fn moduli(&self) -> &[R] {
+		&self.moduli
+	}
+
+	/// The product m of all the moduli
+	pub fn modulus(&self) -> &R {
+		&self.modulus
+	}
+
+	/// The residues of x modulo each m_i
+	pub fn to_residues(&self, x: &R) -> Vec<R> {
+		self.moduli.iter().map(|m| reduce(x, m)).collect()
+	}
+
+	/// The mixed-radix digits v_0, ..., v_(k-1) of the x with the given residues, with each v_i
+	/// reduced modulo m_i and x = v_0 + v_1 m_0 + v_2 m_0 m_1 + ...
+	pub fn to_mixed_radix(&self, residues: &[R]) -> Vec<R> {
+		if residues.len() != self.moduli.len() {
+			panic!("Expected {} residues, got {}", self.moduli.len(), residues.len())
+		}
+
+		let mut digits: Vec<R> = Vec::with_capacity(residues.len());
+		for (i, m) in self.moduli.iter().enumerate() {
+			// The value of the digits so far, modulo m_i
+			let partial = digits.iter().zip(self.radix_residues[i].iter())
+				.fold(R::zero(), |sum, (v, r)| reduce(&sum.add_ref(&v.mul_ref(r)), m));
+			let difference = residues[i].sub_ref(&partial);
+			digits.push(reduce(&reduce(&difference, m).mul_ref(&self.radix_inverses[i]), m));
+		}
+
+		digits
+	}
+
+	/// The unique x modulo m with the given residue modulo each m_i, which over the integers with
+	/// positive moduli is in [0, m)
+	pub fn from_residues(&self, residues: &[R]) -> R {
+		self.to_mixed_radix(residues).iter().zip(self.radices.iter())
+			.fold(R::zero(), |x, (v, radix)| x.add_ref(&v.mul_ref(radix)))
+	}
+
+	/// The residues of every entry of xs, as one vector for each modulus m_i
+	pub fn to_residue_vectors(&self, xs: &[R]) -> Vec<Vec<R>> {
+		self.moduli.iter().map(|m| xs.iter().map(|x| reduce(x, m)).collect()).collect()
+	}
+
+	/// The vector whose residues modulo each m_i are the entries of the given vectors, undoing
+	/// `to_residue_vectors`
+	pub fn from_residue_vectors(&self, vectors: &[Vec<R>]) -> Vec<R> {
+		if vectors.len() != self.moduli.len() {
+			panic!("Expected {} residue vectors, got {}", self.moduli.len(), vectors.len())
+		}
+
+		let n = vectors.first().map_or(0, Vec::len);
+		if vectors.iter().any(|v| v.len() != n) {
+			panic!("The residue vectors must all have the same length")
+		}
+
+		(0..n).map(|j| {
+			let residues: Vec<R> = vectors.iter().map(|v| v[j].clone()).collect();
+			self.from_residues(&residues)
+		}).collect()
+	}
+
+	/// The reductions of p modulo each m_i, coefficient by coefficient
+	pub fn to_residue_polynomials(&self, p: &Polynomial<R>) -> Vec<Polynomial<R>> {
+		self.to_residue_vectors(p.coefficients()).into_iter().map(Polynomial::new).collect()
+	}
+
+	/// The polynomial whose reductions modulo each m_i are the given polynomials, with every
+	/// coefficient recovered as by `from_residues`
+	pub fn from_residue_polynomials(&self, residues: &[Polynomial<R>]) -> Polynomial<R> {
+		let n = residues.iter().map(|p| p.coefficients().len()).max().unwrap_or(0);
+		let vectors: Vec<Vec<R>> = residues.iter().map(|p| (0..n).map(|i| p.coefficient(i)).collect()).collect();
+		Polynomial::new(self.from_residue_vectors(&vectors))
+	}
+
+}
+
+// MARK: Local Decomposition
+
+/// The decomposition of a finite ring R/(m) into the product of the local rings R/(p^e), one for
+/// each prime power p^e exactly dividing m, which is the Chinese remainder theorem for the
+/// factorization of m.
+///
+/// Each R/(p^e) has the single maximal ideal (p), so its units are the elements not divisible by
+/// p. The maps to and from the product are the projections onto each factor and the injections
+/// of each factor, which send y to y e_i for the idempotent e_i that is 1 modulo p_i^(e_i) and 0
+/// modulo the others.
+#[derive(Clone, Debug)]
+pub struct LocalDecomposition<R: EuclideanDomain> {
+	factors: Vec<(R, usize)>,
+	crt: CRT<R>,
+	idempotents: Vec<R>
+}
+
+impl<R: EuclideanDomain> LocalDecomposition<R> {
+
+	/// The decomposition for m = p_1^(e_1) ... p_k^(e_k), given by its prime factors and their
+	/// exponents, or None if two of the primes are associates
+	pub fn from_factorization(factors: Vec<(R, usize)>) -> Option<LocalDecomposition<R>> {
+		let crt = CRT::new(factors.iter().map(|(p, e)| p.power(*e as i64)).collect())?;
+
+		let idempotents = (0..factors.len()).map(|i| {
+			let residues: Vec<R> = (0..factors.len()).map(|j| if i == j { R::one() } else { R::zero() }).collect();
+			crt.from_residues(&residues)
+		}).collect();
+
+		Some(LocalDecomposition { factors, crt, idempotents })
+	}
+
+	/// The modulus m, the product of the p^e
+	pub fn modulus(&self) -> &R {
+		self.crt.modulus()
+	}
+
+	/// The primes p_i and exponents e_i, so the factors of the product are R/(p_i^(e_i))
+	pub fn factors(&self) -> &[(R, usize)] {
+		&self.factors
+	}
+
+	/// The moduli p_i^(e_i) of the local factors
+	pub fn local_moduli(&self) -> &[R] {
+		self.crt.moduli()
+	}
+
+	/// The idempotents e_i, which sum to 1 and have e_i e_j = 0 for i != j
+	pub fn idempotents(&self) -> &[R] {
+		&self.idempotents
+	}
+
+	/// The image of x in each local factor
+	pub fn project(&self, x: &R) -> Vec<R> {
+		self.crt.to_residues(x)
+	}
+
+	/// The image of x in the local factor R/(p_i^(e_i))
+	pub fn projection(&self, i: usize, x: &R) -> R {
+		reduce(x, &self.local_moduli()[i])
+	}
+
+	/// The element of R/(m) which is y in the local factor R/(p_i^(e_i)) and zero in the others
+	pub fn inject(&self, i: usize, y: &R) -> R {
+		reduce(&y.mul_ref(&self.idempotents[i]), self.modulus())
+	}
+
+	/// The element of R/(m) with the given image in each local factor, undoing `project`
+	pub fn lift(&self, components: &[R]) -> R {
+		self.crt.from_residues(components)
+	}
+
+	/// Whether or not x is a unit of R/(m), which is when its image in each local factor lies
+	/// outside the maximal ideal (p_i)
+	pub fn is_unit(&self, x: &R) -> bool {
+		self.factors.iter().all(|(p, _)| !x.quotient_and_remainder(p).1.is_zero())
+	}
+
+}
+
+impl LocalDecomposition<i64> {
+
+	/// The decomposition of Z/(n) into the rings Z/(p^e), for n > 1
+	pub fn of_integers(n: i64) -> LocalDecomposition<i64> {
+		if n <= 1 {
+			panic!("Can only decompose Z/(n) for n > 1, got {}", n)
+		}
+
+		LocalDecomposition::from_factorization(factor(n).into_iter().map(|(p, e)| (p, e as usize)).collect()).unwrap()
+	}
+
+}
+
+impl<const Q: i64> LocalDecomposition<Polynomial<ZM<Q>>> {
+
+	/// The decomposition of ZM<Q>[x] / (f) into the rings ZM<Q>[x] / (g^e) for the monic
+	/// irreducible factors g of a nonconstant f, found by `Polynomial::factor`. Q must be prime.
+	///
+	/// The modulus is f made monic, which generates the same ideal.
+	pub fn of_polynomial(f: &Polynomial<ZM<Q>>) -> LocalDecomposition<Polynomial<ZM<Q>>> {
+		if f.degree() == 0 {
+			panic!("Can only decompose a quotient by a nonconstant polynomial")
+		}
+
+		LocalDecomposition::from_factorization(f.factor()).unwrap()
+	}
+
+}
@@ -0,0 +1,280 @@
+//
+// Constant time arithmetic modulo a prime, for the `ct` feature
+//
+// CtZM<Q> is the field of integers modulo Q like ZM<Q>, but its arithmetic uses masked
+// selections and Barrett reduction instead of branching and dividing on secret values.
+// Inversion uses Fermat's little theorem instead of the extended Euclidean algorithm, whose
+// loop length depends on its input. This needs Q to be a prime below 2^32, which using the
+// arithmetic checks at compile time, and every `val` to be reduced into 0..Q. ZM<Q> itself is
+// left alone, so enabling the feature changes nothing for other moduli.
+//
+
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+use crate::std_impls::{is_prime, ZM};
+
+/// x if x is in 0..Q, or x + Q if x is in -Q..0, without branching
+fn add_q_if_negative<const Q: i64>(x: i64) -> i64 {
+	// x >> 63 is all ones exactly when x is negative
+	x + (Q & (x >> 63))
+}
+
+/// x mod Q for x < Q^2, by Barrett reduction
+fn reduce<const Q: i64>(x: u64) -> i64 {
+	let m = (1u128 << 64) / Q as u128;
+	let q = ((x as u128 * m) >> 64) as u64;
+
+	// The estimate q is at most one less than x / Q, so this is in 0..2Q
+	let r = (x - q * Q as u64) as i64;
+	add_q_if_negative::<Q>(r - Q)
+}
+
+// MARK: Constant Time Residues
+
+/// The field of the integers modulo a prime Q below 2^32, with arithmetic that runs in time
+/// independent of the values involved. Convert to and from ZM<Q> with `From`.
+#[derive(Clone, Copy, Default)]
+pub struct CtZM<const Q: i64> {
+	pub val: i64
+}
+
+impl<const Q: i64> CtZM<Q> {
+
+	/// Evaluating this fails to compile unless Q is a prime below 2^32, which the products need
+	/// to fit in a u64 and Fermat inversion needs to be correct
+	pub const ASSERT_CT: () = assert!(
+		is_prime(Q) && Q < 1 << 32,
+		"The modulus of CtZM<Q> must be a prime below 2^32"
+	);
+
+	/// The residue of val modulo Q, which fails to compile unless Q passes `ASSERT_CT`
+	pub const fn new(val: i64) -> CtZM<Q> {
+		let () = Self::ASSERT_CT;
+		CtZM { val: val.rem_euclid(Q) }
+	}
+
+	/// The inverse by Fermat's little theorem, as self^(Q - 2), which sends zero to zero rather
+	/// than panicking so that it does not branch on the value of self
+	pub fn invert_or_zero(&self) -> CtZM<Q> {
+		let () = Self::ASSERT_CT;
+
+		// The exponent is public, so square and multiply leaks nothing about self
+		self.power(Q - 2)
+	}
+
+}
+
+impl<const Q: i64> From<ZM<Q>> for CtZM<Q> {
+	fn from(value: ZM<Q>) -> Self {
+		CtZM::new(value.val)
+	}
+}
+
+impl<const Q: i64> From<CtZM<Q>> for ZM<Q> {
+	fn from(value: CtZM<Q>) -> Self {
+		ZM::new(value.val)
+	}
+}
+
+impl<const Q: i64> From<i64> for CtZM<Q> {
+	fn from(value: i64) -> Self {
+		CtZM::new(value)
+	}
+}
+
+impl<const Q: i64> Debug for CtZM<Q> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		Debug::fmt(&self.val, f)
+	}
+}
+
+/// The representative in 0..Q with the modulus, like "3 mod 17"
+impl<const Q: i64> Display for CtZM<Q> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{} mod {}", self.val, Q)
+	}
+}
+
+/// Compares in constant time
+impl<const Q: i64> PartialEq for CtZM<Q> {
+	fn eq(&self, other: &Self) -> bool {
+		self.ct_eq(other).into()
+	}
+}
+
+impl<const Q: i64> Eq for CtZM<Q> { }
+
+impl<const Q: i64> Hash for CtZM<Q> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.val.hash(state)
+	}
+}
+
+impl<const Q: i64> RandomElement for CtZM<Q> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		CtZM::new(rng.gen_range(0..Q))
+	}
+}
+
+impl<const Q: i64> Distribution<CtZM<Q>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CtZM<Q> {
+		CtZM::random(rng)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<const Q: i64> Add for CtZM<Q> {
+	type Output = CtZM<Q>;
+
+	fn add(self, rhs: CtZM<Q>) -> Self::Output {
+		CtZM { val: add_q_if_negative::<Q>(self.val + rhs.val - Q) }
+	}
+}
+
+impl<const Q: i64> AddAssign for CtZM<Q> {
+	fn add_assign(&mut self, rhs: CtZM<Q>) {
+		*self = *self + rhs
+	}
+}
+
+impl<const Q: i64> Sub for CtZM<Q> {
+	type Output = CtZM<Q>;
+
+	fn sub(self, rhs: CtZM<Q>) -> Self::Output {
+		CtZM { val: add_q_if_negative::<Q>(self.val - rhs.val) }
+	}
+}
+
+impl<const Q: i64> SubAssign for CtZM<Q> {
+	fn sub_assign(&mut self, rhs: CtZM<Q>) {
+		*self = *self - rhs
+	}
+}
+
+impl<const Q: i64> Mul for CtZM<Q> {
+	type Output = CtZM<Q>;
+
+	fn mul(self, rhs: CtZM<Q>) -> CtZM<Q> {
+		let () = Self::ASSERT_CT;
+		CtZM { val: reduce::<Q>(self.val as u64 * rhs.val as u64) }
+	}
+}
+
+impl<const Q: i64> MulAssign for CtZM<Q> {
+	fn mul_assign(&mut self, rhs: CtZM<Q>) {
+		*self = *self * rhs
+	}
+}
+
+impl<const Q: i64> Neg for CtZM<Q> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		CtZM::zero() - self
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64> Div for CtZM<Q> {
+	type Output = CtZM<Q>;
+
+	fn div(self, rhs: CtZM<Q>) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const Q: i64> DivAssign for CtZM<Q> {
+	fn div_assign(&mut self, rhs: CtZM<Q>) {
+		*self = *self / rhs
+	}
+}
+
+impl<const Q: i64> Semiring for CtZM<Q> {
+	fn one() -> Self {
+		CtZM { val: 1 }
+	}
+
+	fn zero() -> Self {
+		CtZM { val: 0 }
+	}
+
+	fn is_zero(&self) -> bool {
+		*self == CtZM::zero()
+	}
+}
+
+impl<const Q: i64> Ring for CtZM<Q> {
+	/// Square and multiply, which depends on the exponent but not on self
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
+
+		let mut power = CtZM::one();
+		let mut base = *self;
+		let mut e = n;
+
+		while e > 0 {
+			let bit = Choice::from((e & 1) as u8);
+			power = CtZM::conditional_select(&power, &(power * base), bit);
+			base *= base;
+			e >>= 1;
+		}
+
+		power
+	}
+}
+
+impl<const Q: i64> Field for CtZM<Q> {
+	/// The inverse by Fermat's little theorem, which panics on zero like any other field. Use
+	/// `invert_or_zero` to avoid branching on whether self is zero.
+	fn inverse(&self) -> Self {
+		match self.try_inverse() {
+			Some(inverse) => inverse,
+			None => panic!("0 is not invertible modulo {}", Q)
+		}
+	}
+
+	fn try_inverse(&self) -> Option<Self> {
+		if self.is_zero() {
+			None
+		} else {
+			Some(self.invert_or_zero())
+		}
+	}
+}
+
+// MARK: Selection
+
+impl<const Q: i64> ConstantTimeEq for CtZM<Q> {
+	fn ct_eq(&self, other: &Self) -> Choice {
+		self.val.ct_eq(&other.val)
+	}
+}
+
+impl<const Q: i64> ConditionallySelectable for CtZM<Q> {
+	fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+		CtZM { val: i64::conditional_select(&a.val, &b.val, choice) }
+	}
+}
+
+impl<const Q: i64> ConstantTimeEq for ZM<Q> {
+	fn ct_eq(&self, other: &Self) -> Choice {
+		self.val.ct_eq(&other.val)
+	}
+}
+
+impl<const Q: i64> ConditionallySelectable for ZM<Q> {
+	fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+		ZM::<Q> { val: i64::conditional_select(&a.val, &b.val, choice) }
+	}
+}
@@ -0,0 +1,546 @@
+//
+// Montgomery and twisted Edwards models of elliptic curves over the prime fields ZM<Q>
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::elliptic_curve::{ECPoint, EllipticCurve};
+use crate::std_impls::ZM;
+
+// MARK: Montgomery Curves
+
+/// The Montgomery curve By^2 = x^3 + Ax^2 + x over the field of integers modulo an odd prime Q
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MontgomeryCurve<const Q: i64, const A: i64, const B: i64>;
+
+impl<const Q: i64, const A: i64, const B: i64> MontgomeryCurve<Q, A, B> {
+
+	/// The coefficient A of this curve
+	pub fn a() -> ZM<Q> {
+		ZM::from_int(A)
+	}
+
+	/// The coefficient B of this curve
+	pub fn b() -> ZM<Q> {
+		ZM::from_int(B)
+	}
+
+	/// Whether or not this curve is nonsingular, which happens when B(A^2 - 4) is nonzero
+	pub fn is_nonsingular() -> bool {
+		!(Self::b() * (Self::a() * Self::a() - ZM::from_int(4))).is_zero()
+	}
+
+	/// Whether or not the point (x, y) lies on this curve
+	pub fn contains(x: ZM<Q>, y: ZM<Q>) -> bool {
+		Self::b() * y * y == x * x * x + Self::a() * x * x + x
+	}
+
+	/// The affine point (x, y), if it lies on this curve
+	pub fn point(x: ZM<Q>, y: ZM<Q>) -> Option<MontgomeryPoint<Q, A, B>> {
+		if Self::contains(x, y) {
+			Some(MontgomeryPoint::Affine { x, y })
+		} else {
+			None
+		}
+	}
+
+	/// Every point on this curve, including the point at infinity.
+	///
+	/// This walks the whole field, so it is only reasonable for small Q.
+	pub fn points() -> Vec<MontgomeryPoint<Q, A, B>> {
+		let mut points = vec![MontgomeryPoint::Infinity];
+
+		for v in 0..Q {
+			let x = ZM::from_int(v);
+			if let Some((y1, y2)) = ((x * x * x + Self::a() * x * x + x) / Self::b()).sqrt() {
+				points.push(MontgomeryPoint::Affine { x, y: y1 });
+				if y1 != y2 {
+					points.push(MontgomeryPoint::Affine { x, y: y2 });
+				}
+			}
+		}
+
+		points
+	}
+
+	/// The x coordinate of kP, where P is any point with x coordinate x, by the x-only Montgomery ladder.
+	///
+	/// Returns `None` if kP is the point at infinity. The ladder does the same field operations for
+	/// every bit of k, and works even if x is the x coordinate of a point on the quadratic twist.
+	pub fn ladder(x: ZM<Q>, k: u64) -> Option<ZM<Q>> {
+		// The ladder formulas degenerate at (0, 0), which has order 2
+		if x.is_zero() {
+			return if k % 2 == 1 { Some(x) } else { None }
+		}
+
+		let a24 = (Self::a() - ZM::from_int(2)) / ZM::from_int(4);
+
+		// (x2 : z2) is mP and (x3 : z3) is (m + 1)P, for m the bits of k read so far
+		let (mut x2, mut z2) = (ZM::one(), ZM::zero());
+		let (mut x3, mut z3) = (x, ZM::one());
+
+		for i in (0..64).rev() {
+			let bit = (k >> i) & 1 == 1;
+			if bit {
+				std::mem::swap(&mut x2, &mut x3);
+				std::mem::swap(&mut z2, &mut z3);
+			}
+
+			let a = x2 + z2;
+			let aa = a * a;
+			let b = x2 - z2;
+			let bb = b * b;
+			let e = aa - bb;
+			let c = x3 + z3;
+			let d = x3 - z3;
+			let da = d * a;
+			let cb = c * b;
+
+			x3 = (da + cb) * (da + cb);
+			z3 = x * (da - cb) * (da - cb);
+			x2 = aa * bb;
+			z2 = e * (aa + a24 * e);
+
+			if bit {
+				std::mem::swap(&mut x2, &mut x3);
+				std::mem::swap(&mut z2, &mut z3);
+			}
+		}
+
+		if z2.is_zero() { None } else { Some(x2 / z2) }
+	}
+
+	/// The coefficients (a, b) of the short Weierstrass curve y^2 = x^3 + ax + b isomorphic to this curve
+	pub fn weierstrass_coefficients() -> (ZM<Q>, ZM<Q>) {
+		let (a, b) = (Self::a(), Self::b());
+		let weierstrass_a = (ZM::from_int(3) - a * a) / (ZM::from_int(3) * b * b);
+		let weierstrass_b = (ZM::from_int(2) * a * a * a - ZM::from_int(9) * a) / (ZM::from_int(27) * b * b * b);
+		(weierstrass_a, weierstrass_b)
+	}
+
+	/// The coefficients (a, d) of the twisted Edwards curve birationally equivalent to this curve,
+	/// which are (A + 2) / B and (A - 2) / B
+	pub fn edwards_coefficients() -> (ZM<Q>, ZM<Q>) {
+		((Self::a() + ZM::from_int(2)) / Self::b(), (Self::a() - ZM::from_int(2)) / Self::b())
+	}
+
+}
+
+/// A point on the Montgomery curve By^2 = x^3 + Ax^2 + x over ZM<Q>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MontgomeryPoint<const Q: i64, const A: i64, const B: i64> {
+	/// The point at infinity, which is the group identity
+	Infinity,
+
+	/// A point (x, y) in the affine plane
+	Affine { x: ZM<Q>, y: ZM<Q> }
+}
+
+impl<const Q: i64, const A: i64, const B: i64> MontgomeryPoint<Q, A, B> {
+
+	/// Whether or not this is the point at infinity
+	pub fn is_infinity(&self) -> bool {
+		matches!(self, MontgomeryPoint::Infinity)
+	}
+
+	/// The affine coordinates of this point, if it is not the point at infinity
+	pub fn coordinates(&self) -> Option<(ZM<Q>, ZM<Q>)> {
+		match self {
+			MontgomeryPoint::Infinity => None,
+			MontgomeryPoint::Affine { x, y } => Some((*x, *y))
+		}
+	}
+
+	/// Computes k * self with the Montgomery ladder, which does one addition and one doubling per bit of k
+	pub fn scalar_mul(&self, k: i64) -> Self {
+		if k < 0 {
+			return (-*self).scalar_mul(-k)
+		}
+
+		// r1 - r0 is always self
+		let mut r0 = MontgomeryPoint::Infinity;
+		let mut r1 = *self;
+
+		for i in (0..64).rev() {
+			if (k >> i) & 1 == 1 {
+				r0 += r1;
+				r1 += r1;
+			} else {
+				r1 += r0;
+				r0 += r0;
+			}
+		}
+
+		r0
+	}
+
+	/// The image of this point on the isomorphic short Weierstrass curve, under (x, y) -> (x/B + A/3B, y/B).
+	///
+	/// This panics unless (WA, WB) are the coefficients given by `MontgomeryCurve::weierstrass_coefficients`.
+	pub fn to_weierstrass<const WA: i64, const WB: i64>(&self) -> ECPoint<Q, WA, WB> {
+		if MontgomeryCurve::<Q, A, B>::weierstrass_coefficients() != (EllipticCurve::<Q, WA, WB>::a(), EllipticCurve::<Q, WA, WB>::b()) {
+			panic!("y^2 = x^3 + {}x + {} is not the Weierstrass form of this Montgomery curve", WA, WB)
+		}
+
+		match self {
+			MontgomeryPoint::Infinity => ECPoint::Infinity,
+			MontgomeryPoint::Affine { x, y } => {
+				let (a, b) = (MontgomeryCurve::<Q, A, B>::a(), MontgomeryCurve::<Q, A, B>::b());
+				ECPoint::Affine { x: *x / b + a / (ZM::from_int(3) * b), y: *y / b }
+			}
+		}
+	}
+
+	/// The image of this point on the twisted Edwards curve, under (x, y) -> (x/y, (x - 1)/(x + 1)).
+	///
+	/// The point (0, 0) goes to (0, -1). Returns `None` for the points where y = 0 or x = -1 otherwise,
+	/// which have no affine image. This panics unless (EA, ED) are the coefficients given by
+	/// `MontgomeryCurve::edwards_coefficients`.
+	pub fn to_edwards<const EA: i64, const ED: i64>(&self) -> Option<EdwardsPoint<Q, EA, ED>> {
+		if MontgomeryCurve::<Q, A, B>::edwards_coefficients() != (EdwardsCurve::<Q, EA, ED>::a(), EdwardsCurve::<Q, EA, ED>::d()) {
+			panic!("{}x^2 + y^2 = 1 + {}x^2y^2 is not birationally equivalent to this Montgomery curve", EA, ED)
+		}
+
+		match self {
+			MontgomeryPoint::Infinity => Some(EdwardsPoint::identity()),
+			MontgomeryPoint::Affine { x, y } if x.is_zero() && y.is_zero() => Some(EdwardsPoint { x: ZM::zero(), y: -ZM::one() }),
+			MontgomeryPoint::Affine { x, y } => {
+				if y.is_zero() || (*x + ZM::one()).is_zero() {
+					return None
+				}
+				Some(EdwardsPoint { x: *x / *y, y: (*x - ZM::one()) / (*x + ZM::one()) })
+			}
+		}
+	}
+
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Add for MontgomeryPoint<Q, A, B> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let (x1, y1, x2, y2) = match (self, rhs) {
+			(MontgomeryPoint::Infinity, _) => return rhs,
+			(_, MontgomeryPoint::Infinity) => return self,
+			(MontgomeryPoint::Affine { x: x1, y: y1 }, MontgomeryPoint::Affine { x: x2, y: y2 }) => (x1, y1, x2, y2)
+		};
+
+		let (a, b) = (MontgomeryCurve::<Q, A, B>::a(), MontgomeryCurve::<Q, A, B>::b());
+
+		let slope = if x1 != x2 {
+			(y2 - y1) / (x2 - x1)
+		} else if y1 == y2 && !y1.is_zero() {
+			// Doubling: the slope of the tangent line
+			(ZM::from_int(3) * x1 * x1 + ZM::from_int(2) * a * x1 + ZM::one()) / (ZM::from_int(2) * b * y1)
+		} else {
+			// P + (-P), or doubling a point of order 2
+			return MontgomeryPoint::Infinity
+		};
+
+		let x3 = b * slope * slope - a - x1 - x2;
+		let y3 = slope * (x1 - x3) - y1;
+
+		MontgomeryPoint::Affine { x: x3, y: y3 }
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AddAssign for MontgomeryPoint<Q, A, B> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Neg for MontgomeryPoint<Q, A, B> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		match self {
+			MontgomeryPoint::Infinity => MontgomeryPoint::Infinity,
+			MontgomeryPoint::Affine { x, y } => MontgomeryPoint::Affine { x, y: -y }
+		}
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Sub for MontgomeryPoint<Q, A, B> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + (-rhs)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> SubAssign for MontgomeryPoint<Q, A, B> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Mul for MontgomeryPoint<Q, A, B> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> MulAssign for MontgomeryPoint<Q, A, B> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self += rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Div for MontgomeryPoint<Q, A, B> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> DivAssign for MontgomeryPoint<Q, A, B> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self -= rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Monoid for MontgomeryPoint<Q, A, B> {
+	fn identity() -> Self {
+		MontgomeryPoint::Infinity
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Group for MontgomeryPoint<Q, A, B> {
+
+	fn inverse(&self) -> Self {
+		-*self
+	}
+
+	fn power(&self, n: i64) -> Self {
+		self.scalar_mul(n)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AbelianGroup for MontgomeryPoint<Q, A, B> { }
+
+// MARK: Twisted Edwards Curves
+
+/// The twisted Edwards curve Ax^2 + y^2 = 1 + Dx^2y^2 over the field of integers modulo an odd prime Q
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EdwardsCurve<const Q: i64, const A: i64, const D: i64>;
+
+impl<const Q: i64, const A: i64, const D: i64> EdwardsCurve<Q, A, D> {
+
+	/// The coefficient A of this curve
+	pub fn a() -> ZM<Q> {
+		ZM::from_int(A)
+	}
+
+	/// The coefficient D of this curve
+	pub fn d() -> ZM<Q> {
+		ZM::from_int(D)
+	}
+
+	/// Whether or not this curve is nonsingular, which happens when AD(A - D) is nonzero
+	pub fn is_nonsingular() -> bool {
+		!(Self::a() * Self::d() * (Self::a() - Self::d())).is_zero()
+	}
+
+	/// Whether or not the addition law is complete, meaning it never divides by zero.
+	/// This happens when A is a square and D is not.
+	pub fn is_complete() -> bool {
+		Self::a().sqrt().is_some() && Self::d().sqrt().is_none()
+	}
+
+	/// Whether or not the point (x, y) lies on this curve
+	pub fn contains(x: ZM<Q>, y: ZM<Q>) -> bool {
+		Self::a() * x * x + y * y == ZM::one() + Self::d() * x * x * y * y
+	}
+
+	/// The point (x, y), if it lies on this curve
+	pub fn point(x: ZM<Q>, y: ZM<Q>) -> Option<EdwardsPoint<Q, A, D>> {
+		if Self::contains(x, y) {
+			Some(EdwardsPoint { x, y })
+		} else {
+			None
+		}
+	}
+
+	/// Every affine point on this curve, which is all of them when the curve is complete.
+	///
+	/// This walks the whole field, so it is only reasonable for small Q.
+	pub fn points() -> Vec<EdwardsPoint<Q, A, D>> {
+		let mut points = Vec::new();
+
+		for v in 0..Q {
+			let y = ZM::from_int(v);
+			let denominator = Self::a() - Self::d() * y * y;
+			if denominator.is_zero() {
+				continue
+			}
+
+			if let Some((x1, x2)) = ((ZM::one() - y * y) / denominator).sqrt() {
+				points.push(EdwardsPoint { x: x1, y });
+				if x1 != x2 {
+					points.push(EdwardsPoint { x: x2, y });
+				}
+			}
+		}
+
+		points
+	}
+
+}
+
+/// An affine point on the twisted Edwards curve Ax^2 + y^2 = 1 + Dx^2y^2 over ZM<Q>.
+///
+/// The identity is (0, 1), and there is no point at infinity to special-case.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdwardsPoint<const Q: i64, const A: i64, const D: i64> {
+	pub x: ZM<Q>,
+	pub y: ZM<Q>
+}
+
+impl<const Q: i64, const A: i64, const D: i64> EdwardsPoint<Q, A, D> {
+
+	/// Computes k * self using double-and-add
+	pub fn scalar_mul(&self, k: i64) -> Self {
+		if k < 0 {
+			return (-*self).scalar_mul(-k)
+		}
+
+		let mut result = Self::identity();
+		let mut addend = *self;
+		let mut k = k;
+
+		while k > 0 {
+			if k & 1 == 1 {
+				result += addend
+			}
+			addend += addend;
+			k >>= 1;
+		}
+
+		result
+	}
+
+	/// The image of this point on the Montgomery curve, under (x, y) -> ((1 + y)/(1 - y), (1 + y)/((1 - y)x)).
+	///
+	/// The identity goes to the point at infinity and (0, -1) to (0, 0). This panics unless the
+	/// Montgomery curve's `edwards_coefficients` are (A, D).
+	pub fn to_montgomery<const MA: i64, const MB: i64>(&self) -> MontgomeryPoint<Q, MA, MB> {
+		if MontgomeryCurve::<Q, MA, MB>::edwards_coefficients() != (EdwardsCurve::<Q, A, D>::a(), EdwardsCurve::<Q, A, D>::d()) {
+			panic!("By^2 = x^3 + {}x^2 + x with B = {} is not birationally equivalent to this Edwards curve", MA, MB)
+		}
+
+		if self.x.is_zero() {
+			return if self.y == ZM::one() {
+				MontgomeryPoint::Infinity
+			} else {
+				MontgomeryPoint::Affine { x: ZM::zero(), y: ZM::zero() }
+			}
+		}
+
+		let u = (ZM::one() + self.y) / (ZM::one() - self.y);
+		MontgomeryPoint::Affine { x: u, y: u / self.x }
+	}
+
+}
+
+impl<const Q: i64, const A: i64, const D: i64> Add for EdwardsPoint<Q, A, D> {
+	type Output = Self;
+
+	/// The unified addition law, which also doubles, and never fails when the curve is complete
+	fn add(self, rhs: Self) -> Self::Output {
+		let (a, d) = (EdwardsCurve::<Q, A, D>::a(), EdwardsCurve::<Q, A, D>::d());
+		let t = d * self.x * rhs.x * self.y * rhs.y;
+
+		EdwardsPoint {
+			x: (self.x * rhs.y + self.y * rhs.x) / (ZM::one() + t),
+			y: (self.y * rhs.y - a * self.x * rhs.x) / (ZM::one() - t)
+		}
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> AddAssign for EdwardsPoint<Q, A, D> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> Neg for EdwardsPoint<Q, A, D> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		EdwardsPoint { x: -self.x, y: self.y }
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> Sub for EdwardsPoint<Q, A, D> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + (-rhs)
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> SubAssign for EdwardsPoint<Q, A, D> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const D: i64> Mul for EdwardsPoint<Q, A, D> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const D: i64> MulAssign for EdwardsPoint<Q, A, D> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self += rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const D: i64> Div for EdwardsPoint<Q, A, D> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const D: i64> DivAssign for EdwardsPoint<Q, A, D> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self -= rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> Monoid for EdwardsPoint<Q, A, D> {
+	fn identity() -> Self {
+		EdwardsPoint { x: ZM::zero(), y: ZM::one() }
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> Group for EdwardsPoint<Q, A, D> {
+
+	fn inverse(&self) -> Self {
+		-*self
+	}
+
+	fn power(&self, n: i64) -> Self {
+		self.scalar_mul(n)
+	}
+}
+
+impl<const Q: i64, const A: i64, const D: i64> AbelianGroup for EdwardsPoint<Q, A, D> { }
@@ -0,0 +1,339 @@
+//
+// The rings R_q = Z_q[x] / (x^n + 1) of lattice cryptography, with multiplication by the
+// negacyclic number-theoretic transform
+//
+
+use std::sync::OnceLock;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::ntt::{negacyclic_intt_with_root, negacyclic_ntt_with_root, root_of_unity};
+use crate::polynomial::Polynomial;
+use crate::random::RandomElement;
+use crate::sampling::{CenteredBinomial, DiscreteGaussian, UniformBounded};
+use crate::std_impls::ZM;
+
+// MARK: Cyclotomic Rings
+
+/// An element of Z_q[x] / (x^n + 1), stored as its n coefficients.
+///
+/// For n a power of two, x^n + 1 is the 2n-th cyclotomic polynomial, and when Q is a prime with
+/// 2n dividing Q - 1, as in Dilithium or Falcon, products are taken by the negacyclic transform.
+/// Each element keeps its transform once it has been computed, so multiplying one element by
+/// many others, like a public key, only transforms it once, and products keep theirs. Other
+/// moduli, like Kyber's, fall back to schoolbook multiplication.
+#[derive(Clone)]
+pub struct CyclotomicRing<const Q: i64, const N: usize> {
+	coefficients: [ZM<Q>; N],
+	transform: OnceLock<Vec<ZM<Q>>>
+}
+
+impl<const Q: i64, const N: usize> CyclotomicRing<Q, N> {
+
+	/// The element with the given coefficients, constant term first
+	pub fn new(coefficients: [ZM<Q>; N]) -> Self {
+		CyclotomicRing { coefficients, transform: OnceLock::new() }
+	}
+
+	/// The element with the given integer coefficients, reduced modulo Q
+	pub fn from_ints(coefficients: [i64; N]) -> Self {
+		CyclotomicRing::new(coefficients.map(ZM::from_int))
+	}
+
+	/// The constant c
+	pub fn constant(c: ZM<Q>) -> Self {
+		let mut coefficients = [ZM::zero(); N];
+		coefficients[0] = c;
+		CyclotomicRing::new(coefficients)
+	}
+
+	/// The generator x, whose n-th power is -1
+	pub fn x() -> Self {
+		if N < 2 {
+			return CyclotomicRing::constant(-ZM::one())
+		}
+		let mut coefficients = [ZM::zero(); N];
+		coefficients[1] = ZM::one();
+		CyclotomicRing::new(coefficients)
+	}
+
+	/// The coefficients, constant term first
+	pub fn coefficients(&self) -> &[ZM<Q>; N] {
+		&self.coefficients
+	}
+
+	/// The coefficients as representatives in (-Q/2, Q/2], the form in which small elements like
+	/// errors and secrets are small
+	pub fn centered_coefficients(&self) -> [i64; N] {
+		self.coefficients.map(|c| if c.val > Q / 2 { c.val - Q } else { c.val })
+	}
+
+	/// The largest absolute value of a centered coefficient
+	pub fn infinity_norm(&self) -> i64 {
+		self.centered_coefficients().iter().map(|c| c.abs()).max().unwrap_or(0)
+	}
+
+	/// Multiplies every coefficient by c
+	pub fn scale(&self, c: &ZM<Q>) -> Self {
+		CyclotomicRing::new(self.coefficients.map(|x| x * *c))
+	}
+
+	/// The representative of degree less than n of this element, as a polynomial
+	pub fn to_polynomial(&self) -> Polynomial<ZM<Q>> {
+		Polynomial::new(self.coefficients.to_vec())
+	}
+
+	/// The primitive 2n-th root of unity the transform uses, if there is one
+	fn psi() -> Option<ZM<Q>> {
+		root_of_unity(2 * N)
+	}
+
+	/// The negacyclic transform of this element, computed the first time it is needed, or None
+	/// if ZM<Q> has no primitive 2n-th root of unity
+	pub fn transform(&self) -> Option<&[ZM<Q>]> {
+		let psi = Self::psi()?;
+		Some(self.transform.get_or_init(|| negacyclic_ntt_with_root(&self.coefficients, psi).unwrap()))
+	}
+
+	/// The element whose negacyclic transform is the given values, which it keeps
+	fn from_transform(values: Vec<ZM<Q>>, psi: ZM<Q>) -> Self {
+		let coefficients = negacyclic_intt_with_root(&values, psi).unwrap();
+		CyclotomicRing {
+			coefficients: coefficients.try_into().unwrap(),
+			transform: OnceLock::from(values)
+		}
+	}
+
+	/// The product modulo x^n + 1 by the definition, where x^(n + k) = -x^k
+	fn schoolbook_product(&self, rhs: &Self) -> Self {
+		let mut coefficients = [ZM::zero(); N];
+		for (i, a) in self.coefficients.iter().enumerate() {
+			for (j, b) in rhs.coefficients.iter().enumerate() {
+				if i + j < N {
+					coefficients[i + j] += *a * *b;
+				} else {
+					coefficients[i + j - N] -= *a * *b;
+				}
+			}
+		}
+		CyclotomicRing::new(coefficients)
+	}
+
+	/// The product of two elements, pointwise on their transforms when there are transforms
+	fn product(&self, rhs: &Self) -> Self {
+		match (Self::psi(), self.transform(), rhs.transform()) {
+			(Some(psi), Some(a), Some(b)) => Self::from_transform(
+				a.iter().zip(b).map(|(x, y)| *x * *y).collect(),
+				psi
+			),
+			_ => self.schoolbook_product(rhs)
+		}
+	}
+
+	/// The multiplicative inverse, if this element is a unit and the transform is available, which
+	/// is when every value of its transform is nonzero
+	pub fn try_inverse(&self) -> Option<Self> {
+		let psi = Self::psi()?;
+		let values = self.transform()?.iter().map(|x| x.try_inverse()).collect::<Option<Vec<_>>>()?;
+		Some(Self::from_transform(values, psi))
+	}
+
+}
+
+impl<const Q: i64, const N: usize> From<Polynomial<ZM<Q>>> for CyclotomicRing<Q, N> {
+	/// Reduces the polynomial modulo x^n + 1
+	fn from(p: Polynomial<ZM<Q>>) -> Self {
+		let mut coefficients = [ZM::zero(); N];
+		for (i, c) in p.coefficients().iter().enumerate() {
+			if (i / N).is_multiple_of(2) {
+				coefficients[i % N] += *c;
+			} else {
+				coefficients[i % N] -= *c;
+			}
+		}
+		CyclotomicRing::new(coefficients)
+	}
+}
+
+impl<const Q: i64, const N: usize> Debug for CyclotomicRing<Q, N> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_list().entries(self.coefficients.iter()).finish()
+	}
+}
+
+/// Elements are equal when their coefficients are, whether or not their transforms are cached
+impl<const Q: i64, const N: usize> PartialEq for CyclotomicRing<Q, N> {
+	fn eq(&self, other: &Self) -> bool {
+		self.coefficients == other.coefficients
+	}
+}
+
+impl<const Q: i64, const N: usize> Eq for CyclotomicRing<Q, N> { }
+
+impl<const Q: i64, const N: usize> Hash for CyclotomicRing<Q, N> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.coefficients.hash(state)
+	}
+}
+
+impl<const Q: i64, const N: usize> Default for CyclotomicRing<Q, N> {
+	fn default() -> Self {
+		CyclotomicRing::zero()
+	}
+}
+
+// MARK: Sampling
+
+impl<const Q: i64, const N: usize> CyclotomicRing<Q, N> {
+
+	/// An element whose coefficients are uniform in [-bound, bound]
+	pub fn random_small<G: Rng + ?Sized>(bound: i64, rng: &mut G) -> Self {
+		rng.sample(UniformBounded::new(bound))
+	}
+
+	/// An element whose coefficients follow the centered binomial distribution with parameter
+	/// eta, each the difference of two sums of eta random bits, as for Kyber's secrets and errors
+	pub fn random_centered_binomial<G: Rng + ?Sized>(eta: u32, rng: &mut G) -> Self {
+		rng.sample(CenteredBinomial::new(eta))
+	}
+
+	/// An element whose coefficients follow the discrete Gaussian distribution with parameter sigma
+	pub fn random_gaussian<G: Rng + ?Sized>(sigma: f64, rng: &mut G) -> Self {
+		rng.sample(DiscreteGaussian::new(sigma))
+	}
+
+}
+
+/// Coefficients are uniform in ZM<Q>
+impl<const Q: i64, const N: usize> RandomElement for CyclotomicRing<Q, N> {
+	fn random<G: Rng + ?Sized>(rng: &mut G) -> Self {
+		CyclotomicRing::new(std::array::from_fn(|_| ZM::random(rng)))
+	}
+}
+
+impl<const Q: i64, const N: usize> Distribution<CyclotomicRing<Q, N>> for Standard {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> CyclotomicRing<Q, N> {
+		CyclotomicRing::random(rng)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<const Q: i64, const N: usize> Add for CyclotomicRing<Q, N> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.add_ref(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> AddAssign for CyclotomicRing<Q, N> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> Sub for CyclotomicRing<Q, N> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.sub_ref(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> SubAssign for CyclotomicRing<Q, N> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.sub_assign_ref(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> Neg for CyclotomicRing<Q, N> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		CyclotomicRing::new(self.coefficients.map(|c| -c))
+	}
+}
+
+impl<const Q: i64, const N: usize> Mul for CyclotomicRing<Q, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.product(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> MulAssign for CyclotomicRing<Q, N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.product(&rhs)
+	}
+}
+
+impl<const Q: i64, const N: usize> Semiring for CyclotomicRing<Q, N> {
+	fn one() -> Self {
+		CyclotomicRing::constant(ZM::one())
+	}
+
+	fn zero() -> Self {
+		CyclotomicRing::new([ZM::zero(); N])
+	}
+
+	fn is_zero(&self) -> bool {
+		self.coefficients.iter().all(|c| c.is_zero())
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		for (a, b) in self.coefficients.iter_mut().zip(&rhs.coefficients) {
+			*a += *b;
+		}
+		self.transform = OnceLock::new();
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.product(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		self.product(rhs)
+	}
+}
+
+/// Negative powers need an inverse from `try_inverse`, and panic without one
+impl<const Q: i64, const N: usize> Ring for CyclotomicRing<Q, N> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return match self.try_inverse() {
+				Some(inverse) => inverse.power(-n),
+				None => panic!("Cannot invert ring element")
+			}
+		}
+
+		let mut power = CyclotomicRing::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power = power.product(&base)
+			}
+			base = base.product(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		for (a, b) in self.coefficients.iter_mut().zip(&rhs.coefficients) {
+			*a -= *b;
+		}
+		self.transform = OnceLock::new();
+	}
+}
+
+impl<const Q: i64, const N: usize> CommutativeRing for CyclotomicRing<Q, N> { }
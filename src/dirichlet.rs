@@ -0,0 +1,332 @@
+//
+// Dirichlet characters modulo n, the homomorphisms from the units of Z/(n) to the roots of unity,
+// built from the structure of (Z/n)* as a product of cyclic groups
+//
+
+use std::f64::consts::PI;
+use std::ops::Mul;
+
+use crate::algebra::*;
+use crate::complex::Complex;
+use crate::crt::LocalDecomposition;
+use crate::std_impls::factor;
+
+// MARK: The Group of Characters
+
+/// ab mod n
+fn mul_mod(a: i64, b: i64, n: i64) -> i64 {
+	((a as i128 * b as i128) % n as i128) as i64
+}
+
+/// The smallest primitive root modulo an odd prime p
+fn primitive_root(p: i64) -> i64 {
+	let prime_factors: Vec<i64> = factor(p - 1).into_iter().map(|(q, _)| q).collect();
+	let power = |mut base: i64, mut e: i64| {
+		let mut result = 1;
+		while e > 0 {
+			if e & 1 == 1 {
+				result = mul_mod(result, base, p);
+			}
+			base = mul_mod(base, base, p);
+			e >>= 1;
+		}
+		result
+	};
+
+	(2..p).find(|&g| prime_factors.iter().all(|&q| power(g, (p - 1) / q) != 1)).unwrap()
+}
+
+/// The Dirichlet characters modulo n, together with a decomposition of (Z/n)* into cyclic groups
+/// which every character is described by.
+///
+/// (Z/n)* is the product of the (Z/p^e)* over the prime powers dividing n. For odd p that is
+/// cyclic of order p^(e-1) (p - 1), generated by a primitive root mod p which stays primitive mod
+/// p^2, and (Z/2^e)* is generated by -1 and 5. A character is then determined by where it sends
+/// each generator. Every unit's exponents are tabulated, so n should be small enough for a table
+/// of n entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirichletGroup {
+	modulus: i64,
+
+	/// Generators of (Z/n)* with their orders, each unit being a unique product of their powers
+	generators: Vec<(i64, u64)>,
+
+	/// The exponent of (Z/n)*, the lcm of the orders of the generators, so every character value
+	/// is a power of a primitive root of unity of this order
+	exponent: u64,
+
+	/// logs[a] holds the exponents of the generators giving a, or None if a is not a unit
+	logs: Vec<Option<Vec<u64>>>
+}
+
+impl DirichletGroup {
+
+	/// The characters modulo n, for n >= 1
+	pub fn new(n: i64) -> DirichletGroup {
+		if n < 1 {
+			panic!("Dirichlet characters need a positive modulus, got {}", n)
+		}
+
+		// Generators of each local factor, as (generator mod p^e, order, local index)
+		let factors = factor(n);
+		let mut local: Vec<(i64, u64, usize)> = Vec::new();
+		for (i, &(p, e)) in factors.iter().enumerate() {
+			let q = p.pow(e);
+			if p == 2 {
+				if e >= 2 {
+					local.push((q - 1, 2, i));
+				}
+				if e >= 3 {
+					local.push((5, 1 << (e - 2), i));
+				}
+			} else {
+				let mut g = primitive_root(p);
+				if e >= 2 && (0..p - 1).fold(1, |x, _| mul_mod(x, g, p * p)) == 1 {
+					g += p;
+				}
+				local.push((g, (q - q / p) as u64, i));
+			}
+		}
+
+		// Each generator is lifted to be itself in its own factor and 1 in the others
+		let generators: Vec<(i64, u64)> = if n == 1 {
+			Vec::new()
+		} else {
+			let decomposition = LocalDecomposition::of_integers(n);
+			local.iter().map(|&(g, order, i)| {
+				let components: Vec<i64> = (0..factors.len()).map(|j| if j == i { g } else { 1 }).collect();
+				(decomposition.lift(&components).rem_euclid(n), order)
+			}).collect()
+		};
+
+		let exponent = generators.iter().fold(1u64, |m, &(_, order)| m / gcd(&(m as i64), &(order as i64)) as u64 * order);
+
+		// Walk through every choice of exponents like an odometer, where suffixes[i] is the product
+		// of the powers of generators i and up
+		let mut logs = vec![None; n as usize];
+		let mut exponents = vec![0u64; generators.len()];
+		let mut suffixes = vec![1 % n; generators.len() + 1];
+		loop {
+			logs[suffixes[0] as usize] = Some(exponents.clone());
+
+			let Some(i) = (0..generators.len()).find(|&i| exponents[i] + 1 < generators[i].1) else {
+				break
+			};
+			exponents[i] += 1;
+			suffixes[i] = mul_mod(suffixes[i], generators[i].0, n);
+			for j in 0..i {
+				exponents[j] = 0;
+				suffixes[j] = suffixes[i];
+			}
+		}
+
+		DirichletGroup { modulus: n, generators, exponent, logs }
+	}
+
+	/// The modulus n
+	pub fn modulus(&self) -> i64 {
+		self.modulus
+	}
+
+	/// The number of characters, which is the number φ(n) of units modulo n
+	pub fn order(&self) -> usize {
+		self.logs.iter().filter(|log| log.is_some()).count()
+	}
+
+	/// The exponent of (Z/n)*, so that every character value is a power of e^(2πi / exponent)
+	pub fn exponent(&self) -> u64 {
+		self.exponent
+	}
+
+	/// Generators of (Z/n)* with their orders, such that each unit is a unique product of powers
+	/// of them
+	pub fn generators(&self) -> &[(i64, u64)] {
+		&self.generators
+	}
+
+	/// The exponents of the generators whose product is a, or None if a is not a unit mod n
+	pub fn discrete_log(&self, a: i64) -> Option<&[u64]> {
+		self.logs[a.rem_euclid(self.modulus) as usize].as_deref()
+	}
+
+	/// The character sending generator i to e^(2πi c_i / m_i), where m_i is its order
+	pub fn character(&self, c: &[u64]) -> DirichletCharacter {
+		if c.len() != self.generators.len() {
+			panic!("A character modulo {} needs {} exponents, got {}", self.modulus, self.generators.len(), c.len())
+		}
+
+		let m = self.exponent;
+		let values = self.logs.iter().map(|log| log.as_ref().map(|k| {
+			k.iter().zip(c.iter()).zip(self.generators.iter())
+				.fold(0, |sum, ((&k, &c), &(_, order))| (sum + k * (c % order) % order * (m / order)) % m)
+		})).collect();
+
+		DirichletCharacter { modulus: self.modulus, exponent: m, values }
+	}
+
+	/// The principal character, which is 1 on every unit
+	pub fn principal(&self) -> DirichletCharacter {
+		self.character(&vec![0; self.generators.len()])
+	}
+
+	/// Every character modulo n
+	pub fn characters(&self) -> Vec<DirichletCharacter> {
+		let mut characters = Vec::with_capacity(self.order());
+		let mut c = vec![0u64; self.generators.len()];
+		loop {
+			characters.push(self.character(&c));
+
+			let Some(i) = (0..c.len()).find(|&i| c[i] + 1 < self.generators[i].1) else {
+				return characters
+			};
+			c[i] += 1;
+			c[..i].fill(0);
+		}
+	}
+
+	/// The inner product of two characters, the average of χ(a) conj(ψ(a)) over the units a,
+	/// which is 1 if they are equal and 0 otherwise
+	pub fn inner_product(&self, chi: &DirichletCharacter, psi: &DirichletCharacter) -> Complex<f64> {
+		let sum = (0..self.modulus).fold(Complex::zero(), |sum, a| sum + chi.evaluate(a) * psi.evaluate(a).conj());
+		sum.scale(&(1.0 / self.order() as f64))
+	}
+
+	/// The sum of χ(a) conj(χ(b)) over every character χ, which is φ(n) if a and b are congruent
+	/// units and 0 otherwise
+	pub fn character_sum(&self, a: i64, b: i64) -> Complex<f64> {
+		self.characters().iter().fold(Complex::zero(), |sum, chi| sum + chi.evaluate(a) * chi.evaluate(b).conj())
+	}
+
+}
+
+// MARK: Characters
+
+/// A Dirichlet character χ modulo n, sending each unit a to a root of unity e^(2πi k / m), where m
+/// is the exponent of (Z/n)*, and every other a to 0
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DirichletCharacter {
+	modulus: i64,
+	exponent: u64,
+
+	/// values[a] holds k with χ(a) = e^(2πi k / m), or None if a is not a unit
+	values: Vec<Option<u64>>
+}
+
+impl DirichletCharacter {
+
+	/// The modulus n
+	pub fn modulus(&self) -> i64 {
+		self.modulus
+	}
+
+	/// The order m of the roots of unity the values are written as powers of
+	pub fn root_order(&self) -> u64 {
+		self.exponent
+	}
+
+	/// The k with χ(a) = e^(2πi k / m), or None if a is not a unit, so χ(a) = 0
+	pub fn value_exponent(&self, a: i64) -> Option<u64> {
+		self.values[a.rem_euclid(self.modulus) as usize]
+	}
+
+	/// χ(a) as a complex number
+	pub fn evaluate(&self, a: i64) -> Complex<f64> {
+		match self.value_exponent(a) {
+			Some(k) => Complex::from_polar(1.0, 2.0 * PI * k as f64 / self.exponent as f64),
+			None => Complex::zero()
+		}
+	}
+
+	/// χ(a) in a field F containing the roots of unity of order m, given a primitive one ζ, as
+	/// ζ^k for a unit a and 0 otherwise
+	pub fn evaluate_in<F: Field>(&self, a: i64, zeta: &F) -> F {
+		match self.value_exponent(a) {
+			Some(k) => zeta.power(k as i64),
+			None => F::zero()
+		}
+	}
+
+	/// The order of this character in the group of characters
+	pub fn order(&self) -> u64 {
+		let g = self.values.iter().flatten().fold(self.exponent as i64, |g, &k| gcd(&g, &(k as i64)));
+		self.exponent / g as u64
+	}
+
+	/// Whether or not this is the principal character
+	pub fn is_principal(&self) -> bool {
+		self.values.iter().flatten().all(|&k| k == 0)
+	}
+
+	/// Whether or not χ(-1) = 1
+	pub fn is_even(&self) -> bool {
+		self.value_exponent(-1) == Some(0)
+	}
+
+	/// Whether or not χ(-1) = -1
+	pub fn is_odd(&self) -> bool {
+		!self.is_even()
+	}
+
+	/// The complex conjugate character, which is also the inverse
+	pub fn conjugate(&self) -> DirichletCharacter {
+		let m = self.exponent;
+		DirichletCharacter {
+			modulus: self.modulus,
+			exponent: m,
+			values: self.values.iter().map(|v| v.map(|k| (m - k) % m)).collect()
+		}
+	}
+
+	/// The conductor, the least d dividing n such that χ comes from a character modulo d, which is
+	/// when χ(a) = 1 for every unit a congruent to 1 modulo d
+	pub fn conductor(&self) -> i64 {
+		let n = self.modulus;
+		(1..=n).filter(|d| n % d == 0).find(|&d| {
+			(0..n).filter(|a| a % d == 1 % d).all(|a| self.value_exponent(a).is_none_or(|k| k == 0))
+		}).unwrap()
+	}
+
+	/// Whether or not this character is primitive, not coming from any smaller modulus
+	pub fn is_primitive(&self) -> bool {
+		self.conductor() == self.modulus
+	}
+
+	/// The Gauss sum, the sum of χ(a) e^(2πi a / n) over a mod n
+	pub fn gauss_sum(&self) -> Complex<f64> {
+		let n = self.modulus;
+		(0..n).fold(Complex::zero(), |sum, a| {
+			sum + self.evaluate(a) * Complex::from_polar(1.0, 2.0 * PI * a as f64 / n as f64)
+		})
+	}
+
+	/// The Gauss sum in a field F containing the roots of unity of order L = lcm(m, n), given a
+	/// primitive one ζ, so that χ(a) = ζ^(kL/m) and e^(2πi a / n) = ζ^(aL/n). With F = ZM<Q> for a
+	/// prime Q = 1 mod L, this computes exactly in the cyclotomic integers reduced modulo a prime.
+	pub fn gauss_sum_in<F: Field>(&self, zeta: &F) -> F {
+		let n = self.modulus as u64;
+		let m = self.exponent;
+		let l = m / gcd(&(m as i64), &(n as i64)) as u64 * n;
+
+		(0..n).fold(F::zero(), |sum, a| match self.values[a as usize] {
+			Some(k) => sum + zeta.power((k * (l / m) + a * (l / n)) as i64 % l as i64),
+			None => sum
+		})
+	}
+
+}
+
+/// The pointwise product of two characters with the same modulus
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for DirichletCharacter {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		if self.modulus != rhs.modulus {
+			panic!("Cannot multiply characters modulo {} and {}", self.modulus, rhs.modulus)
+		}
+
+		let m = self.exponent;
+		let values = self.values.iter().zip(rhs.values.iter()).map(|(a, b)| a.zip(*b).map(|(a, b)| (a + b) % m)).collect();
+		DirichletCharacter { modulus: self.modulus, exponent: m, values }
+	}
+}
@@ -0,0 +1,302 @@
+//
+// Discrete logarithm solvers, generic over any group
+//
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::std_impls::ZM;
+
+/// The largest baby-step table we are willing to build before falling back to Pollard rho
+const BSGS_TABLE_LIMIT: u64 = 1 << 22;
+
+/// How many random restarts Pollard rho gets before giving up
+const RHO_ATTEMPTS: usize = 32;
+
+/// Finds x in [0, order) such that base^x = target, where `order` is the order of `base`
+/// (or any multiple of it).
+///
+/// Uses baby-step giant-step when the table fits in memory, and Pollard rho otherwise.
+/// Returns `None` if target is not in the subgroup generated by base.
+pub fn discrete_log<G: Group + Hash + Eq>(base: &G, target: &G, order: u64) -> Option<u64> {
+	if order == 0 {
+		panic!("The order of a group element cannot be zero")
+	}
+
+	if isqrt_ceil(order) <= BSGS_TABLE_LIMIT {
+		baby_step_giant_step(base, target, order)
+	} else {
+		pollard_rho(base, target, order)
+	}
+}
+
+/// Baby-step giant-step: O(sqrt(order)) time and memory
+pub fn baby_step_giant_step<G: Group + Hash + Eq>(base: &G, target: &G, order: u64) -> Option<u64> {
+	let m = isqrt_ceil(order);
+
+	// Baby steps: base^j for 0 <= j < m
+	let mut table = HashMap::with_capacity(m as usize);
+	let mut baby = G::identity();
+	for j in 0..m {
+		table.entry(baby.clone()).or_insert(j);
+		baby *= base.clone();
+	}
+
+	// Giant steps: target * base^(-im)
+	let giant = base.power(m as i64).inverse();
+	let mut gamma = target.clone();
+	for i in 0..m {
+		if let Some(j) = table.get(&gamma) {
+			return Some((i * m + j) % order)
+		}
+		gamma *= giant.clone();
+	}
+
+	None
+}
+
+/// Pollard's rho for discrete logarithms: O(sqrt(order)) expected time and constant memory.
+///
+/// Works best when `order` is prime. Since this is a randomized algorithm, it may return `None`
+/// even when a logarithm exists, though that is unlikely.
+pub fn pollard_rho<G: Group + Hash + Eq>(base: &G, target: &G, order: u64) -> Option<u64> {
+	pollard_rho_with_rng(base, target, order, &mut rand::thread_rng())
+}
+
+/// Pollard's rho, drawing its random starting points from the given source of randomness
+pub fn pollard_rho_with_rng<G: Group + Hash + Eq, T: Rng + ?Sized>(base: &G, target: &G, order: u64, rng: &mut T) -> Option<u64> {
+	let n = order as u128;
+
+	// One step of the walk, keeping track of x = base^a * target^b
+	let step = |x: &mut G, a: &mut u128, b: &mut u128| {
+		match partition(x) {
+			0 => {
+				*x *= base.clone();
+				*a = (*a + 1) % n;
+			},
+			1 => {
+				*x *= x.clone();
+				*a = (*a * 2) % n;
+				*b = (*b * 2) % n;
+			},
+			_ => {
+				*x *= target.clone();
+				*b = (*b + 1) % n;
+			}
+		}
+	};
+
+	for _ in 0..RHO_ATTEMPTS {
+		let a0 = rng.gen_range(0..order) as u128;
+		let b0 = rng.gen_range(0..order) as u128;
+		let start = power_u64(base, a0 as u64) * power_u64(target, b0 as u64);
+
+		let (mut x, mut a, mut b) = (start.clone(), a0, b0);
+		let (mut y, mut c, mut d) = (start, a0, b0);
+
+		// Floyd cycle finding
+		loop {
+			step(&mut x, &mut a, &mut b);
+			step(&mut y, &mut c, &mut d);
+			step(&mut y, &mut c, &mut d);
+
+			if x == y {
+				break
+			}
+		}
+
+		// base^a target^b = base^c target^d, so (b - d) log = (c - a) mod n
+		let lhs = (b + n - d) % n;
+		let rhs = (c + n - a) % n;
+
+		if let Some(x) = solve_linear_congruence(lhs, rhs, n, |k| power_u64(base, k as u64) == *target) {
+			return Some(x as u64)
+		}
+	}
+
+	None
+}
+
+/// g^e for any u64 exponent, including those too large for `power`, which takes an i64
+fn power_u64<G: Group>(g: &G, e: u64) -> G {
+	if let Ok(e) = i64::try_from(e) {
+		return g.power(e)
+	}
+
+	let half = g.power((e / 2) as i64);
+	let square = half.clone() * half;
+	if e % 2 == 1 { square * g.clone() } else { square }
+}
+
+/// Splits the group into three (roughly) equal parts for the rho walk
+fn partition<G: Hash>(x: &G) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	x.hash(&mut hasher);
+	hasher.finish() % 3
+}
+
+/// Finds a solution k of lhs * k = rhs (mod n) passing `check`, trying every solution when
+/// gcd(lhs, n) is small enough
+fn solve_linear_congruence<F: Fn(u128) -> bool>(lhs: u128, rhs: u128, n: u128, check: F) -> Option<u128> {
+	// Extended Euclid, tracking only the coefficient of lhs
+	let (mut r0, mut r1) = (lhs as i128, n as i128);
+	let (mut s0, mut s1) = (1i128, 0i128);
+	while r1 != 0 {
+		let q = r0 / r1;
+		(r0, r1) = (r1, r0 - q * r1);
+		(s0, s1) = (s1, s0 - q * s1);
+	}
+	let (g, inv) = (r0, s0);
+
+	let g = g as u128;
+
+	if g == 0 || !rhs.is_multiple_of(g) || g > BSGS_TABLE_LIMIT as u128 {
+		return None
+	}
+
+	let reduced = n / g;
+	let inv = inv.rem_euclid(reduced as i128) as u128;
+	let k0 = ((rhs / g) % reduced) * inv % reduced;
+
+	(0..g).map(|j| k0 + j * reduced).find(|k| check(*k))
+}
+
+/// The smallest m such that m^2 >= n
+fn isqrt_ceil(n: u64) -> u64 {
+	let mut m = (n as f64).sqrt() as u64;
+	while m * m < n {
+		m += 1
+	}
+	while m > 0 && (m - 1) * (m - 1) >= n {
+		m -= 1
+	}
+	m
+}
+
+// MARK: Log Tables
+
+/// The largest prime modulus we are willing to tabulate every power and logarithm of
+const LOG_TABLE_LIMIT: i64 = 1 << 24;
+
+/// Tables of every power of a primitive root g of ZM<Q> and the logarithm of every unit, for a
+/// small prime Q, so that multiplication, powers and logarithms are all lookups.
+///
+/// Building the tables takes O(Q) time and memory, which pays off when there are many queries.
+pub struct LogTable<const Q: i64> {
+	/// g^k for 0 <= k < Q - 1
+	powers: Vec<ZM<Q>>,
+
+	/// The k with g^k = x at index x, for x a unit
+	logs: Vec<u64>
+}
+
+impl<const Q: i64> LogTable<Q> {
+
+	/// The tables for the smallest primitive root of ZM<Q>. Q must be prime, or this fails to
+	/// compile, and at most 2^24.
+	pub fn new() -> LogTable<Q> {
+		let () = ZM::<Q>::ASSERT_PRIME;
+		if Q > LOG_TABLE_LIMIT {
+			panic!("Log tables modulo {} are too large to build", Q)
+		}
+
+		let g = ZM::<Q>::primitive_root();
+		let mut powers = Vec::with_capacity(Q as usize - 1);
+		let mut logs = vec![0; Q as usize];
+		let mut power = ZM::one();
+
+		for k in 0..Q as u64 - 1 {
+			logs[power.val as usize] = k;
+			powers.push(power);
+			power *= g;
+		}
+
+		LogTable { powers, logs }
+	}
+
+	/// The primitive root g that every logarithm is taken to the base of
+	pub fn generator(&self) -> ZM<Q> {
+		self.powers.get(1).copied().unwrap_or(ZM::one())
+	}
+
+	/// g^k
+	pub fn exp(&self, k: u64) -> ZM<Q> {
+		self.powers[(k % self.powers.len() as u64) as usize]
+	}
+
+	/// The k in [0, Q - 1) with g^k = x, or None if x is zero
+	pub fn log(&self, x: &ZM<Q>) -> Option<u64> {
+		(!x.is_zero()).then(|| self.logs[x.val as usize])
+	}
+
+	/// The product xy, as g^(log x + log y)
+	pub fn mul(&self, x: &ZM<Q>, y: &ZM<Q>) -> ZM<Q> {
+		match (self.log(x), self.log(y)) {
+			(Some(a), Some(b)) => self.exp(a + b),
+			_ => ZM::zero()
+		}
+	}
+
+	/// x^e, as g^(e log x). Panics if x is zero and e is negative.
+	pub fn pow(&self, x: &ZM<Q>, e: i64) -> ZM<Q> {
+		let Some(a) = self.log(x) else {
+			if e < 0 {
+				panic!("Zero has no negative powers")
+			}
+			return if e == 0 { ZM::one() } else { ZM::zero() }
+		};
+
+		let n = self.powers.len() as i128;
+		self.exp((a as i128 * e as i128).rem_euclid(n) as u64)
+	}
+
+	/// The inverse of x, as g^(-log x), or None if x is zero
+	pub fn inverse(&self, x: &ZM<Q>) -> Option<ZM<Q>> {
+		self.log(x).map(|a| self.exp(self.powers.len() as u64 - a))
+	}
+
+	/// The multiplicative order of x, which is (Q - 1) / gcd(log x, Q - 1), or None if x is zero
+	pub fn order(&self, x: &ZM<Q>) -> Option<u64> {
+		let n = self.powers.len() as u64;
+		self.log(x).map(|a| n / gcd(&(a as i64), &(n as i64)) as u64)
+	}
+
+	/// The least k >= 0 with base^k = target, or None if there is none.
+	///
+	/// This solves k log(base) = log(target) modulo Q - 1, which has a solution exactly when
+	/// d = gcd(log(base), Q - 1) divides log(target), and then a unique one modulo (Q - 1) / d.
+	pub fn discrete_log(&self, base: &ZM<Q>, target: &ZM<Q>) -> Option<u64> {
+		let (Some(a), Some(b)) = (self.log(base), self.log(target)) else {
+			// Only zero has powers equal to zero, and zero^0 = 1
+			return match (base.is_zero(), target.is_zero()) {
+				(true, true) => Some(1),
+				(true, false) => (*target == ZM::one()).then_some(0),
+				_ => None
+			}
+		};
+
+		let n = self.powers.len() as i64;
+		let d = gcd(&(a as i64), &n);
+		if b as i64 % d != 0 {
+			return None
+		}
+
+		let modulus = n / d;
+		if modulus == 1 {
+			return Some(0)
+		}
+		let inverse = mod_inverse(&(a as i64 / d), &modulus).unwrap();
+		Some(((b as i64 / d) as i128 * inverse as i128 % modulus as i128) as u64)
+	}
+
+}
+
+impl<const Q: i64> Default for LogTable<Q> {
+	fn default() -> Self {
+		LogTable::new()
+	}
+}
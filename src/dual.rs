@@ -0,0 +1,254 @@
+//
+// Dual numbers a + bε over any ring, where ε^2 = 0, which differentiate any function written
+// against the ring traits by evaluating it
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+use crate::random::RandomElement;
+
+// MARK: Dual Numbers
+
+/// The number value + derivative ε in R[ε] = R[x]/(x^2).
+///
+/// Since ε^2 = 0, any polynomial or rational function f satisfies f(a + ε) = f(a) + f'(a) ε, so
+/// evaluating a function written against the generic traits at `Dual::variable(a)` computes its
+/// derivative at a along with its value, exactly and without symbolic manipulation. This is
+/// forward mode automatic differentiation.
+///
+/// Over a field, the elements with nonzero value are invertible and the rest, the multiples of ε,
+/// are not. Since ε is a zero divisor this is not a field or even an integral domain, so it
+/// does not implement `Field`, and inverses and division are inherent methods instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dual<R: Ring> {
+	pub value: R,
+	pub derivative: R
+}
+
+impl<R: Ring> Dual<R> {
+
+	/// The number value + derivative ε
+	pub fn new(value: R, derivative: R) -> Dual<R> {
+		Dual { value, derivative }
+	}
+
+	/// The constant x, with derivative zero
+	pub fn constant(x: R) -> Dual<R> {
+		Dual::new(x, R::zero())
+	}
+
+	/// The variable being differentiated with respect to, at x, so with derivative one
+	pub fn variable(x: R) -> Dual<R> {
+		Dual::new(x, R::one())
+	}
+
+	/// The infinitesimal ε
+	pub fn epsilon() -> Dual<R> {
+		Dual::new(R::zero(), R::one())
+	}
+
+	/// Multiplies both parts by c
+	pub fn scale(&self, c: &R) -> Dual<R> {
+		Dual::new(self.value.mul_ref(c), self.derivative.mul_ref(c))
+	}
+
+	/// The inverse of this number, when the value has an inverse v^-1, which is
+	/// v^-1 - v^-1 d v^-1 ε by the quotient rule
+	fn inverse_from(&self, value_inverse: R) -> Dual<R> {
+		let derivative = -value_inverse.mul_ref(&self.derivative).mul_ref(&value_inverse);
+		Dual::new(value_inverse, derivative)
+	}
+
+}
+
+impl<R: Ring> From<R> for Dual<R> {
+	fn from(x: R) -> Self {
+		Dual::constant(x)
+	}
+}
+
+impl<R: Ring + RandomElement> RandomElement for Dual<R> {
+	fn random<G: Rng + ?Sized>(rng: &mut G) -> Self {
+		Dual::new(R::random(rng), R::random(rng))
+	}
+}
+
+/// The value and derivative of f at x, from evaluating f at x + ε
+pub fn value_and_derivative<R: Ring>(f: impl Fn(Dual<R>) -> Dual<R>, x: R) -> (R, R) {
+	let y = f(Dual::variable(x));
+	(y.value, y.derivative)
+}
+
+/// The derivative of f at x, from evaluating f at x + ε
+pub fn derivative<R: Ring>(f: impl Fn(Dual<R>) -> Dual<R>, x: R) -> R {
+	value_and_derivative(f, x).1
+}
+
+impl<R: Ring> Polynomial<R> {
+
+	/// The value and derivative of this polynomial at x, found together by evaluating at x + ε
+	pub fn evaluate_with_derivative(&self, x: &R) -> (R, R) {
+		let lifted: Polynomial<Dual<R>> = Polynomial::new(self.coefficients().iter().cloned().map(Dual::constant).collect());
+		let y = lifted.evaluate(&Dual::variable(x.clone()));
+		(y.value, y.derivative)
+	}
+
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring> Add for Dual<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+	}
+}
+
+impl<R: Ring> AddAssign for Dual<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.value += rhs.value;
+		self.derivative += rhs.derivative;
+	}
+}
+
+impl<R: Ring> Neg for Dual<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Dual::new(-self.value, -self.derivative)
+	}
+}
+
+impl<R: Ring> Sub for Dual<R> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+	}
+}
+
+impl<R: Ring> SubAssign for Dual<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.value -= rhs.value;
+		self.derivative -= rhs.derivative;
+	}
+}
+
+/// (a + bε)(c + dε) = ac + (ad + bc)ε, the product rule
+impl<R: Ring> Mul for Dual<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring> MulAssign for Dual<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<F: Field> Div for Dual<F> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<F: Field> DivAssign for Dual<F> {
+	fn div_assign(&mut self, rhs: Self) {
+		self.mul_assign_ref(&rhs.inverse())
+	}
+}
+
+impl<R: Ring> Semiring for Dual<R> {
+	fn one() -> Self {
+		Dual::constant(R::one())
+	}
+
+	fn zero() -> Self {
+		Dual::constant(R::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.value.is_zero() && self.derivative.is_zero()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		self.value.add_assign_ref(&rhs.value);
+		self.derivative.add_assign_ref(&rhs.derivative);
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		Dual::new(
+			self.value.mul_ref(&rhs.value),
+			self.value.mul_ref(&rhs.derivative) + self.derivative.mul_ref(&rhs.value)
+		)
+	}
+}
+
+/// Negative powers invert the value with its own `power`, so they work whenever that does
+impl<R: Ring> Ring for Dual<R> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse_from(self.value.power(-1)).power(-n)
+		}
+
+		let mut power = Dual::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base)
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		self.value.sub_assign_ref(&rhs.value);
+		self.derivative.sub_assign_ref(&rhs.derivative);
+	}
+}
+
+impl<R: CommutativeRing> CommutativeRing for Dual<R> { }
+
+// MARK: Division
+
+impl<F: Field> Dual<F> {
+
+	/// The inverse, which panics for a multiple of ε
+	pub fn inverse(&self) -> Dual<F> {
+		match self.try_inverse() {
+			Some(inverse) => inverse,
+			None => panic!("{:?} is a multiple of ε, so it is not invertible", self)
+		}
+	}
+
+	/// The inverse, which exists exactly when the value is invertible
+	pub fn try_inverse(&self) -> Option<Dual<F>> {
+		self.value.try_inverse().map(|v| self.inverse_from(v))
+	}
+
+	/// self / rhs, or `None` when rhs is a multiple of ε
+	pub fn checked_div(&self, rhs: &Dual<F>) -> Option<Dual<F>> {
+		rhs.try_inverse().map(|inverse| self.mul_ref(&inverse))
+	}
+
+}
@@ -0,0 +1,251 @@
+//
+// Exact eigenvalues and eigenvectors of rational matrices, over the algebraic closure of Q
+//
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+
+// MARK: Algebraic Numbers
+
+/// An element of Q[t]/(p) for a monic squarefree p, which is the value of a polynomial at a root
+/// t of p.
+///
+/// Every computation with it holds for each root of p at once. When p is irreducible this is just
+/// an element of the number field Q(t); otherwise Q[t]/(p) is a product of number fields, one for
+/// each irreducible factor of p.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlgebraicNumber {
+	value: Polynomial<BigRational>,
+	modulus: Polynomial<BigRational>
+}
+
+impl AlgebraicNumber {
+
+	/// The value of the polynomial f at a root of the modulus
+	pub fn new(value: Polynomial<BigRational>, modulus: Polynomial<BigRational>) -> AlgebraicNumber {
+		if modulus.degree() == 0 {
+			panic!("The modulus of an algebraic number must have positive degree")
+		}
+
+		let modulus = modulus.monic();
+		AlgebraicNumber { value: value % modulus.clone(), modulus }
+	}
+
+	/// A root t of the modulus itself
+	pub fn root_of(modulus: Polynomial<BigRational>) -> AlgebraicNumber {
+		AlgebraicNumber::new(Polynomial::x(), modulus)
+	}
+
+	/// The polynomial in t, of degree less than that of the modulus, giving this number
+	pub fn value(&self) -> &Polynomial<BigRational> {
+		&self.value
+	}
+
+	/// The monic polynomial p that t is a root of
+	pub fn modulus(&self) -> &Polynomial<BigRational> {
+		&self.modulus
+	}
+
+	/// The rational number this is, if it is the same at every root of the modulus
+	pub fn to_rational(&self) -> Option<BigRational> {
+		if self.value.degree() == 0 { Some(self.value.coefficient(0)) } else { None }
+	}
+
+	/// Whether or not this number is zero
+	pub fn is_zero(&self) -> bool {
+		self.value.is_zero()
+	}
+
+}
+
+// MARK: Eigenspaces
+
+/// The eigenspace of every root of a squarefree polynomial, where each root has the same algebraic
+/// multiplicity and an eigenspace of the same dimension
+#[derive(Clone, Debug, PartialEq)]
+pub struct Eigenspace {
+
+	/// The eigenvalue, as a root of its modulus
+	pub eigenvalue: AlgebraicNumber,
+
+	/// The multiplicity of the eigenvalue as a root of the characteristic polynomial
+	pub algebraic_multiplicity: usize,
+
+	/// A basis of the eigenspace, with entries in the same ring as the eigenvalue
+	pub basis: Vec<Vec<AlgebraicNumber>>
+
+}
+
+impl Eigenspace {
+
+	/// The dimension of the eigenspace
+	pub fn geometric_multiplicity(&self) -> usize {
+		self.basis.len()
+	}
+
+}
+
+/// The squarefree factors f_i of a monic polynomial f = f_1 f_2^2 f_3^3 ..., by Yun's algorithm,
+/// paired with their multiplicities and leaving out those which are 1
+fn squarefree_decomposition(f: &Polynomial<BigRational>) -> Vec<(Polynomial<BigRational>, usize)> {
+	let mut factors = Vec::new();
+	if f.degree() == 0 {
+		return factors
+	}
+
+	let derivative = f.derivative();
+	let a = gcd(f, &derivative).monic();
+	let mut b = f.clone() / a.clone();
+	let mut d = derivative / a - b.derivative();
+	let mut multiplicity = 1;
+
+	while b.degree() > 0 {
+		let factor = gcd(&b, &d).monic();
+		b /= factor.clone();
+		d = d / factor.clone() - b.derivative();
+
+		if factor.degree() > 0 {
+			factors.push((factor, multiplicity));
+		}
+		multiplicity += 1;
+	}
+
+	factors
+}
+
+/// Splits a monic squarefree polynomial into the linear factors t - r for its rational roots r,
+/// and the rest. Gives up on finding roots, leaving f whole, when the coefficients are too large
+/// to factor.
+fn split_rational_roots(f: &Polynomial<BigRational>) -> Vec<Polynomial<BigRational>> {
+	let denominators = f.coefficients().iter().fold(BigInt::from(1), |l, c| l.lcm(c.denom()));
+	let scaled = Polynomial::new(f.coefficients().iter().map(|c| (c * BigRational::from_integer(denominators.clone())).to_integer()).collect());
+
+	let Some(roots) = scaled.rational_roots() else {
+		return vec![f.clone()]
+	};
+
+	let mut factors = Vec::new();
+	let mut rest = f.clone();
+	for root in roots {
+		let linear = Polynomial::new(vec![-root, BigRational::one()]);
+		rest /= linear.clone();
+		factors.push(linear);
+	}
+
+	if rest.degree() > 0 {
+		factors.push(rest);
+	}
+
+	factors
+}
+
+/// The outcome of row reducing A - tI over Q[t]/(p)
+enum Reduction {
+
+	/// A basis of the kernel, as polynomials in t
+	Kernel(Vec<Vec<Polynomial<BigRational>>>),
+
+	/// A factorization p = gh into coprime factors, found from a zero divisor
+	Split(Polynomial<BigRational>, Polynomial<BigRational>)
+
+}
+
+/// Row reduces A - tI over Q[t]/(p). Either every pivot is invertible, giving the kernel, or some
+/// pivot shares a factor with p, which splits p.
+fn reduce(a: &Matrix<BigRational>, modulus: &Polynomial<BigRational>) -> Reduction {
+	let n = a.rows();
+	let mut m = Matrix::from_fn(n, n, |i, j| {
+		let entry = Polynomial::constant(a[(i, j)].clone());
+		let shifted = if i == j { entry - Polynomial::x() } else { entry };
+		shifted % modulus.clone()
+	});
+
+	let mut pivots = Vec::new();
+	for col in 0..n {
+		let row = pivots.len();
+		let Some(pivot) = (row..n).find(|&i| !m[(i, col)].is_zero()) else {
+			continue
+		};
+
+		let (g, s, _) = ext_gcd(&m[(pivot, col)], modulus);
+		if g.degree() > 0 {
+			let g = g.monic();
+			return Reduction::Split(g.clone(), modulus.clone() / g)
+		}
+
+		m.swap_rows(pivot, row);
+		let inverse = s.scale(&g.coefficient(0).inverse());
+		for j in 0..n {
+			m[(row, j)] = m[(row, j)].mul_ref(&inverse).quotient_and_remainder(modulus).1;
+		}
+
+		for i in (0..n).filter(|&i| i != row) {
+			let factor = m[(i, col)].clone();
+			if factor.is_zero() {
+				continue
+			}
+			for j in 0..n {
+				let delta = factor.mul_ref(&m[(row, j)]);
+				m[(i, j)] = m[(i, j)].sub_ref(&delta).quotient_and_remainder(modulus).1;
+			}
+		}
+
+		pivots.push(col);
+	}
+
+	// Each free column gives a kernel vector, solving for the pivot variables
+	let kernel = (0..n).filter(|col| !pivots.contains(col)).map(|free| {
+		let mut v = vec![Polynomial::zero(); n];
+		v[free] = Polynomial::one();
+		for (row, &col) in pivots.iter().enumerate() {
+			v[col] = -m[(row, free)].clone();
+		}
+		v
+	}).collect();
+
+	Reduction::Kernel(kernel)
+}
+
+/// The eigenspaces of a square rational matrix, grouping together eigenvalues that are
+/// indistinguishable by their multiplicities and eigenspace dimensions.
+///
+/// This factors the characteristic polynomial only as far as it has to. Eigenvalues with different
+/// algebraic multiplicities are separated by a squarefree decomposition, rational eigenvalues are
+/// found by the rational root theorem, and the rest are separated whenever row reducing A - tI over Q[t]/(p) meets a pivot which is zero at some roots of p but not
+/// others. So each modulus can be reducible, but every root of it has an eigenspace of the same
+/// dimension, spanned by the given basis with t set to that root.
+pub fn eigenspaces(a: &Matrix<BigRational>) -> Vec<Eigenspace> {
+	if !a.is_square() {
+		panic!("Only square matrices have eigenvalues")
+	}
+
+	let mut pending: Vec<(Polynomial<BigRational>, usize)> = squarefree_decomposition(&a.char_poly())
+		.into_iter()
+		.flat_map(|(f, multiplicity)| split_rational_roots(&f).into_iter().map(move |g| (g, multiplicity)))
+		.collect();
+	let mut spaces = Vec::new();
+
+	while let Some((modulus, multiplicity)) = pending.pop() {
+		match reduce(a, &modulus) {
+			Reduction::Kernel(kernel) => spaces.push(Eigenspace {
+				eigenvalue: AlgebraicNumber::root_of(modulus.clone()),
+				algebraic_multiplicity: multiplicity,
+				basis: kernel.into_iter().map(|v| {
+					v.into_iter().map(|x| AlgebraicNumber::new(x, modulus.clone())).collect()
+				}).collect()
+			}),
+			Reduction::Split(g, h) => {
+				pending.push((g, multiplicity));
+				pending.push((h, multiplicity));
+			}
+		}
+	}
+
+	spaces.sort_by_key(|space| (space.eigenvalue.modulus().degree(), space.algebraic_multiplicity));
+	spaces
+}
@@ -0,0 +1,729 @@
+//
+// Elliptic curves in short Weierstrass form over the prime fields ZM<Q>
+//
+
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::{rngs::StdRng, SeedableRng, Rng};
+
+use crate::algebra::*;
+use crate::discrete_log::discrete_log;
+use crate::polynomial::Polynomial;
+use crate::projective::ProjectivePoint;
+use crate::std_impls::{factor, ZM};
+
+// MARK: Curves
+
+/// The elliptic curve y^2 = x^3 + Ax + B over the field of integers modulo a prime Q
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EllipticCurve<const Q: i64, const A: i64, const B: i64>;
+
+impl<const Q: i64, const A: i64, const B: i64> EllipticCurve<Q, A, B> {
+
+	/// The coefficient A of this curve
+	pub fn a() -> ZM<Q> {
+		ZM::from_int(A)
+	}
+
+	/// The coefficient B of this curve
+	pub fn b() -> ZM<Q> {
+		ZM::from_int(B)
+	}
+
+	/// The discriminant -16(4A^3 + 27B^2) of this curve
+	pub fn discriminant() -> ZM<Q> {
+		let a = Self::a();
+		let b = Self::b();
+		ZM::from_int(-16) * (ZM::from_int(4) * a * a * a + ZM::from_int(27) * b * b)
+	}
+
+	/// Whether or not this curve is nonsingular, which is required for the points to form a group
+	pub fn is_nonsingular() -> bool {
+		!Self::discriminant().is_zero()
+	}
+
+	/// The right hand side x^3 + Ax + B of the curve equation
+	pub fn rhs(x: ZM<Q>) -> ZM<Q> {
+		x * x * x + Self::a() * x + Self::b()
+	}
+
+	/// Whether or not the point (x, y) lies on this curve
+	pub fn contains(x: ZM<Q>, y: ZM<Q>) -> bool {
+		y * y == Self::rhs(x)
+	}
+
+	/// The affine point (x, y), if it lies on this curve
+	pub fn point(x: ZM<Q>, y: ZM<Q>) -> Option<ECPoint<Q, A, B>> {
+		if Self::contains(x, y) {
+			Some(ECPoint::Affine { x, y })
+		} else {
+			None
+		}
+	}
+
+	/// The point with projective coordinates (X : Y : Z), if it lies on the projective curve
+	/// Y^2 Z = X^3 + AXZ^2 + BZ^3. The only point with Z = 0 is (0 : 1 : 0), the point at infinity.
+	pub fn projective_point(p: &ProjectivePoint<ZM<Q>, 3>) -> Option<ECPoint<Q, A, B>> {
+		let [x, y, z] = *p.coordinates();
+
+		if y * y * z != x * x * x + Self::a() * x * z * z + Self::b() * z * z * z {
+			return None
+		}
+
+		if z.is_zero() {
+			return Some(ECPoint::Infinity)
+		}
+
+		let [x, y, _] = *p.normalized().coordinates();
+		Some(ECPoint::Affine { x, y })
+	}
+
+	/// Both points on the curve with the given x coordinate, if there are any
+	pub fn lift_x(x: ZM<Q>) -> Option<(ECPoint<Q, A, B>, ECPoint<Q, A, B>)> {
+		let (y1, y2) = Self::rhs(x).sqrt()?;
+		Some((ECPoint::Affine { x, y: y1 }, ECPoint::Affine { x, y: y2 }))
+	}
+
+	/// Every point on this curve, including the point at infinity.
+	///
+	/// This walks the whole field, so it is only reasonable for small Q.
+	pub fn points() -> Vec<ECPoint<Q, A, B>> {
+		let mut points = vec![ECPoint::Infinity];
+
+		for v in 0..Q {
+			let x = ZM::from_int(v);
+			if let Some((p1, p2)) = Self::lift_x(x) {
+				points.push(p1);
+				if p1 != p2 {
+					points.push(p2);
+				}
+			}
+		}
+
+		points
+	}
+
+	/// Generates a random affine point on the curve
+	///
+	/// NOT cryptographically secure!
+	pub fn random_point() -> ECPoint<Q, A, B> {
+		Self::random_point_with_rng(&mut StdRng::from_entropy())
+	}
+
+	/// Generates a random affine point on the curve using the given source of randomness
+	pub fn random_point_with_rng<G: Rng + ?Sized>(rng: &mut G) -> ECPoint<Q, A, B> {
+		loop {
+			let x = ZM::from_int(rng.gen_range(0..Q));
+			if let Some((p1, p2)) = Self::lift_x(x) {
+				return if rng.gen::<bool>() { p1 } else { p2 }
+			}
+		}
+	}
+
+}
+
+// MARK: Points
+
+/// A point on the elliptic curve y^2 = x^3 + Ax + B over ZM<Q>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ECPoint<const Q: i64, const A: i64, const B: i64> {
+	/// The point at infinity, which is the group identity
+	Infinity,
+
+	/// A point (x, y) in the affine plane
+	Affine { x: ZM<Q>, y: ZM<Q> }
+}
+
+impl<const Q: i64, const A: i64, const B: i64> ECPoint<Q, A, B> {
+
+	/// Whether or not this is the point at infinity
+	pub fn is_infinity(&self) -> bool {
+		matches!(self, ECPoint::Infinity)
+	}
+
+	/// The affine coordinates of this point, if it is not the point at infinity
+	pub fn coordinates(&self) -> Option<(ZM<Q>, ZM<Q>)> {
+		match self {
+			ECPoint::Infinity => None,
+			ECPoint::Affine { x, y } => Some((*x, *y))
+		}
+	}
+
+	/// This point in projective coordinates, where (x, y) is (x : y : 1) and infinity is (0 : 1 : 0)
+	pub fn to_projective(&self) -> ProjectivePoint<ZM<Q>, 3> {
+		let coordinates = match self {
+			ECPoint::Infinity => [ZM::zero(), ZM::one(), ZM::zero()],
+			ECPoint::Affine { x, y } => [*x, *y, ZM::one()]
+		};
+
+		ProjectivePoint::new(coordinates).unwrap()
+	}
+
+	/// Adds this point to itself
+	pub fn double(&self) -> Self {
+		*self + *self
+	}
+
+	/// Computes k * self using double-and-add in Jacobian coordinates, inverting only once at the end
+	pub fn scalar_mul(&self, k: i64) -> Self {
+		JacobianPoint::from(*self).scalar_mul(k).to_affine()
+	}
+
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Add for ECPoint<Q, A, B> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let (x1, y1, x2, y2) = match (self, rhs) {
+			(ECPoint::Infinity, _) => return rhs,
+			(_, ECPoint::Infinity) => return self,
+			(ECPoint::Affine { x: x1, y: y1 }, ECPoint::Affine { x: x2, y: y2 }) => (x1, y1, x2, y2)
+		};
+
+		let slope = if x1 != x2 {
+			(y2 - y1) / (x2 - x1)
+		} else if y1 == y2 && !y1.is_zero() {
+			// Doubling: the slope of the tangent line
+			(ZM::from_int(3) * x1 * x1 + EllipticCurve::<Q, A, B>::a()) / (y1 + y1)
+		} else {
+			// P + (-P), or doubling a point of order 2
+			return ECPoint::Infinity
+		};
+
+		let x3 = slope * slope - x1 - x2;
+		let y3 = slope * (x1 - x3) - y1;
+
+		ECPoint::Affine { x: x3, y: y3 }
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AddAssign for ECPoint<Q, A, B> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Neg for ECPoint<Q, A, B> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		match self {
+			ECPoint::Infinity => ECPoint::Infinity,
+			ECPoint::Affine { x, y } => ECPoint::Affine { x, y: -y }
+		}
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Sub for ECPoint<Q, A, B> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + (-rhs)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> SubAssign for ECPoint<Q, A, B> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Mul for ECPoint<Q, A, B> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> MulAssign for ECPoint<Q, A, B> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self += rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Div for ECPoint<Q, A, B> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> DivAssign for ECPoint<Q, A, B> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self -= rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Monoid for ECPoint<Q, A, B> {
+	fn identity() -> Self {
+		ECPoint::Infinity
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Group for ECPoint<Q, A, B> {
+
+	fn inverse(&self) -> Self {
+		-*self
+	}
+
+	fn power(&self, n: i64) -> Self {
+		self.scalar_mul(n)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AbelianGroup for ECPoint<Q, A, B> { }
+
+// MARK: Group Structure
+
+/// The order of a point P with nP = O, for n with the given prime factorization, found by dividing
+/// out each prime for as long as the multiple stays at infinity
+fn order_dividing<const Q: i64, const A: i64, const B: i64>(point: &ECPoint<Q, A, B>, n: i64, factors: &[(i64, u32)]) -> i64 {
+	let mut order = n;
+	for (p, _) in factors {
+		while order % p == 0 && point.scalar_mul(order / p).is_infinity() {
+			order /= p;
+		}
+	}
+	order
+}
+
+/// The largest power of the prime l dividing n
+fn prime_part(n: i64, l: i64) -> i64 {
+	let mut power = 1;
+	while n % (power * l) == 0 {
+		power *= l;
+	}
+	power
+}
+
+/// A point of order lcm(a, b), from points P and R of orders a and b dividing n, made by taking the
+/// part of order a power of each prime from whichever of the two has the larger power of it
+fn combine_orders<const Q: i64, const A: i64, const B: i64>(
+	p: &ECPoint<Q, A, B>, a: i64, r: &ECPoint<Q, A, B>, b: i64, factors: &[(i64, u32)]
+) -> (ECPoint<Q, A, B>, i64) {
+	let mut point = ECPoint::Infinity;
+	let mut order = 1;
+
+	for (l, _) in factors {
+		let (source, source_order) = if prime_part(a, *l) >= prime_part(b, *l) { (p, a) } else { (r, b) };
+		let power = prime_part(source_order, *l);
+		point += source.scalar_mul(source_order / power);
+		order *= power;
+	}
+
+	(point, order)
+}
+
+impl<const Q: i64, const A: i64, const B: i64> FiniteGroup for ECPoint<Q, A, B> {
+
+	/// The number of points on the curve, from `EllipticCurve::order`
+	fn order() -> usize {
+		EllipticCurve::<Q, A, B>::order() as usize
+	}
+
+	/// Every point on the curve, from `EllipticCurve::points`
+	fn elements() -> impl Iterator<Item = Self> {
+		EllipticCurve::<Q, A, B>::points().into_iter()
+	}
+
+	/// The order of this point, dividing the number of points on the curve
+	fn element_order(&self) -> usize {
+		let n = EllipticCurve::<Q, A, B>::order();
+		order_dividing(self, n, &factor(n)) as usize
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> EllipticCurve<Q, A, B> {
+
+	/// The number of points on this curve, including the point at infinity, which is Q + 1 plus the
+	/// sum of the Legendre symbols of x^3 + Ax + B over every x.
+	///
+	/// This walks the whole field, so it is only reasonable for small Q.
+	pub fn order() -> i64 {
+		(0..Q).fold(Q + 1, |count, v| count + Self::rhs(ZM::from_int(v)).legendre())
+	}
+
+	/// The trace of Frobenius t = Q + 1 - #E, which Hasse's theorem bounds by |t| <= 2 sqrt(Q)
+	pub fn trace_of_frobenius() -> i64 {
+		Q + 1 - Self::order()
+	}
+
+	/// Points P and R of orders n1 and n2 with the group of points the direct sum of the cyclic
+	/// groups they generate, so it is isomorphic to Z/(n1) x Z/(n2), with n2 dividing n1, and n2
+	/// dividing Q - 1 by the Weil pairing
+	pub fn generators() -> ((ECPoint<Q, A, B>, i64), (ECPoint<Q, A, B>, i64)) {
+		Self::generators_with_rng(&mut StdRng::from_entropy())
+	}
+
+	/// The generators of the group of points, sampling random points from the given source of
+	/// randomness.
+	///
+	/// Random points are combined into P of the largest order n1 found so far. Each point whose
+	/// order divides n1 is then reduced modulo the subgroup P generates, and once the orders of P
+	/// and the best reduced point R multiply to the number of points, they generate everything.
+	pub fn generators_with_rng<G: Rng + ?Sized>(rng: &mut G) -> ((ECPoint<Q, A, B>, i64), (ECPoint<Q, A, B>, i64)) {
+		let n = Self::order();
+		let factors = factor(n);
+		let (mut p, mut n1) = (ECPoint::Infinity, 1);
+		let (mut r, mut n2) = (ECPoint::Infinity, 1);
+
+		while n1 * n2 != n {
+			let x = Self::random_point_with_rng(rng);
+			let m = order_dividing(&x, n, &factors);
+			if n1 % m != 0 {
+				(p, n1) = combine_orders(&p, n1, &x, m, &factors);
+				(r, n2) = (ECPoint::Infinity, 1);
+				continue
+			}
+
+			// The order of x modulo the subgroup P generates, the least k with kx a multiple of P
+			let in_span = |k: i64| discrete_log(&p, &x.scalar_mul(k), n1 as u64);
+			let mut k = m;
+			for (l, _) in factors.iter() {
+				while k % l == 0 && in_span(k / l).is_some() {
+					k /= l;
+				}
+			}
+
+			if k > n2 {
+				// With kx = sP, n1 x = O forces k to divide s, so x - (s / k) P has order k and
+				// meets the subgroup P generates only at infinity
+				let s = in_span(k).unwrap() as i64;
+				(r, n2) = (x - p.scalar_mul(s / k), k);
+			}
+		}
+
+		((p, n1), (r, n2))
+	}
+
+	/// The invariants (n1, n2) with the group of points isomorphic to Z/(n1) x Z/(n2) and n2
+	/// dividing n1, so it is cyclic exactly when n2 = 1
+	pub fn group_structure() -> (i64, i64) {
+		let ((_, n1), (_, n2)) = Self::generators();
+		(n1, n2)
+	}
+
+	/// A point of order exactly n, if there is one, which happens when n divides the largest
+	/// order n1 of a point
+	pub fn point_of_order(n: i64) -> Option<ECPoint<Q, A, B>> {
+		let ((p, n1), _) = Self::generators();
+		(n > 0 && n1 % n == 0).then(|| p.scalar_mul(n1 / n))
+	}
+
+}
+
+// MARK: Division Polynomials
+
+impl<const Q: i64, const A: i64, const B: i64> EllipticCurve<Q, A, B> {
+
+	/// The n-th division polynomial as a polynomial in x alone: psi_n for n odd, and psi_n / 2y for
+	/// n even. The points P other than infinity with nP = O are those with psi_n(P) = 0, so for n
+	/// odd they are the points whose x coordinate is a root of this polynomial, and for n even the
+	/// points with such an x, along with those of order 2.
+	///
+	/// With psi_n in this form, psi_(2m) = psi_m (psi_(m+2) psi_(m-1)^2 - psi_(m-2) psi_(m+1)^2)
+	/// and psi_(2m+1) = psi_(m+2) psi_m^3 - psi_(m-1) psi_(m+1)^3, with whichever product has the
+	/// even indices multiplied by (4(x^3 + Ax + B))^2 = (2y)^4. Only the O(log n) polynomials these
+	/// recurrences reach from n are computed.
+	pub fn division_polynomial(n: usize) -> Polynomial<ZM<Q>> {
+		Self::division_polynomial_memo(n, &mut BTreeMap::new())
+	}
+
+	fn division_polynomial_memo(n: usize, memo: &mut BTreeMap<usize, Polynomial<ZM<Q>>>) -> Polynomial<ZM<Q>> {
+		if let Some(psi) = memo.get(&n) {
+			return psi.clone()
+		}
+
+		let (a, b) = (Self::a(), Self::b());
+		let int = |k: i64| ZM::<Q>::from_int(k);
+
+		let psi = match n {
+			0 => Polynomial::zero(),
+			1 | 2 => Polynomial::one(),
+			3 => Polynomial::new(vec![-a * a, int(12) * b, int(6) * a, ZM::zero(), int(3)]),
+			4 => Polynomial::new(vec![
+				-int(8) * b * b - a * a * a, -int(4) * a * b, -int(5) * a * a, int(20) * b, int(5) * a, ZM::zero(), ZM::one()
+			]).scale(&int(2)),
+			_ => {
+				let m = n / 2;
+				let mut psi = |k: usize| Self::division_polynomial_memo(k, memo);
+				let (before, at, after) = (psi(m - 1), psi(m), psi(m + 1));
+
+				if n.is_multiple_of(2) {
+					let (below, above) = (psi(m - 2), psi(m + 2));
+					at * (above * before.power(2) - below * after.power(2))
+				} else {
+					let above = psi(m + 2);
+					let curve = Polynomial::new(vec![b, a, ZM::zero(), ZM::one()]).scale(&int(4)).power(2);
+					let (first, second) = (above * at.power(3), before * after.power(3));
+					if m.is_multiple_of(2) { curve * first - second } else { first - curve * second }
+				}
+			}
+		};
+
+		memo.insert(n, psi.clone());
+		psi
+	}
+
+	/// Every point P with nP = O, for n positive, read off the roots of the n-th division
+	/// polynomial, starting with the point at infinity
+	pub fn torsion_points(n: usize) -> Vec<ECPoint<Q, A, B>> {
+		if n == 0 {
+			panic!("Torsion points are defined for positive n, got 0")
+		}
+
+		let mut psi = Self::division_polynomial(n);
+		if n.is_multiple_of(2) {
+			psi *= Polynomial::new(vec![Self::b(), Self::a(), ZM::zero(), ZM::one()]);
+		}
+
+		let mut points = vec![ECPoint::Infinity];
+		for x in psi.roots_mod_p() {
+			if let Some((p1, p2)) = Self::lift_x(x) {
+				points.push(p1);
+				if p1 != p2 {
+					points.push(p2);
+				}
+			}
+		}
+
+		points
+	}
+
+}
+
+// MARK: Jacobian Coordinates
+
+/// A point on the elliptic curve y^2 = x^3 + Ax + B over ZM<Q> in Jacobian coordinates,
+/// where (X, Y, Z) stands for the affine point (X / Z^2, Y / Z^3) and Z = 0 is the point at infinity.
+///
+/// Adding and doubling in these coordinates needs no field inversions, so long chains of
+/// operations are much faster than with `ECPoint`. Convert back with `to_affine` when done.
+#[derive(Clone, Copy, Debug)]
+pub struct JacobianPoint<const Q: i64, const A: i64, const B: i64> {
+	pub x: ZM<Q>,
+	pub y: ZM<Q>,
+	pub z: ZM<Q>
+}
+
+impl<const Q: i64, const A: i64, const B: i64> JacobianPoint<Q, A, B> {
+
+	/// The point at infinity, (1, 1, 0)
+	pub fn infinity() -> Self {
+		JacobianPoint { x: ZM::one(), y: ZM::one(), z: ZM::zero() }
+	}
+
+	/// Whether or not this is the point at infinity
+	pub fn is_infinity(&self) -> bool {
+		self.z.is_zero()
+	}
+
+	/// The same point as an `ECPoint`, which costs one field inversion
+	pub fn to_affine(&self) -> ECPoint<Q, A, B> {
+		if self.is_infinity() {
+			return ECPoint::Infinity
+		}
+
+		let z_inverse = self.z.inverse();
+		let z_inverse_squared = z_inverse * z_inverse;
+
+		ECPoint::Affine { x: self.x * z_inverse_squared, y: self.y * z_inverse_squared * z_inverse }
+	}
+
+	/// Adds this point to itself
+	pub fn double(&self) -> Self {
+		// The "dbl-2007-bl" formulas from the Explicit-Formulas Database
+		let xx = self.x * self.x;
+		let yy = self.y * self.y;
+		let yyyy = yy * yy;
+		let zz = self.z * self.z;
+
+		let s = ZM::from_int(2) * ((self.x + yy) * (self.x + yy) - xx - yyyy);
+		let m = ZM::from_int(3) * xx + EllipticCurve::<Q, A, B>::a() * zz * zz;
+		let x = m * m - s - s;
+		let y = m * (s - x) - ZM::from_int(8) * yyyy;
+		let z = (self.y + self.z) * (self.y + self.z) - yy - zz;
+
+		JacobianPoint { x, y, z }
+	}
+
+	/// Computes k * self using double-and-add
+	pub fn scalar_mul(&self, k: i64) -> Self {
+		if k < 0 {
+			return (-*self).scalar_mul(-k)
+		}
+
+		let mut result = Self::infinity();
+		let mut addend = *self;
+		let mut k = k;
+
+		while k > 0 {
+			if k & 1 == 1 {
+				result += addend
+			}
+			addend = addend.double();
+			k >>= 1;
+		}
+
+		result
+	}
+
+}
+
+impl<const Q: i64, const A: i64, const B: i64> From<ECPoint<Q, A, B>> for JacobianPoint<Q, A, B> {
+	fn from(point: ECPoint<Q, A, B>) -> Self {
+		match point {
+			ECPoint::Infinity => JacobianPoint::infinity(),
+			ECPoint::Affine { x, y } => JacobianPoint { x, y, z: ZM::one() }
+		}
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> PartialEq for JacobianPoint<Q, A, B> {
+	/// Equality of the points represented, rather than of the coordinates
+	fn eq(&self, other: &Self) -> bool {
+		if self.is_infinity() || other.is_infinity() {
+			return self.is_infinity() == other.is_infinity()
+		}
+
+		let z1z1 = self.z * self.z;
+		let z2z2 = other.z * other.z;
+
+		self.x * z2z2 == other.x * z1z1 && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Eq for JacobianPoint<Q, A, B> { }
+
+/// Hashes the affine point, so that equal points hash alike whatever their Z coordinate
+impl<const Q: i64, const A: i64, const B: i64> Hash for JacobianPoint<Q, A, B> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.to_affine().hash(state)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Add for JacobianPoint<Q, A, B> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		if self.is_infinity() {
+			return rhs
+		} else if rhs.is_infinity() {
+			return self
+		}
+
+		// The "add-2007-bl" formulas from the Explicit-Formulas Database
+		let z1z1 = self.z * self.z;
+		let z2z2 = rhs.z * rhs.z;
+		let u1 = self.x * z2z2;
+		let u2 = rhs.x * z1z1;
+		let s1 = self.y * rhs.z * z2z2;
+		let s2 = rhs.y * self.z * z1z1;
+
+		let h = u2 - u1;
+		let r = (s2 - s1) + (s2 - s1);
+
+		if h.is_zero() {
+			// Either the points are equal or they are inverses
+			return if r.is_zero() { self.double() } else { Self::infinity() }
+		}
+
+		let i = (h + h) * (h + h);
+		let j = h * i;
+		let v = u1 * i;
+
+		let x = r * r - j - v - v;
+		let y = r * (v - x) - ZM::from_int(2) * s1 * j;
+		let z = ((self.z + rhs.z) * (self.z + rhs.z) - z1z1 - z2z2) * h;
+
+		JacobianPoint { x, y, z }
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AddAssign for JacobianPoint<Q, A, B> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Neg for JacobianPoint<Q, A, B> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		JacobianPoint { x: self.x, y: -self.y, z: self.z }
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Sub for JacobianPoint<Q, A, B> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + (-rhs)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> SubAssign for JacobianPoint<Q, A, B> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Mul for JacobianPoint<Q, A, B> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> MulAssign for JacobianPoint<Q, A, B> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self += rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const Q: i64, const A: i64, const B: i64> Div for JacobianPoint<Q, A, B> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const Q: i64, const A: i64, const B: i64> DivAssign for JacobianPoint<Q, A, B> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self -= rhs
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Monoid for JacobianPoint<Q, A, B> {
+	fn identity() -> Self {
+		Self::infinity()
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Group for JacobianPoint<Q, A, B> {
+
+	fn inverse(&self) -> Self {
+		-*self
+	}
+
+	fn power(&self, n: i64) -> Self {
+		self.scalar_mul(n)
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> AbelianGroup for JacobianPoint<Q, A, B> { }
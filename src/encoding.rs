@@ -0,0 +1,151 @@
+//
+// Canonical byte encodings of ring and field elements
+//
+
+use crate::algebra::*;
+use crate::big_zm::{BigModulus, BigZM};
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+/// A canonical little-endian byte encoding, so that equal elements always encode to the same bytes
+/// and every element can be read back from its encoding.
+pub trait Encodable: Sized {
+
+	/// The canonical encoding of this element
+	fn to_bytes(&self) -> Vec<u8>;
+
+	/// The element with the given encoding, or `None` if the bytes are not exactly the canonical
+	/// encoding of some element
+	fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+}
+
+/// Reads a little-endian u64 length from the front of bytes, returning it and the rest
+fn split_length(bytes: &[u8]) -> Option<(usize, &[u8])> {
+	if bytes.len() < 8 {
+		return None
+	}
+
+	let (length, rest) = bytes.split_at(8);
+	let length = u64::from_le_bytes(length.try_into().unwrap());
+	Some((usize::try_from(length).ok()?, rest))
+}
+
+// MARK: Integers
+
+macro_rules! impl_encodable_integer {
+	($($t:ty),*) => {$(
+		/// The two's complement bytes, little-endian, always `size_of::<Self>()` of them
+		impl Encodable for $t {
+			fn to_bytes(&self) -> Vec<u8> {
+				self.to_le_bytes().to_vec()
+			}
+
+			fn from_bytes(bytes: &[u8]) -> Option<Self> {
+				Some(<$t>::from_le_bytes(bytes.try_into().ok()?))
+			}
+		}
+	)*};
+}
+
+impl_encodable_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+#[cfg(feature = "bigint")]
+impl Encodable for num_bigint::BigInt {
+	/// The shortest two's complement bytes, little-endian, where zero is a single zero byte
+	fn to_bytes(&self) -> Vec<u8> {
+		self.to_signed_bytes_le()
+	}
+
+	fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		let n = num_bigint::BigInt::from_signed_bytes_le(bytes);
+		if n.to_signed_bytes_le() == bytes { Some(n) } else { None }
+	}
+}
+
+// MARK: Prime Fields
+
+impl<const Q: i64> ZM<Q> {
+
+	/// The number of bytes needed to write any element, which is the number needed for Q - 1
+	pub fn encoded_len() -> usize {
+		(64 - (Q - 1).max(1).leading_zeros() as usize).div_ceil(8)
+	}
+
+}
+
+impl<const Q: i64> Encodable for ZM<Q> {
+	/// The representative in 0..Q, little-endian, in exactly `encoded_len()` bytes
+	fn to_bytes(&self) -> Vec<u8> {
+		self.val.rem_euclid(Q).to_le_bytes()[..Self::encoded_len()].to_vec()
+	}
+
+	fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		if bytes.len() != Self::encoded_len() {
+			return None
+		}
+
+		let mut buffer = [0; 8];
+		buffer[..bytes.len()].copy_from_slice(bytes);
+		let val = i64::from_le_bytes(buffer);
+
+		if val < Q { Some(ZM { val }) } else { None }
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Encodable for BigZM<M, LIMBS> {
+	/// The value below the modulus, little-endian, in exactly 8 LIMBS bytes
+	fn to_bytes(&self) -> Vec<u8> {
+		self.to_limbs().iter().flat_map(|limb| limb.to_le_bytes()).collect()
+	}
+
+	fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		if bytes.len() != 8 * LIMBS {
+			return None
+		}
+
+		let limbs = std::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap()));
+		BigZM::from_limbs(limbs)
+	}
+}
+
+// MARK: Polynomials
+
+impl<R: Ring + Encodable> Encodable for Polynomial<R> {
+	/// The number of coefficients as a little-endian u64, then each coefficient from the constant
+	/// term up, as the length of its encoding as a little-endian u64 followed by the encoding itself
+	fn to_bytes(&self) -> Vec<u8> {
+		let mut bytes = (self.coefficients().len() as u64).to_le_bytes().to_vec();
+
+		for c in self.coefficients() {
+			let encoding = c.to_bytes();
+			bytes.extend((encoding.len() as u64).to_le_bytes());
+			bytes.extend(encoding);
+		}
+
+		bytes
+	}
+
+	fn from_bytes(bytes: &[u8]) -> Option<Self> {
+		let (count, mut rest) = split_length(bytes)?;
+		let mut coefficients = Vec::new();
+
+		for _ in 0..count {
+			let (length, tail) = split_length(rest)?;
+			if tail.len() < length {
+				return None
+			}
+
+			let (encoding, tail) = tail.split_at(length);
+			coefficients.push(R::from_bytes(encoding)?);
+			rest = tail;
+		}
+
+		// Trailing bytes, or a zero leading coefficient, would make the encoding non-canonical
+		if !rest.is_empty() || coefficients.last().is_some_and(|c| c.is_zero()) {
+			return None
+		}
+
+		Some(Polynomial::new(coefficients))
+	}
+}
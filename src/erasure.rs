@@ -0,0 +1,178 @@
+//
+// Erasure coding: k data blocks spread over n shares, any k of which recover the data
+//
+
+use crate::algebra::*;
+use crate::binary_field::GF256;
+use crate::matrix::Matrix;
+
+// MARK: Shares
+
+/// One of the n shares of an encoding, with its index among them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Share<T> {
+	pub index: usize,
+	pub symbols: Vec<T>
+}
+
+// MARK: Erasure Code
+
+/// A systematic (n, k) erasure code over a field F, built from polynomial interpolation.
+///
+/// The code fixes n distinct evaluation points x_0, ..., x_(n-1). Each symbol position of the k
+/// data blocks gives the values of a polynomial of degree less than k at x_0, ..., x_(k-1), and
+/// share j holds its value at x_j. So the first k shares are the data itself, and since a
+/// polynomial of degree less than k is determined by any k of its values, any k shares recover it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErasureCode<F: Field> {
+	data_shares: usize,
+	points: Vec<F>,
+	encoding: Matrix<F>
+}
+
+impl<F: Field> ErasureCode<F> {
+
+	/// A code with k data shares and one share for each evaluation point, or None unless
+	/// 0 < k <= n and the points are distinct
+	pub fn new(data_shares: usize, points: Vec<F>) -> Option<ErasureCode<F>> {
+		if data_shares == 0 || data_shares > points.len() {
+			return None
+		}
+
+		for (i, a) in points.iter().enumerate() {
+			if points[..i].contains(a) {
+				return None
+			}
+		}
+
+		// Row j holds the Lagrange basis polynomials for x_0, ..., x_(k-1), evaluated at x_j
+		let k = data_shares;
+		let mut entries = Vec::with_capacity(points.len() * k);
+		for x in points.iter() {
+			for i in 0..k {
+				let mut weight = F::one();
+				for m in (0..k).filter(|&m| m != i) {
+					weight *= x.sub_ref(&points[m]) / points[i].sub_ref(&points[m]);
+				}
+				entries.push(weight);
+			}
+		}
+
+		let encoding = Matrix::new(points.len(), k, entries);
+		Some(ErasureCode { data_shares, points, encoding })
+	}
+
+	/// The number k of shares needed to recover the data
+	pub fn data_shares(&self) -> usize {
+		self.data_shares
+	}
+
+	/// The total number n of shares
+	pub fn total_shares(&self) -> usize {
+		self.points.len()
+	}
+
+	/// The evaluation points x_0, ..., x_(n-1)
+	pub fn points(&self) -> &[F] {
+		&self.points
+	}
+
+	/// The n x k matrix taking a column of data symbols to a column of share symbols
+	pub fn encoding_matrix(&self) -> &Matrix<F> {
+		&self.encoding
+	}
+
+	/// Encodes k data blocks of equal length into n shares, the first k of which are the blocks
+	pub fn encode(&self, data: &[Vec<F>]) -> Vec<Share<F>> {
+		if data.len() != self.data_shares {
+			panic!("Expected {} data blocks, got {}", self.data_shares, data.len())
+		}
+
+		let length = data[0].len();
+		if data.iter().any(|block| block.len() != length) {
+			panic!("Data blocks must all have the same length")
+		}
+
+		(0..self.total_shares()).map(|j| Share {
+			index: j,
+			symbols: (0..length).map(|s| {
+				(0..self.data_shares).fold(F::zero(), |sum, i| sum + self.encoding[(j, i)].mul_ref(&data[i][s]))
+			}).collect()
+		}).collect()
+	}
+
+	/// Recovers the k data blocks from shares with at least k distinct indices, or None if there
+	/// are too few, an index is out of range, or the shares have different lengths
+	pub fn reconstruct(&self, shares: &[Share<F>]) -> Option<Vec<Vec<F>>> {
+		let k = self.data_shares;
+
+		let mut chosen: Vec<&Share<F>> = Vec::with_capacity(k);
+		for share in shares {
+			if share.index >= self.total_shares() {
+				return None
+			}
+
+			if chosen.len() < k && chosen.iter().all(|c| c.index != share.index) {
+				chosen.push(share);
+			}
+		}
+
+		if chosen.len() < k {
+			return None
+		}
+
+		let length = chosen[0].symbols.len();
+		if chosen.iter().any(|share| share.symbols.len() != length) {
+			return None
+		}
+
+		// The rows of the encoding matrix for the chosen shares form an invertible k x k matrix,
+		// since a polynomial of degree less than k is determined by its values at any k points
+		let rows = Matrix::from_fn(k, k, |r, i| self.encoding[(chosen[r].index, i)].clone());
+		let decoding = rows.inverse()?;
+
+		Some((0..k).map(|i| {
+			(0..length).map(|s| {
+				(0..k).fold(F::zero(), |sum, r| sum + decoding[(i, r)].mul_ref(&chosen[r].symbols[s]))
+			}).collect()
+		}).collect())
+	}
+
+}
+
+// MARK: Bytes
+
+impl ErasureCode<GF256> {
+
+	/// A code over GF(2^8) with k data shares out of n, using the points 0, 1, ..., n - 1, or None
+	/// unless 0 < k <= n <= 256
+	pub fn for_bytes(data_shares: usize, total_shares: usize) -> Option<Self> {
+		if total_shares > 256 {
+			return None
+		}
+
+		ErasureCode::new(data_shares, (0..total_shares).map(|b| GF256(b as u8)).collect())
+	}
+
+	/// Encodes k byte blocks of equal length into n byte shares
+	pub fn encode_bytes(&self, data: &[&[u8]]) -> Vec<Share<u8>> {
+		let blocks: Vec<Vec<GF256>> = data.iter().map(|block| block.iter().map(|&b| GF256(b)).collect()).collect();
+
+		self.encode(&blocks).into_iter().map(|share| Share {
+			index: share.index,
+			symbols: share.symbols.iter().map(|b| b.0).collect()
+		}).collect()
+	}
+
+	/// Recovers the k byte blocks from any k byte shares
+	pub fn reconstruct_bytes(&self, shares: &[Share<u8>]) -> Option<Vec<Vec<u8>>> {
+		let shares: Vec<Share<GF256>> = shares.iter().map(|share| Share {
+			index: share.index,
+			symbols: share.symbols.iter().map(|&b| GF256(b)).collect()
+		}).collect();
+
+		let data = self.reconstruct(&shares)?;
+		Some(data.iter().map(|block| block.iter().map(|b| b.0).collect()).collect())
+	}
+
+}
@@ -0,0 +1,30 @@
+//
+// Errors for operations that can fail, for callers who would rather not panic
+//
+
+use core::fmt::Display;
+
+/// The ways an algebraic operation can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AlgebraError {
+	/// Division by zero, or inverting zero
+	DivisionByZero,
+
+	/// Inverting a nonzero element which has no inverse, like 2 modulo 6
+	NotInvertible,
+
+	/// Integer arithmetic which overflowed its type
+	Overflow
+}
+
+impl Display for AlgebraError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			AlgebraError::DivisionByZero => write!(f, "Cannot divide by zero"),
+			AlgebraError::NotInvertible => write!(f, "Element is not invertible"),
+			AlgebraError::Overflow => write!(f, "Integer arithmetic overflowed")
+		}
+	}
+}
+
+impl core::error::Error for AlgebraError { }
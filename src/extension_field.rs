@@ -0,0 +1,516 @@
+//
+// Finite fields GF(Q^K), as polynomials over ZM<Q> modulo an irreducible polynomial
+//
+
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::finite_set::FiniteSet;
+use crate::integer_polynomial::cyclotomic;
+use crate::linear_operator::LinearOperator;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+use crate::random::RandomElement;
+use crate::std_impls::{factor, ZM};
+
+// MARK: Moduli
+
+/// A monic irreducible polynomial x^K + c_(K-1) x^(K-1) + ... + c_0 over ZM<Q>, given at the type level.
+///
+/// For example, x^2 + 1 is irreducible over ZM<Q> whenever Q is 3 mod 4:
+///
+/// ```
+/// use algebra_kit::extension_field::ModulusPolynomial;
+///
+/// struct ISquaredPlusOne;
+///
+/// impl ModulusPolynomial<59, 2> for ISquaredPlusOne {
+///     const COEFFICIENTS: [i64; 2] = [1, 0];
+/// }
+/// ```
+pub trait ModulusPolynomial<const Q: i64, const K: usize> {
+
+	/// The coefficients c_0, ..., c_(K-1) below the leading 1
+	const COEFFICIENTS: [i64; K];
+
+}
+
+/// The modulus x^8 + x^4 + x^3 + x + 1 of AES, which makes GF(2^8) the field of bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rijndael;
+
+impl ModulusPolynomial<2, 8> for Rijndael {
+	const COEFFICIENTS: [i64; 8] = [1, 1, 0, 1, 1, 0, 0, 0];
+}
+
+// MARK: Field Elements
+
+/// An element of the field GF(Q^K) = ZM<Q>[x] / (P), written as a polynomial of degree less than K
+pub struct GF<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> {
+	coefficients: [ZM<Q>; K],
+	_modulus: PhantomData<P>
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> GF<P, Q, K> {
+
+	/// The element c_0 + c_1 x + ... + c_(K-1) x^(K-1)
+	pub fn new(coefficients: [ZM<Q>; K]) -> Self {
+		GF { coefficients, _modulus: PhantomData }
+	}
+
+	/// The image of c under the inclusion of ZM<Q> as the constants
+	pub fn from_base(c: ZM<Q>) -> Self {
+		let mut coefficients = [ZM::zero(); K];
+		coefficients[0] = c;
+		Self::new(coefficients)
+	}
+
+	/// The class of x, which is a root of the modulus
+	pub fn x() -> Self {
+		Self::from_polynomial(&Polynomial::x())
+	}
+
+	/// The coefficients of this element, from the constant term up
+	pub fn coefficients(&self) -> &[ZM<Q>; K] {
+		&self.coefficients
+	}
+
+	/// The constant term, if this element lies in ZM<Q>
+	pub fn to_base(&self) -> Option<ZM<Q>> {
+		if self.coefficients[1..].iter().all(|c| c.is_zero()) {
+			Some(self.coefficients[0])
+		} else {
+			None
+		}
+	}
+
+	/// The modulus as a polynomial
+	pub fn modulus() -> Polynomial<ZM<Q>> {
+		let mut coefficients: Vec<ZM<Q>> = P::COEFFICIENTS.iter().map(|&c| ZM::from_int(c)).collect();
+		coefficients.push(ZM::one());
+		Polynomial::new(coefficients)
+	}
+
+	/// The number of elements in the field, Q^K
+	pub fn order() -> u128 {
+		(Q as u128).pow(K as u32)
+	}
+
+	/// This element as a polynomial of degree less than K
+	pub fn to_polynomial(&self) -> Polynomial<ZM<Q>> {
+		Polynomial::new(self.coefficients.to_vec())
+	}
+
+	/// The reduction of a polynomial modulo P
+	pub fn from_polynomial(p: &Polynomial<ZM<Q>>) -> Self {
+		let remainder = p.clone() % Self::modulus();
+		Self::new(std::array::from_fn(|i| remainder.coefficient(i)))
+	}
+
+	/// The Frobenius automorphism, sending this element to its Qth power
+	pub fn frobenius(&self) -> Self {
+		self.pow_u128(Q as u128)
+	}
+
+	/// This element to a power e, which may be too large for `Ring::power`
+	pub fn pow_u128(&self, e: u128) -> Self {
+		let mut power = Self::one();
+		let mut base = *self;
+		let mut e = e;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base
+			}
+			base *= base;
+			e >>= 1;
+		}
+
+		power
+	}
+
+}
+
+impl<P: ModulusPolynomial<2, 8>> GF<P, 2, 8> {
+
+	/// The element whose coefficients are the bits of b, with the constant term in the lowest bit
+	pub fn from_byte(b: u8) -> Self {
+		Self::new(std::array::from_fn(|i| ZM::from_int(((b >> i) & 1) as i64)))
+	}
+
+	/// The byte whose bits are the coefficients of this element
+	pub fn to_byte(&self) -> u8 {
+		self.coefficients.iter().enumerate().fold(0, |b, (i, c)| b | ((c.val as u8) << i))
+	}
+
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> RandomElement for GF<P, Q, K> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		Self::new(std::array::from_fn(|_| ZM::random(rng)))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Distribution<GF<P, Q, K>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GF<P, Q, K> {
+		GF::random(rng)
+	}
+}
+
+/// Each element is numbered by its coefficients as base Q digits, the constant term lowest, so
+/// over GF(2^8) the number is the same as the byte
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> FiniteSet for GF<P, Q, K> {
+	fn size() -> u128 {
+		Self::order()
+	}
+
+	fn to_index(&self) -> u128 {
+		self.coefficients.to_index()
+	}
+
+	fn from_index(index: u128) -> Self {
+		Self::new(<[ZM<Q>; K]>::from_index(index))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> FiniteRing for GF<P, Q, K> {
+	fn characteristic() -> u128 {
+		Q as u128
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> FiniteField for GF<P, Q, K> {
+	fn degree() -> u32 {
+		K as u32
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Clone for GF<P, Q, K> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Copy for GF<P, Q, K> { }
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Debug for GF<P, Q, K> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		self.coefficients.fmt(f)
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> PartialEq for GF<P, Q, K> {
+	fn eq(&self, other: &Self) -> bool {
+		self.coefficients == other.coefficients
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Eq for GF<P, Q, K> { }
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Hash for GF<P, Q, K> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.coefficients.hash(state)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Add for GF<P, Q, K> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Self::new(std::array::from_fn(|i| self.coefficients[i] + rhs.coefficients[i]))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> AddAssign for GF<P, Q, K> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Neg for GF<P, Q, K> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::new(self.coefficients.map(|c| -c))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Sub for GF<P, Q, K> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Self::new(std::array::from_fn(|i| self.coefficients[i] - rhs.coefficients[i]))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> SubAssign for GF<P, Q, K> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Mul for GF<P, Q, K> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let mut product = vec![ZM::<Q>::zero(); 2 * K - 1];
+		for (i, a) in self.coefficients.iter().enumerate() {
+			for (j, b) in rhs.coefficients.iter().enumerate() {
+				product[i + j] += *a * *b;
+			}
+		}
+
+		// Reduce from the top down, using x^K = -(c_0 + c_1 x + ... + c_(K-1) x^(K-1))
+		for d in (K..2 * K - 1).rev() {
+			let top = product[d];
+			for (i, &c) in P::COEFFICIENTS.iter().enumerate() {
+				product[d - K + i] -= top * ZM::from_int(c);
+			}
+		}
+
+		Self::new(std::array::from_fn(|i| product[i]))
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> MulAssign for GF<P, Q, K> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Div for GF<P, Q, K> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> DivAssign for GF<P, Q, K> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Semiring for GF<P, Q, K> {
+	fn one() -> Self {
+		Self::from_base(ZM::one())
+	}
+
+	fn zero() -> Self {
+		Self::new([ZM::zero(); K])
+	}
+
+	fn is_zero(&self) -> bool {
+		self.coefficients.iter().all(|c| c.is_zero())
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Ring for GF<P, Q, K> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			self.inverse().pow_u128(n.unsigned_abs() as u128)
+		} else {
+			self.pow_u128(n as u128)
+		}
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> Field for GF<P, Q, K> {
+	/// The inverse by the extended Euclidean algorithm against the modulus
+	fn inverse(&self) -> Self {
+		self.try_inverse().unwrap_or_else(|| panic!("{:?} is not invertible in GF({}^{})", self, Q, K))
+	}
+
+	fn try_inverse(&self) -> Option<Self> {
+		let (g, s, _) = ext_gcd(&self.to_polynomial(), &Self::modulus());
+
+		// g is a nonzero constant exactly when this element is coprime to the modulus
+		if g.is_zero() || g.degree() > 0 {
+			return None
+		}
+
+		Some(Self::from_polynomial(&s.scale(&g.coefficient(0).inverse())))
+	}
+}
+
+// MARK: Conjugates
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> GF<P, Q, K> {
+
+	/// The distinct conjugates a, a^Q, a^(Q^2), ... of this element over ZM<Q>, which are the roots
+	/// of its minimal polynomial. There are d of them when this element generates GF(Q^d), and d
+	/// divides K.
+	pub fn conjugates(&self) -> Vec<Self> {
+		let mut conjugates = vec![*self];
+		let mut conjugate = self.frobenius();
+		while conjugate != *self {
+			conjugates.push(conjugate);
+			conjugate = conjugate.frobenius();
+		}
+		conjugates
+	}
+
+	/// The monic polynomial of least degree over ZM<Q> with this element as a root, the product of
+	/// x - c over its distinct conjugates c
+	pub fn minimal_polynomial(&self) -> Polynomial<ZM<Q>> {
+		let product: Polynomial<Self> = self.conjugates().into_iter()
+			.fold(Polynomial::one(), |product, c| product * Polynomial::new(vec![-c, Self::one()]));
+		Polynomial::new(product.coefficients().iter().map(|c| c.to_base().unwrap()).collect())
+	}
+
+	/// The trace to ZM<Q>, the sum a + a^Q + ... + a^(Q^(K-1)) of the whole Frobenius orbit, which
+	/// is a ZM<Q>-linear map onto ZM<Q>
+	pub fn trace(&self) -> ZM<Q> {
+		self.frobenius_orbit().into_iter().fold(Self::zero(), |sum, c| sum + c).to_base().unwrap()
+	}
+
+	/// The norm to ZM<Q>, the product a a^Q ... a^(Q^(K-1)) of the whole Frobenius orbit, which is
+	/// multiplicative and sends the units onto the units of ZM<Q>
+	pub fn norm(&self) -> ZM<Q> {
+		self.frobenius_orbit().into_iter().fold(Self::one(), |product, c| product * c).to_base().unwrap()
+	}
+
+}
+
+// MARK: Generators
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> GF<P, Q, K> {
+
+	/// The distinct primes dividing Q^K - 1, the order of the multiplicative group.
+	///
+	/// Since Q^K - 1 is the product of the cyclotomic values Φ_d(Q) over the divisors d of K, only
+	/// those need factoring, which keeps the numbers small enough for trial division far beyond
+	/// where Q^K - 1 itself would be.
+	fn group_order_primes() -> Vec<u128> {
+		let mut primes: Vec<u128> = (1..=K).filter(|d| K.is_multiple_of(*d)).flat_map(|d| {
+			let value = cyclotomic(d).coefficients().iter().rev()
+				.try_fold(0i128, |sum, &c| sum.checked_mul(Q as i128)?.checked_add(c as i128))
+				.and_then(|v| i64::try_from(v).ok())
+				.unwrap_or_else(|| panic!("The cyclotomic factor for d = {} of {}^{} - 1 is too large to factor", d, Q, K));
+			factor(value).into_iter().map(|(p, _)| p as u128)
+		}).collect();
+		primes.sort();
+		primes.dedup();
+		primes
+	}
+
+	/// Whether or not this element generates the multiplicative group, meaning it has order Q^K - 1
+	pub fn is_generator(&self) -> bool {
+		let n = Self::order() - 1;
+		!self.is_zero() && Self::group_order_primes().into_iter().all(|p| self.pow_u128(n / p) != Self::one())
+	}
+
+	/// The first generator of the multiplicative group in order of `FiniteSet` index, a primitive
+	/// element whose powers are every nonzero element. It is x when the modulus is a primitive
+	/// polynomial.
+	pub fn generator() -> Self {
+		let n = Self::order() - 1;
+		let primes = Self::group_order_primes();
+		(1..Self::order()).map(Self::from_index)
+			.find(|g| primes.iter().all(|p| g.pow_u128(n / p) != Self::one()))
+			.unwrap()
+	}
+
+}
+
+// MARK: Normal Bases
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> GF<P, Q, K> {
+
+	/// The Frobenius orbit a, a^Q, a^(Q^2), ..., a^(Q^(K-1)) of this element
+	pub fn frobenius_orbit(&self) -> [Self; K] {
+		let mut conjugate = *self;
+		std::array::from_fn(|_| {
+			let current = conjugate;
+			conjugate = conjugate.frobenius();
+			current
+		})
+	}
+
+	/// Whether or not the Frobenius orbit of this element is a basis of GF(Q^K) over ZM<Q>
+	pub fn is_normal(&self) -> bool {
+		!NormalBasis::orbit_matrix(self).determinant().is_zero()
+	}
+
+}
+
+/// A normal basis g, g^Q, ..., g^(Q^(K-1)) of GF(Q^K) over ZM<Q>, the Frobenius orbit of a single
+/// normal element g.
+///
+/// In coordinates with respect to a normal basis the Frobenius map is a cyclic shift, so raising
+/// to the power Q, and any power whose exponent has few nonzero digits in base Q, is cheap.
+pub struct NormalBasis<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> {
+	generator: GF<P, Q, K>,
+
+	/// The change of basis from coefficients in the polynomial basis 1, x, ..., x^(K-1) to
+	/// coordinates in the normal basis
+	to_normal: Matrix<ZM<Q>>
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize> NormalBasis<P, Q, K> {
+
+	/// The matrix whose ith column holds the coefficients of g^(Q^i)
+	fn orbit_matrix(g: &GF<P, Q, K>) -> Matrix<ZM<Q>> {
+		let orbit = g.frobenius_orbit();
+		Matrix::from_fn(K, K, |j, i| orbit[i].coefficients[j])
+	}
+
+	/// The normal basis generated by g, or None if g is not a normal element
+	pub fn new(generator: GF<P, Q, K>) -> Option<Self> {
+		let to_normal = Self::orbit_matrix(&generator).inverse()?;
+		Some(NormalBasis { generator, to_normal })
+	}
+
+	/// The normal basis generated by the first normal element c_0 + c_1 x + ... + c_(K-1) x^(K-1)
+	/// in increasing order of c_0 + c_1 Q + ... + c_(K-1) Q^(K-1), so the same basis every time.
+	///
+	/// Normal elements exist in every finite field, and make up a large enough fraction of it that
+	/// the search is short.
+	pub fn find() -> Self {
+		(0u128..).find_map(|index| {
+			let mut index = index;
+			Self::new(GF::new(std::array::from_fn(|_| {
+				let digit = ZM::from((index % Q as u128) as i64);
+				index /= Q as u128;
+				digit
+			})))
+		}).unwrap()
+	}
+
+	/// The normal element g generating this basis
+	pub fn generator(&self) -> GF<P, Q, K> {
+		self.generator
+	}
+
+	/// The basis g, g^Q, ..., g^(Q^(K-1)) itself
+	pub fn basis(&self) -> [GF<P, Q, K>; K] {
+		self.generator.frobenius_orbit()
+	}
+
+	/// The coordinates a_0, ..., a_(K-1) of an element a = a_0 g + a_1 g^Q + ... in this basis
+	pub fn to_coordinates(&self, a: &GF<P, Q, K>) -> [ZM<Q>; K] {
+		let coordinates = self.to_normal.apply(a.coefficients());
+		std::array::from_fn(|i| coordinates[i])
+	}
+
+	/// The element a_0 g + a_1 g^Q + ... + a_(K-1) g^(Q^(K-1)) with the given coordinates
+	pub fn from_coordinates(&self, coordinates: &[ZM<Q>; K]) -> GF<P, Q, K> {
+		self.basis().iter().zip(coordinates.iter()).fold(GF::zero(), |sum, (b, &c)| sum + *b * GF::from_base(c))
+	}
+
+	/// The coordinates of a^Q given those of a, which are those of a shifted cyclically by one
+	pub fn frobenius_coordinates(coordinates: &[ZM<Q>; K]) -> [ZM<Q>; K] {
+		std::array::from_fn(|i| coordinates[(i + K - 1) % K])
+	}
+
+}
@@ -0,0 +1,427 @@
+//
+// Finite dimensional algebras over a field, given by structure constants
+//
+
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::rc::Rc;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+
+// MARK: Structure Constants
+
+/// The multiplication table of a finite dimensional algebra with basis b_0, ..., b_(d-1),
+/// given by the structure constants c(i, j, k) with b_i b_j = sum over k of c(i, j, k) b_k.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructureConstants<F: Field> {
+	dimension: usize,
+	constants: Vec<F>,
+	identity: Vec<F>
+}
+
+impl<F: Field> StructureConstants<F> {
+
+	/// Creates an algebra from its structure constants, where `constants(i, j)` is the list of
+	/// coordinates of b_i b_j.
+	///
+	/// Returns `None` if the algebra has no multiplicative identity.
+	pub fn new<C: Fn(usize, usize) -> Vec<F>>(dimension: usize, constants: C) -> Option<Rc<StructureConstants<F>>> {
+		let mut table = Vec::with_capacity(dimension * dimension * dimension);
+		for i in 0..dimension {
+			for j in 0..dimension {
+				let product = constants(i, j);
+				if product.len() != dimension {
+					panic!("Every product of basis vectors needs {} coordinates", dimension)
+				}
+				table.extend(product);
+			}
+		}
+
+		let mut structure = StructureConstants { dimension, constants: table, identity: vec![] };
+		structure.identity = structure.find_identity()?;
+		Some(Rc::new(structure))
+	}
+
+	/// The quotient F[x] / (f) of the polynomial ring, with basis 1, x, ..., x^(n-1) for f of degree
+	/// n >= 1
+	pub fn quotient(f: &Polynomial<F>) -> Rc<StructureConstants<F>> {
+		if f.degree() == 0 {
+			panic!("Can only take the quotient by a polynomial of positive degree")
+		}
+
+		let n = f.degree();
+		StructureConstants::new(n, |i, j| {
+			let product = Polynomial::monomial(F::one(), i + j).quotient_and_remainder(f).1;
+			(0..n).map(|k| product.coefficient(k)).collect()
+		}).unwrap()
+	}
+
+	/// The dimension of this algebra over F
+	pub fn dimension(&self) -> usize {
+		self.dimension
+	}
+
+	/// The structure constant c(i, j, k), the coefficient of b_k in b_i b_j
+	pub fn constant(&self, i: usize, j: usize, k: usize) -> F {
+		self.constants[(i * self.dimension + j) * self.dimension + k].clone()
+	}
+
+	/// The coordinates of the product of two elements given in coordinates
+	fn multiply(&self, a: &[F], b: &[F]) -> Vec<F> {
+		let d = self.dimension;
+		let mut product = vec![F::zero(); d];
+
+		for (i, ai) in a.iter().enumerate() {
+			if ai.is_zero() {
+				continue
+			}
+			for (j, bj) in b.iter().enumerate() {
+				if bj.is_zero() {
+					continue
+				}
+				let coefficient = ai.mul_ref(bj);
+				for (k, pk) in product.iter_mut().enumerate() {
+					pk.add_assign_ref(&coefficient.mul_ref(&self.constant(i, j, k)));
+				}
+			}
+		}
+
+		product
+	}
+
+	/// Solves for the element e with e b_i = b_i = b_i e for every basis vector
+	fn find_identity(&self) -> Option<Vec<F>> {
+		let d = self.dimension;
+		let mut rows = Vec::new();
+		let mut rhs = Vec::new();
+
+		for i in 0..d {
+			for k in 0..d {
+				let target = if i == k { F::one() } else { F::zero() };
+				rows.push((0..d).map(|j| self.constant(j, i, k)).collect());
+				rhs.push(target.clone());
+				rows.push((0..d).map(|j| self.constant(i, j, k)).collect());
+				rhs.push(target);
+			}
+		}
+
+		solve_linear_system(rows, rhs, d)
+	}
+
+	/// The element of the algebra with the given coordinates
+	pub fn element(self: &Rc<Self>, coordinates: Vec<F>) -> Algebra<F> {
+		if coordinates.len() != self.dimension {
+			panic!("Expected {} coordinates", self.dimension)
+		}
+
+		Algebra::Element { structure: self.clone(), coordinates }
+	}
+
+	/// The basis vector b_i of the algebra
+	pub fn basis(self: &Rc<Self>, i: usize) -> Algebra<F> {
+		let coordinates = (0..self.dimension).map(|k| if k == i { F::one() } else { F::zero() }).collect();
+		self.element(coordinates)
+	}
+
+	/// A basis for the center of the algebra: the elements commuting with everything
+	pub fn center(self: &Rc<Self>) -> Vec<Algebra<F>> {
+		let d = self.dimension;
+
+		// z = sum z_j b_j commutes with b_i when sum over j of z_j (c(j, i, k) - c(i, j, k)) = 0 for all k
+		let mut rows = Vec::new();
+		for i in 0..d {
+			for k in 0..d {
+				rows.push((0..d).map(|j| self.constant(j, i, k) - self.constant(i, j, k)).collect());
+			}
+		}
+
+		Matrix::from_rows(rows).null_space().into_iter().map(|v| self.element(v)).collect()
+	}
+
+	/// Every idempotent (e^2 = e) of the algebra whose coordinates all lie in `scalars`.
+	///
+	/// For an algebra over a finite field, passing every field element finds all idempotents.
+	/// This checks |scalars|^dimension elements, so it is only practical in small dimensions.
+	pub fn idempotents(self: &Rc<Self>, scalars: &[F]) -> Vec<Algebra<F>> {
+		let d = self.dimension;
+		let mut found = Vec::new();
+
+		if scalars.is_empty() {
+			return found
+		}
+
+		let mut indices = vec![0; d];
+		loop {
+			let coordinates: Vec<F> = indices.iter().map(|&i| scalars[i].clone()).collect();
+			if self.multiply(&coordinates, &coordinates) == coordinates {
+				found.push(self.element(coordinates));
+			}
+
+			// Advance the odometer
+			let mut position = 0;
+			loop {
+				if position == d {
+					return found
+				}
+				indices[position] += 1;
+				if indices[position] < scalars.len() {
+					break
+				}
+				indices[position] = 0;
+				position += 1;
+			}
+		}
+	}
+
+	/// The trace of multiplication by b_i, as a linear map of the algebra
+	fn basis_trace(&self, i: usize) -> F {
+		(0..self.dimension).fold(F::zero(), |sum, k| sum + self.constant(i, k, k))
+	}
+
+	/// The matrix of the trace form (a, b) -> Tr(ab), whose (i, j) entry is the trace of
+	/// multiplication by b_i b_j
+	pub fn trace_form(&self) -> Matrix<F> {
+		let d = self.dimension;
+		let traces: Vec<F> = (0..d).map(|k| self.basis_trace(k)).collect();
+		Matrix::from_fn(d, d, |i, j| (0..d).fold(F::zero(), |sum, k| sum + self.constant(i, j, k) * traces[k].clone()))
+	}
+
+	/// Whether or not the trace form is nondegenerate, meaning its determinant is nonzero.
+	///
+	/// For a commutative algebra this holds exactly when the algebra is separable, a product of
+	/// separable field extensions of F. For F[x] / (f) that means f has no repeated roots in an
+	/// algebraic closure, and the determinant is the discriminant of f up to a nonzero factor. In
+	/// general a nondegenerate trace form still implies separability, but the converse can fail in
+	/// positive characteristic.
+	pub fn is_separable(&self) -> bool {
+		!self.trace_form().determinant().is_zero()
+	}
+
+	/// Whether or not the multiplication is associative, (b_i b_j) b_k = b_i (b_j b_k)
+	pub fn is_associative(&self) -> bool {
+		let d = self.dimension;
+		let basis = |i: usize| -> Vec<F> { (0..d).map(|k| if k == i { F::one() } else { F::zero() }).collect() };
+
+		(0..d).all(|i| (0..d).all(|j| (0..d).all(|k| {
+			let left = self.multiply(&self.multiply(&basis(i), &basis(j)), &basis(k));
+			let right = self.multiply(&basis(i), &self.multiply(&basis(j), &basis(k)));
+			left == right
+		})))
+	}
+
+}
+
+// MARK: Elements
+
+/// An element of a finite dimensional algebra.
+///
+/// Since `Ring::zero` and `Ring::one` can't know which algebra they belong to, scalar multiples
+/// of the identity can be stored without reference to an algebra, and are promoted as needed.
+#[derive(Clone)]
+pub enum Algebra<F: Field> {
+	/// The scalar c times the identity of whichever algebra this is combined with
+	Scalar(F),
+
+	/// An element given by its coordinates in the basis of `structure`
+	Element { structure: Rc<StructureConstants<F>>, coordinates: Vec<F> }
+}
+
+impl<F: Field> Algebra<F> {
+
+	/// The coordinates of this element, or `None` for a scalar not attached to an algebra
+	pub fn coordinates(&self) -> Option<&[F]> {
+		match self {
+			Algebra::Scalar(_) => None,
+			Algebra::Element { coordinates, .. } => Some(coordinates)
+		}
+	}
+
+	/// The algebra this element belongs to, or `None` for a scalar not attached to an algebra
+	pub fn structure(&self) -> Option<&Rc<StructureConstants<F>>> {
+		match self {
+			Algebra::Scalar(_) => None,
+			Algebra::Element { structure, .. } => Some(structure)
+		}
+	}
+
+	/// The coordinates of this element, viewing it inside the given algebra
+	fn coordinates_in(&self, structure: &StructureConstants<F>) -> Vec<F> {
+		match self {
+			Algebra::Scalar(c) => structure.identity.iter().map(|e| e.mul_ref(c)).collect(),
+			Algebra::Element { coordinates, .. } => coordinates.clone()
+		}
+	}
+
+	/// Combines two elements coordinate-wise, or as scalars if neither has an algebra
+	fn combine<S: Fn(&F, &F) -> F, C: Fn(&StructureConstants<F>, &[F], &[F]) -> Vec<F>>(&self, rhs: &Self, scalar: S, coordinates: C) -> Self {
+		let structure = match (self, rhs) {
+			(Algebra::Scalar(a), Algebra::Scalar(b)) => return Algebra::Scalar(scalar(a, b)),
+			(Algebra::Element { structure, .. }, _) | (_, Algebra::Element { structure, .. }) => structure.clone()
+		};
+
+		if let (Some(a), Some(b)) = (self.structure(), rhs.structure()) {
+			if !Rc::ptr_eq(a, b) && a != b {
+				panic!("Cannot combine elements of different algebras")
+			}
+		}
+
+		let a = self.coordinates_in(&structure);
+		let b = rhs.coordinates_in(&structure);
+		let result = coordinates(&structure, &a, &b);
+		Algebra::Element { structure, coordinates: result }
+	}
+
+}
+
+impl<F: Field> Debug for Algebra<F> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Algebra::Scalar(c) => write!(f, "{:?} * 1", c),
+			Algebra::Element { coordinates, .. } => coordinates.fmt(f)
+		}
+	}
+}
+
+impl<F: Field> PartialEq for Algebra<F> {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Algebra::Scalar(a), Algebra::Scalar(b)) => a == b,
+			(Algebra::Element { structure, .. }, _) | (_, Algebra::Element { structure, .. }) => {
+				self.coordinates_in(structure) == other.coordinates_in(structure)
+			}
+		}
+	}
+}
+
+impl<F: Field> Add for Algebra<F> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.add_ref(&rhs)
+	}
+}
+
+impl<F: Field> AddAssign for Algebra<F> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = self.add_ref(&rhs)
+	}
+}
+
+impl<F: Field> Neg for Algebra<F> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		match self {
+			Algebra::Scalar(c) => Algebra::Scalar(-c),
+			Algebra::Element { structure, coordinates } => {
+				Algebra::Element { structure, coordinates: coordinates.into_iter().map(|c| -c).collect() }
+			}
+		}
+	}
+}
+
+impl<F: Field> Sub for Algebra<F> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.sub_ref(&rhs)
+	}
+}
+
+impl<F: Field> SubAssign for Algebra<F> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = self.sub_ref(&rhs)
+	}
+}
+
+impl<F: Field> Mul for Algebra<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<F: Field> MulAssign for Algebra<F> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl<F: Field> Semiring for Algebra<F> {
+	fn one() -> Self {
+		Algebra::Scalar(F::one())
+	}
+
+	fn zero() -> Self {
+		Algebra::Scalar(F::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		match self {
+			Algebra::Scalar(c) => c.is_zero(),
+			Algebra::Element { coordinates, .. } => coordinates.iter().all(|c| c.is_zero())
+		}
+	}
+
+	fn add_ref(&self, rhs: &Self) -> Self {
+		self.combine(rhs, |a, b| a.add_ref(b), |_, a, b| a.iter().zip(b).map(|(x, y)| x.add_ref(y)).collect())
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		self.combine(rhs, |a, b| a.mul_ref(b), |s, a, b| s.multiply(a, b))
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		*self = self.add_ref(rhs)
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+}
+
+impl<F: Field> Ring for Algebra<F> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+
+		let mut power = Self::one();
+		for _ in 0..n {
+			power.mul_assign_ref(self);
+		}
+		power
+	}
+
+	fn sub_ref(&self, rhs: &Self) -> Self {
+		self.combine(rhs, |a, b| a.sub_ref(b), |_, a, b| a.iter().zip(b).map(|(x, y)| x.sub_ref(y)).collect())
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		*self = self.sub_ref(rhs)
+	}
+}
+
+// MARK: Linear Algebra
+
+/// Some solution x of the system rows * x = rhs in n unknowns, if one exists, read off the reduced
+/// row echelon form of the augmented matrix
+fn solve_linear_system<F: Field>(rows: Vec<Vec<F>>, rhs: Vec<F>, n: usize) -> Option<Vec<F>> {
+	let augmented = Matrix::from_rows(rows.into_iter().zip(rhs).map(|(mut row, b)| { row.push(b); row }).collect());
+	let echelon = augmented.reduced_row_echelon_form();
+	let pivots = Matrix::pivot_columns(&echelon);
+
+	// Inconsistent if some row reads 0 = nonzero, meaning the last column has a pivot
+	if pivots.contains(&n) {
+		return None
+	}
+
+	let mut x = vec![F::zero(); n];
+	for (r, &col) in pivots.iter().enumerate() {
+		x[col] = echelon[(r, n)].clone();
+	}
+	Some(x)
+}
@@ -0,0 +1,108 @@
+//
+// Numbering the elements of finite types, for indexing tables by elements, storing elements as
+// integers, and listing every element
+//
+
+/// A type with finitely many elements, numbered 0, 1, ..., size - 1.
+///
+/// The numbering of a product takes its first component as the lowest digit in a mixed radix
+/// representation, so that (a, b) is numbered a + |A| b, and in the same way a finite field
+/// element written in a basis is numbered by its coordinates as base q digits.
+pub trait FiniteSet: Sized {
+
+	/// The number of elements
+	fn size() -> u128;
+
+	/// The number of this element, in [0, size)
+	fn to_index(&self) -> u128;
+
+	/// The element numbered index, which must be less than size
+	fn from_index(index: u128) -> Self;
+
+	/// Every element, in order of index
+	fn all_elements() -> impl Iterator<Item = Self> {
+		(0..Self::size()).map(Self::from_index)
+	}
+
+}
+
+/// Panics unless index is less than the size of T
+pub(crate) fn check_index<T: FiniteSet>(index: u128) {
+	if index >= T::size() {
+		panic!("Index {} is out of range for a set of {} elements", index, T::size())
+	}
+}
+
+/// The product of the sizes of some sets, panicking if it does not fit in a u128
+fn product_size(sizes: impl IntoIterator<Item = u128>) -> u128 {
+	sizes.into_iter().try_fold(1u128, |product, size| product.checked_mul(size))
+		.unwrap_or_else(|| panic!("A product of sets has too many elements to number"))
+}
+
+// MARK: Products
+
+impl FiniteSet for bool {
+	fn size() -> u128 {
+		2
+	}
+
+	fn to_index(&self) -> u128 {
+		*self as u128
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<bool>(index);
+		index == 1
+	}
+}
+
+impl<A: FiniteSet, B: FiniteSet> FiniteSet for (A, B) {
+	fn size() -> u128 {
+		product_size([A::size(), B::size()])
+	}
+
+	fn to_index(&self) -> u128 {
+		self.0.to_index() + A::size() * self.1.to_index()
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		(A::from_index(index % A::size()), B::from_index(index / A::size()))
+	}
+}
+
+impl<A: FiniteSet, B: FiniteSet, C: FiniteSet> FiniteSet for (A, B, C) {
+	fn size() -> u128 {
+		product_size([A::size(), B::size(), C::size()])
+	}
+
+	fn to_index(&self) -> u128 {
+		self.0.to_index() + A::size() * (self.1.to_index() + B::size() * self.2.to_index())
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		let (a, rest) = (index % A::size(), index / A::size());
+		(A::from_index(a), B::from_index(rest % B::size()), C::from_index(rest / B::size()))
+	}
+}
+
+impl<T: FiniteSet, const N: usize> FiniteSet for [T; N] {
+	fn size() -> u128 {
+		product_size((0..N).map(|_| T::size()))
+	}
+
+	fn to_index(&self) -> u128 {
+		self.iter().rev().fold(0, |index, x| index * T::size() + x.to_index())
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		let mut rest = index;
+		core::array::from_fn(|_| {
+			let x = T::from_index(rest % T::size());
+			rest /= T::size();
+			x
+		})
+	}
+}
@@ -0,0 +1,348 @@
+//
+// Writing polynomials and matrices out in mathematical notation, with configurable variable
+// names, exponents, and term order, and writing elements as LaTeX
+//
+
+use std::fmt::{Debug, Display};
+
+use crate::algebra::*;
+use crate::complex::Complex;
+use crate::matrix::Matrix;
+use crate::multivariate::{Lex, Monomial, MonomialOrder, MultiPoly};
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+// MARK: Options
+
+/// How polynomials are written out.
+///
+/// The defaults write 3x^2 - x + 1 for a univariate polynomial and x_0^2 x_1 + 2 x_2 style names
+/// for multivariate ones, and can be changed field by field, as in
+/// `FormatOptions { superscripts: true, ..Default::default() }`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+	/// The names of the variables. A univariate polynomial uses the first. A multivariate one uses
+	/// one for each variable if there are enough, and otherwise the first with the index of the
+	/// variable as a subscript.
+	pub variables: Vec<String>,
+
+	/// Whether exponents and subscripts are written with Unicode, as x₀², rather than as x_0^2
+	pub superscripts: bool,
+
+	/// Whether terms are written from the smallest up, rather than from the largest down
+	pub ascending: bool,
+
+	/// Whether coefficients of one are written out, as 1x rather than x
+	pub explicit_ones: bool,
+
+	/// What is written between a coefficient and the variables, and between variables, like "" or
+	/// "*". Multivariate monomials always separate variables with a space when this is empty.
+	pub multiplication: String
+}
+
+impl Default for FormatOptions {
+	fn default() -> Self {
+		FormatOptions {
+			variables: vec!["x".to_string()],
+			superscripts: false,
+			ascending: false,
+			explicit_ones: false,
+			multiplication: String::new()
+		}
+	}
+}
+
+impl FormatOptions {
+
+	/// The default options with the given variable names
+	pub fn with_variables(names: &[&str]) -> FormatOptions {
+		FormatOptions { variables: names.iter().map(|s| s.to_string()).collect(), ..Default::default() }
+	}
+
+	/// The name of variable i out of n
+	fn variable(&self, i: usize, n: usize) -> String {
+		let first = self.variables.first().map_or("x", String::as_str);
+		if n == 1 {
+			first.to_string()
+		} else if self.variables.len() >= n {
+			self.variables[i].clone()
+		} else if self.superscripts {
+			format!("{}{}", first, script(i as u64, "₀₁₂₃₄₅₆₇₈₉"))
+		} else {
+			format!("{}_{}", first, i)
+		}
+	}
+
+	/// A variable raised to the power e > 0
+	fn power(&self, variable: &str, e: u64) -> String {
+		match (e, self.superscripts) {
+			(1, _) => variable.to_string(),
+			(_, true) => format!("{}{}", variable, script(e, "⁰¹²³⁴⁵⁶⁷⁸⁹")),
+			(_, false) => format!("{}^{}", variable, e)
+		}
+	}
+
+	/// The terms c m, given as the text of each coefficient and monomial, joined into a sum
+	fn sum(&self, terms: Vec<(String, String)>, one: &str, minus_one: &str) -> String {
+		let mut text = String::new();
+
+		for (coefficient, monomial) in terms {
+			let body = if monomial.is_empty() {
+				coefficient
+			} else if !self.explicit_ones && coefficient == one {
+				monomial
+			} else if !self.explicit_ones && coefficient == minus_one {
+				format!("-{}", monomial)
+			} else if is_atomic(&coefficient) {
+				format!("{}{}{}", coefficient, self.multiplication, monomial)
+			} else {
+				format!("({}){}{}", coefficient, self.multiplication, monomial)
+			};
+
+			text = match (text.is_empty(), body.strip_prefix('-')) {
+				(true, _) => body,
+				(false, Some(rest)) if is_atomic(&body) => format!("{} - {}", text, rest),
+				(false, _) => format!("{} + {}", text, body)
+			};
+		}
+
+		if text.is_empty() { "0".to_string() } else { text }
+	}
+
+}
+
+/// The digits of n written with the given ten characters
+fn script(n: u64, digits: &str) -> String {
+	let digits: Vec<char> = digits.chars().collect();
+	n.to_string().bytes().map(|d| digits[(d - b'0') as usize]).collect()
+}
+
+/// Whether or not a coefficient can be written next to a monomial without parentheses, meaning it
+/// is a single number, possibly negative, rather than a sum
+fn is_atomic(text: &str) -> bool {
+	let body = text.strip_prefix('-').unwrap_or(text);
+	let mut previous = ' ';
+	body.chars().all(|c| {
+		let fine = !(c == ' ' || c == '+' || (c == '-' && previous != 'e' && previous != 'E'));
+		previous = c;
+		fine
+	})
+}
+
+// MARK: Coefficients
+
+/// The text of a coefficient or matrix entry, which is its `Debug` form except for types whose
+/// `Display` form is more readable
+trait Written {
+	fn written(&self) -> String;
+}
+
+impl<T: Debug> Written for T {
+	default fn written(&self) -> String {
+		format!("{:?}", self)
+	}
+}
+
+#[cfg(feature = "bigint")]
+impl Written for num_bigint::BigInt {
+	fn written(&self) -> String {
+		self.to_string()
+	}
+}
+
+#[cfg(feature = "bigint")]
+impl Written for num_rational::BigRational {
+	fn written(&self) -> String {
+		self.to_string()
+	}
+}
+
+// MARK: Polynomials
+
+impl<R: Ring> Polynomial<R> {
+
+	/// This polynomial written out with the given options
+	pub fn format(&self, options: &FormatOptions) -> String {
+		let variable = options.variable(0, 1);
+		let mut terms: Vec<(String, String)> = self.coefficients().iter().enumerate()
+			.filter(|(_, c)| !c.is_zero())
+			.map(|(i, c)| (c.written(), if i == 0 { String::new() } else { options.power(&variable, i as u64) }))
+			.collect();
+		if !options.ascending {
+			terms.reverse();
+		}
+
+		options.sum(terms, &R::one().written(), &(-R::one()).written())
+	}
+
+}
+
+/// Written with the default `FormatOptions`, highest power first
+impl<R: Ring> Display for Polynomial<R> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.format(&FormatOptions::default()))
+	}
+}
+
+impl<R: Ring, const N: usize> MultiPoly<R, N> {
+
+	/// This polynomial written out with the given options, with its terms sorted by the given
+	/// monomial order
+	pub fn format<O: MonomialOrder>(&self, options: &FormatOptions, order: &O) -> String {
+		let separator = if options.multiplication.is_empty() { " " } else { options.multiplication.as_str() };
+		let monomial = |m: &Monomial<N>| (0..N).filter(|&i| m.exponents[i] > 0)
+			.map(|i| options.power(&options.variable(i, N), m.exponents[i] as u64))
+			.collect::<Vec<String>>()
+			.join(separator);
+
+		let mut terms: Vec<(String, String)> = self.sorted_terms(order).iter().map(|(m, c)| (c.written(), monomial(m))).collect();
+		if options.ascending {
+			terms.reverse();
+		}
+
+		options.sum(terms, &R::one().written(), &(-R::one()).written())
+	}
+
+}
+
+/// Written with the default `FormatOptions`, in decreasing lexicographic order
+impl<R: Ring, const N: usize> Display for MultiPoly<R, N> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.format(&FormatOptions::default(), &Lex))
+	}
+}
+
+// MARK: Matrices
+
+impl<R: Semiring> Matrix<R> {
+
+	/// This matrix written one row per line, each in brackets, with each entry written by the
+	/// given function and the columns right aligned
+	pub fn format_with(&self, entry: impl Fn(&R) -> String) -> String {
+		let texts: Vec<Vec<String>> = (0..self.rows()).map(|i| (0..self.cols()).map(|j| entry(&self[(i, j)])).collect()).collect();
+		let widths: Vec<usize> = (0..self.cols()).map(|j| texts.iter().map(|row| row[j].chars().count()).max().unwrap_or(0)).collect();
+
+		texts.iter().map(|row| {
+			let cells: Vec<String> = row.iter().zip(widths.iter()).map(|(text, &width)| format!("{:>width$}", text, width = width)).collect();
+			format!("[{}]", cells.join("  "))
+		}).collect::<Vec<String>>().join("\n")
+	}
+
+}
+
+/// One row per line, with the columns aligned
+impl<R: Semiring> Display for Matrix<R> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.format_with(|x| x.written()))
+	}
+}
+
+// MARK: LaTeX
+
+/// An element which can be written as LaTeX math, for typesetting
+pub trait ToLatex {
+
+	/// This element as LaTeX math, without surrounding dollar signs
+	fn to_latex(&self) -> String;
+
+	/// This element as LaTeX math when it is a coefficient of a polynomial or an entry of a
+	/// matrix, which leaves out context shared by all of them, like a modulus
+	fn to_latex_entry(&self) -> String {
+		self.to_latex()
+	}
+
+}
+
+macro_rules! impl_to_latex {
+	($($t:ty),*) => {$(
+		impl ToLatex for $t {
+			fn to_latex(&self) -> String {
+				self.to_string()
+			}
+		}
+	)*};
+}
+
+impl_to_latex!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+/// Written as "3 \pmod{17}", or as just the representative "3" as an entry
+impl<const Q: i64> ToLatex for ZM<Q> {
+	fn to_latex(&self) -> String {
+		format!("{} \\pmod{{{}}}", self.val.rem_euclid(Q), Q)
+	}
+
+	fn to_latex_entry(&self) -> String {
+		self.val.rem_euclid(Q).to_string()
+	}
+}
+
+#[cfg(feature = "bigint")]
+impl ToLatex for num_bigint::BigInt {
+	fn to_latex(&self) -> String {
+		self.to_string()
+	}
+}
+
+/// Written as a fraction \frac{a}{b}, with any minus sign in front, or as an integer
+#[cfg(feature = "bigint")]
+impl ToLatex for num_rational::BigRational {
+	fn to_latex(&self) -> String {
+		if self.is_integer() {
+			return self.numer().to_string()
+		}
+
+		let numerator = self.numer().to_string();
+		match numerator.strip_prefix('-') {
+			Some(magnitude) => format!("-\\frac{{{}}}{{{}}}", magnitude, self.denom()),
+			None => format!("\\frac{{{}}}{{{}}}", numerator, self.denom())
+		}
+	}
+}
+
+impl<F: Field + ToLatex> ToLatex for Complex<F> {
+	fn to_latex(&self) -> String {
+		let im = self.im.to_latex_entry();
+		match im.strip_prefix('-') {
+			Some(magnitude) => format!("{} - {}i", self.re.to_latex_entry(), magnitude),
+			None => format!("{} + {}i", self.re.to_latex_entry(), im)
+		}
+	}
+}
+
+/// x raised to the power e > 0 in LaTeX
+fn latex_power(variable: &str, e: u64) -> String {
+	if e == 1 { variable.to_string() } else { format!("{}^{{{}}}", variable, e) }
+}
+
+/// Written highest power first in the variable x, like x^{3} + 2x + 1
+impl<R: Ring + ToLatex> ToLatex for Polynomial<R> {
+	fn to_latex(&self) -> String {
+		let terms = self.coefficients().iter().enumerate().rev()
+			.filter(|(_, c)| !c.is_zero())
+			.map(|(i, c)| (c.to_latex_entry(), if i == 0 { String::new() } else { latex_power("x", i as u64) }))
+			.collect();
+		FormatOptions::default().sum(terms, &R::one().to_latex_entry(), &(-R::one()).to_latex_entry())
+	}
+}
+
+/// Written in decreasing lexicographic order in the variables x_{0}, ..., x_{N-1}
+impl<R: Ring + ToLatex, const N: usize> ToLatex for MultiPoly<R, N> {
+	fn to_latex(&self) -> String {
+		let monomial = |m: &Monomial<N>| (0..N).filter(|&i| m.exponents[i] > 0)
+			.map(|i| latex_power(&if N == 1 { "x".to_string() } else { format!("x_{{{}}}", i) }, m.exponents[i] as u64))
+			.collect::<Vec<String>>()
+			.join(" ");
+		let terms = self.sorted_terms(&Lex).iter().map(|(m, c)| (c.to_latex_entry(), monomial(m))).collect();
+		FormatOptions::default().sum(terms, &R::one().to_latex_entry(), &(-R::one()).to_latex_entry())
+	}
+}
+
+/// Written as a pmatrix environment
+impl<R: Semiring + ToLatex> ToLatex for Matrix<R> {
+	fn to_latex(&self) -> String {
+		let rows: Vec<String> = (0..self.rows())
+			.map(|i| (0..self.cols()).map(|j| self[(i, j)].to_latex_entry()).collect::<Vec<String>>().join(" & "))
+			.collect();
+		format!("\\begin{{pmatrix}} {} \\end{{pmatrix}}", rows.join(" \\\\ "))
+	}
+}
@@ -0,0 +1,424 @@
+//
+// Polynomials over GF(2) packed one coefficient per bit into machine words, for CRCs, linear
+// feedback shift registers, and other bit-level work
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::binary_field::GF2;
+use crate::polynomial::Polynomial;
+use crate::std_impls::{factor, ZM};
+
+// MARK: Carryless Multiplication
+
+/// The carryless product of two words, the product of the polynomials over GF(2) whose
+/// coefficients are their bits, by shifting and adding a for each set bit of b
+fn clmul_software(a: u64, b: u64) -> u128 {
+	let a = a as u128;
+	let mut b = b;
+	let mut product = 0;
+
+	while b != 0 {
+		product ^= a << b.trailing_zeros();
+		b &= b - 1;
+	}
+
+	product
+}
+
+/// The carryless product of two words with the PCLMULQDQ instruction
+///
+/// # Safety
+///
+/// The CPU must support PCLMULQDQ.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "pclmulqdq")]
+unsafe fn clmul_hardware(a: u64, b: u64) -> u128 {
+	use std::arch::x86_64::{_mm_clmulepi64_si128, _mm_cvtsi128_si64, _mm_set_epi64x, _mm_unpackhi_epi64};
+
+	let product = _mm_clmulepi64_si128(_mm_set_epi64x(0, a as i64), _mm_set_epi64x(0, b as i64), 0);
+	let low = _mm_cvtsi128_si64(product) as u64;
+	let high = _mm_cvtsi128_si64(_mm_unpackhi_epi64(product, product)) as u64;
+	((high as u128) << 64) | low as u128
+}
+
+/// The fastest carryless multiplication this CPU supports, checked once per product of
+/// polynomials rather than once per word
+fn clmul_kernel() -> fn(u64, u64) -> u128 {
+	#[cfg(target_arch = "x86_64")]
+	if std::arch::is_x86_feature_detected!("pclmulqdq") {
+		// Safe to call, since the instruction is supported
+		return |a, b| unsafe { clmul_hardware(a, b) }
+	}
+
+	clmul_software
+}
+
+// MARK: Polynomials over GF(2)
+
+/// A polynomial over GF(2), with the coefficient of x^i stored in bit i % 64 of word i / 64.
+///
+/// Addition is exclusive or of words, and multiplication uses carryless multiplication of words,
+/// in hardware where the CPU has it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PolyGF2 {
+	words: Vec<u64>
+}
+
+impl PolyGF2 {
+
+	/// The polynomial whose coefficients are the bits of the given words, the lowest bit of the
+	/// first word holding the constant term
+	pub fn new(words: Vec<u64>) -> PolyGF2 {
+		let mut p = PolyGF2 { words };
+		p.normalize();
+		p
+	}
+
+	/// The polynomial with the given coefficients, from the constant term up
+	pub fn from_coefficients(coefficients: &[GF2]) -> PolyGF2 {
+		let mut words = vec![0; coefficients.len().div_ceil(64)];
+		for (i, c) in coefficients.iter().enumerate() {
+			words[i / 64] |= (c.0 as u64) << (i % 64);
+		}
+		PolyGF2::new(words)
+	}
+
+	/// The sum of x^e over the given exponents, with repeated exponents cancelling in pairs
+	pub fn from_exponents(exponents: &[usize]) -> PolyGF2 {
+		let mut words = vec![0; exponents.iter().max().map_or(0, |e| e / 64 + 1)];
+		for e in exponents {
+			words[e / 64] ^= 1 << (e % 64);
+		}
+		PolyGF2::new(words)
+	}
+
+	/// The polynomial x
+	pub fn x() -> PolyGF2 {
+		PolyGF2::monomial(1)
+	}
+
+	/// The polynomial x^n
+	pub fn monomial(n: usize) -> PolyGF2 {
+		PolyGF2::from_exponents(&[n])
+	}
+
+	/// The packed coefficients, with no zero words at the end
+	pub fn words(&self) -> &[u64] {
+		&self.words
+	}
+
+	/// The coefficient of x^i
+	pub fn coefficient(&self, i: usize) -> GF2 {
+		GF2(self.words.get(i / 64).is_some_and(|w| w >> (i % 64) & 1 == 1))
+	}
+
+	/// The degree of this polynomial. The zero polynomial is given degree 0.
+	pub fn degree(&self) -> usize {
+		self.words.last().map_or(0, |w| 64 * (self.words.len() - 1) + 63 - w.leading_zeros() as usize)
+	}
+
+	/// The number of nonzero coefficients
+	pub fn weight(&self) -> usize {
+		self.words.iter().map(|w| w.count_ones() as usize).sum()
+	}
+
+	/// Evaluates this polynomial at x, where it is the parity of the number of terms at 1, and the
+	/// constant term at 0
+	pub fn evaluate(&self, x: GF2) -> GF2 {
+		if x.0 { GF2(self.weight() % 2 == 1) } else { self.coefficient(0) }
+	}
+
+	/// The formal derivative, keeping the odd powers of x and lowering each by one, which never
+	/// moves a bit into the next word down
+	pub fn derivative(&self) -> PolyGF2 {
+		PolyGF2::new(self.words.iter().map(|w| (w & 0xAAAA_AAAA_AAAA_AAAA) >> 1).collect())
+	}
+
+	/// self * other modulo m
+	pub fn mul_mod(&self, other: &PolyGF2, m: &PolyGF2) -> PolyGF2 {
+		self.mul_ref(other).quotient_and_remainder(m).1
+	}
+
+	/// self^e modulo m, by repeated squaring, which for self = x steps a linear feedback shift
+	/// register with feedback polynomial m forward e times
+	pub fn pow_mod(&self, e: u128, m: &PolyGF2) -> PolyGF2 {
+		let mut power = PolyGF2::one().quotient_and_remainder(m).1;
+		let mut base = self.quotient_and_remainder(m).1;
+		let mut e = e;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power = power.mul_mod(&base, m);
+			}
+			base = base.mul_mod(&base, m);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	/// Whether or not this polynomial is irreducible, by Rabin's test: a polynomial f of degree n
+	/// is irreducible exactly when x^(2^n) = x modulo f, and gcd(x^(2^(n/p)) - x, f) = 1 for each
+	/// prime p dividing n
+	pub fn is_irreducible(&self) -> bool {
+		let n = self.degree();
+		if n == 0 {
+			return false
+		}
+
+		// x^(2^k) modulo self, by squaring k times
+		let frobenius = |k: usize| (0..k).fold(PolyGF2::x(), |y, _| y.mul_mod(&y, self));
+
+		let x = PolyGF2::x().quotient_and_remainder(self).1;
+		factor(n as i64).iter().all(|&(p, _)| {
+			gcd(&(frobenius(n / p as usize) - x.clone()), self).degree() == 0
+		}) && frobenius(n) == x
+	}
+
+	/// This polynomial with a coefficient in ZM<2> for each bit
+	pub fn to_polynomial(&self) -> Polynomial<ZM<2>> {
+		Polynomial::new((0..64 * self.words.len()).map(|i| ZM::from_int(self.coefficient(i).0 as i64)).collect())
+	}
+
+	/// Drops the zero words from the end
+	fn normalize(&mut self) {
+		while self.words.last() == Some(&0) {
+			self.words.pop();
+		}
+	}
+
+	/// Adds other x^shift to the words of a polynomial in place, which must be long enough
+	fn add_shifted(words: &mut [u64], other: &[u64], shift: usize) {
+		let (offset, bits) = (shift / 64, shift % 64);
+		for (i, w) in other.iter().enumerate() {
+			words[i + offset] ^= w << bits;
+			if bits > 0 && w >> (64 - bits) != 0 {
+				words[i + offset + 1] ^= w >> (64 - bits);
+			}
+		}
+	}
+
+}
+
+impl From<&Polynomial<ZM<2>>> for PolyGF2 {
+	fn from(p: &Polynomial<ZM<2>>) -> Self {
+		let coefficients: Vec<GF2> = p.coefficients().iter().map(|&c| GF2::from(c)).collect();
+		PolyGF2::from_coefficients(&coefficients)
+	}
+}
+
+impl From<&PolyGF2> for Polynomial<ZM<2>> {
+	fn from(p: &PolyGF2) -> Self {
+		p.to_polynomial()
+	}
+}
+
+// MARK: Arithmetic
+
+impl Add for PolyGF2 {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self.add_assign_ref(&rhs);
+		self
+	}
+}
+
+impl AddAssign for PolyGF2 {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+/// Subtraction is addition, since every coefficient is its own negative
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Sub for PolyGF2 {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+impl SubAssign for PolyGF2 {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl Neg for PolyGF2 {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		self
+	}
+}
+
+impl Mul for PolyGF2 {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl MulAssign for PolyGF2 {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl Semiring for PolyGF2 {
+	fn one() -> Self {
+		PolyGF2 { words: vec![1] }
+	}
+
+	fn zero() -> Self {
+		PolyGF2 { words: Vec::new() }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.words.is_empty()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		if self.words.len() < rhs.words.len() {
+			self.words.resize(rhs.words.len(), 0);
+		}
+		for (a, b) in self.words.iter_mut().zip(rhs.words.iter()) {
+			*a ^= b;
+		}
+		self.normalize();
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	/// The schoolbook product of the words, each pair multiplied carrylessly
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		if self.is_zero() || rhs.is_zero() {
+			return PolyGF2::zero()
+		}
+
+		let clmul = clmul_kernel();
+		let mut words = vec![0; self.words.len() + rhs.words.len()];
+		for (i, &a) in self.words.iter().enumerate() {
+			for (j, &b) in rhs.words.iter().enumerate() {
+				let product = clmul(a, b);
+				words[i + j] ^= product as u64;
+				words[i + j + 1] ^= (product >> 64) as u64;
+			}
+		}
+
+		PolyGF2::new(words)
+	}
+}
+
+impl Ring for PolyGF2 {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+
+		let mut power = Self::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base);
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		self.add_assign_ref(rhs)
+	}
+}
+
+impl CommutativeRing for PolyGF2 { }
+
+impl IntegralDomain for PolyGF2 {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		euclidean_divide_exact(self, divisor)
+	}
+}
+
+// MARK: Division
+
+impl Div for PolyGF2 {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl DivAssign for PolyGF2 {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl Rem for PolyGF2 {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl RemAssign for PolyGF2 {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl UniqueFactorizationDomain for PolyGF2 {
+	fn gcd(&self, other: &Self) -> Self {
+		euclidean_gcd(self, other)
+	}
+}
+
+impl PrincipalIdealDomain for PolyGF2 { }
+
+impl EuclideanDomain for PolyGF2 {
+	type SizeType = usize;
+
+	fn euc_size(&self) -> usize {
+		self.degree()
+	}
+
+	/// Long division, clearing the leading bit of the remainder with a shifted copy of the divisor
+	/// one bit at a time, a word of the divisor at once
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() {
+			panic!("Cannot divide by the zero polynomial")
+		}
+
+		if self.is_zero() || self.degree() < divisor.degree() {
+			return (PolyGF2::zero(), self.clone())
+		}
+
+		let d = divisor.degree();
+		let mut remainder = self.words.clone();
+		remainder.push(0);
+		let mut quotient = vec![0; (self.degree() - d) / 64 + 1];
+
+		for e in (d..=self.degree()).rev() {
+			if remainder[e / 64] >> (e % 64) & 1 == 1 {
+				PolyGF2::add_shifted(&mut remainder, &divisor.words, e - d);
+				quotient[(e - d) / 64] |= 1 << ((e - d) % 64);
+			}
+		}
+
+		(PolyGF2::new(quotient), PolyGF2::new(remainder))
+	}
+}
@@ -0,0 +1,191 @@
+//
+// Gröbner bases of polynomial ideals over a field, by Buchberger's algorithm
+//
+
+use crate::algebra::*;
+use crate::multivariate::{BlockOrder, Monomial, MonomialOrder, MultiPoly};
+
+// MARK: Division
+
+/// The S-polynomial (L / lt(f)) f - (L / lt(g)) g, where L is the lcm of the leading monomials,
+/// which cancels the leading terms of f and g
+pub fn s_polynomial<F: Field, const N: usize, O: MonomialOrder>(f: &MultiPoly<F, N>, g: &MultiPoly<F, N>, order: &O) -> MultiPoly<F, N> {
+	let (Some((mf, cf)), Some((mg, cg))) = (f.leading_term(order), g.leading_term(order)) else {
+		return MultiPoly::zero()
+	};
+
+	let lcm = mf.lcm(&mg);
+	let left = f.mul_term(&lcm.checked_div(&mf).unwrap(), &cf.inverse());
+	let right = g.mul_term(&lcm.checked_div(&mg).unwrap(), &cg.inverse());
+	left - right
+}
+
+/// Divides f by the divisors f_1, ..., f_k, returning quotients q_i and a remainder r with
+/// f = q_1 f_1 + ... + q_k f_k + r, where no term of r is divisible by any leading monomial
+/// lm(f_i). Zero divisors are skipped, getting a zero quotient.
+pub fn divide<F: Field, const N: usize, O: MonomialOrder>(f: &MultiPoly<F, N>, divisors: &[MultiPoly<F, N>], order: &O) -> (Vec<MultiPoly<F, N>>, MultiPoly<F, N>) {
+	let leads: Vec<Option<(Monomial<N>, F)>> = divisors.iter().map(|g| g.leading_term(order)).collect();
+	let mut quotients = vec![MultiPoly::zero(); divisors.len()];
+	let mut remainder = MultiPoly::zero();
+	let mut p = f.clone();
+
+	while let Some((m, c)) = p.leading_term(order) {
+		let divisor = leads.iter().enumerate().find_map(|(i, lead)| {
+			let (lm, lc) = lead.as_ref()?;
+			m.checked_div(lm).map(|quotient| (i, quotient, c.div_ref(lc)))
+		});
+
+		match divisor {
+			Some((i, quotient, coefficient)) => {
+				p -= divisors[i].mul_term(&quotient, &coefficient);
+				quotients[i] += MultiPoly::monomial(coefficient, quotient);
+			},
+			None => {
+				// The leading term can't be divided, so it moves to the remainder
+				let term = MultiPoly::monomial(c, m);
+				p.sub_assign_ref(&term);
+				remainder += term;
+			}
+		}
+	}
+
+	(quotients, remainder)
+}
+
+/// The remainder of f on division by the given polynomials. When they form a Gröbner basis, this
+/// is the unique normal form of f modulo the ideal they generate.
+pub fn normal_form<F: Field, const N: usize, O: MonomialOrder>(f: &MultiPoly<F, N>, basis: &[MultiPoly<F, N>], order: &O) -> MultiPoly<F, N> {
+	divide(f, basis, order).1
+}
+
+// MARK: Buchberger's Algorithm
+
+/// The reduced Gröbner basis of the ideal generated by the given polynomials, in the given order.
+///
+/// Runs Buchberger's algorithm, skipping a pair whose leading monomials are coprime (Buchberger's
+/// first criterion) or whose lcm is divisible by the leading monomial of a third basis element
+/// whose pairs with both have already been handled (the chain criterion). The result is made
+/// monic and interreduced, so it depends only on the ideal and the order. The zero ideal has the
+/// empty basis.
+pub fn groebner_basis<F: Field, const N: usize, O: MonomialOrder>(generators: &[MultiPoly<F, N>], order: &O) -> Vec<MultiPoly<F, N>> {
+	groebner_basis_with_trace(generators, order, &mut |_| { })
+}
+
+/// What became of a critical pair in Buchberger's algorithm
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PairOutcome<'a, F: Field, const N: usize> {
+	/// Skipped since the leading monomials are coprime
+	Coprime,
+	/// Skipped by the chain criterion
+	Chain,
+	/// The S-polynomial reduced to zero
+	ReducedToZero,
+	/// The S-polynomial reduced to this new, monic basis element
+	Added(&'a MultiPoly<F, N>)
+}
+
+/// One critical pair (i, j) handled by Buchberger's algorithm, with the size of the basis and the
+/// number of pairs left to handle afterward
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BuchbergerStep<'a, F: Field, const N: usize> {
+	pub pair: (usize, usize),
+	pub outcome: PairOutcome<'a, F, N>,
+	pub basis_size: usize,
+	pub pairs_left: usize
+}
+
+/// The reduced Gröbner basis as in `groebner_basis`, calling trace with every critical pair as it
+/// is handled, before the basis is interreduced at the end
+pub fn groebner_basis_with_trace<F: Field, const N: usize, O: MonomialOrder>(generators: &[MultiPoly<F, N>], order: &O, trace: &mut impl FnMut(BuchbergerStep<'_, F, N>)) -> Vec<MultiPoly<F, N>> {
+	let mut basis: Vec<MultiPoly<F, N>> = generators.iter().filter(|g| !g.is_zero()).map(|g| monic(g, order)).collect();
+	let mut pairs: Vec<(usize, usize)> = (0..basis.len()).flat_map(|j| (0..j).map(move |i| (i, j))).collect();
+
+	while let Some((i, j)) = pairs.pop() {
+		let mi = basis[i].leading_monomial(order).unwrap();
+		let mj = basis[j].leading_monomial(order).unwrap();
+		let lcm = mi.lcm(&mj);
+		let step = |outcome, basis_size, pairs_left| BuchbergerStep { pair: (i, j), outcome, basis_size, pairs_left };
+
+		if lcm == mi * mj {
+			trace(step(PairOutcome::Coprime, basis.len(), pairs.len()));
+			continue
+		}
+
+		let chain = (0..basis.len()).any(|k| {
+			k != i && k != j
+				&& basis[k].leading_monomial(order).unwrap().divides(&lcm)
+				&& !pairs.contains(&(i.min(k), i.max(k)))
+				&& !pairs.contains(&(j.min(k), j.max(k)))
+		});
+		if chain {
+			trace(step(PairOutcome::Chain, basis.len(), pairs.len()));
+			continue
+		}
+
+		let remainder = normal_form(&s_polynomial(&basis[i], &basis[j], order), &basis, order);
+		if remainder.is_zero() {
+			trace(step(PairOutcome::ReducedToZero, basis.len(), pairs.len()));
+		} else {
+			let k = basis.len();
+			basis.push(monic(&remainder, order));
+			pairs.extend((0..k).map(|i| (i, k)));
+			trace(step(PairOutcome::Added(&basis[k]), basis.len(), pairs.len()));
+		}
+	}
+
+	reduce_basis(basis, order)
+}
+
+/// Whether or not f lies in the ideal generated by a Gröbner basis, meaning f reduces to zero
+pub fn in_ideal<F: Field, const N: usize, O: MonomialOrder>(f: &MultiPoly<F, N>, basis: &[MultiPoly<F, N>], order: &O) -> bool {
+	normal_form(f, basis, order).is_zero()
+}
+
+/// Whether or not the given polynomials form a Gröbner basis, meaning every S-polynomial reduces
+/// to zero
+pub fn is_groebner_basis<F: Field, const N: usize, O: MonomialOrder>(basis: &[MultiPoly<F, N>], order: &O) -> bool {
+	(0..basis.len()).all(|j| (0..j).all(|i| in_ideal(&s_polynomial(&basis[i], &basis[j], order), basis, order)))
+}
+
+// MARK: Elimination
+
+/// The reduced Gröbner basis of the elimination ideal I ∩ F[x_k, ..., x_(N-1)], for I the ideal
+/// generated by the given polynomials, in the elimination order for the first k variables.
+///
+/// By the elimination theorem, the elements of a Gröbner basis of I in an elimination order for
+/// x_0, ..., x_(k-1) which do not involve those variables form a Gröbner basis of this ideal.
+pub fn elimination_ideal<F: Field, const N: usize>(generators: &[MultiPoly<F, N>], k: usize) -> Vec<MultiPoly<F, N>> {
+	groebner_basis(generators, &BlockOrder::elimination(k)).into_iter()
+		.filter(|g| g.terms().all(|(m, _)| m.exponents[..k].iter().all(|&e| e == 0)))
+		.collect()
+}
+
+/// f divided by its leading coefficient
+fn monic<F: Field, const N: usize, O: MonomialOrder>(f: &MultiPoly<F, N>, order: &O) -> MultiPoly<F, N> {
+	f.scale(&f.leading_coefficient(order).inverse())
+}
+
+/// Turns a monic Gröbner basis into the reduced one, by dropping elements whose leading monomial
+/// is divisible by another's and then reducing each element by the rest
+fn reduce_basis<F: Field, const N: usize, O: MonomialOrder>(basis: Vec<MultiPoly<F, N>>, order: &O) -> Vec<MultiPoly<F, N>> {
+	let mut minimal: Vec<MultiPoly<F, N>> = Vec::new();
+	for (i, g) in basis.iter().enumerate() {
+		let m = g.leading_monomial(order).unwrap();
+		let redundant = basis.iter().enumerate().any(|(j, h)| {
+			let n = h.leading_monomial(order).unwrap();
+			j != i && n.divides(&m) && (n != m || j < i)
+		});
+
+		if !redundant {
+			minimal.push(g.clone());
+		}
+	}
+
+	let mut reduced: Vec<MultiPoly<F, N>> = (0..minimal.len()).map(|i| {
+		let others: Vec<MultiPoly<F, N>> = minimal.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, h)| h.clone()).collect();
+		normal_form(&minimal[i], &others, order)
+	}).collect();
+
+	reduced.sort_by(|f, g| order.compare(&g.leading_monomial(order).unwrap(), &f.leading_monomial(order).unwrap()));
+	reduced
+}
@@ -0,0 +1,140 @@
+//
+// Groups acting on sets, with orbits, stabilizers, and counting orbits by Burnside's lemma
+//
+
+use crate::algebra::*;
+use crate::permutation::Perm;
+use crate::subgroup::Subgroup;
+
+// MARK: Group Actions
+
+/// An action of G on X, given at the type level like `AutomorphismAction`.
+///
+/// The identity must fix every point, and (gh)x must equal g(hx). The orbits and stabilizers
+/// are computed within a finite subgroup of G, which may be `Subgroup::whole_group()`.
+pub trait GroupAction<G: Group, X> {
+
+	/// The image of x under g
+	fn act(g: &G, x: &X) -> X;
+
+	/// Every image of x under the subgroup, starting with x, found by applying its generators
+	/// until nothing new appears
+	fn orbit(group: &Subgroup<G>, x: &X) -> Vec<X> where X: Clone + PartialEq {
+		let mut orbit = vec![x.clone()];
+		let mut frontier = vec![x.clone()];
+
+		while let Some(y) = frontier.pop() {
+			for g in group.generators() {
+				let image = Self::act(g, &y);
+				if !orbit.contains(&image) {
+					orbit.push(image.clone());
+					frontier.push(image);
+				}
+			}
+		}
+
+		orbit
+	}
+
+	/// The subgroup of elements fixing x, whose index is the size of the orbit of x
+	fn stabilizer(group: &Subgroup<G>, x: &X) -> Subgroup<G> where X: PartialEq {
+		Subgroup::from_elements(group.elements().iter().filter(|g| Self::act(g, x) == *x).cloned().collect())
+	}
+
+	/// The points of the set which g fixes
+	fn fixed_points(g: &G, set: &[X]) -> Vec<X> where X: Clone + PartialEq {
+		set.iter().filter(|x| Self::act(g, x) == **x).cloned().collect()
+	}
+
+	/// The distinct orbits of the subgroup on the set, which must be closed under the action, in
+	/// order of their first points in the set
+	fn orbits(group: &Subgroup<G>, set: &[X]) -> Vec<Vec<X>> where X: Clone + PartialEq {
+		let mut orbits: Vec<Vec<X>> = Vec::new();
+		for x in set {
+			if !orbits.iter().any(|orbit| orbit.contains(x)) {
+				orbits.push(Self::orbit(group, x));
+			}
+		}
+		orbits
+	}
+
+	/// The number of orbits of the subgroup on the set, which must be closed under the action,
+	/// by Burnside's lemma as the average number of points fixed by an element
+	fn count_orbits(group: &Subgroup<G>, set: &[X]) -> usize where X: PartialEq {
+		let fixed: usize = group.elements().iter()
+			.map(|g| set.iter().filter(|x| Self::act(g, x) == **x).count())
+			.sum();
+		fixed / group.order()
+	}
+
+	/// Whether or not the identity fixes every point of the set and (gh)x = g(hx) for all g and
+	/// h in the subgroup and x in the set
+	fn is_valid_action(group: &Subgroup<G>, set: &[X]) -> bool where X: PartialEq {
+		set.iter().all(|x| Self::act(&G::identity(), x) == *x) &&
+		group.elements().iter().all(|g| group.elements().iter().all(|h| set.iter().all(|x|
+			Self::act(&(g.clone() * h.clone()), x) == Self::act(g, &Self::act(h, x))
+		)))
+	}
+
+}
+
+// MARK: Actions
+
+/// Permutations acting on the points 0, 1, ..., N - 1
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointAction;
+
+impl<const N: usize> GroupAction<Perm<N>, usize> for PointAction {
+	fn act(g: &Perm<N>, x: &usize) -> usize {
+		g.apply(*x)
+	}
+}
+
+/// Permutations acting on arrays by moving the entry at position i to position g(i), like
+/// symmetries acting on colorings of the vertices of a shape
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct EntryPermutation;
+
+impl<T: Clone, const N: usize> GroupAction<Perm<N>, [T; N]> for EntryPermutation {
+	fn act(g: &Perm<N>, x: &[T; N]) -> [T; N] {
+		let inverse = g.inverse();
+		std::array::from_fn(|i| x[inverse.apply(i)].clone())
+	}
+}
+
+/// A group acting on itself by conjugation, g x g^-1, whose orbits are the conjugacy classes and
+/// whose stabilizers are the centralizers
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Conjugation;
+
+impl<G: Group> GroupAction<G, G> for Conjugation {
+	fn act(g: &G, x: &G) -> G {
+		g.clone() * x.clone() * g.inverse()
+	}
+}
+
+/// A group acting on itself by left multiplication, g x, which has a single orbit and trivial
+/// stabilizers
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LeftTranslation;
+
+impl<G: Group> GroupAction<G, G> for LeftTranslation {
+	fn act(g: &G, x: &G) -> G {
+		g.clone() * x.clone()
+	}
+}
+
+// MARK: Counting Colorings
+
+/// The number of ways to color the points 0, 1, ..., N - 1 with the given number of colors, up
+/// to the symmetries in the subgroup, like necklaces up to rotation.
+///
+/// This is the orbit count of `EntryPermutation` without listing the colorings, since by
+/// Burnside's lemma a permutation with c cycles, counting fixed points, fixes colors^c of them.
+pub fn count_colorings<const N: usize>(group: &Subgroup<Perm<N>>, colors: u128) -> u128 {
+	let fixed: u128 = group.elements().iter().map(|g| {
+		let moved: usize = g.cycle_decomposition().iter().map(|c| c.len() - 1).sum();
+		colors.pow((N - moved) as u32)
+	}).sum();
+	fixed / group.order() as u128
+}
@@ -0,0 +1,162 @@
+//
+// Structure preserving maps between groups and between rings
+//
+
+use std::marker::PhantomData;
+
+use crate::algebra::*;
+
+// MARK: Group Homomorphisms
+
+/// A homomorphism of groups G -> H
+pub trait GroupHom<G: Group, H: Group> {
+
+	/// The image of g under this homomorphism
+	fn apply(&self, g: &G) -> H;
+
+	/// Every element of G sent to the identity of H
+	fn kernel(&self) -> Vec<G> where G: FiniteGroup {
+		G::elements().filter(|g| self.apply(g) == H::identity()).collect()
+	}
+
+	/// Every element of H hit by this homomorphism, each listed once
+	fn image(&self) -> Vec<H> where G: FiniteGroup {
+		distinct(G::elements().map(|g| self.apply(&g)))
+	}
+
+	/// Checks that this map really is a homomorphism, by testing f(ab) = f(a)f(b) for every pair
+	fn is_homomorphism(&self) -> bool where G: FiniteGroup {
+		let elements: Vec<G> = G::elements().collect();
+		let images: Vec<H> = elements.iter().map(|g| self.apply(g)).collect();
+
+		elements.iter().zip(images.iter()).all(|(a, fa)| {
+			elements.iter().zip(images.iter()).all(|(b, fb)| {
+				self.apply(&(a.clone() * b.clone())) == fa.clone() * fb.clone()
+			})
+		})
+	}
+
+	/// The composite homomorphism G -> H -> K, applying self first
+	fn then<K: Group, M: GroupHom<H, K>>(self, next: M) -> Composition<Self, M, H> where Self: Sized {
+		Composition { first: self, second: next, _middle: PhantomData }
+	}
+
+}
+
+/// A group homomorphism given by a function
+pub struct GroupMap<G: Group, H: Group, F: Fn(&G) -> H> {
+	map: F,
+	_types: PhantomData<fn(&G) -> H>
+}
+
+impl<G: Group, H: Group, F: Fn(&G) -> H> GroupMap<G, H, F> {
+
+	/// Wraps a function as a group homomorphism. The function is trusted to respect the
+	/// group operation; use `is_homomorphism` to check this on finite groups.
+	pub fn new(map: F) -> GroupMap<G, H, F> {
+		GroupMap { map, _types: PhantomData }
+	}
+
+}
+
+impl<G: Group, H: Group, F: Fn(&G) -> H> GroupHom<G, H> for GroupMap<G, H, F> {
+	fn apply(&self, g: &G) -> H {
+		(self.map)(g)
+	}
+}
+
+// MARK: Ring Homomorphisms
+
+/// A homomorphism of (unital) rings R -> S
+pub trait RingHom<R: Ring, S: Ring> {
+
+	/// The image of r under this homomorphism
+	fn apply(&self, r: &R) -> S;
+
+	/// Every element of the given finite domain sent to zero
+	fn kernel_in<I: IntoIterator<Item = R>>(&self, domain: I) -> Vec<R> {
+		domain.into_iter().filter(|r| self.apply(r).is_zero()).collect()
+	}
+
+	/// Every element hit by this homomorphism on the given finite domain, each listed once
+	fn image_of<I: IntoIterator<Item = R>>(&self, domain: I) -> Vec<S> {
+		distinct(domain.into_iter().map(|r| self.apply(&r)))
+	}
+
+	/// Checks that this map respects addition, multiplication, and the identity
+	/// on every pair of elements of the given finite domain
+	fn is_homomorphism_on<I: IntoIterator<Item = R>>(&self, domain: I) -> bool {
+		let elements: Vec<R> = domain.into_iter().collect();
+
+		if self.apply(&R::one()) != S::one() {
+			return false
+		}
+
+		elements.iter().all(|a| {
+			elements.iter().all(|b| {
+				self.apply(&a.add_ref(b)) == self.apply(a).add_ref(&self.apply(b))
+					&& self.apply(&a.mul_ref(b)) == self.apply(a).mul_ref(&self.apply(b))
+			})
+		})
+	}
+
+	/// The composite homomorphism R -> S -> T, applying self first
+	fn then<T: Ring, M: RingHom<S, T>>(self, next: M) -> Composition<Self, M, S> where Self: Sized {
+		Composition { first: self, second: next, _middle: PhantomData }
+	}
+
+}
+
+/// A ring homomorphism given by a function
+pub struct RingMap<R: Ring, S: Ring, F: Fn(&R) -> S> {
+	map: F,
+	_types: PhantomData<fn(&R) -> S>
+}
+
+impl<R: Ring, S: Ring, F: Fn(&R) -> S> RingMap<R, S, F> {
+
+	/// Wraps a function as a ring homomorphism. The function is trusted to respect the
+	/// ring operations; use `is_homomorphism_on` to check this on finite rings.
+	pub fn new(map: F) -> RingMap<R, S, F> {
+		RingMap { map, _types: PhantomData }
+	}
+
+}
+
+impl<R: Ring, S: Ring, F: Fn(&R) -> S> RingHom<R, S> for RingMap<R, S, F> {
+	fn apply(&self, r: &R) -> S {
+		(self.map)(r)
+	}
+}
+
+// MARK: Composition
+
+/// The composite of two homomorphisms, passing through the middle structure M
+pub struct Composition<A, B, M> {
+	first: A,
+	second: B,
+	_middle: PhantomData<fn() -> M>
+}
+
+impl<G: Group, H: Group, K: Group, A: GroupHom<G, H>, B: GroupHom<H, K>> GroupHom<G, K> for Composition<A, B, H> {
+	fn apply(&self, g: &G) -> K {
+		self.second.apply(&self.first.apply(g))
+	}
+}
+
+impl<R: Ring, S: Ring, T: Ring, A: RingHom<R, S>, B: RingHom<S, T>> RingHom<R, T> for Composition<A, B, S> {
+	fn apply(&self, r: &R) -> T {
+		self.second.apply(&self.first.apply(r))
+	}
+}
+
+/// The distinct items of an iterator, in order of first appearance
+fn distinct<T: PartialEq, I: Iterator<Item = T>>(items: I) -> Vec<T> {
+	let mut seen = Vec::new();
+	for item in items {
+		if !seen.contains(&item) {
+			seen.push(item);
+		}
+	}
+	seen
+}
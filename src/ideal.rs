@@ -0,0 +1,202 @@
+//
+// Finitely generated ideals of commutative rings, with membership, intersections, and radicals
+// where the ring makes them computable
+//
+
+use std::ops::{Add, Mul};
+
+use crate::algebra::*;
+use crate::groebner::{groebner_basis, in_ideal};
+use crate::multivariate::{GRevLex, MonomialOrder, MultiPoly};
+use crate::polynomial::Polynomial;
+use crate::std_impls::radical;
+
+// MARK: Ideals
+
+/// The ideal of a commutative ring generated by finitely many elements, the sums of multiples of
+/// the generators.
+///
+/// Sums and products work in every ring. Over a Euclidean domain every ideal is principal, which
+/// makes membership, containment, and intersections computable, and for multivariate polynomials
+/// over a field membership goes through a Gröbner basis.
+#[derive(Clone, Debug)]
+pub struct Ideal<R: CommutativeRing> {
+	generators: Vec<R>
+}
+
+impl<R: CommutativeRing> Ideal<R> {
+
+	/// The ideal generated by the given elements, leaving out any zeros
+	pub fn new(generators: Vec<R>) -> Ideal<R> {
+		Ideal { generators: generators.into_iter().filter(|g| !g.is_zero()).collect() }
+	}
+
+	/// The principal ideal (a)
+	pub fn principal(a: R) -> Ideal<R> {
+		Ideal::new(vec![a])
+	}
+
+	/// The zero ideal
+	pub fn zero() -> Ideal<R> {
+		Ideal { generators: vec![] }
+	}
+
+	/// The whole ring, the ideal generated by one
+	pub fn whole() -> Ideal<R> {
+		Ideal::principal(R::one())
+	}
+
+	/// The generators, without zeros
+	pub fn generators(&self) -> &[R] {
+		&self.generators
+	}
+
+	/// Whether or not this is the zero ideal
+	pub fn is_zero(&self) -> bool {
+		self.generators.is_empty()
+	}
+
+	/// The sum I + J, generated by the generators of both
+	pub fn sum(&self, other: &Ideal<R>) -> Ideal<R> {
+		Ideal { generators: self.generators.iter().chain(&other.generators).cloned().collect() }
+	}
+
+	/// The product IJ, generated by the products of a generator of each
+	pub fn product(&self, other: &Ideal<R>) -> Ideal<R> {
+		Ideal::new(self.generators.iter().flat_map(|a| other.generators.iter().map(move |b| a.mul_ref(b))).collect())
+	}
+
+	/// The power I^n, with I^0 the whole ring
+	pub fn power(&self, n: u32) -> Ideal<R> {
+		(0..n).fold(Ideal::whole(), |power, _| power.product(self))
+	}
+
+}
+
+impl<R: CommutativeRing> Add for Ideal<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.sum(&rhs)
+	}
+}
+
+impl<R: CommutativeRing> Mul for Ideal<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.product(&rhs)
+	}
+}
+
+// MARK: Principal Ideal Domains
+
+impl<R: EuclideanDomain> Ideal<R> {
+
+	/// The generator of this ideal as a principal ideal, the gcd of the generators, as the
+	/// associate chosen by gcd(g, 0), so over the integers it is nonnegative
+	pub fn generator(&self) -> R {
+		self.generators.iter().fold(R::zero(), |g, a| g.gcd(a))
+	}
+
+	/// Whether or not x is in this ideal, meaning the generator divides it
+	pub fn contains(&self, x: &R) -> bool {
+		let g = self.generator();
+		if g.is_zero() {
+			return x.is_zero()
+		}
+		x.quotient_and_remainder(&g).1.is_zero()
+	}
+
+	/// Whether or not every element of this ideal is in the other
+	pub fn is_subset_of(&self, other: &Ideal<R>) -> bool {
+		self.generators.iter().all(|a| other.contains(a))
+	}
+
+	/// Whether or not this ideal is the whole ring, meaning its generator is a unit
+	pub fn is_whole(&self) -> bool {
+		self.contains(&R::one())
+	}
+
+	/// The intersection of two ideals, generated by the lcm of their generators
+	pub fn intersection(&self, other: &Ideal<R>) -> Ideal<R> {
+		let (a, b) = (self.generator(), other.generator());
+		if a.is_zero() || b.is_zero() {
+			return Ideal::zero()
+		}
+
+		let product = a.mul_ref(&b);
+		Ideal::principal(product.quotient_and_remainder(&a.gcd(&b)).0)
+	}
+
+}
+
+/// Ideals of a Euclidean domain are equal when their generators are associates
+impl<R: EuclideanDomain> PartialEq for Ideal<R> {
+	fn eq(&self, other: &Self) -> bool {
+		self.is_subset_of(other) && other.is_subset_of(self)
+	}
+}
+
+impl Ideal<i64> {
+
+	/// The radical of this ideal, the elements with a power in it, generated by the product of the
+	/// distinct primes dividing the generator
+	pub fn radical(&self) -> Ideal<i64> {
+		match self.generator() {
+			0 => Ideal::zero(),
+			g => Ideal::principal(radical(g))
+		}
+	}
+
+}
+
+impl<F: Field> Ideal<Polynomial<F>> {
+
+	/// The radical of this ideal, the polynomials with a power in it, generated by the squarefree
+	/// part of the generator, with the same caveats as `Polynomial::squarefree_part`
+	pub fn radical(&self) -> Ideal<Polynomial<F>> {
+		let g = self.generator();
+		if g.is_zero() {
+			return Ideal::zero()
+		}
+		Ideal::principal(g.squarefree_part())
+	}
+
+}
+
+// MARK: Multivariate Polynomial Ideals
+
+impl<F: Field, const N: usize> Ideal<MultiPoly<F, N>> {
+
+	/// The reduced Gröbner basis of this ideal in the given monomial order
+	pub fn groebner_basis<O: MonomialOrder>(&self, order: &O) -> Vec<MultiPoly<F, N>> {
+		groebner_basis(&self.generators, order)
+	}
+
+	/// Whether or not f is in this ideal, by reducing it with a Gröbner basis in the graded reverse
+	/// lexicographic order, which is usually the cheapest to compute
+	pub fn contains(&self, f: &MultiPoly<F, N>) -> bool {
+		in_ideal(f, &self.groebner_basis(&GRevLex), &GRevLex)
+	}
+
+	/// Whether or not every element of this ideal is in the other
+	pub fn is_subset_of(&self, other: &Ideal<MultiPoly<F, N>>) -> bool {
+		let basis = other.groebner_basis(&GRevLex);
+		self.generators.iter().all(|f| in_ideal(f, &basis, &GRevLex))
+	}
+
+	/// Whether or not this ideal is the whole ring, meaning its reduced Gröbner basis is 1, so the
+	/// generators have no common zero over the algebraic closure
+	pub fn is_whole(&self) -> bool {
+		self.contains(&MultiPoly::one())
+	}
+
+}
+
+/// Ideals of multivariate polynomials are equal when each contains the generators of the other
+impl<F: Field, const N: usize> PartialEq for Ideal<MultiPoly<F, N>> {
+	fn eq(&self, other: &Self) -> bool {
+		self.is_subset_of(other) && other.is_subset_of(self)
+	}
+}
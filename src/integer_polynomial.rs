@@ -0,0 +1,373 @@
+//
+// Factoring polynomials with integer coefficients: Hensel lifting of a factorization modulo p to
+// one modulo p^k, and the Zassenhaus algorithm, which recombines the lifted factors into the
+// factors over Z
+//
+
+use crate::algebra::*;
+use crate::checked::Checked;
+use crate::polynomial::Polynomial;
+use crate::sieve::mobius_up_to;
+use crate::std_impls::ZM;
+
+// MARK: Arithmetic Modulo p^k
+
+/// The product of two polynomials with coefficients in [0, m), reduced modulo m, for m < 2^63
+fn mul_mod(a: &[i128], b: &[i128], m: i128) -> Vec<i128> {
+	if a.is_empty() || b.is_empty() {
+		return Vec::new()
+	}
+
+	let mut product = vec![0; a.len() + b.len() - 1];
+	for (i, x) in a.iter().enumerate() {
+		for (j, y) in b.iter().enumerate() {
+			product[i + j] = (product[i + j] + x * y % m) % m;
+		}
+	}
+	product
+}
+
+/// The coefficients of a polynomial over ZM<P>, as integers in [0, P)
+fn lift_coefficients<const P: i64>(f: &Polynomial<ZM<P>>) -> Vec<i128> {
+	f.coefficients().iter().map(|c| c.val as i128).collect()
+}
+
+/// The reduction modulo P of a polynomial with integer coefficients
+fn reduce<const P: i64>(f: &[i128]) -> Polynomial<ZM<P>> {
+	Polynomial::new(f.iter().map(|&c| ZM::from_int(c.rem_euclid(P as i128) as i64)).collect())
+}
+
+/// Lifts f = g h modulo p to f = g h modulo p^k, one digit at a time, keeping g monic, where
+/// s g + t h = 1 modulo p for some s
+fn lift_pair<const P: i64>(f: &[i128], g: &mut Vec<i128>, h: &mut Vec<i128>, t: &Polynomial<ZM<P>>, k: u32) {
+	let p = P as i128;
+	let mut modulus = p;
+
+	for _ in 1..k {
+		let next = modulus * p;
+
+		// e = (f - g h) / p^j, which is exact since g h = f modulo p^j
+		let product = mul_mod(g, h, next);
+		let e: Vec<i128> = (0..f.len().max(product.len()))
+			.map(|i| (f.get(i).copied().unwrap_or(0) - product.get(i).copied().unwrap_or(0)).rem_euclid(next) / modulus)
+			.collect();
+		let e = reduce::<P>(&e);
+
+		// (g + p^j a)(h + p^j b) = f modulo p^(j+1) when g b + h a = e modulo p, and taking a
+		// with degree less than g keeps g monic
+		let (g_bar, h_bar) = (reduce::<P>(g), reduce::<P>(h));
+		let a = t.mul_ref(&e).quotient_and_remainder(&g_bar).1;
+		let b = (e - h_bar * a.clone()) / g_bar;
+
+		for (c, x) in [(&mut *g, a), (&mut *h, b)] {
+			let x = lift_coefficients(&x);
+			if c.len() < x.len() {
+				c.resize(x.len(), 0);
+			}
+			for (c, x) in c.iter_mut().zip(x) {
+				*c = (*c + modulus * x) % next;
+			}
+		}
+
+		modulus = next;
+	}
+}
+
+// MARK: Hensel Lifting
+
+/// Lifts a factorization of f modulo P to one modulo P^k: given monic, pairwise coprime factors
+/// g_i with f = lc(f) g_1 ... g_r modulo P, the monic polynomials with coefficients in [0, P^k)
+/// which reduce to them modulo P and whose product times lc(f) is f modulo P^k. P must be prime,
+/// must not divide lc(f), and P^k must fit in an i64.
+///
+/// The factors are split off one at a time, lifting f = g_1 (g_2 ... g_r) first, then the lifted
+/// second factor, and so on. Each split lifts one P-adic digit at a time from a Bezout relation
+/// s g + t h = 1 modulo P.
+pub fn hensel_lift<const P: i64>(f: &Polynomial<i64>, factors: &[Polynomial<ZM<P>>], k: u32) -> Vec<Polynomial<i64>> {
+	let modulus = (P as i128).checked_pow(k).filter(|&m| m <= i64::MAX as i128)
+		.unwrap_or_else(|| panic!("{}^{} does not fit in an i64", P, k));
+	let lead = ZM::<P>::from_int(f.leading_coefficient());
+	if lead.is_zero() {
+		panic!("{} divides the leading coefficient of the polynomial", P)
+	}
+
+	let product = factors.iter().fold(Polynomial::constant(lead), |product, g| product * g.clone());
+	let reduced = Polynomial::new(f.coefficients().iter().map(|&c| ZM::<P>::from_int(c)).collect());
+	if factors.iter().any(|g| !g.is_monic()) || product != reduced {
+		panic!("The factors must be monic with product f / lc(f) modulo {}", P)
+	}
+
+	let mut lifted = Vec::with_capacity(factors.len());
+	let mut rest: Vec<i128> = f.coefficients().iter().map(|&c| (c as i128).rem_euclid(modulus)).collect();
+
+	for (i, g) in factors.iter().enumerate().take(factors.len().saturating_sub(1)) {
+		let h = factors[i + 1..].iter().fold(Polynomial::constant(lead), |product, g| product * g.clone());
+
+		let (d, _, t) = ext_gcd(g, &h);
+		if d.degree() > 0 {
+			panic!("The factors modulo {} must be pairwise coprime", P)
+		}
+		let t = t * Polynomial::constant(d.leading_coefficient().inverse());
+
+		let mut g_lifted = lift_coefficients(g);
+		let mut h_lifted = lift_coefficients(&h);
+		lift_pair(&rest, &mut g_lifted, &mut h_lifted, &t, k);
+
+		lifted.push(g_lifted);
+		rest = h_lifted;
+	}
+
+	// What is left is lc(f) times the last factor, which is made monic
+	if !factors.is_empty() {
+		let lead_inverse = mod_inverse(&rest.last().copied().unwrap_or(1), &modulus).unwrap();
+		lifted.push(rest.iter().map(|c| c * lead_inverse % modulus).collect());
+	}
+
+	lifted.into_iter().map(|g| Polynomial::new(g.into_iter().map(|c| c as i64).collect())).collect()
+}
+
+// MARK: Zassenhaus
+
+/// A step of factoring an integer polynomial, reported by `Polynomial::factor_with_trace`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FactorEvent<'a> {
+	/// The polynomial has repeated factors, and this squarefree part of it with the given
+	/// multiplicity is factored next
+	SquarefreePart { part: &'a Polynomial<i64>, multiplicity: usize },
+	/// A squarefree polynomial has this many irreducible factors modulo the prime
+	ModularFactors { polynomial: &'a Polynomial<i64>, prime: i64, count: usize },
+	/// Those factors were lifted modulo prime^exponent
+	Lifted { prime: i64, exponent: u32 },
+	/// An irreducible factor over the integers was found
+	Factor(&'a Polynomial<i64>)
+}
+
+/// The quotient f / g of integer polynomials if it is exact, found without overflowing
+fn divide_exact_integer(f: &Polynomial<i64>, g: &[i128]) -> Option<Polynomial<i64>> {
+	let f: Polynomial<Checked<i128>> = Polynomial::new(f.coefficients().iter().map(|&c| Checked::new(c as i128)).collect());
+	let g: Polynomial<Checked<i128>> = Polynomial::new(g.iter().map(|&c| Checked::new(c)).collect());
+
+	let q = f.divide_exact(&g)?;
+	q.coefficients().iter().map(|c| c.value().ok().and_then(|c| i64::try_from(c).ok())).collect::<Option<Vec<i64>>>().map(Polynomial::new)
+}
+
+/// The primitive part of a polynomial with i128 coefficients, with positive leading coefficient
+fn primitive_part_integer(f: &[i128]) -> Vec<i128> {
+	let content = f.iter().fold(0, |g, c| gcd(&g, c));
+	let sign = if f.last().is_some_and(|&c| c < 0) { -1 } else { 1 };
+	f.iter().map(|c| c / content * sign).collect()
+}
+
+/// Advances to the next k-element subset of 0..n in lexicographic order, returning false if it
+/// was the last
+fn next_subset(subset: &mut [usize], n: usize) -> bool {
+	let k = subset.len();
+	let Some(i) = (0..k).rev().find(|&i| subset[i] < n - k + i) else {
+		return false
+	};
+	subset[i] += 1;
+	for j in i + 1..k {
+		subset[j] = subset[j - 1] + 1;
+	}
+	true
+}
+
+/// The irreducible factors of a primitive, squarefree integer polynomial of positive degree with
+/// positive leading coefficient, using the prime P, or None if P divides the leading coefficient
+/// or the polynomial is not squarefree modulo P.
+///
+/// The factors modulo P are lifted modulo P^k for P^k beyond twice the leading coefficient times
+/// the Mignotte bound, which bounds the coefficients of lc(f) times any factor. Then products
+/// of subsets of the lifted factors, taken with coefficients in (-P^k / 2, P^k / 2], are tried
+/// as divisors, smallest subsets first.
+fn zassenhaus<const P: i64>(f: &Polynomial<i64>, trace: &mut impl FnMut(FactorEvent<'_>)) -> Option<Vec<Polynomial<i64>>> {
+	let lead = f.leading_coefficient();
+	let reduced = Polynomial::new(f.coefficients().iter().map(|&c| ZM::<P>::from_int(c)).collect());
+	if reduced.degree() != f.degree() || !reduced.is_squarefree() {
+		return None
+	}
+
+	let modular: Vec<Polynomial<ZM<P>>> = reduced.factor().into_iter().map(|(g, _)| g).collect();
+	trace(FactorEvent::ModularFactors { polynomial: f, prime: P, count: modular.len() });
+	if modular.len() == 1 {
+		trace(FactorEvent::Factor(f));
+		return Some(vec![f.clone()])
+	}
+
+	let bound = 2.0 * lead as f64 * f.mignotte_bound();
+	let mut k = 1;
+	while (P as f64).powi(k as i32) <= bound {
+		k += 1;
+	}
+	let modulus = (P as i128).pow(k);
+	trace(FactorEvent::Lifted { prime: P, exponent: k });
+
+	let mut remaining: Vec<Vec<i128>> = hensel_lift(f, &modular, k).iter()
+		.map(|g| g.coefficients().iter().map(|&c| c as i128).collect())
+		.collect();
+	let mut rest = f.clone();
+	let mut factors = Vec::new();
+	let mut size = 1;
+
+	'sizes: while 2 * size <= remaining.len() {
+		let mut subset: Vec<usize> = (0..size).collect();
+		loop {
+			let lead = rest.leading_coefficient() as i128;
+			let product = subset.iter().fold(vec![lead.rem_euclid(modulus)], |product, &i| mul_mod(&product, &remaining[i], modulus));
+			let candidate = primitive_part_integer(&product.iter().map(|&c| if 2 * c > modulus { c - modulus } else { c }).collect::<Vec<i128>>());
+
+			if let Some(quotient) = divide_exact_integer(&rest, &candidate) {
+				factors.push(Polynomial::new(candidate.iter().map(|&c| c as i64).collect()));
+				trace(FactorEvent::Factor(factors.last().unwrap()));
+				rest = quotient;
+				for &i in subset.iter().rev() {
+					remaining.remove(i);
+				}
+				continue 'sizes
+			}
+
+			if !next_subset(&mut subset, remaining.len()) {
+				break
+			}
+		}
+		size += 1;
+	}
+
+	trace(FactorEvent::Factor(&rest));
+	factors.push(rest);
+	Some(factors)
+}
+
+/// The irreducible factors of a primitive, squarefree polynomial of positive degree with positive
+/// leading coefficient, using the first suitable prime, or None if no prime tried is suitable
+fn factor_squarefree(f: &Polynomial<i64>, trace: &mut impl FnMut(FactorEvent<'_>)) -> Option<Vec<Polynomial<i64>>> {
+	if f.degree() == 1 {
+		trace(FactorEvent::Factor(f));
+		return Some(vec![f.clone()])
+	}
+
+	macro_rules! first_suitable {
+		($($p:literal),*) => { None$(.or_else(|| zassenhaus::<$p>(f, trace)))* };
+	}
+	first_suitable!(3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53)
+}
+
+impl Polynomial<i64> {
+
+	/// The Mignotte bound 2^n |f|, for the degree n and Euclidean norm |f| of this polynomial,
+	/// which bounds the absolute values of the coefficients of every factor of it over Z
+	pub fn mignotte_bound(&self) -> f64 {
+		let norm = self.coefficients().iter().map(|&c| (c as f64) * (c as f64)).sum::<f64>().sqrt();
+		2f64.powi(self.degree() as i32) * norm
+	}
+
+	/// The factorization of this nonzero polynomial over the integers, as a constant c and the
+	/// primitive irreducible factors with positive leading coefficients, with multiplicities and
+	/// sorted by degree, whose product with c is this polynomial. By Gauss's lemma these are also
+	/// the irreducible factors over the rationals, up to constant multiples.
+	///
+	/// Each squarefree part is factored by the Zassenhaus algorithm, with a small prime for which
+	/// it stays squarefree: the factors modulo that prime are lifted to a power of it beyond the
+	/// Mignotte bound, then recombined by trying products of subsets of them as divisors.
+	/// Coefficients of the factors, and the bound on them, must fit in an i64, so this is meant
+	/// for polynomials of moderate degree and size.
+	pub fn factor(&self) -> (i64, Vec<(Polynomial<i64>, usize)>) {
+		self.factor_with_trace(&mut |_| { })
+	}
+
+	/// The factorization as in `factor`, calling trace as the squarefree parts, the factorizations
+	/// modulo a prime, the Hensel lifts, and the irreducible factors are found
+	pub fn factor_with_trace(&self, trace: &mut impl FnMut(FactorEvent<'_>)) -> (i64, Vec<(Polynomial<i64>, usize)>) {
+		if self.is_zero() {
+			panic!("The zero polynomial has no factorization")
+		}
+
+		let sign = if self.leading_coefficient() < 0 { -1 } else { 1 };
+		let content = self.content() * sign;
+		let f = Polynomial::new(self.coefficients().iter().map(|c| c / content).collect());
+		if f.degree() == 0 {
+			return (content, Vec::new())
+		}
+
+		let mut factors: Vec<(Polynomial<i64>, usize)> = match factor_squarefree(&f, trace) {
+			Some(factors) => factors.into_iter().map(|g| (g, 1)).collect(),
+			None => {
+				// Every prime tried divides the discriminant, which almost always means f has
+				// repeated factors, so split it into squarefree parts first
+				let parts = squarefree_parts(&f);
+				if parts.len() == 1 && parts[0].1 == 1 {
+					panic!("No small prime keeps {:?} squarefree", self)
+				}
+				parts.into_iter().flat_map(|(part, i)| {
+					trace(FactorEvent::SquarefreePart { part: &part, multiplicity: i });
+					part.factor_with_trace(trace).1.into_iter().map(move |(g, j)| (g, i * j))
+				}).collect()
+			}
+		};
+
+		factors.sort_by_key(|(g, _)| (g.degree(), g.coefficients().iter().rev().copied().collect::<Vec<i64>>()));
+		(content, factors)
+	}
+
+	/// Whether or not this polynomial is irreducible over the rationals, having positive degree
+	/// and no factorization into polynomials of smaller degree
+	pub fn is_irreducible(&self) -> bool {
+		if self.degree() == 0 {
+			return false
+		}
+
+		let (_, factors) = self.factor();
+		factors.len() == 1 && factors[0].1 == 1
+	}
+
+}
+
+/// Pairs (f_i, i) of squarefree, pairwise coprime, primitive polynomials of positive degree with
+/// positive leading coefficients, whose product of f_i^i is the primitive polynomial f, by
+/// repeated gcds. The gcds work in i128 since pseudo-remainders grow quickly.
+fn squarefree_parts(f: &Polynomial<i64>) -> Vec<(Polynomial<i64>, usize)> {
+	let normalize = |g: Polynomial<i128>| if g.leading_coefficient() < 0 { -g } else { g };
+	let f: Polynomial<i128> = Polynomial::new(f.coefficients().iter().map(|&c| c as i128).collect());
+
+	let mut c = normalize(f.primitive_gcd(&f.derivative()).primitive_part());
+	let mut w = f.divide_exact(&c).unwrap();
+	let mut parts = Vec::new();
+	let mut i = 1;
+
+	while w.degree() > 0 {
+		let y = normalize(w.primitive_gcd(&c).primitive_part());
+		let part = w.divide_exact(&y).unwrap();
+		if part.degree() > 0 {
+			parts.push((Polynomial::new(normalize(part).coefficients().iter().map(|&c| c as i64).collect()), i));
+		}
+		c = c.divide_exact(&y).unwrap();
+		w = y;
+		i += 1;
+	}
+
+	parts
+}
+
+// MARK: Cyclotomic Polynomials
+
+/// The n-th cyclotomic polynomial, for n positive, whose roots are the primitive n-th roots of
+/// unity. It is monic with integer coefficients, irreducible over the rationals, and of degree
+/// totient(n), and modulo a prime Q not dividing n its roots are the elements of order n.
+///
+/// Since x^n - 1 is the product of the cyclotomic polynomials of the divisors of n, Möbius
+/// inversion writes this one as the product of (x^d - 1)^μ(n / d) over the divisors d of n, so the
+/// factors with exponent 1 are multiplied together and then those with exponent -1 divided out.
+pub fn cyclotomic(n: usize) -> Polynomial<i64> {
+	if n == 0 {
+		panic!("Cyclotomic polynomials are indexed by positive integers")
+	}
+
+	let mu = mobius_up_to(n);
+	let binomial = |d: usize| Polynomial::monomial(1, d) - Polynomial::one();
+	let divisors: Vec<usize> = (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+
+	let numerator = divisors.iter().filter(|&&d| mu[n / d] == 1)
+		.fold(Polynomial::one(), |product, &d| product * binomial(d));
+	divisors.iter().filter(|&&d| mu[n / d] == -1)
+		.fold(numerator, |quotient, &d| quotient.divide_exact(&binomial(d)).unwrap())
+}
@@ -0,0 +1,364 @@
+//
+// Intervals of floating point numbers with outward rounding, so that numerical computations carry
+// rigorous bounds on their error
+//
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+
+// MARK: Intervals
+
+/// A closed interval [lower, upper] of floating point numbers, standing for some unknown real
+/// number within it.
+///
+/// Arithmetic rounds outward: every operation gives an interval containing the exact result of
+/// that operation on every choice of numbers from its operands, so a computation run through the
+/// generic traits on intervals ends with an interval guaranteed to contain the true answer. An
+/// endpoint is only moved when the floating point result is inexact, so computations on exactly
+/// representable values stay exact points.
+///
+/// Two intervals are equal when their endpoints are, and only [0, 0] is zero, so a generic
+/// algorithm treats an interval as zero only when it is certainly zero. Dividing by an interval
+/// which contains zero panics, since nothing can be said about the quotient, and `try_inverse`
+/// gives None for such intervals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval<T> {
+	lower: T,
+	upper: T
+}
+
+impl<T: Copy + PartialOrd + Debug> Interval<T> {
+
+	/// The interval [lower, upper], which must have lower <= upper
+	pub fn new(lower: T, upper: T) -> Interval<T> {
+		if !matches!(lower.partial_cmp(&upper), Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)) {
+			panic!("Invalid interval bounds {:?} and {:?}", lower, upper)
+		}
+		Interval { lower, upper }
+	}
+
+	/// The lower endpoint
+	pub fn lower(&self) -> T {
+		self.lower
+	}
+
+	/// The upper endpoint
+	pub fn upper(&self) -> T {
+		self.upper
+	}
+
+	/// Whether or not x lies in this interval
+	pub fn contains(&self, x: T) -> bool {
+		self.lower <= x && x <= self.upper
+	}
+
+	/// Whether or not this interval lies within other
+	pub fn is_subset(&self, other: &Interval<T>) -> bool {
+		other.lower <= self.lower && self.upper <= other.upper
+	}
+
+	/// The intersection of two intervals, or None if they are disjoint
+	pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+		let lower = if self.lower < other.lower { other.lower } else { self.lower };
+		let upper = if self.upper < other.upper { self.upper } else { other.upper };
+		(lower <= upper).then_some(Interval { lower, upper })
+	}
+
+	/// The smallest interval containing both intervals
+	pub fn hull(&self, other: &Interval<T>) -> Interval<T> {
+		Interval {
+			lower: if self.lower < other.lower { self.lower } else { other.lower },
+			upper: if self.upper < other.upper { other.upper } else { self.upper }
+		}
+	}
+
+}
+
+impl<T: Display> Display for Interval<T> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "[{}, {}]", self.lower, self.upper)
+	}
+}
+
+// MARK: Floating Point Intervals
+
+macro_rules! impl_interval {
+	($($t:ty),*) => {$(
+		impl Interval<$t> {
+
+			/// The interval [x, x] holding just x
+			pub fn point(x: $t) -> Interval<$t> {
+				Self::new(x, x)
+			}
+
+			/// The interval [x - radius, x + radius], rounded outward
+			pub fn with_radius(x: $t, radius: $t) -> Interval<$t> {
+				Self::point(x) + Self::new(-radius, radius)
+			}
+
+			/// A number near the middle of this interval
+			pub fn midpoint(&self) -> $t {
+				self.lower / 2.0 + self.upper / 2.0
+			}
+
+			/// An upper bound on upper - lower
+			pub fn width(&self) -> $t {
+				Self::round_up(Self::sum(self.upper, -self.lower))
+			}
+
+			/// Whether or not zero lies in this interval
+			pub fn contains_zero(&self) -> bool {
+				self.contains(0.0)
+			}
+
+			/// The absolute values of the numbers in this interval
+			pub fn abs(&self) -> Interval<$t> {
+				if self.lower >= 0.0 {
+					*self
+				} else if self.upper <= 0.0 {
+					-*self
+				} else {
+					Interval { lower: 0.0, upper: self.upper.max(-self.lower) }
+				}
+			}
+
+			/// The square roots of the nonnegative numbers in this interval, which must contain one
+			pub fn sqrt(&self) -> Interval<$t> {
+				if self.upper < 0.0 {
+					panic!("Cannot take the square root of the negative interval {}", self)
+				}
+				let lower = if self.lower <= 0.0 { 0.0 } else { Self::round_down(Self::square_root(self.lower)) };
+				Interval { lower, upper: Self::round_up(Self::square_root(self.upper)) }
+			}
+
+			/// An exact result is given with zero error. Otherwise the error is the sign of the true
+			/// result minus the rounded one, or NaN if not even that is known.
+			fn round_down((x, error): ($t, $t)) -> $t {
+				if error >= 0.0 { x } else { x.next_down() }
+			}
+
+			fn round_up((x, error): ($t, $t)) -> $t {
+				if error <= 0.0 { x } else { x.next_up() }
+			}
+
+			/// An infinite result of finite operands overflowed, so the true result is finite
+			fn overflowed(x: $t, a: $t, b: $t) -> Option<($t, $t)> {
+				(!x.is_finite() && a.is_finite() && b.is_finite()).then_some((x, -x))
+			}
+
+			/// a + b, with its rounding error found exactly by Knuth's two-sum
+			fn sum(a: $t, b: $t) -> ($t, $t) {
+				let s = a + b;
+				if let Some(result) = Self::overflowed(s, a, b) {
+					return result
+				}
+				if !s.is_finite() {
+					return (s, 0.0)
+				}
+				let b_part = s - a;
+				(s, (a - (s - b_part)) + (b - b_part))
+			}
+
+			/// a b, with the sign of its rounding error found by a fused multiply-add, unless the
+			/// result underflowed
+			fn product(a: $t, b: $t) -> ($t, $t) {
+				let p = a * b;
+				if let Some(result) = Self::overflowed(p, a, b) {
+					return result
+				}
+				if a == 0.0 || b == 0.0 || !p.is_finite() {
+					return (p, 0.0)
+				}
+				if p.abs() < <$t>::MIN_POSITIVE {
+					return (p, <$t>::NAN)
+				}
+				(p, a.mul_add(b, -p))
+			}
+
+			/// a / b for nonzero b, with the sign of its rounding error found from the remainder
+			/// a - q b, computed exactly by a fused multiply-add, unless the result underflowed
+			fn quotient(a: $t, b: $t) -> ($t, $t) {
+				let q = a / b;
+				if let Some(result) = Self::overflowed(q, a, b) {
+					return result
+				}
+				if a == 0.0 || !q.is_finite() || !b.is_finite() {
+					return (q, 0.0)
+				}
+				if q.abs() < <$t>::MIN_POSITIVE {
+					return (q, <$t>::NAN)
+				}
+				let r = (-q).mul_add(b, a);
+				(q, if r == 0.0 { 0.0 } else if (r > 0.0) == (b > 0.0) { 1.0 } else { -1.0 })
+			}
+
+			/// The square root of x >= 0, with the sign of its rounding error found from x - s^2
+			fn square_root(x: $t) -> ($t, $t) {
+				let s = x.sqrt();
+				(s, (-s).mul_add(s, x))
+			}
+
+			/// The interval with the smallest and largest of the given rounded results
+			fn enclosing(results: [($t, $t); 4]) -> Interval<$t> {
+				Interval {
+					lower: results.iter().map(|&r| Self::round_down(r)).fold(<$t>::INFINITY, <$t>::min),
+					upper: results.iter().map(|&r| Self::round_up(r)).fold(<$t>::NEG_INFINITY, <$t>::max)
+				}
+			}
+
+			/// x^n for a point x, enclosed by repeated squaring
+			fn point_power(x: $t, n: i64) -> Interval<$t> {
+				let mut result = Self::one();
+				let mut base = Self::point(x);
+				let mut n = n;
+				while n > 0 {
+					if n & 1 == 1 {
+						result *= base;
+					}
+					base *= base;
+					n >>= 1;
+				}
+				result
+			}
+
+		}
+
+		impl From<$t> for Interval<$t> {
+			fn from(x: $t) -> Self {
+				Self::point(x)
+			}
+		}
+
+		impl Add for Interval<$t> {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self::Output {
+				Interval {
+					lower: Self::round_down(Self::sum(self.lower, rhs.lower)),
+					upper: Self::round_up(Self::sum(self.upper, rhs.upper))
+				}
+			}
+		}
+
+		impl AddAssign for Interval<$t> {
+			fn add_assign(&mut self, rhs: Self) {
+				*self = *self + rhs
+			}
+		}
+
+		impl Neg for Interval<$t> {
+			type Output = Self;
+
+			fn neg(self) -> Self::Output {
+				Interval { lower: -self.upper, upper: -self.lower }
+			}
+		}
+
+		#[allow(clippy::suspicious_arithmetic_impl)]
+		impl Sub for Interval<$t> {
+			type Output = Self;
+
+			fn sub(self, rhs: Self) -> Self::Output {
+				self + -rhs
+			}
+		}
+
+		impl SubAssign for Interval<$t> {
+			fn sub_assign(&mut self, rhs: Self) {
+				*self = *self - rhs
+			}
+		}
+
+		impl Mul for Interval<$t> {
+			type Output = Self;
+
+			fn mul(self, rhs: Self) -> Self::Output {
+				Self::enclosing([
+					Self::product(self.lower, rhs.lower),
+					Self::product(self.lower, rhs.upper),
+					Self::product(self.upper, rhs.lower),
+					Self::product(self.upper, rhs.upper)
+				])
+			}
+		}
+
+		impl MulAssign for Interval<$t> {
+			fn mul_assign(&mut self, rhs: Self) {
+				*self = *self * rhs
+			}
+		}
+
+		impl Div for Interval<$t> {
+			type Output = Self;
+
+			fn div(self, rhs: Self) -> Self::Output {
+				if rhs.contains_zero() {
+					panic!("Cannot divide by the interval {}, which contains zero", rhs)
+				}
+				Self::enclosing([
+					Self::quotient(self.lower, rhs.lower),
+					Self::quotient(self.lower, rhs.upper),
+					Self::quotient(self.upper, rhs.lower),
+					Self::quotient(self.upper, rhs.upper)
+				])
+			}
+		}
+
+		impl DivAssign for Interval<$t> {
+			fn div_assign(&mut self, rhs: Self) {
+				*self = *self / rhs
+			}
+		}
+
+		impl Semiring for Interval<$t> {
+			fn one() -> Self {
+				Self::point(1.0)
+			}
+
+			fn zero() -> Self {
+				Self::point(0.0)
+			}
+
+			fn is_zero(&self) -> bool {
+				self.lower == 0.0 && self.upper == 0.0
+			}
+		}
+
+		/// Powers are taken of the endpoints, so that an even power of an interval containing
+		/// zero is as tight as it can be rather than the product of n copies of the interval
+		impl Ring for Interval<$t> {
+			fn power(&self, n: i64) -> Self {
+				if n < 0 {
+					return self.inverse().power(-n)
+				}
+				if n == 0 {
+					return Self::one()
+				}
+
+				let lower = Self::point_power(self.lower, n);
+				let upper = Self::point_power(self.upper, n);
+				if n % 2 == 1 || self.lower >= 0.0 {
+					Interval { lower: lower.lower, upper: upper.upper }
+				} else if self.upper <= 0.0 {
+					Interval { lower: upper.lower, upper: lower.upper }
+				} else {
+					Interval { lower: 0.0, upper: lower.upper.max(upper.upper) }
+				}
+			}
+		}
+
+		impl Field for Interval<$t> {
+			fn inverse(&self) -> Self {
+				Self::one() / *self
+			}
+
+			fn try_inverse(&self) -> Option<Self> {
+				(!self.contains_zero()).then(|| self.inverse())
+			}
+		}
+	)*};
+}
+
+impl_interval!(f64, f32);
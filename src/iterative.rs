@@ -0,0 +1,174 @@
+//
+// Iterative solvers for large linear systems over the reals, which only need to apply the matrix
+// to vectors, so they work on sparse and structured operators without factoring them
+//
+
+use crate::linear_operator::LinearOperator;
+
+// MARK: Vector Helpers
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+	a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+fn norm(a: &[f64]) -> f64 {
+	dot(a, a).sqrt()
+}
+
+/// y <- y + c x
+fn add_scaled(y: &mut [f64], c: f64, x: &[f64]) {
+	for (yi, xi) in y.iter_mut().zip(x.iter()) {
+		*yi += c * xi;
+	}
+}
+
+/// b - Ax
+fn residual<A: LinearOperator<f64>>(a: &A, b: &[f64], x: &[f64]) -> Vec<f64> {
+	b.iter().zip(a.apply(x)).map(|(bi, ai)| bi - ai).collect()
+}
+
+fn check_system<A: LinearOperator<f64>>(a: &A, b: &[f64]) {
+	if a.input_dimension() != a.output_dimension() {
+		panic!("Iterative solvers need a square operator, got {}x{}", a.output_dimension(), a.input_dimension())
+	}
+
+	if b.len() != a.output_dimension() {
+		panic!("The system has {} rows but {} right hand sides", a.output_dimension(), b.len())
+	}
+}
+
+// MARK: Conjugate Gradient
+
+/// The solution of Ax = b for a symmetric positive definite A, by the conjugate gradient method,
+/// or None if the residual b - Ax has not fallen to `tolerance` times |b| within `max_iterations`
+/// steps.
+///
+/// Each step moves along a search direction A-conjugate to all the earlier ones, so in exact
+/// arithmetic it finishes within n steps, and in practice much sooner when A is well conditioned.
+pub fn conjugate_gradient<A: LinearOperator<f64>>(a: &A, b: &[f64], tolerance: f64, max_iterations: usize) -> Option<Vec<f64>> {
+	check_system(a, b);
+
+	let target = tolerance * norm(b);
+	let mut x = vec![0.0; b.len()];
+	let mut r = b.to_vec();
+	let mut p = r.clone();
+	let mut r_squared = dot(&r, &r);
+
+	for _ in 0..max_iterations {
+		if r_squared.sqrt() <= target {
+			return Some(x)
+		}
+
+		let ap = a.apply(&p);
+		let curvature = dot(&p, &ap);
+		if curvature <= 0.0 {
+			// A is not positive definite along p
+			return None
+		}
+
+		let alpha = r_squared / curvature;
+		add_scaled(&mut x, alpha, &p);
+		add_scaled(&mut r, -alpha, &ap);
+
+		let next_squared = dot(&r, &r);
+		let beta = next_squared / r_squared;
+		r_squared = next_squared;
+		for (pi, ri) in p.iter_mut().zip(r.iter()) {
+			*pi = ri + beta * *pi;
+		}
+	}
+
+	(r_squared.sqrt() <= target).then_some(x)
+}
+
+// MARK: GMRES
+
+/// The solution of Ax = b for any invertible A, by GMRES restarted every `restart` steps, or None
+/// if the residual b - Ax has not fallen to `tolerance` times |b| within `max_iterations` steps in
+/// total.
+///
+/// Each cycle builds an orthonormal basis of the Krylov space spanned by r, Ar, A^2 r, ... by the
+/// Arnoldi process, and picks the x in it with the smallest residual, which is a small least
+/// squares problem kept in triangular form by Givens rotations. Restarting bounds the memory at
+/// `restart` vectors, at the cost of slower convergence.
+pub fn gmres<A: LinearOperator<f64>>(a: &A, b: &[f64], tolerance: f64, max_iterations: usize, restart: usize) -> Option<Vec<f64>> {
+	check_system(a, b);
+
+	if restart == 0 {
+		panic!("GMRES needs to run at least one step between restarts")
+	}
+
+	let target = tolerance * norm(b);
+	let mut x = vec![0.0; b.len()];
+	let mut iterations = 0;
+
+	loop {
+		let r = residual(a, b, &x);
+		let beta = norm(&r);
+		if beta <= target {
+			return Some(x)
+		}
+
+		if iterations >= max_iterations {
+			return None
+		}
+
+		// basis[j] are the Arnoldi vectors, h[j] the jth column of the Hessenberg matrix after
+		// rotation, and g the rotated right hand side beta e_1
+		let mut basis = vec![r.iter().map(|ri| ri / beta).collect::<Vec<f64>>()];
+		let mut h: Vec<Vec<f64>> = Vec::new();
+		let mut rotations: Vec<(f64, f64)> = Vec::new();
+		let mut g = vec![beta];
+
+		while h.len() < restart && iterations < max_iterations {
+			iterations += 1;
+			let j = h.len();
+
+			// Orthogonalize A v_j against the basis by modified Gram-Schmidt
+			let mut w = a.apply(&basis[j]);
+			let mut column = Vec::with_capacity(j + 2);
+			for v in basis.iter() {
+				let c = dot(&w, v);
+				add_scaled(&mut w, -c, v);
+				column.push(c);
+			}
+			let w_norm = norm(&w);
+			column.push(w_norm);
+
+			for (i, &(c, s)) in rotations.iter().enumerate() {
+				let (u, v) = (column[i], column[i + 1]);
+				column[i] = c * u + s * v;
+				column[i + 1] = -s * u + c * v;
+			}
+
+			// The rotation zeroing the new subdiagonal entry
+			let (u, v) = (column[j], column[j + 1]);
+			let radius = u.hypot(v);
+			let (c, s) = if radius == 0.0 { (1.0, 0.0) } else { (u / radius, v / radius) };
+			column[j] = radius;
+			column[j + 1] = 0.0;
+			rotations.push((c, s));
+			g.push(-s * g[j]);
+			g[j] *= c;
+
+			h.push(column);
+
+			// |g_(j+1)| is the residual of the best x in the space so far
+			if g[j + 1].abs() <= target || w_norm == 0.0 {
+				break
+			}
+			basis.push(w.iter().map(|wi| wi / w_norm).collect());
+		}
+
+		// Solve the triangular system H y = g, then move x by the combination of the basis
+		let k = h.len();
+		let mut y = vec![0.0; k];
+		for i in (0..k).rev() {
+			let sum: f64 = (i + 1..k).map(|l| h[l][i] * y[l]).sum();
+			y[i] = (g[i] - sum) / h[i][i];
+		}
+		for (yi, v) in y.iter().zip(basis.iter()) {
+			add_scaled(&mut x, *yi, v);
+		}
+	}
+}
@@ -0,0 +1,214 @@
+//
+// Lattice basis reduction: size reduction and the LLL algorithm on integer bases, with exact
+// rational Gram-Schmidt coefficients
+//
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+
+// MARK: Gram-Schmidt Data
+
+/// The Gram-Schmidt orthogonalization b*_0, ..., b*_(n-1) of the rows b_i of an integer basis,
+/// kept as the coefficients mu_ij = <b_i, b*_j> / <b*_j, b*_j> and the squared lengths
+/// B_i = <b*_i, b*_i>, which are all that reduction needs
+struct GramSchmidt {
+	basis: Vec<Vec<BigInt>>,
+	orthogonal: Vec<Vec<BigRational>>,
+	mu: Vec<Vec<BigRational>>,
+	lengths: Vec<BigRational>
+}
+
+fn dot(a: &[BigRational], b: &[BigRational]) -> BigRational {
+	a.iter().zip(b.iter()).fold(BigRational::zero(), |sum, (x, y)| sum + x * y)
+}
+
+fn rational(v: &[BigInt]) -> Vec<BigRational> {
+	v.iter().map(|x| BigRational::from_integer(x.clone())).collect()
+}
+
+impl GramSchmidt {
+
+	/// The orthogonalization of the rows of a basis, which must be linearly independent
+	fn new(basis: &Matrix<BigInt>) -> GramSchmidt {
+		let n = basis.rows();
+		let mut data = GramSchmidt {
+			basis: (0..n).map(|i| basis.row(i).to_vec()).collect(),
+			orthogonal: Vec::with_capacity(n),
+			mu: vec![vec![BigRational::zero(); n]; n],
+			lengths: Vec::with_capacity(n)
+		};
+
+		for k in 0..n {
+			let b = rational(&data.basis[k]);
+			let mut star = b.clone();
+			for j in 0..k {
+				let mu = dot(&b, &data.orthogonal[j]) / &data.lengths[j];
+				for (s, t) in star.iter_mut().zip(data.orthogonal[j].iter()) {
+					*s -= &mu * t;
+				}
+				data.mu[k][j] = mu;
+			}
+
+			let length = dot(&star, &star);
+			if length.is_zero() {
+				panic!("The rows of a lattice basis must be linearly independent")
+			}
+			data.orthogonal.push(star);
+			data.lengths.push(length);
+		}
+
+		data
+	}
+
+	/// Makes |mu_kl| at most 1/2 by subtracting the nearest integer multiple of b_l from b_k, which
+	/// leaves the orthogonalization unchanged
+	fn reduce(&mut self, k: usize, l: usize) {
+		let half = BigRational::new(BigInt::from(1), BigInt::from(2));
+		if self.mu[k][l].abs() <= half {
+			return
+		}
+
+		let q = self.mu[k][l].round();
+		let q_integer = q.to_integer();
+		let b_l = self.basis[l].clone();
+		for (x, y) in self.basis[k].iter_mut().zip(b_l.iter()) {
+			*x -= &q_integer * y;
+		}
+
+		self.mu[k][l] -= &q;
+		for i in 0..l {
+			let delta = &q * &self.mu[l][i];
+			self.mu[k][i] -= delta;
+		}
+	}
+
+	/// Swaps b_(k-1) and b_k, updating the orthogonalization of every vector from k - 1 on
+	fn swap(&mut self, k: usize) {
+		let n = self.basis.len();
+		self.basis.swap(k - 1, k);
+		for j in 0..k - 1 {
+			let t = self.mu[k][j].clone();
+			self.mu[k][j] = std::mem::replace(&mut self.mu[k - 1][j], t);
+		}
+
+		let mu = self.mu[k][k - 1].clone();
+		let length = &self.lengths[k] + &mu * &mu * &self.lengths[k - 1];
+		self.mu[k][k - 1] = &mu * &self.lengths[k - 1] / &length;
+
+		// The new b*_(k-1) is the old b*_k + mu b*_(k-1), and the new b*_k is what is left of the
+		// old b*_(k-1) after projecting away from it
+		let old = self.orthogonal[k - 1].clone();
+		let new_previous: Vec<BigRational> = self.orthogonal[k].iter().zip(old.iter()).map(|(s, t)| s + &mu * t).collect();
+		let ratio = &self.lengths[k] / &length;
+		let new_current: Vec<BigRational> = self.orthogonal[k].iter().zip(old.iter())
+			.map(|(s, t)| -(&self.mu[k][k - 1] * s) + &ratio * t).collect();
+		self.orthogonal[k - 1] = new_previous;
+		self.orthogonal[k] = new_current;
+
+		self.lengths[k] = &self.lengths[k - 1] * &ratio;
+		self.lengths[k - 1] = length;
+
+		for i in k + 1..n {
+			let t = self.mu[i][k].clone();
+			self.mu[i][k] = &self.mu[i][k - 1] - &mu * &t;
+			self.mu[i][k - 1] = t + &self.mu[k][k - 1] * &self.mu[i][k];
+		}
+	}
+
+	fn into_matrix(self, cols: usize) -> Matrix<BigInt> {
+		let rows = self.basis.len();
+		Matrix::new(rows, cols, self.basis.into_iter().flatten().collect())
+	}
+
+}
+
+// MARK: Reduction
+
+/// The size reduction of a basis given by the rows of a matrix, which must be linearly
+/// independent: the basis of the same lattice with every Gram-Schmidt coefficient |mu_ij| at most
+/// 1/2, found by subtracting integer multiples of earlier rows from later ones
+pub fn size_reduce(basis: &Matrix<BigInt>) -> Matrix<BigInt> {
+	let mut data = GramSchmidt::new(basis);
+	for k in 1..basis.rows() {
+		for l in (0..k).rev() {
+			data.reduce(k, l);
+		}
+	}
+
+	data.into_matrix(basis.cols())
+}
+
+/// The LLL reduction of a basis given by the rows of a matrix, which must be linearly independent,
+/// with the parameter delta in (1/4, 1].
+///
+/// The result is a size reduced basis of the same lattice satisfying the Lovász condition
+/// B_k >= (delta - mu_(k,k-1)^2) B_(k-1) for each k. Its first row is then at most
+/// (4 / (4 delta - 1))^((n-1)/2) times as long as the shortest nonzero vector of the lattice.
+pub fn lll_with_delta(basis: &Matrix<BigInt>, delta: &BigRational) -> Matrix<BigInt> {
+	lll_with_trace(basis, delta, &mut |_| { })
+}
+
+/// One step of the LLL algorithm at row k, after size reducing b_k against b_(k-1): whether the
+/// Lovász condition failed so that rows k - 1 and k were swapped, and the current basis rows with
+/// the squared lengths B_i of their Gram-Schmidt vectors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LllStep<'a> {
+	pub k: usize,
+	pub swapped: bool,
+	pub basis: &'a [Vec<BigInt>],
+	pub squared_lengths: &'a [BigRational]
+}
+
+/// The LLL reduction as in `lll_with_delta`, calling trace after every step, so that the swaps
+/// and the shrinking Gram-Schmidt lengths can be watched
+pub fn lll_with_trace(basis: &Matrix<BigInt>, delta: &BigRational, trace: &mut impl FnMut(LllStep<'_>)) -> Matrix<BigInt> {
+	let quarter = BigRational::new(BigInt::from(1), BigInt::from(4));
+	if *delta <= quarter || *delta > BigRational::one() {
+		panic!("The LLL parameter must be in (1/4, 1], got {}", delta)
+	}
+
+	let n = basis.rows();
+	let mut data = GramSchmidt::new(basis);
+	let mut k = 1;
+
+	while k < n {
+		data.reduce(k, k - 1);
+
+		let mu = &data.mu[k][k - 1];
+		let swapped = data.lengths[k] < (delta - mu * mu) * &data.lengths[k - 1];
+		if swapped {
+			data.swap(k);
+		} else {
+			for l in (0..k - 1).rev() {
+				data.reduce(k, l);
+			}
+		}
+
+		trace(LllStep { k, swapped, basis: &data.basis, squared_lengths: &data.lengths });
+		k = if swapped { (k - 1).max(1) } else { k + 1 };
+	}
+
+	data.into_matrix(basis.cols())
+}
+
+/// The LLL reduction with the usual parameter delta = 3/4
+pub fn lll(basis: &Matrix<BigInt>) -> Matrix<BigInt> {
+	lll_with_delta(basis, &BigRational::new(BigInt::from(3), BigInt::from(4)))
+}
+
+/// Whether or not the rows of a matrix form an LLL reduced basis for the parameter delta
+pub fn is_lll_reduced(basis: &Matrix<BigInt>, delta: &BigRational) -> bool {
+	let data = GramSchmidt::new(basis);
+	let half = BigRational::new(BigInt::from(1), BigInt::from(2));
+
+	let size_reduced = (0..basis.rows()).all(|k| (0..k).all(|j| data.mu[k][j].abs() <= half));
+	let lovasz = (1..basis.rows()).all(|k| {
+		let mu = &data.mu[k][k - 1];
+		data.lengths[k] >= (delta - mu * mu) * &data.lengths[k - 1]
+	});
+
+	size_reduced && lovasz
+}
@@ -1,4 +1,178 @@
 #![feature(min_specialization)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Without the default `std` feature, the crate is `no_std` and needs only `alloc`. The trait
+//! hierarchy in `algebra`, `ZM` and the other types in `std_impls`, `Polynomial`, and `Matrix`
+//! are available then, and everything else needs `std`.
+
+extern crate alloc;
+
+/// Asserts an invariant in debug builds with the `debug-checks` feature, and does nothing
+/// otherwise, so that checks on hot paths cost nothing unless asked for
+macro_rules! debug_check {
+	($($arg:tt)*) => {
+		#[cfg(feature = "debug-checks")]
+		debug_assert!($($arg)*);
+	};
+}
 
 pub mod algebra;
-pub mod std_impls;
\ No newline at end of file
+pub mod error;
+pub mod random;
+pub mod finite_set;
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(feature = "std")]
+pub mod parse;
+pub mod std_impls;
+#[cfg(feature = "std")]
+pub mod checked;
+#[cfg(feature = "std")]
+pub mod counted;
+#[cfg(feature = "std")]
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod approx;
+#[cfg(feature = "std")]
+pub mod complex;
+#[cfg(feature = "std")]
+pub mod dual;
+#[cfg(feature = "std")]
+pub mod quaternion;
+#[cfg(feature = "std")]
+pub mod quadratic_integer;
+#[cfg(feature = "std")]
+pub mod quadratic_form;
+#[cfg(feature = "std")]
+pub mod big_zm;
+#[cfg(feature = "std")]
+pub mod padic;
+#[cfg(feature = "std")]
+pub mod discrete_log;
+#[cfg(feature = "std")]
+pub mod order_finding;
+#[cfg(feature = "std")]
+pub mod crt;
+#[cfg(feature = "std")]
+pub mod continued_fraction;
+#[cfg(feature = "std")]
+pub mod sieve;
+#[cfg(feature = "std")]
+pub mod arithmetic_function;
+#[cfg(feature = "std")]
+pub mod dirichlet;
+#[cfg(feature = "std")]
+pub mod extension_field;
+#[cfg(feature = "std")]
+pub mod binary_field;
+#[cfg(feature = "std")]
+pub mod elliptic_curve;
+#[cfg(feature = "std")]
+pub mod curve_models;
+#[cfg(feature = "std")]
+pub mod pairing;
+#[cfg(feature = "std")]
+pub mod permutation;
+#[cfg(feature = "std")]
+pub mod partition;
+#[cfg(feature = "std")]
+pub mod homomorphism;
+#[cfg(feature = "std")]
+pub mod subgroup;
+#[cfg(feature = "std")]
+pub mod group_action;
+#[cfg(feature = "std")]
+pub mod small_groups;
+#[cfg(feature = "std")]
+pub mod cayley;
+#[cfg(feature = "std")]
+pub mod finite_algebra;
+pub mod polynomial;
+#[cfg(feature = "std")]
+pub mod sparse_polynomial;
+#[cfg(feature = "std")]
+pub mod gf2_polynomial;
+#[cfg(feature = "std")]
+pub mod integer_polynomial;
+#[cfg(feature = "std")]
+pub mod sturm;
+pub mod ntt;
+#[cfg(feature = "std")]
+pub mod cyclotomic_ring;
+#[cfg(feature = "std")]
+pub mod sampling;
+#[cfg(feature = "std")]
+pub mod power_series;
+#[cfg(feature = "std")]
+pub mod multivariate;
+#[cfg(feature = "std")]
+pub mod groebner;
+#[cfg(feature = "std")]
+pub mod ideal;
+#[cfg(feature = "std")]
+pub mod polynomial_system;
+#[cfg(feature = "std")]
+pub mod symmetric;
+#[cfg(feature = "std")]
+pub mod macaulay;
+#[cfg(feature = "std")]
+pub mod commitment;
+#[cfg(feature = "std")]
+pub mod erasure;
+#[cfg(feature = "std")]
+pub mod reed_solomon;
+#[cfg(feature = "std")]
+pub mod secret_sharing;
+#[cfg(feature = "std")]
+pub mod semiring;
+#[cfg(feature = "std")]
+pub mod vector;
+#[cfg(feature = "std")]
+pub mod orthogonal;
+pub mod matrix;
+#[cfg(feature = "std")]
+pub mod matrix_io;
+#[cfg(feature = "std")]
+pub mod linear_operator;
+#[cfg(feature = "std")]
+pub mod iterative;
+#[cfg(feature = "std")]
+pub mod polynomial_matrix;
+#[cfg(feature = "std")]
+pub mod toeplitz;
+#[cfg(feature = "std")]
+pub mod sparse_vector;
+#[cfg(feature = "std")]
+pub mod sparse_matrix;
+#[cfg(feature = "std")]
+pub mod normal_form;
+#[cfg(feature = "std")]
+pub mod abelian_group;
+#[cfg(feature = "std")]
+pub mod pivoting;
+#[cfg(feature = "std")]
+pub mod multimodular;
+#[cfg(feature = "std")]
+pub mod matrix_group;
+#[cfg(feature = "std")]
+pub mod nonnegative;
+#[cfg(feature = "std")]
+pub mod projective;
+#[cfg(feature = "std")]
+pub mod product;
+#[cfg(feature = "num-traits")]
+pub mod num_interop;
+#[cfg(feature = "bigint")]
+pub mod bigint;
+#[cfg(feature = "bigint")]
+pub mod eigen;
+#[cfg(feature = "bigint")]
+pub mod lattice;
+#[cfg(feature = "ct")]
+pub mod ct;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "rayon")]
+pub mod parallel;
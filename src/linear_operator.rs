@@ -0,0 +1,155 @@
+//
+// Linear maps R^n -> R^m given only by how they act on vectors, so that iterative methods can be
+// written once for dense, sparse, and structured matrices alike
+//
+
+use crate::algebra::*;
+use crate::complex::Complex;
+use crate::matrix::Matrix;
+
+// MARK: Linear Operators
+
+/// A linear map R^n -> R^m, acting on vectors stored as slices
+pub trait LinearOperator<R: Semiring> {
+
+	/// The dimension n of the vectors this acts on
+	fn input_dimension(&self) -> usize;
+
+	/// The dimension m of the vectors this produces
+	fn output_dimension(&self) -> usize;
+
+	/// The image of x, which must have length `input_dimension`
+	fn apply(&self, x: &[R]) -> Vec<R>;
+
+	/// The composite self ∘ inner, which applies inner first
+	fn compose<B: LinearOperator<R>>(self, inner: B) -> Composition<Self, B> where Self: Sized {
+		if self.input_dimension() != inner.output_dimension() {
+			panic!("Cannot compose an operator on R^{} with one into R^{}", self.input_dimension(), inner.output_dimension())
+		}
+
+		Composition { outer: self, inner }
+	}
+
+	/// The matrix of this operator in the standard bases, whose columns are the images of the
+	/// standard basis vectors
+	fn to_matrix(&self) -> Matrix<R> {
+		let n = self.input_dimension();
+		let columns: Vec<Vec<R>> = (0..n).map(|j| {
+			let mut e = vec![R::zero(); n];
+			e[j] = R::one();
+			self.apply(&e)
+		}).collect();
+
+		Matrix::from_fn(self.output_dimension(), n, |i, j| columns[j][i].clone())
+	}
+
+}
+
+/// A linear operator whose adjoint can be applied, the map A* with <Ax, y> = <x, A*y>. For real
+/// and finite field entries this is the transpose, and for complex ones the conjugate transpose.
+pub trait AdjointOperator<R: Semiring>: LinearOperator<R> {
+
+	/// The image of y, which must have length `output_dimension`, under the adjoint
+	fn apply_adjoint(&self, y: &[R]) -> Vec<R>;
+
+}
+
+impl<R: Semiring, A: LinearOperator<R> + ?Sized> LinearOperator<R> for &A {
+	fn input_dimension(&self) -> usize {
+		(**self).input_dimension()
+	}
+
+	fn output_dimension(&self) -> usize {
+		(**self).output_dimension()
+	}
+
+	fn apply(&self, x: &[R]) -> Vec<R> {
+		(**self).apply(x)
+	}
+}
+
+impl<R: Semiring, A: AdjointOperator<R> + ?Sized> AdjointOperator<R> for &A {
+	fn apply_adjoint(&self, y: &[R]) -> Vec<R> {
+		(**self).apply_adjoint(y)
+	}
+}
+
+// MARK: Composition
+
+/// The composite of two linear operators, made by `LinearOperator::compose`
+pub struct Composition<A, B> {
+	outer: A,
+	inner: B
+}
+
+impl<R: Semiring, A: LinearOperator<R>, B: LinearOperator<R>> LinearOperator<R> for Composition<A, B> {
+	fn input_dimension(&self) -> usize {
+		self.inner.input_dimension()
+	}
+
+	fn output_dimension(&self) -> usize {
+		self.outer.output_dimension()
+	}
+
+	fn apply(&self, x: &[R]) -> Vec<R> {
+		self.outer.apply(&self.inner.apply(x))
+	}
+}
+
+impl<R: Semiring, A: AdjointOperator<R>, B: AdjointOperator<R>> AdjointOperator<R> for Composition<A, B> {
+	/// (AB)* = B* A*
+	fn apply_adjoint(&self, y: &[R]) -> Vec<R> {
+		self.inner.apply_adjoint(&self.outer.apply_adjoint(y))
+	}
+}
+
+// MARK: Matrices
+
+impl<R: Semiring> LinearOperator<R> for Matrix<R> {
+	fn input_dimension(&self) -> usize {
+		self.cols()
+	}
+
+	fn output_dimension(&self) -> usize {
+		self.rows()
+	}
+
+	fn apply(&self, x: &[R]) -> Vec<R> {
+		if x.len() != self.cols() {
+			panic!("Cannot apply a {}x{} matrix to a vector of length {}", self.rows(), self.cols(), x.len())
+		}
+
+		(0..self.rows()).map(|i| {
+			self.row(i).iter().zip(x.iter()).fold(R::zero(), |sum, (a, b)| sum.add_ref(&a.mul_ref(b)))
+		}).collect()
+	}
+
+	fn to_matrix(&self) -> Matrix<R> {
+		self.clone()
+	}
+}
+
+impl<R: Semiring> AdjointOperator<R> for Matrix<R> {
+	/// The transpose applied to y
+	default fn apply_adjoint(&self, y: &[R]) -> Vec<R> {
+		if y.len() != self.rows() {
+			panic!("Cannot apply the adjoint of a {}x{} matrix to a vector of length {}", self.rows(), self.cols(), y.len())
+		}
+
+		let mut image = vec![R::zero(); self.cols()];
+		for (row, b) in (0..self.rows()).map(|i| self.row(i)).zip(y.iter()) {
+			for (sum, a) in image.iter_mut().zip(row.iter()) {
+				sum.add_assign_ref(&a.mul_ref(b));
+			}
+		}
+		image
+	}
+}
+
+impl<F: Field> AdjointOperator<Complex<F>> for Matrix<Complex<F>> {
+	/// The conjugate transpose applied to y
+	fn apply_adjoint(&self, y: &[Complex<F>]) -> Vec<Complex<F>> {
+		let conjugated: Vec<Complex<F>> = y.iter().map(Complex::conj).collect();
+		self.transpose().apply(&conjugated).iter().map(Complex::conj).collect()
+	}
+}
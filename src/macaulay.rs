@@ -0,0 +1,97 @@
+//
+// Macaulay matrices of polynomial systems, and the XL algorithm, which solves a system by row
+// reducing one large Macaulay matrix instead of computing a Gröbner basis
+//
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::multivariate::{monomials_up_to_degree, Lex, Monomial, MonomialOrder, MultiPoly};
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+// MARK: Macaulay Matrices
+
+/// The Macaulay matrix of a system at degree d, together with the monomials labelling its columns.
+///
+/// Each row holds the coefficients of m f for an equation f and a monomial m with
+/// deg m + deg f <= d, and the columns are every monomial of degree at most d, from the largest
+/// down in the given order. Its row space is the part of the ideal the products up to degree d
+/// reach, and row reducing it puts leading monomials in the leftmost columns.
+pub fn macaulay_matrix<F: Field, const N: usize, O: MonomialOrder>(system: &[MultiPoly<F, N>], d: u32, order: &O) -> (Matrix<F>, Vec<Monomial<N>>) {
+	let mut columns = monomials_up_to_degree::<N, O>(d, order);
+	columns.reverse();
+
+	let rows: Vec<MultiPoly<F, N>> = system.iter().filter(|f| !f.is_zero() && f.total_degree() <= d).flat_map(|f| {
+		monomials_up_to_degree::<N, O>(d - f.total_degree(), order).into_iter().map(move |m| f.mul_term(&m, &F::one()))
+	}).collect();
+
+	let matrix = Matrix::from_fn(rows.len(), columns.len(), |i, j| rows[i].coefficient(&columns[j]));
+	(matrix, columns)
+}
+
+/// A basis of the polynomials of degree at most d reached by the Macaulay matrix of the system,
+/// read off its reduced row echelon form, so each has a different leading monomial in the given
+/// order and is monic.
+///
+/// For d large enough this contains a Gröbner basis of the ideal, and with a lexicographic order
+/// the polynomials in later variables only come last.
+pub fn xl_eliminate<F: Field, const N: usize, O: MonomialOrder>(system: &[MultiPoly<F, N>], d: u32, order: &O) -> Vec<MultiPoly<F, N>> {
+	let (matrix, columns) = macaulay_matrix(system, d, order);
+	let echelon = matrix.reduced_row_echelon_form();
+
+	(0..echelon.rows()).map(|i| {
+		MultiPoly::new(echelon.row(i).iter().zip(columns.iter()).map(|(c, m)| (*m, c.clone())).collect())
+	}).take_while(|f| !f.is_zero()).collect()
+}
+
+// MARK: XL Solving
+
+/// The monic nonzero polynomial of least degree in x_k alone that `xl_eliminate` finds at degree
+/// d with the lexicographic order, for a system in which x_(k+1), ..., x_(N-1) no longer appear.
+/// A nonzero constant means the system has no solutions.
+fn univariate<F: Field, const N: usize>(system: &[MultiPoly<F, N>], k: usize, d: u32) -> Option<Polynomial<F>> {
+	xl_eliminate(system, d, &Lex).into_iter()
+		.filter(|f| f.terms().all(|(m, _)| (0..N).all(|i| i == k || m.exponents[i] == 0)))
+		.min_by_key(MultiPoly::total_degree)
+		.map(|f| {
+			let degree = f.total_degree() as usize;
+			Polynomial::new((0..=degree).map(|e| {
+				let mut exponents = [0; N];
+				exponents[k] = e as u32;
+				f.coefficient(&Monomial::new(exponents))
+			}).collect())
+		})
+}
+
+/// The system with x_k replaced by the value v
+fn substitute<F: Field, const N: usize>(system: &[MultiPoly<F, N>], k: usize, v: &F) -> Vec<MultiPoly<F, N>> {
+	system.iter().map(|f| f.specialize(k, v)).collect()
+}
+
+/// Every solution in ZM<Q>^N of a system with finitely many solutions over the algebraic closure,
+/// by the XL algorithm, or None if some variable is not pinned down by a Macaulay matrix of degree
+/// at most `max_degree`. Q must be prime.
+///
+/// For x_(N-1), then each earlier variable in turn, this raises the degree d until row reducing
+/// the lexicographic Macaulay matrix leaves a polynomial in that variable alone, then substitutes
+/// each of its roots found by `Polynomial::roots_mod_p` and carries on with the smaller system.
+pub fn solve_xl_zm<const Q: i64, const N: usize>(system: &[MultiPoly<ZM<Q>, N>], max_degree: u32) -> Option<Vec<[ZM<Q>; N]>> {
+	let mut branches = vec![(system.to_vec(), [ZM::zero(); N])];
+
+	for k in (0..N).rev() {
+		let mut next = Vec::new();
+		for (equations, point) in branches {
+			let start = equations.iter().map(MultiPoly::total_degree).max().unwrap_or(0);
+			let p = (start..=max_degree).find_map(|d| univariate(&equations, k, d))?;
+
+			for v in p.roots_mod_p() {
+				let mut point = point;
+				point[k] = v;
+				next.push((substitute(&equations, k, &v), point));
+			}
+		}
+		branches = next;
+	}
+
+	Some(branches.into_iter().map(|(_, point)| point).filter(|point| system.iter().all(|f| f.evaluate(point).is_zero())).collect())
+}
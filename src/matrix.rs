@@ -0,0 +1,768 @@
+//
+// Matrices with entries in a semiring
+//
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+// MARK: Matrix
+
+/// A rows x cols matrix with entries in a semiring R, stored row by row
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Matrix<R: Semiring> {
+	rows: usize,
+	cols: usize,
+	entries: Vec<R>
+}
+
+impl<R: Semiring> Matrix<R> {
+
+	/// Creates a matrix from its entries, listed row by row
+	pub fn new(rows: usize, cols: usize, entries: Vec<R>) -> Matrix<R> {
+		if entries.len() != rows * cols {
+			panic!("A {}x{} matrix needs {} entries, got {}", rows, cols, rows * cols, entries.len())
+		}
+
+		Matrix { rows, cols, entries }
+	}
+
+	/// Creates a matrix from a list of rows, which must all have the same length
+	pub fn from_rows(rows: Vec<Vec<R>>) -> Matrix<R> {
+		let cols = rows.first().map_or(0, |row| row.len());
+		if rows.iter().any(|row| row.len() != cols) {
+			panic!("Every row of a matrix must have the same length")
+		}
+
+		Matrix { rows: rows.len(), cols, entries: rows.into_iter().flatten().collect() }
+	}
+
+	/// Creates a matrix whose (i, j) entry is f(i, j)
+	pub fn from_fn<F: Fn(usize, usize) -> R>(rows: usize, cols: usize, f: F) -> Matrix<R> {
+		Matrix { rows, cols, entries: (0..rows * cols).map(|k| f(k / cols, k % cols)).collect() }
+	}
+
+	/// The rows x cols matrix of zeros
+	pub fn zero(rows: usize, cols: usize) -> Matrix<R> {
+		Matrix { rows, cols, entries: vec![R::zero(); rows * cols] }
+	}
+
+	/// The n x n identity matrix
+	pub fn identity(n: usize) -> Matrix<R> {
+		Matrix::from_fn(n, n, |i, j| if i == j { R::one() } else { R::zero() })
+	}
+
+	/// The number of rows
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	/// The number of columns
+	pub fn cols(&self) -> usize {
+		self.cols
+	}
+
+	/// Whether or not this matrix has as many rows as columns
+	pub fn is_square(&self) -> bool {
+		self.rows == self.cols
+	}
+
+	/// The entries of row i
+	pub fn row(&self, i: usize) -> &[R] {
+		&self.entries[i * self.cols..(i + 1) * self.cols]
+	}
+
+	/// The entries of column j
+	pub fn column(&self, j: usize) -> Vec<R> {
+		(0..self.rows).map(|i| self[(i, j)].clone()).collect()
+	}
+
+	/// Every entry, row by row
+	pub fn entries(&self) -> &[R] {
+		&self.entries
+	}
+
+	/// Swaps rows i and j in place
+	pub fn swap_rows(&mut self, i: usize, j: usize) {
+		if i == j {
+			return
+		}
+
+		for k in 0..self.cols {
+			self.entries.swap(i * self.cols + k, j * self.cols + k);
+		}
+	}
+
+	/// Swaps columns i and j in place
+	pub fn swap_columns(&mut self, i: usize, j: usize) {
+		if i == j {
+			return
+		}
+
+		for k in 0..self.rows {
+			self.entries.swap(k * self.cols + i, k * self.cols + j);
+		}
+	}
+
+	/// The transpose of this matrix
+	pub fn transpose(&self) -> Matrix<R> {
+		Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+	}
+
+	/// Multiplies every entry by c on the right
+	pub fn scale(&self, c: &R) -> Matrix<R> {
+		Matrix { rows: self.rows, cols: self.cols, entries: self.entries.iter().map(|a| a.mul_ref(c)).collect() }
+	}
+
+	/// The sum of two borrowed matrices of the same shape
+	pub fn add_ref(&self, rhs: &Matrix<R>) -> Matrix<R> {
+		if self.rows != rhs.rows || self.cols != rhs.cols {
+			panic!("Cannot add a {}x{} matrix to a {}x{} matrix", rhs.rows, rhs.cols, self.rows, self.cols)
+		}
+
+		Matrix { rows: self.rows, cols: self.cols, entries: self.entries.iter().zip(rhs.entries.iter()).map(|(a, b)| a.add_ref(b)).collect() }
+	}
+
+	/// The product of two borrowed matrices, using only the semiring operations
+	pub fn mul_ref(&self, rhs: &Matrix<R>) -> Matrix<R> {
+		if self.cols != rhs.rows {
+			panic!("Cannot multiply a {}x{} matrix by a {}x{} matrix", self.rows, self.cols, rhs.rows, rhs.cols)
+		}
+
+		let mut entries = vec![R::zero(); self.rows * rhs.cols];
+		for i in 0..self.rows {
+			for k in 0..self.cols {
+				let a = &self[(i, k)];
+				for j in 0..rhs.cols {
+					entries[i * rhs.cols + j].add_assign_ref(&a.mul_ref(&rhs[(k, j)]));
+				}
+			}
+		}
+
+		Matrix { rows: self.rows, cols: rhs.cols, entries }
+	}
+
+	/// This square matrix to the nth power, by repeated squaring
+	pub fn power(&self, n: u64) -> Matrix<R> {
+		if !self.is_square() {
+			panic!("Only square matrices have powers")
+		}
+
+		let mut power = Matrix::identity(self.rows);
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power = power.mul_ref(&base);
+			}
+			e >>= 1;
+			if e > 0 {
+				base = base.mul_ref(&base);
+			}
+		}
+
+		power
+	}
+
+	/// The companion matrix of the recurrence a_n = c_1 a_(n-1) + ... + c_d a_(n-d), which takes
+	/// (a_(n-1), ..., a_(n-d)) to (a_n, ..., a_(n-d+1))
+	pub fn companion(coeffs: &[R]) -> Matrix<R> {
+		let d = coeffs.len();
+		Matrix::from_fn(d, d, |i, j| {
+			if i == 0 {
+				coeffs[j].clone()
+			} else if i == j + 1 {
+				R::one()
+			} else {
+				R::zero()
+			}
+		})
+	}
+
+	/// The Toeplitz matrix with the given first column and first row, whose entry (i, j) depends
+	/// only on i - j. The two must start with the same entry.
+	pub fn toeplitz(column: &[R], row: &[R]) -> Matrix<R> {
+		if column.first() != row.first() {
+			panic!("The first column and row of a Toeplitz matrix must start with the same entry")
+		}
+
+		Matrix::from_fn(column.len(), row.len(), |i, j| {
+			if i >= j { column[i - j].clone() } else { row[j - i].clone() }
+		})
+	}
+
+	/// The Hankel matrix with entry (i, j) equal to h_(i+j), for h of length rows + cols - 1
+	pub fn hankel(h: &[R], rows: usize, cols: usize) -> Matrix<R> {
+		if h.len() + 1 != rows + cols {
+			panic!("A {} by {} Hankel matrix needs {} entries, got {}", rows, cols, rows + cols - 1, h.len())
+		}
+
+		Matrix::from_fn(rows, cols, |i, j| h[i + j].clone())
+	}
+
+}
+
+impl<R: Ring> Matrix<R> {
+
+	/// The Sylvester matrix of f and g, of degrees m and n: n shifted rows of the coefficients of f
+	/// followed by m of g, from the leading coefficient down. Its determinant is the resultant
+	/// `polynomial::resultant(f, g)`.
+	pub fn sylvester(f: &Polynomial<R>, g: &Polynomial<R>) -> Matrix<R> {
+		if f.is_zero() || g.is_zero() {
+			panic!("The zero polynomial has no Sylvester matrix")
+		}
+
+		Matrix::sylvester_of_coefficients(f.coefficients(), g.coefficients())
+	}
+
+	/// The Sylvester matrix of the polynomials with the given coefficients, from the constant term
+	/// up, taking their degrees to be f.len() - 1 and g.len() - 1 even if the leading ones are zero
+	pub(crate) fn sylvester_of_coefficients(f: &[R], g: &[R]) -> Matrix<R> {
+		let (m, n) = (f.len() - 1, g.len() - 1);
+
+		Matrix::from_fn(m + n, m + n, |i, j| {
+			let (p, shift) = if i < n { (f, i) } else { (g, i - n) };
+			let degree = p.len() - 1;
+			if j < shift || j > shift + degree { R::zero() } else { p[degree + shift - j].clone() }
+		})
+	}
+
+}
+
+impl<R: CommutativeRing> Matrix<R> {
+
+	/// The Bezout matrix of f and g, the symmetric n x n matrix B with
+	/// (f(x) g(y) - f(y) g(x)) / (x - y) = sum of B_ij x^i y^j, where n is the larger degree.
+	///
+	/// When f has degree n and leading coefficient a, and g has degree m <= n, its determinant is
+	/// (-1)^(n(n-1)/2) a^(n-m) times the resultant, so it is a smaller matrix detecting common roots.
+	pub fn bezout(f: &Polynomial<R>, g: &Polynomial<R>) -> Matrix<R> {
+		let n = f.degree().max(g.degree());
+		let mut b: Matrix<R> = Matrix::zero(n, n);
+
+		// For a > b, x^a y^b - x^b y^a = (x - y) times the sum of x^(b+t) y^(a-1-t) over t < a - b
+		for i in 0..=n {
+			for j in 0..i {
+				let c = f.coefficient(i).mul_ref(&g.coefficient(j)) - f.coefficient(j).mul_ref(&g.coefficient(i));
+				if c.is_zero() {
+					continue
+				}
+				for t in 0..i - j {
+					b[(j + t, i - 1 - t)].add_assign_ref(&c);
+				}
+			}
+		}
+
+		b
+	}
+
+	/// The determinant of this square matrix without any division, so over any commutative ring,
+	/// by the Samuelson-Berkowitz algorithm in O(n^4) ring operations
+	pub fn berkowitz_determinant(&self) -> R {
+		if !self.is_square() {
+			panic!("Only square matrices have determinants")
+		}
+
+		// p holds the coefficients of det(tI - B) from t^k down, for B the trailing k x k block
+		let n = self.rows;
+		let mut p = vec![R::one()];
+
+		for k in (0..n).rev() {
+			// B = [[a, r], [c, M]] for the block M after row and column k, and the new polynomial is
+			// T p, where T is lower triangular Toeplitz with first column 1, -a, -rc, -rMc, -rM^2c, ...
+			let mut column = vec![R::one(), -self[(k, k)].clone()];
+			let mut v: Vec<R> = (k + 1..n).map(|i| self[(i, k)].clone()).collect();
+			for _ in 0..n - k - 1 {
+				let rv = (k + 1..n).fold(R::zero(), |sum, j| sum.add_ref(&self[(k, j)].mul_ref(&v[j - k - 1])));
+				column.push(-rv);
+				v = (k + 1..n).map(|i| (k + 1..n).fold(R::zero(), |sum, j| sum.add_ref(&self[(i, j)].mul_ref(&v[j - k - 1])))).collect();
+			}
+
+			p = (0..=p.len()).map(|i| (0..p.len().min(i + 1)).fold(R::zero(), |sum, j| sum.add_ref(&column[i - j].mul_ref(&p[j])))).collect();
+		}
+
+		// det(-A) is the constant term
+		if n.is_multiple_of(2) { p[n].clone() } else { -p[n].clone() }
+	}
+
+}
+
+impl<R: IntegralDomain> Matrix<R> {
+
+	/// The determinant of this square matrix, computed exactly with no fractions.
+	///
+	/// This is Gaussian elimination with a unit pivot from each column, which always exists over a
+	/// field unless the determinant is zero. When a column has nonzero entries but no unit, as for
+	/// the integers or for ZM<Q> with Q composite, it starts over with `berkowitz_determinant`,
+	/// which divides by nothing. For integer matrices `bareiss_determinant` is usually faster.
+	pub fn determinant(&self) -> R {
+		if !self.is_square() {
+			panic!("Only square matrices have determinants")
+		}
+
+		let n = self.rows;
+		let mut m = self.clone();
+		let mut determinant = R::one();
+
+		for col in 0..n {
+			if (col..n).all(|i| m[(i, col)].is_zero()) {
+				return R::zero()
+			}
+
+			let Some((pivot, pivot_inverse)) = (col..n).find_map(|i| R::one().divide_exact(&m[(i, col)]).map(|inverse| (i, inverse))) else {
+				return self.berkowitz_determinant()
+			};
+
+			if pivot != col {
+				m.swap_rows(pivot, col);
+				determinant = -determinant;
+			}
+			determinant.mul_assign_ref(&m[(col, col)]);
+
+			for i in col + 1..n {
+				let factor = m[(i, col)].mul_ref(&pivot_inverse);
+				for j in col..n {
+					let delta = factor.mul_ref(&m[(col, j)]);
+					m[(i, j)].sub_assign_ref(&delta);
+				}
+			}
+		}
+
+		determinant
+	}
+
+	/// The determinant of this square matrix by Bareiss fraction-free elimination, in O(n^3)
+	/// operations on entries no larger than minors of the matrix.
+	///
+	/// After step k each entry of the trailing block is a (k + 1) x (k + 1) minor, found from the
+	/// previous ones by a 2 x 2 determinant divided exactly by the previous pivot.
+	pub fn bareiss_determinant(&self) -> R {
+		if !self.is_square() {
+			panic!("Only square matrices have determinants")
+		}
+
+		let n = self.rows;
+		let mut m = self.clone();
+		let mut previous = R::one();
+		let mut negated = false;
+
+		for k in 0..n {
+			let Some(pivot) = (k..n).find(|&i| !m[(i, k)].is_zero()) else {
+				return R::zero()
+			};
+
+			if pivot != k {
+				m.swap_rows(pivot, k);
+				negated = !negated;
+			}
+
+			for i in k + 1..n {
+				for j in k + 1..n {
+					let mut minor = m[(k, k)].mul_ref(&m[(i, j)]);
+					minor.sub_assign_ref(&m[(i, k)].mul_ref(&m[(k, j)]));
+					m[(i, j)] = minor.divide_exact(&previous).expect("Bareiss divisions are exact");
+				}
+				m[(i, k)] = R::zero();
+			}
+
+			previous = m[(k, k)].clone();
+		}
+
+		if negated { -previous } else { previous }
+	}
+
+}
+
+impl<F: Field> Matrix<F> {
+
+	/// The inverse of this square matrix, if it is invertible, by Gauss-Jordan elimination
+	pub fn inverse(&self) -> Option<Matrix<F>> {
+		if !self.is_square() {
+			panic!("Only square matrices have inverses")
+		}
+
+		let n = self.rows;
+		let mut m = self.clone();
+		let mut inverse: Matrix<F> = Matrix::identity(n);
+
+		for col in 0..n {
+			let pivot = (col..n).find(|&i| !m[(i, col)].is_zero())?;
+			m.swap_rows(pivot, col);
+			inverse.swap_rows(pivot, col);
+
+			let pivot_inverse = m[(col, col)].inverse();
+			for j in 0..n {
+				m[(col, j)].mul_assign_ref(&pivot_inverse);
+				inverse[(col, j)].mul_assign_ref(&pivot_inverse);
+			}
+
+			for i in (0..n).filter(|&i| i != col) {
+				let factor = m[(i, col)].clone();
+				if factor.is_zero() {
+					continue
+				}
+				for j in 0..n {
+					let delta = factor.mul_ref(&m[(col, j)]);
+					m[(i, j)].sub_assign_ref(&delta);
+					let delta = factor.mul_ref(&inverse[(col, j)]);
+					inverse[(i, j)].sub_assign_ref(&delta);
+				}
+			}
+		}
+
+		Some(inverse)
+	}
+
+	/// The reduced row echelon form, by Gauss-Jordan elimination: each nonzero row starts with a
+	/// 1 further right than the row above, which is the only nonzero entry in its column, and the
+	/// zero rows come last
+	pub fn reduced_row_echelon_form(&self) -> Matrix<F> {
+		let mut m = self.clone();
+		let mut row = 0;
+
+		for col in 0..self.cols {
+			let Some(pivot) = (row..self.rows).find(|&i| !m[(i, col)].is_zero()) else {
+				continue
+			};
+			m.swap_rows(pivot, row);
+
+			let pivot_inverse = m[(row, col)].inverse();
+			for j in col..self.cols {
+				m[(row, j)].mul_assign_ref(&pivot_inverse);
+			}
+
+			for i in (0..self.rows).filter(|&i| i != row) {
+				let factor = m[(i, col)].clone();
+				if factor.is_zero() {
+					continue
+				}
+				for j in col..self.cols {
+					let delta = factor.mul_ref(&m[(row, j)]);
+					m[(i, j)].sub_assign_ref(&delta);
+				}
+			}
+
+			row += 1;
+			if row == self.rows {
+				break
+			}
+		}
+
+		m
+	}
+
+	/// The rank, the dimension of the row space, which is the number of nonzero rows of the
+	/// reduced row echelon form
+	pub fn rank(&self) -> usize {
+		let echelon = self.reduced_row_echelon_form();
+		(0..self.rows).take_while(|&i| echelon.row(i).iter().any(|x| !x.is_zero())).count()
+	}
+
+	/// The columns holding the leading 1 of each nonzero row of a matrix in reduced row echelon
+	/// form, in order
+	pub(crate) fn pivot_columns(echelon: &Matrix<F>) -> Vec<usize> {
+		(0..echelon.rows).filter_map(|i| echelon.row(i).iter().position(|x| !x.is_zero())).collect()
+	}
+
+	/// A basis of the null space, the vectors x with Ax = 0, with one basis vector for each column
+	/// of the reduced row echelon form without a pivot. That free variable is 1 in it, the other
+	/// free variables are 0, and the pivot variables are whatever makes Ax = 0.
+	pub fn null_space(&self) -> Vec<Vec<F>> {
+		let echelon = self.reduced_row_echelon_form();
+		let pivots = Self::pivot_columns(&echelon);
+
+		(0..self.cols).filter(|j| !pivots.contains(j)).map(|free| {
+			let mut x = vec![F::zero(); self.cols];
+			x[free] = F::one();
+			for (i, &p) in pivots.iter().enumerate() {
+				x[p] = -echelon[(i, free)].clone();
+			}
+			x
+		}).collect()
+	}
+
+	/// A basis of the column space, the image of A, made of the columns of A where the reduced row
+	/// echelon form has pivots
+	pub fn column_space(&self) -> Vec<Vec<F>> {
+		Self::pivot_columns(&self.reduced_row_echelon_form()).into_iter().map(|j| self.column(j)).collect()
+	}
+
+	/// A basis of the row space, the nonzero rows of the reduced row echelon form
+	pub fn row_space(&self) -> Vec<Vec<F>> {
+		let echelon = self.reduced_row_echelon_form();
+		(0..self.rows).map(|i| echelon.row(i).to_vec()).take_while(|row| row.iter().any(|x| !x.is_zero())).collect()
+	}
+
+	/// An upper Hessenberg matrix similar to this square one, with zeros below the subdiagonal, by
+	/// Gaussian elimination applied as a similarity transformation
+	fn hessenberg(&self) -> Matrix<F> {
+		let n = self.rows;
+		let mut h = self.clone();
+
+		for m in 0..n.saturating_sub(2) {
+			let Some(pivot) = (m + 1..n).find(|&i| !h[(i, m)].is_zero()) else {
+				continue
+			};
+			h.swap_rows(pivot, m + 1);
+			h.swap_columns(pivot, m + 1);
+
+			// Subtracting u times row m + 1 from row j is undone on the right by adding u times
+			// column j to column m + 1
+			let pivot_inverse = h[(m + 1, m)].inverse();
+			for j in m + 2..n {
+				let u = h[(j, m)].mul_ref(&pivot_inverse);
+				if u.is_zero() {
+					continue
+				}
+				for k in 0..n {
+					let delta = u.mul_ref(&h[(m + 1, k)]);
+					h[(j, k)].sub_assign_ref(&delta);
+				}
+				for k in 0..n {
+					let delta = u.mul_ref(&h[(k, j)]);
+					h[(k, m + 1)].add_assign_ref(&delta);
+				}
+			}
+		}
+
+		h
+	}
+
+	/// The characteristic polynomial det(xI - A) of this square matrix, in O(n^3) field
+	/// operations over any field, by reducing to Hessenberg form H and expanding det(xI - H) along
+	/// its last column one leading block at a time
+	pub fn char_poly(&self) -> Polynomial<F> {
+		if !self.is_square() {
+			panic!("Only square matrices have characteristic polynomials")
+		}
+
+		let h = self.hessenberg();
+
+		// p[m] is the characteristic polynomial of the leading m x m block of H
+		let mut p = vec![Polynomial::one()];
+		for m in 0..self.rows {
+			let mut next = (Polynomial::x() - Polynomial::constant(h[(m, m)].clone())) * p[m].clone();
+
+			// The product of the subdiagonal entries from row i + 1 down to row m
+			let mut subdiagonal = F::one();
+			for i in (0..m).rev() {
+				subdiagonal.mul_assign_ref(&h[(i + 1, i)]);
+				next -= p[i].scale(&h[(i, m)].mul_ref(&subdiagonal));
+			}
+			p.push(next);
+		}
+
+		p.pop().unwrap()
+	}
+
+	/// The minimal polynomial of v under this square matrix, the monic p of least degree with
+	/// p(A) v = 0, from the first linear dependence among v, Av, A^2 v, ...
+	fn local_min_poly(&self, v: Vec<F>) -> Polynomial<F> {
+		let n = self.rows;
+		let mut krylov = vec![v];
+		for k in 0..n {
+			let next = (0..n).map(|i| (0..n).fold(F::zero(), |sum, j| sum.add_ref(&self[(i, j)].mul_ref(&krylov[k][j])))).collect();
+			krylov.push(next);
+		}
+
+		// In the reduced row echelon form of the matrix with columns A^i v, the first column without a
+		// pivot is A^k v, and holds its coordinates in terms of v, ..., A^(k-1) v
+		let echelon = Matrix::from_fn(n, n + 1, |i, j| krylov[j][i].clone()).reduced_row_echelon_form();
+		let k = (0..=n).find(|&k| k == n || echelon[(k, k)].is_zero()).unwrap();
+
+		let mut coefficients: Vec<F> = (0..k).map(|i| -echelon[(i, k)].clone()).collect();
+		coefficients.push(F::one());
+		Polynomial::new(coefficients)
+	}
+
+	/// The minimal polynomial of this square matrix, the monic p of least degree with p(A) = 0,
+	/// which divides the characteristic polynomial and has the same roots.
+	///
+	/// This is the least common multiple of the minimal polynomials of A on each basis vector e_i.
+	pub fn min_poly(&self) -> Polynomial<F> {
+		if !self.is_square() {
+			panic!("Only square matrices have minimal polynomials")
+		}
+
+		(0..self.rows).fold(Polynomial::one(), |lcm, i| {
+			let basis = (0..self.rows).map(|j| if i == j { F::one() } else { F::zero() }).collect();
+			let p = self.local_min_poly(basis);
+			let g = gcd(&lcm, &p);
+			(lcm * p).quotient_and_remainder(&g).0.monic()
+		})
+	}
+
+}
+
+impl<const Q: i64> Matrix<ZM<Q>> {
+
+	/// The eigenvalues of this square matrix in ZM<Q>, with their algebraic multiplicities, in
+	/// increasing order, from the linear factors of the characteristic polynomial. Q must be prime.
+	#[cfg(feature = "std")]
+	pub fn eigenvalues(&self) -> Vec<(ZM<Q>, usize)> {
+		let mut eigenvalues: Vec<(ZM<Q>, usize)> = self.char_poly().factor().into_iter()
+			.filter(|(f, _)| f.degree() == 1)
+			.map(|(f, multiplicity)| (-f.coefficient(0), multiplicity))
+			.collect();
+		eigenvalues.sort_by_key(|(x, _)| x.val);
+		eigenvalues
+	}
+
+}
+
+impl<R: Semiring> Index<(usize, usize)> for Matrix<R> {
+	type Output = R;
+
+	fn index(&self, (i, j): (usize, usize)) -> &R {
+		debug_check!(i < self.rows && j < self.cols, "Index ({}, {}) is out of range for a {}x{} matrix", i, j, self.rows, self.cols);
+		&self.entries[i * self.cols + j]
+	}
+}
+
+impl<R: Semiring> IndexMut<(usize, usize)> for Matrix<R> {
+	fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut R {
+		debug_check!(i < self.rows && j < self.cols, "Index ({}, {}) is out of range for a {}x{} matrix", i, j, self.rows, self.cols);
+		&mut self.entries[i * self.cols + j]
+	}
+}
+
+// MARK: Arithmetic
+
+impl<R: Semiring> Add for Matrix<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.add_ref(&rhs)
+	}
+}
+
+impl<R: Semiring> AddAssign for Matrix<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		if self.rows != rhs.rows || self.cols != rhs.cols {
+			panic!("Cannot add a {}x{} matrix to a {}x{} matrix", rhs.rows, rhs.cols, self.rows, self.cols)
+		}
+
+		for (a, b) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+			a.add_assign_ref(b);
+		}
+	}
+}
+
+impl<R: Semiring> Mul for Matrix<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Semiring> MulAssign for Matrix<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Semiring> Mul<R> for Matrix<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: R) -> Self::Output {
+		self.scale(&rhs)
+	}
+}
+
+impl<R: Semiring> MulAssign<R> for Matrix<R> {
+	fn mul_assign(&mut self, rhs: R) {
+		*self = self.scale(&rhs)
+	}
+}
+
+/// Scalar multiplication on the left, c * A, for the commutative scalar types the orphan rules
+/// allow, since a blanket impl over every semiring would not be allowed
+macro_rules! impl_left_scale_matrix {
+	($($t:ty),*) => {$(
+		impl Mul<Matrix<$t>> for $t {
+			type Output = Matrix<$t>;
+
+			fn mul(self, rhs: Matrix<$t>) -> Self::Output {
+				rhs.scale(&self)
+			}
+		}
+	)*};
+}
+
+impl_left_scale_matrix!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, f32, f64);
+
+impl<const Q: i64> Mul<Matrix<ZM<Q>>> for ZM<Q> {
+	type Output = Matrix<ZM<Q>>;
+
+	fn mul(self, rhs: Matrix<ZM<Q>>) -> Self::Output {
+		rhs.scale(&self)
+	}
+}
+
+impl<R: Ring> Neg for Matrix<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Matrix { rows: self.rows, cols: self.cols, entries: self.entries.into_iter().map(|a| -a).collect() }
+	}
+}
+
+impl<R: Ring> Sub for Matrix<R> {
+	type Output = Self;
+
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self -= rhs;
+		self
+	}
+}
+
+impl<R: Ring> SubAssign for Matrix<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		if self.rows != rhs.rows || self.cols != rhs.cols {
+			panic!("Cannot subtract a {}x{} matrix from a {}x{} matrix", rhs.rows, rhs.cols, self.rows, self.cols)
+		}
+
+		for (a, b) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+			a.sub_assign_ref(b);
+		}
+	}
+}
+
+impl<R: Ring> Module<R> for Matrix<R> {
+	fn scale(&self, r: &R) -> Self {
+		Matrix::scale(self, r)
+	}
+}
+
+// MARK: Linear Recurrences
+
+/// The kth term of the recurrence a_n = c_1 a_(n-1) + c_2 a_(n-2) + ... + c_d a_(n-d), where
+/// `coeffs` is c_1, ..., c_d and `init` is a_0, ..., a_(d-1), by powering the companion matrix.
+///
+/// This only needs a semiring, so it also solves recurrences like a_n = max(a_(n-1) + 2, a_(n-2) + 5)
+/// over `MaxPlus`. Over a commutative ring, `polynomial::kth_term_of_recurrence` is faster.
+pub fn solve_linear_recurrence<R: Semiring>(coeffs: &[R], init: &[R], k: u64) -> R {
+	let d = coeffs.len();
+	if init.len() != d {
+		panic!("A recurrence of order {} needs {} initial terms, got {}", d, d, init.len())
+	}
+
+	if d == 0 {
+		return R::zero()
+	}
+
+	if k < d as u64 {
+		return init[k as usize].clone()
+	}
+
+	// C^(k - d + 1) takes (a_(d-1), ..., a_0) to (a_k, ..., a_(k-d+1))
+	let state = Matrix::new(d, 1, init.iter().rev().cloned().collect());
+	let advanced = Matrix::companion(coeffs).power(k - d as u64 + 1).mul_ref(&state);
+	advanced[(0, 0)].clone()
+}
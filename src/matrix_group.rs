@@ -0,0 +1,245 @@
+//
+// Groups of invertible matrices over the finite fields Z/(Q)
+//
+
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::random::RandomElement;
+use crate::std_impls::ZM;
+
+// MARK: Order Formulas
+
+/// The order of GL(n, q), which is (q^n - 1)(q^n - q)...(q^n - q^(n - 1))
+pub fn general_linear_order(n: u32, q: u128) -> u128 {
+	(0..n).map(|i| q.pow(n) - q.pow(i)).product()
+}
+
+/// The order of SL(n, q), which is |GL(n, q)| / (q - 1)
+pub fn special_linear_order(n: u32, q: u128) -> u128 {
+	general_linear_order(n, q) / (q - 1)
+}
+
+/// Every N x N matrix over Z/(Q), in order of their entries read as base Q numbers
+fn all_matrices<const N: usize, const Q: i64>() -> impl Iterator<Item = Matrix<ZM<Q>>> {
+	let count = (Q as usize).pow((N * N) as u32);
+	(0..count).map(|mut k| {
+		let entries = (0..N * N).map(|_| {
+			let digit = k % Q as usize;
+			k /= Q as usize;
+			ZM::from(digit as i64)
+		}).collect();
+		Matrix::new(N, N, entries)
+	})
+}
+
+// MARK: General Linear Groups
+
+/// An element of GL(N, Q), an invertible N x N matrix over Z/(Q) for a prime Q
+#[derive(Clone, Debug, PartialEq)]
+pub struct GL<const N: usize, const Q: i64> {
+	matrix: Matrix<ZM<Q>>
+}
+
+impl<const N: usize, const Q: i64> GL<N, Q> {
+
+	/// Whether or not m is an element of GL(N, Q), meaning it is N x N with nonzero determinant
+	pub fn contains(m: &Matrix<ZM<Q>>) -> bool {
+		m.rows() == N && m.cols() == N && !m.determinant().is_zero()
+	}
+
+	/// The element given by the matrix m, or `None` if m is not an invertible N x N matrix
+	pub fn new(matrix: Matrix<ZM<Q>>) -> Option<GL<N, Q>> {
+		if Self::contains(&matrix) { Some(GL { matrix }) } else { None }
+	}
+
+	/// The underlying matrix
+	pub fn matrix(&self) -> &Matrix<ZM<Q>> {
+		&self.matrix
+	}
+
+	/// The determinant of this element, which is never zero
+	pub fn determinant(&self) -> ZM<Q> {
+		self.matrix.determinant()
+	}
+
+}
+
+impl<const N: usize, const Q: i64> RandomElement for GL<N, Q> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		// A random matrix is invertible with probability at least 1/4, so this finishes quickly
+		loop {
+			let matrix = Matrix::new(N, N, (0..N * N).map(|_| ZM::random(rng)).collect());
+			if let Some(g) = Self::new(matrix) {
+				return g
+			}
+		}
+	}
+}
+
+impl<const N: usize, const Q: i64> Distribution<GL<N, Q>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GL<N, Q> {
+		GL::random(rng)
+	}
+}
+
+impl<const N: usize, const Q: i64> Mul for GL<N, Q> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		GL { matrix: self.matrix.mul_ref(&rhs.matrix) }
+	}
+}
+
+impl<const N: usize, const Q: i64> MulAssign for GL<N, Q> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.matrix = self.matrix.mul_ref(&rhs.matrix)
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const N: usize, const Q: i64> Div for GL<N, Q> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const N: usize, const Q: i64> DivAssign for GL<N, Q> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<const N: usize, const Q: i64> Monoid for GL<N, Q> {
+	fn identity() -> Self {
+		GL { matrix: Matrix::identity(N) }
+	}
+}
+
+impl<const N: usize, const Q: i64> Group for GL<N, Q> {
+	fn inverse(&self) -> Self {
+		GL { matrix: self.matrix.inverse().unwrap() }
+	}
+}
+
+impl<const N: usize, const Q: i64> FiniteGroup for GL<N, Q> {
+
+	fn order() -> usize {
+		general_linear_order(N as u32, Q as u128) as usize
+	}
+
+	/// Every element, found by checking all Q^(N^2) matrices, so only practical when that is small
+	fn elements() -> impl Iterator<Item = Self> {
+		all_matrices::<N, Q>().filter_map(Self::new)
+	}
+}
+
+// MARK: Special Linear Groups
+
+/// An element of SL(N, Q), an N x N matrix over Z/(Q) with determinant 1, for a prime Q
+#[derive(Clone, Debug, PartialEq)]
+pub struct SL<const N: usize, const Q: i64> {
+	matrix: Matrix<ZM<Q>>
+}
+
+impl<const N: usize, const Q: i64> SL<N, Q> {
+
+	/// Whether or not m is an element of SL(N, Q), meaning it is N x N with determinant 1
+	pub fn contains(m: &Matrix<ZM<Q>>) -> bool {
+		m.rows() == N && m.cols() == N && m.determinant() == ZM::one()
+	}
+
+	/// The element given by the matrix m, or `None` if m is not an N x N matrix of determinant 1
+	pub fn new(matrix: Matrix<ZM<Q>>) -> Option<SL<N, Q>> {
+		if Self::contains(&matrix) { Some(SL { matrix }) } else { None }
+	}
+
+	/// The underlying matrix
+	pub fn matrix(&self) -> &Matrix<ZM<Q>> {
+		&self.matrix
+	}
+
+	/// This element as an element of GL(N, Q)
+	pub fn to_general_linear(&self) -> GL<N, Q> {
+		GL { matrix: self.matrix.clone() }
+	}
+
+}
+
+impl<const N: usize, const Q: i64> RandomElement for SL<N, Q> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		// Scaling the first row by 1/det is a (Q - 1)-to-one map from GL onto SL
+		let g = GL::<N, Q>::random(rng);
+		let scale = g.determinant().inverse();
+		let matrix = Matrix::from_fn(N, N, |i, j| {
+			if i == 0 { g.matrix[(i, j)] * scale } else { g.matrix[(i, j)] }
+		});
+
+		SL { matrix }
+	}
+}
+
+impl<const N: usize, const Q: i64> Distribution<SL<N, Q>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SL<N, Q> {
+		SL::random(rng)
+	}
+}
+
+impl<const N: usize, const Q: i64> Mul for SL<N, Q> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		SL { matrix: self.matrix.mul_ref(&rhs.matrix) }
+	}
+}
+
+impl<const N: usize, const Q: i64> MulAssign for SL<N, Q> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.matrix = self.matrix.mul_ref(&rhs.matrix)
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const N: usize, const Q: i64> Div for SL<N, Q> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const N: usize, const Q: i64> DivAssign for SL<N, Q> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<const N: usize, const Q: i64> Monoid for SL<N, Q> {
+	fn identity() -> Self {
+		SL { matrix: Matrix::identity(N) }
+	}
+}
+
+impl<const N: usize, const Q: i64> Group for SL<N, Q> {
+	fn inverse(&self) -> Self {
+		SL { matrix: self.matrix.inverse().unwrap() }
+	}
+}
+
+impl<const N: usize, const Q: i64> FiniteGroup for SL<N, Q> {
+
+	fn order() -> usize {
+		special_linear_order(N as u32, Q as u128) as usize
+	}
+
+	/// Every element, found by checking all Q^(N^2) matrices, so only practical when that is small
+	fn elements() -> impl Iterator<Item = Self> {
+		all_matrices::<N, Q>().filter_map(Self::new)
+	}
+}
@@ -0,0 +1,371 @@
+//
+// Reading and writing matrices as text, in the Matrix Market exchange format and as CSV, with
+// exact parsing of integer and rational entries and reduction of them into prime fields
+//
+
+use std::fmt::Display;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::sparse_matrix::SparseMatrix;
+use crate::std_impls::ZM;
+
+// MARK: Errors
+
+/// A line of matrix text which could not be read
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseMatrixError {
+	/// The line the problem is on, counting from 1, or 0 if the text ended too early
+	pub line: usize,
+
+	/// What was wrong with it
+	pub reason: String
+}
+
+impl Display for ParseMatrixError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.line == 0 {
+			write!(f, "Unexpected end of matrix text: {}", self.reason)
+		} else {
+			write!(f, "Line {}: {}", self.line, self.reason)
+		}
+	}
+}
+
+impl std::error::Error for ParseMatrixError { }
+
+fn error<T>(line: usize, reason: impl Into<String>) -> Result<T, ParseMatrixError> {
+	Err(ParseMatrixError { line, reason: reason.into() })
+}
+
+// MARK: Entries
+
+/// A ring element which can be written as text and read back from it, as a matrix entry
+pub trait TextEntry: Semiring {
+
+	/// The Matrix Market field this type is written as, "integer" or "real"
+	const MATRIX_MARKET_FIELD: &'static str;
+
+	/// The element written as text, which `parse_entry` reads back
+	fn to_entry(&self) -> String;
+
+	/// The element written as the given text, or None if it is not valid
+	fn parse_entry(s: &str) -> Option<Self>;
+
+}
+
+macro_rules! impl_text_entry {
+	($field:literal: $($t:ty),*) => {$(
+		impl TextEntry for $t {
+			const MATRIX_MARKET_FIELD: &'static str = $field;
+
+			fn to_entry(&self) -> String {
+				self.to_string()
+			}
+
+			fn parse_entry(s: &str) -> Option<Self> {
+				s.parse().ok()
+			}
+		}
+	)*};
+}
+
+impl_text_entry!("integer": i8, i16, i32, i64, i128);
+impl_text_entry!("real": f32, f64);
+
+/// The residue modulo m of an integer of any size written in decimal, reduced a digit at a time
+fn parse_residue(s: &str, m: i64) -> Option<i64> {
+	let (negative, digits) = match s.strip_prefix('-') {
+		Some(digits) => (true, digits),
+		None => (false, s.strip_prefix('+').unwrap_or(s))
+	};
+	if digits.is_empty() || !digits.bytes().all(|d| d.is_ascii_digit()) {
+		return None
+	}
+
+	let residue = digits.bytes().fold(0i128, |r, d| (r * 10 + (d - b'0') as i128) % m as i128) as i64;
+	Some(if negative { (m - residue) % m } else { residue })
+}
+
+/// Integers of any size are reduced modulo Q, and so are fractions a/b with b invertible modulo
+/// Q, so integer or rational data can be read straight into a prime field
+impl<const Q: i64> TextEntry for ZM<Q> {
+	const MATRIX_MARKET_FIELD: &'static str = "integer";
+
+	fn to_entry(&self) -> String {
+		self.val.rem_euclid(Q).to_string()
+	}
+
+	fn parse_entry(s: &str) -> Option<Self> {
+		match s.split_once('/') {
+			Some((a, b)) => {
+				let (a, b) = (ZM::from_int(parse_residue(a, Q)?), ZM::from_int(parse_residue(b, Q)?));
+				b.try_inverse().map(|b| a * b)
+			},
+			None => parse_residue(s, Q).map(ZM::from_int)
+		}
+	}
+}
+
+#[cfg(feature = "bigint")]
+impl TextEntry for num_bigint::BigInt {
+	const MATRIX_MARKET_FIELD: &'static str = "integer";
+
+	fn to_entry(&self) -> String {
+		self.to_string()
+	}
+
+	fn parse_entry(s: &str) -> Option<Self> {
+		s.parse().ok()
+	}
+}
+
+/// Written as an exact decimal when the denominator divides a power of ten, and as a/b otherwise,
+/// which other tools may not read. Reads fractions a/b, and decimals like 2.5 or 1.5e-3 exactly,
+/// so real valued data can be read without rounding.
+#[cfg(feature = "bigint")]
+impl TextEntry for num_rational::BigRational {
+	const MATRIX_MARKET_FIELD: &'static str = "real";
+
+	fn to_entry(&self) -> String {
+		use num_bigint::BigInt;
+
+		// The denominator divides 10^k exactly when it is 2^a 5^b, with k = max(a, b)
+		let mut rest = self.denom().clone();
+		let twos = rest.trailing_zeros().unwrap_or(0);
+		rest >>= twos;
+		let mut fives = 0;
+		while (&rest % 5u32).is_zero() {
+			rest /= 5u32;
+			fives += 1;
+		}
+		if rest != BigInt::from(1) {
+			return self.to_string()
+		}
+		if twos == 0 && fives == 0 {
+			return self.numer().to_string()
+		}
+
+		let k = twos.max(fives) as usize;
+		let scaled = self.numer() * BigInt::from(10).pow(k as u32) / self.denom();
+		let digits = format!("{:0>width$}", scaled.magnitude().to_string(), width = k + 1);
+		let sign = if self.numer() < &BigInt::from(0) { "-" } else { "" };
+		format!("{}{}.{}", sign, &digits[..digits.len() - k], &digits[digits.len() - k..])
+	}
+
+	fn parse_entry(s: &str) -> Option<Self> {
+		use num_bigint::BigInt;
+		use num_rational::BigRational;
+
+		if let Some((a, b)) = s.split_once('/') {
+			let (a, b): (BigInt, BigInt) = (a.parse().ok()?, b.parse().ok()?);
+			return (!b.is_zero()).then(|| BigRational::new(a, b))
+		}
+
+		let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+			Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+			None => (s, 0)
+		};
+		let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		if !fraction.bytes().all(|d| d.is_ascii_digit()) || (whole.trim_start_matches(['+', '-']).is_empty() && fraction.is_empty()) {
+			return None
+		}
+
+		let digits: BigInt = format!("{}{}", whole, fraction).parse().ok()?;
+		let shift = exponent - fraction.len() as i32;
+		let power = BigRational::from_integer(BigInt::from(10).pow(shift.unsigned_abs()));
+		let value = BigRational::from_integer(digits);
+		Some(if shift >= 0 { value * power } else { value / power })
+	}
+}
+
+// MARK: Matrix Market
+
+/// The symmetry of a Matrix Market file, where the symmetric kinds store only the lower triangle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Symmetry {
+	General,
+	Symmetric,
+	SkewSymmetric
+}
+
+/// The dimensions and (row, column, value) entries of a matrix, counting from zero
+type Entries<R> = (usize, usize, Vec<(usize, usize, R)>);
+
+/// The matrix in Matrix Market text, as its dimensions and entries, with the entries a symmetric
+/// file leaves out filled in
+fn read_matrix_market<R: Ring + TextEntry>(text: &str) -> Result<Entries<R>, ParseMatrixError> {
+	let mut lines = text.lines().enumerate().map(|(i, line)| (i + 1, line.trim()));
+
+	let Some((_, header)) = lines.next() else {
+		return error(0, "missing header")
+	};
+	let words: Vec<String> = header.split_whitespace().map(|w| w.to_lowercase()).collect();
+	if words.len() != 5 || words[0] != "%%matrixmarket" || words[1] != "matrix" {
+		return error(1, "expected a header %%MatrixMarket matrix <format> <field> <symmetry>")
+	}
+
+	let coordinate = match words[2].as_str() {
+		"coordinate" => true,
+		"array" => false,
+		format => return error(1, format!("unknown format {}", format))
+	};
+	let pattern = match words[3].as_str() {
+		"pattern" if coordinate => true,
+		"integer" | "real" => false,
+		field => return error(1, format!("unsupported field {}", field))
+	};
+	let symmetry = match words[4].as_str() {
+		"general" => Symmetry::General,
+		"symmetric" => Symmetry::Symmetric,
+		"skew-symmetric" => Symmetry::SkewSymmetric,
+		symmetry => return error(1, format!("unsupported symmetry {}", symmetry))
+	};
+
+	let mut lines = lines.filter(|(_, line)| !line.is_empty() && !line.starts_with('%'));
+	let Some((size_line, size)) = lines.next() else {
+		return error(0, "missing dimensions")
+	};
+	let size: Vec<usize> = size.split_whitespace().map(|n| n.parse().ok()).collect::<Option<_>>()
+		.ok_or(ParseMatrixError { line: size_line, reason: "invalid dimensions".to_string() })?;
+	let (rows, cols, count) = match (coordinate, size.as_slice()) {
+		(true, &[rows, cols, count]) => (rows, cols, count),
+		(false, &[rows, cols]) => (rows, cols, rows * cols),
+		_ => return error(size_line, "wrong number of dimensions")
+	};
+	if symmetry != Symmetry::General && rows != cols {
+		return error(size_line, "a symmetric matrix must be square")
+	}
+
+	let mut entries = Vec::with_capacity(count);
+	let mut positions = (0..cols).flat_map(|j| (0..rows).map(move |i| (i, j)))
+		.filter(|&(i, j)| match symmetry {
+			Symmetry::General => true,
+			Symmetry::Symmetric => i >= j,
+			Symmetry::SkewSymmetric => i > j
+		});
+
+	for _ in 0..count {
+		let Some((line, entry)) = lines.next() else {
+			return error(0, format!("expected {} entries", count))
+		};
+		let words: Vec<&str> = entry.split_whitespace().collect();
+
+		let (i, j, value) = if coordinate {
+			let expected = if pattern { 2 } else { 3 };
+			if words.len() != expected {
+				return error(line, format!("expected {} values", expected))
+			}
+			let index = |w: &str, bound: usize| w.parse::<usize>().ok().filter(|&k| 1 <= k && k <= bound).map(|k| k - 1);
+			let (Some(i), Some(j)) = (index(words[0], rows), index(words[1], cols)) else {
+				return error(line, "index out of range")
+			};
+			(i, j, if pattern { Some(R::one()) } else { R::parse_entry(words[2]) })
+		} else {
+			if words.len() != 1 {
+				return error(line, "expected a single value")
+			}
+			let Some((i, j)) = positions.next() else {
+				return error(line, "too many entries")
+			};
+			(i, j, R::parse_entry(words[0]))
+		};
+
+		let Some(value) = value else {
+			return error(line, format!("invalid entry {}", words.last().unwrap()))
+		};
+		match symmetry {
+			Symmetry::General => { },
+			Symmetry::Symmetric if i != j => entries.push((j, i, value.clone())),
+			Symmetry::SkewSymmetric => entries.push((j, i, -value.clone())),
+			Symmetry::Symmetric => { }
+		}
+		entries.push((i, j, value));
+	}
+
+	if let Some((line, _)) = lines.next() {
+		return error(line, "too many entries")
+	}
+
+	Ok((rows, cols, entries))
+}
+
+impl<R: Ring + TextEntry> Matrix<R> {
+
+	/// The matrix written in the Matrix Market exchange format, either as a dense array or as
+	/// coordinates of its nonzero entries, with general, symmetric, or skew-symmetric symmetry.
+	/// The integer, real, and pattern fields are read, with each entry parsed by `TextEntry`.
+	pub fn from_matrix_market(text: &str) -> Result<Matrix<R>, ParseMatrixError> {
+		let (rows, cols, entries) = read_matrix_market(text)?;
+		let mut m = Matrix::zero(rows, cols);
+		for (i, j, x) in entries {
+			m[(i, j)] = x;
+		}
+		Ok(m)
+	}
+
+	/// This matrix in the Matrix Market array format, with entries listed column by column
+	pub fn to_matrix_market(&self) -> String {
+		let mut text = format!("%%MatrixMarket matrix array {} general\n{} {}\n", R::MATRIX_MARKET_FIELD, self.rows(), self.cols());
+		for j in 0..self.cols() {
+			for i in 0..self.rows() {
+				text.push_str(&self[(i, j)].to_entry());
+				text.push('\n');
+			}
+		}
+		text
+	}
+
+	/// The matrix written as comma separated values, one row per line, ignoring blank lines
+	pub fn from_csv(text: &str) -> Result<Matrix<R>, ParseMatrixError> {
+		let mut entries = Vec::new();
+		let mut cols = None;
+		let mut rows = 0;
+
+		for (line, row) in text.lines().enumerate().map(|(i, row)| (i + 1, row.trim())).filter(|(_, row)| !row.is_empty()) {
+			let values: Vec<&str> = row.split(',').map(str::trim).collect();
+			if *cols.get_or_insert(values.len()) != values.len() {
+				return error(line, format!("expected {} values", cols.unwrap()))
+			}
+
+			for value in values {
+				match R::parse_entry(value) {
+					Some(x) => entries.push(x),
+					None => return error(line, format!("invalid entry {}", value))
+				}
+			}
+			rows += 1;
+		}
+
+		Ok(Matrix::new(rows, cols.unwrap_or(0), entries))
+	}
+
+	/// This matrix as comma separated values, one row per line
+	pub fn to_csv(&self) -> String {
+		(0..self.rows()).map(|i| {
+			(0..self.cols()).map(|j| self[(i, j)].to_entry()).collect::<Vec<String>>().join(",") + "\n"
+		}).collect()
+	}
+
+}
+
+impl<R: Ring + TextEntry> SparseMatrix<R> {
+
+	/// The matrix written in the Matrix Market exchange format, as for `Matrix::from_matrix_market`
+	pub fn from_matrix_market(text: &str) -> Result<SparseMatrix<R>, ParseMatrixError> {
+		let (rows, cols, entries) = read_matrix_market(text)?;
+		Ok(SparseMatrix::new(rows, cols, entries))
+	}
+
+	/// This matrix in the Matrix Market coordinate format, listing its nonzero entries row by row
+	pub fn to_matrix_market(&self) -> String {
+		let mut text = format!("%%MatrixMarket matrix coordinate {} general\n{} {} {}\n", R::MATRIX_MARKET_FIELD, self.rows(), self.cols(), self.nonzero_count());
+		for i in 0..self.rows() {
+			for (j, x) in self.row(i) {
+				text.push_str(&format!("{} {} {}\n", i + 1, j + 1, x.to_entry()));
+			}
+		}
+		text
+	}
+
+}
@@ -0,0 +1,274 @@
+//
+// Exact linear algebra over the integers by multimodular methods: the same computation modulo
+// several word-sized primes, with the results put back together by the Chinese remainder theorem
+// and rational reconstruction
+//
+
+use crate::algebra::*;
+use crate::continued_fraction::rational_reconstruct_with_bounds;
+use crate::crt::CRT;
+use crate::matrix::Matrix;
+use crate::std_impls::ZM;
+
+// MARK: Primes
+
+/// The largest primes below 2^31, so products of two residues fit in an i64 and the product of
+/// four of them still fits in an i128
+const PRIMES: [i64; 8] = [2147483647, 2147483629, 2147483587, 2147483579, 2147483563, 2147483549, 2147483543, 2147483497];
+
+/// The most primes whose product fits in an i128
+const MAX_PRIMES: usize = 4;
+
+/// A computation over ZM<P>, carried out for each prime of a multimodular algorithm
+trait ModularTask: Sync {
+	type Output: Send;
+
+	fn run<const P: i64>(&self) -> Self::Output;
+}
+
+/// The task run modulo PRIMES[i]
+fn run_modulo<T: ModularTask>(task: &T, i: usize) -> T::Output {
+	macro_rules! dispatch {
+		($($i:literal),*) => {
+			match i {
+				$($i => task.run::<{ PRIMES[$i] }>(),)*
+				_ => unreachable!()
+			}
+		};
+	}
+	dispatch!(0, 1, 2, 3, 4, 5, 6, 7)
+}
+
+/// The task run modulo each of the primes with the given indices, in parallel
+#[cfg(feature = "rayon")]
+fn run_all<T: ModularTask>(task: &T, indices: &[usize]) -> Vec<T::Output> {
+	use rayon::prelude::*;
+	indices.par_iter().map(|&i| run_modulo(task, i)).collect()
+}
+
+/// The task run modulo each of the primes with the given indices
+#[cfg(not(feature = "rayon"))]
+fn run_all<T: ModularTask>(task: &T, indices: &[usize]) -> Vec<T::Output> {
+	indices.iter().map(|&i| run_modulo(task, i)).collect()
+}
+
+/// The reduction of an integer matrix modulo P
+fn reduce<const P: i64>(a: &Matrix<i64>) -> Matrix<ZM<P>> {
+	Matrix::new(a.rows(), a.cols(), a.entries().iter().map(|&x| ZM::from_int(x)).collect())
+}
+
+/// The number of primes whose product is more than twice 2^log_bound, so that CRT recovers any
+/// integer of absolute value at most 2^log_bound, or None if that takes more than fit in an i128
+fn primes_needed(log_bound: f64) -> Option<usize> {
+	let mut log_product = 0.0;
+	for (k, p) in PRIMES.iter().take(MAX_PRIMES).enumerate() {
+		log_product += (*p as f64).log2();
+		if log_product > log_bound + 1.0 {
+			return Some(k + 1)
+		}
+	}
+	None
+}
+
+/// The base 2 logarithm of the Hadamard bound on the determinant of a square integer matrix, the
+/// product of the Euclidean norms of its rows, which also bounds every minor
+fn log_hadamard_bound(a: &Matrix<i64>) -> f64 {
+	(0..a.rows()).map(|i| {
+		let norm = a.row(i).iter().map(|&x| (x as f64) * (x as f64)).sum::<f64>();
+		if norm == 0.0 { 0.0 } else { norm.log2() / 2.0 }
+	}).sum()
+}
+
+/// The integer with the given residues modulo the moduli of the CRT, in the symmetric range
+/// around zero
+fn combine(crt: &CRT<i128>, residues: &[i128]) -> i128 {
+	let x = crt.from_residues(residues);
+	if 2 * x > *crt.modulus() { x - crt.modulus() } else { x }
+}
+
+/// Panics unless the matrix is square
+fn check_square(a: &Matrix<i64>) {
+	if !a.is_square() {
+		panic!("Expected a square matrix, got a {}x{} matrix", a.rows(), a.cols())
+	}
+}
+
+// MARK: Determinants
+
+struct DeterminantTask<'a>(&'a Matrix<i64>);
+
+impl ModularTask for DeterminantTask<'_> {
+	type Output = i128;
+
+	fn run<const P: i64>(&self) -> i128 {
+		reduce::<P>(self.0).determinant().val as i128
+	}
+}
+
+/// The determinant of a square integer matrix, computed modulo enough primes to pin it down by
+/// the Hadamard bound, or None if that bound does not fit in an i128.
+///
+/// Each determinant modulo a prime costs O(n^3) operations on machine words, so this avoids the
+/// growth of intermediate entries that fraction-free elimination over the integers suffers.
+pub fn determinant(a: &Matrix<i64>) -> Option<i128> {
+	check_square(a);
+	let count = primes_needed(log_hadamard_bound(a))?;
+	let indices: Vec<usize> = (0..count).collect();
+
+	let residues = run_all(&DeterminantTask(a), &indices);
+	let crt = CRT::new(indices.iter().map(|&i| PRIMES[i] as i128).collect()).unwrap();
+	Some(combine(&crt, &residues))
+}
+
+/// Indices of primes not dividing the nonzero integer d, as many as are needed for the bound
+fn primes_not_dividing(d: i128, count: usize) -> Vec<usize> {
+	// d fits in an i128, so at most four of the primes divide it, leaving at least four others
+	(0..PRIMES.len()).filter(|&i| d % PRIMES[i] as i128 != 0).take(count).collect()
+}
+
+// MARK: Inverses
+
+struct AdjugateTask<'a>(&'a Matrix<i64>);
+
+impl ModularTask for AdjugateTask<'_> {
+	type Output = Vec<i128>;
+
+	/// The entries of the adjugate det(A) A^(-1) modulo P, where P does not divide det(A)
+	fn run<const P: i64>(&self) -> Vec<i128> {
+		let a = reduce::<P>(self.0);
+		let d = a.determinant();
+		a.inverse().unwrap().scale(&d).entries().iter().map(|x| x.val as i128).collect()
+	}
+}
+
+/// The inverse of a square integer matrix as (N, d) with inverse N / d, d > 0, and no common
+/// factor of d and every entry of N, or None if the matrix is singular or its Hadamard bound does
+/// not fit in an i128.
+///
+/// The determinant and the adjugate det(A) A^(-1), whose entries are minors and so are bounded by
+/// the same Hadamard bound, are found modulo primes not dividing the determinant and recombined.
+pub fn inverse(a: &Matrix<i64>) -> Option<(Matrix<i128>, i128)> {
+	check_square(a);
+	let count = primes_needed(log_hadamard_bound(a))?;
+	let d = determinant(a)?;
+	if d == 0 {
+		return None
+	}
+
+	let indices = primes_not_dividing(d, count);
+	let residues = run_all(&AdjugateTask(a), &indices);
+	let crt = CRT::new(indices.iter().map(|&i| PRIMES[i] as i128).collect()).unwrap();
+
+	let adjugate: Vec<i128> = (0..a.rows() * a.cols())
+		.map(|k| combine(&crt, &residues.iter().map(|r| r[k]).collect::<Vec<i128>>()))
+		.collect();
+
+	let g = adjugate.iter().fold(d, |g, x| gcd(&g, x));
+	let sign = d.signum();
+	let numerator = adjugate.iter().map(|x| x / g * sign).collect();
+	Some((Matrix::new(a.rows(), a.cols(), numerator), d / g * sign))
+}
+
+// MARK: Linear Systems
+
+struct SolveTask<'a>(&'a Matrix<i64>, &'a [i64]);
+
+impl ModularTask for SolveTask<'_> {
+	type Output = Option<Vec<i128>>;
+
+	/// The solution of A x = b modulo P, or None if P divides det(A)
+	fn run<const P: i64>(&self) -> Option<Vec<i128>> {
+		let inverse = reduce::<P>(self.0).inverse()?;
+		let b: Vec<ZM<P>> = self.1.iter().map(|&x| ZM::from_int(x)).collect();
+		Some((0..inverse.rows()).map(|i| {
+			inverse.row(i).iter().zip(b.iter()).fold(ZM::<P>::zero(), |sum, (x, y)| sum + *x * *y).val as i128
+		}).collect())
+	}
+}
+
+/// Whether or not A v = d b, checking for overflow
+fn satisfies(a: &Matrix<i64>, b: &[i64], v: &[i128], d: i128) -> bool {
+	(0..a.rows()).all(|i| {
+		let lhs = a.row(i).iter().zip(v).try_fold(0i128, |sum, (&x, y)| sum.checked_add((x as i128).checked_mul(*y)?));
+		lhs.is_some() && lhs == (b[i] as i128).checked_mul(d)
+	})
+}
+
+/// The base 2 logarithm of a bound on the determinants of A with one column replaced by b, the
+/// numerators in Cramer's rule, by the Hadamard bound on the columns
+fn log_cramer_bound(a: &Matrix<i64>, b: &[i64]) -> f64 {
+	let log_norm = |v: &[i64]| {
+		let norm = v.iter().map(|&x| (x as f64) * (x as f64)).sum::<f64>();
+		if norm == 0.0 { 0.0 } else { norm.log2() / 2.0 }
+	};
+
+	let columns: Vec<f64> = (0..a.cols()).map(|j| log_norm(&a.column(j))).collect();
+	let total: f64 = columns.iter().sum();
+	columns.iter().map(|c| total - c + log_norm(b)).fold(total, f64::max)
+}
+
+/// v / d in lowest terms with d > 0
+fn lowest_terms(v: &[i128], d: i128) -> (Vec<i128>, i128) {
+	let g = v.iter().fold(d, |g, x| gcd(&g, x)) * d.signum();
+	(v.iter().map(|x| x / g).collect(), d / g)
+}
+
+/// The solution of A x = b from its residues modulo the first few primes in the list, by
+/// rational reconstruction of each entry, if that gives fractions which satisfy the system
+fn reconstruct_solution(a: &Matrix<i64>, b: &[i64], residues: &[(i128, Vec<i128>)]) -> Option<(Vec<i128>, i128)> {
+	let crt = CRT::new(residues.iter().map(|(p, _)| *p).collect()).unwrap();
+	let modulus = *crt.modulus();
+	let bound = ((modulus / 2) as f64).sqrt() as u128;
+
+	let fractions: Vec<(i128, i128)> = (0..a.rows()).map(|k| {
+		let x: Vec<i128> = residues.iter().map(|(_, x)| x[k]).collect();
+		let (n, d) = rational_reconstruct_with_bounds(&crt.from_residues(&x), &modulus, bound, bound)?;
+		Some(if d < 0 { (-n, -d) } else { (n, d) })
+	}).collect::<Option<_>>()?;
+
+	// Put the fractions over their least common denominator
+	let d = fractions.iter().try_fold(1i128, |l, (_, d)| (l / gcd(&l, d)).checked_mul(*d))?;
+	let v: Vec<i128> = fractions.iter().map(|(n, e)| n.checked_mul(d / e)).collect::<Option<_>>()?;
+	satisfies(a, b, &v, d).then(|| lowest_terms(&v, d))
+}
+
+/// The solution of A x = b for a square nonsingular integer matrix A, as (v, d) with x = v / d,
+/// d > 0, and no common factor of d and every entry of v, or None if A is singular or the
+/// solution cannot be pinned down within an i128.
+///
+/// The solutions modulo each prime are recombined by CRT one prime at a time, and each entry is
+/// recovered as a fraction by rational reconstruction, stopping as soon as the fractions satisfy
+/// the system exactly. That often happens with far fewer primes than the bounds on the solution
+/// need, but the numerators and denominators together must fit in the modulus. Failing that,
+/// det(A) x, whose entries are the numerators in Cramer's rule, is recovered by CRT instead.
+pub fn solve(a: &Matrix<i64>, b: &[i64]) -> Option<(Vec<i128>, i128)> {
+	check_square(a);
+	if b.len() != a.rows() {
+		panic!("Expected a right hand side of length {}, got {}", a.rows(), b.len())
+	}
+
+	// The primes for which A is invertible are exactly those not dividing det(A)
+	let indices: Vec<usize> = (0..PRIMES.len()).collect();
+	let lucky: Vec<(i128, Vec<i128>)> = indices.iter().zip(run_all(&SolveTask(a, b), &indices))
+		.filter_map(|(&i, x)| Some((PRIMES[i] as i128, x?)))
+		.take(MAX_PRIMES)
+		.collect();
+
+	if let Some(solution) = (1..=lucky.len()).find_map(|count| reconstruct_solution(a, b, &lucky[..count])) {
+		return Some(solution)
+	}
+
+	let d = determinant(a)?;
+	let count = primes_needed(log_cramer_bound(a, b))?;
+	if d == 0 || count > lucky.len() {
+		return None
+	}
+
+	let crt = CRT::new(lucky[..count].iter().map(|(p, _)| *p).collect()).unwrap();
+	let v: Vec<i128> = (0..a.rows()).map(|k| {
+		let scaled: Vec<i128> = lucky[..count].iter().map(|(p, x)| x[k] * d.rem_euclid(*p) % p).collect();
+		combine(&crt, &scaled)
+	}).collect();
+
+	satisfies(a, b, &v, d).then(|| lowest_terms(&v, d))
+}
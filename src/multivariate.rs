@@ -0,0 +1,647 @@
+//
+// Multivariate polynomials over a ring, stored sparsely, with monomial orderings
+//
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+
+// MARK: Monomials
+
+/// The monomial x_0^e_0 x_1^e_1 ... x_(N-1)^e_(N-1), written by its exponents.
+///
+/// Monomials form a commutative monoid under multiplication, which adds exponents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Monomial<const N: usize> {
+	pub exponents: [u32; N]
+}
+
+impl<const N: usize> Monomial<N> {
+
+	/// The monomial with the given exponents
+	pub fn new(exponents: [u32; N]) -> Monomial<N> {
+		Monomial { exponents }
+	}
+
+	/// The variable x_i
+	pub fn var(i: usize) -> Monomial<N> {
+		let mut exponents = [0; N];
+		exponents[i] = 1;
+		Monomial { exponents }
+	}
+
+	/// The total degree, the sum of the exponents
+	pub fn degree(&self) -> u32 {
+		self.exponents.iter().sum()
+	}
+
+	/// Whether or not this monomial divides other
+	pub fn divides(&self, other: &Monomial<N>) -> bool {
+		self.exponents.iter().zip(other.exponents.iter()).all(|(a, b)| a <= b)
+	}
+
+	/// self / other, if other divides self
+	pub fn checked_div(&self, other: &Monomial<N>) -> Option<Monomial<N>> {
+		if other.divides(self) {
+			Some(Monomial { exponents: std::array::from_fn(|i| self.exponents[i] - other.exponents[i]) })
+		} else {
+			None
+		}
+	}
+
+	/// The least common multiple, taking the larger exponent of each variable
+	pub fn lcm(&self, other: &Monomial<N>) -> Monomial<N> {
+		Monomial { exponents: std::array::from_fn(|i| self.exponents[i].max(other.exponents[i])) }
+	}
+
+	/// The greatest common divisor, taking the smaller exponent of each variable
+	pub fn gcd(&self, other: &Monomial<N>) -> Monomial<N> {
+		Monomial { exponents: std::array::from_fn(|i| self.exponents[i].min(other.exponents[i])) }
+	}
+
+	/// The weighted degree, the sum of each exponent times the weight of its variable
+	pub fn weighted_degree(&self, weights: &[u32]) -> u64 {
+		if weights.len() != N {
+			panic!("Expected {} weights, got {}", N, weights.len())
+		}
+		self.exponents.iter().zip(weights).map(|(&e, &w)| e as u64 * w as u64).sum()
+	}
+
+}
+
+impl<const N: usize> Mul for Monomial<N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Monomial { exponents: std::array::from_fn(|i| self.exponents[i] + rhs.exponents[i]) }
+	}
+}
+
+impl<const N: usize> MulAssign for Monomial<N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+impl<const N: usize> Monoid for Monomial<N> {
+	fn identity() -> Self {
+		Monomial { exponents: [0; N] }
+	}
+}
+
+// MARK: Monomial Orders
+
+/// A monomial order: a total order on monomials, compatible with multiplication, in which 1 is the
+/// smallest monomial
+pub trait MonomialOrder {
+
+	/// Compares two monomials in this order
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering;
+
+}
+
+/// The lexicographic order, with x_0 > x_1 > ... > x_(N-1)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Lex;
+
+/// The graded lexicographic order, comparing total degrees first and breaking ties lexicographically
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GrLex;
+
+/// The graded reverse lexicographic order, comparing total degrees first and breaking ties in
+/// favor of the smaller exponent of the last variable where they differ
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GRevLex;
+
+impl MonomialOrder for Lex {
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering {
+		a.exponents.cmp(&b.exponents)
+	}
+}
+
+impl MonomialOrder for GrLex {
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering {
+		a.degree().cmp(&b.degree()).then_with(|| a.exponents.cmp(&b.exponents))
+	}
+}
+
+impl MonomialOrder for GRevLex {
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering {
+		a.degree().cmp(&b.degree()).then_with(|| {
+			let last = (0..N).rev().find(|&i| a.exponents[i] != b.exponents[i]);
+			last.map_or(Ordering::Equal, |i| b.exponents[i].cmp(&a.exponents[i]))
+		})
+	}
+}
+
+/// The order comparing weighted degrees first, for a vector of nonnegative weights, one per
+/// variable, and breaking ties with another monomial order. With all weights 1 and GrLex or
+/// GRevLex to break ties this is that order again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightOrder<O: MonomialOrder> {
+	pub weights: Vec<u32>,
+	pub tie_break: O
+}
+
+impl<O: MonomialOrder> WeightOrder<O> {
+
+	/// The order by weighted degree with the given weights, breaking ties with the given order
+	pub fn new(weights: Vec<u32>, tie_break: O) -> WeightOrder<O> {
+		WeightOrder { weights, tie_break }
+	}
+
+}
+
+impl<O: MonomialOrder> MonomialOrder for WeightOrder<O> {
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering {
+		a.weighted_degree(&self.weights).cmp(&b.weighted_degree(&self.weights))
+			.then_with(|| self.tie_break.compare(a, b))
+	}
+}
+
+/// The block order on x_0, ..., x_(k-1) and x_k, ..., x_(N-1), comparing the exponents of the
+/// first block of variables by one order and breaking ties by comparing those of the second by
+/// another.
+///
+/// Each block is compared as the monomial with the exponents of the other block set to zero, so a
+/// weight order used within a block sees the weights of every variable but only the exponents of
+/// its own. Every monomial involving the first block is larger than every one which does not, so
+/// this is an elimination order for x_0, ..., x_(k-1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockOrder<O: MonomialOrder, P: MonomialOrder> {
+	pub split: usize,
+	pub first: O,
+	pub second: P
+}
+
+impl<O: MonomialOrder, P: MonomialOrder> BlockOrder<O, P> {
+
+	/// The block order with blocks x_0, ..., x_(split-1) and x_split, ..., x_(N-1), ordered by
+	/// first and second respectively
+	pub fn new(split: usize, first: O, second: P) -> BlockOrder<O, P> {
+		BlockOrder { split, first, second }
+	}
+
+}
+
+impl BlockOrder<GRevLex, GRevLex> {
+
+	/// The elimination order for the first k variables, with GRevLex within each block
+	pub fn elimination(k: usize) -> BlockOrder<GRevLex, GRevLex> {
+		BlockOrder::new(k, GRevLex, GRevLex)
+	}
+
+}
+
+impl<O: MonomialOrder, P: MonomialOrder> MonomialOrder for BlockOrder<O, P> {
+	fn compare<const N: usize>(&self, a: &Monomial<N>, b: &Monomial<N>) -> Ordering {
+		let block = |m: &Monomial<N>, first: bool| Monomial::<N> {
+			exponents: std::array::from_fn(|i| if (i < self.split) == first { m.exponents[i] } else { 0 })
+		};
+		self.first.compare(&block(a, true), &block(b, true))
+			.then_with(|| self.second.compare(&block(a, false), &block(b, false)))
+	}
+}
+
+// MARK: Monomial Enumeration
+
+/// The monomials of total degree exactly d in N variables, in decreasing lexicographic order, so
+/// starting with x_0^d and ending with x_(N-1)^d
+pub fn monomials_of_degree<const N: usize>(d: u32) -> MonomialsOfDegree<N> {
+	let mut exponents = [0; N];
+	let next = match exponents.first_mut() {
+		Some(e) => {
+			*e = d;
+			Some(Monomial { exponents })
+		},
+		None => (d == 0).then_some(Monomial { exponents })
+	};
+
+	MonomialsOfDegree { next }
+}
+
+/// An iterator over the monomials of a fixed total degree, made by `monomials_of_degree`
+#[derive(Clone, Debug)]
+pub struct MonomialsOfDegree<const N: usize> {
+	next: Option<Monomial<N>>
+}
+
+impl<const N: usize> Iterator for MonomialsOfDegree<N> {
+	type Item = Monomial<N>;
+
+	fn next(&mut self) -> Option<Monomial<N>> {
+		let current = self.next?;
+
+		// Move one from the last nonzero exponent before the end to its right, and the whole of
+		// the last exponent along with it
+		let mut e = current.exponents;
+		let tail = e.last().copied().unwrap_or(0);
+		if let Some(last) = e.last_mut() {
+			*last = 0;
+		}
+
+		self.next = (0..N.saturating_sub(1)).rev().find(|&j| e[j] > 0).map(|j| {
+			e[j] -= 1;
+			e[j + 1] = tail + 1;
+			Monomial { exponents: e }
+		});
+
+		Some(current)
+	}
+}
+
+/// The monomials of total degree at most d in N variables, sorted from smallest to largest in the
+/// given order
+pub fn monomials_up_to_degree<const N: usize, O: MonomialOrder>(d: u32, order: &O) -> Vec<Monomial<N>> {
+	let mut monomials: Vec<Monomial<N>> = (0..=d).flat_map(monomials_of_degree).collect();
+	monomials.sort_by(|a, b| order.compare(a, b));
+	monomials
+}
+
+/// The number of monomials of total degree at most d in N variables, the binomial coefficient
+/// (N + d) choose N
+pub fn monomial_count<const N: usize>(d: u32) -> usize {
+	(1..=N).fold(1, |count, k| count * (d as usize + k) / k)
+}
+
+// MARK: Multivariate Polynomials
+
+/// A polynomial in the N variables x_0, ..., x_(N-1) with coefficients in a ring R, stored as a
+/// map from monomials to their nonzero coefficients
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MultiPoly<R: Ring, const N: usize> {
+	terms: BTreeMap<Monomial<N>, R>
+}
+
+impl<R: Ring, const N: usize> MultiPoly<R, N> {
+
+	/// The sum of the given terms, combining any with the same monomial
+	pub fn new(terms: Vec<(Monomial<N>, R)>) -> MultiPoly<R, N> {
+		let mut p = MultiPoly { terms: BTreeMap::new() };
+		for (m, c) in terms {
+			p.add_term(m, &c);
+		}
+		p
+	}
+
+	/// The constant polynomial c
+	pub fn constant(c: R) -> MultiPoly<R, N> {
+		MultiPoly::monomial(c, Monomial::identity())
+	}
+
+	/// The variable x_i
+	pub fn var(i: usize) -> MultiPoly<R, N> {
+		MultiPoly::monomial(R::one(), Monomial::var(i))
+	}
+
+	/// The single term c m
+	pub fn monomial(c: R, m: Monomial<N>) -> MultiPoly<R, N> {
+		MultiPoly::new(vec![(m, c)])
+	}
+
+	/// The terms with nonzero coefficients, in increasing lexicographic order of monomial
+	pub fn terms(&self) -> impl Iterator<Item = (&Monomial<N>, &R)> {
+		self.terms.iter()
+	}
+
+	/// The terms with nonzero coefficients, from the largest monomial down in the given order
+	pub fn sorted_terms<O: MonomialOrder>(&self, order: &O) -> Vec<(Monomial<N>, R)> {
+		let mut terms: Vec<(Monomial<N>, R)> = self.terms.iter().map(|(m, c)| (*m, c.clone())).collect();
+		terms.sort_by(|(a, _), (b, _)| order.compare(b, a));
+		terms
+	}
+
+	/// The number of nonzero terms
+	pub fn term_count(&self) -> usize {
+		self.terms.len()
+	}
+
+	/// The coefficient of the monomial m
+	pub fn coefficient(&self, m: &Monomial<N>) -> R {
+		self.terms.get(m).cloned().unwrap_or_else(R::zero)
+	}
+
+	/// The largest total degree of a term. The zero polynomial is given degree 0.
+	pub fn total_degree(&self) -> u32 {
+		self.terms.keys().map(Monomial::degree).max().unwrap_or(0)
+	}
+
+	/// The largest power of x_i appearing in a term
+	pub fn degree_in(&self, i: usize) -> u32 {
+		self.terms.keys().map(|m| m.exponents[i]).max().unwrap_or(0)
+	}
+
+	/// Whether or not this polynomial is a constant
+	pub fn is_constant(&self) -> bool {
+		self.terms.keys().all(|m| m.degree() == 0)
+	}
+
+	/// The largest monomial and its coefficient in the given order, or None for zero
+	pub fn leading_term<O: MonomialOrder>(&self, order: &O) -> Option<(Monomial<N>, R)> {
+		self.terms.iter().max_by(|(a, _), (b, _)| order.compare(a, b)).map(|(m, c)| (*m, c.clone()))
+	}
+
+	/// The largest monomial in the given order, or None for zero
+	pub fn leading_monomial<O: MonomialOrder>(&self, order: &O) -> Option<Monomial<N>> {
+		self.leading_term(order).map(|(m, _)| m)
+	}
+
+	/// The coefficient of the largest monomial in the given order, or zero for zero
+	pub fn leading_coefficient<O: MonomialOrder>(&self, order: &O) -> R {
+		self.leading_term(order).map_or_else(R::zero, |(_, c)| c)
+	}
+
+	/// Evaluates this polynomial at a point
+	pub fn evaluate(&self, point: &[R; N]) -> R {
+		self.terms.iter().fold(R::zero(), |sum, (m, c)| {
+			let value = (0..N).fold(c.clone(), |value, i| value.mul_ref(&point[i].power(m.exponents[i] as i64)));
+			sum.add_ref(&value)
+		})
+	}
+
+	/// Multiplies every coefficient by c
+	pub fn scale(&self, c: &R) -> MultiPoly<R, N> {
+		MultiPoly::new(self.terms.iter().map(|(m, a)| (*m, a.mul_ref(c))).collect())
+	}
+
+	/// Multiplies this polynomial by the term c m
+	pub fn mul_term(&self, m: &Monomial<N>, c: &R) -> MultiPoly<R, N> {
+		MultiPoly::new(self.terms.iter().map(|(n, a)| (*n * *m, a.mul_ref(c))).collect())
+	}
+
+	/// Replaces x_i with the value v, leaving a polynomial not involving x_i
+	pub fn specialize(&self, i: usize, v: &R) -> MultiPoly<R, N> {
+		let mut values: [Option<R>; N] = std::array::from_fn(|_| None);
+		values[i] = Some(v.clone());
+		self.partial_evaluate(&values)
+	}
+
+	/// Replaces each x_i with values[i] where it is given, leaving the other variables alone
+	pub fn partial_evaluate(&self, values: &[Option<R>; N]) -> MultiPoly<R, N> {
+		MultiPoly::new(self.terms.iter().map(|(m, c)| {
+			let mut exponents = m.exponents;
+			let mut c = c.clone();
+			for (e, v) in exponents.iter_mut().zip(values) {
+				if let Some(v) = v {
+					c.mul_assign_ref(&v.power(std::mem::replace(e, 0) as i64));
+				}
+			}
+			(Monomial::new(exponents), c)
+		}).collect())
+	}
+
+	/// Replaces each x_i with the polynomial g_i, the composition f(g_0, ..., g_(N-1))
+	pub fn substitute(&self, polynomials: &[MultiPoly<R, N>; N]) -> MultiPoly<R, N> {
+		let mut result = MultiPoly::zero();
+		for (m, c) in self.terms.iter() {
+			let mut term = MultiPoly::constant(c.clone());
+			for (g, e) in polynomials.iter().zip(m.exponents) {
+				if e > 0 {
+					term.mul_assign_ref(&g.power(e as i64));
+				}
+			}
+			result.add_assign_ref(&term);
+		}
+		result
+	}
+
+	/// Renames the variables, replacing each x_i with x_(permutation[i]). Panics unless permutation
+	/// is a permutation of 0, ..., N - 1.
+	pub fn permute_variables(&self, permutation: &[usize; N]) -> MultiPoly<R, N> {
+		let mut seen = [false; N];
+		for &j in permutation {
+			if j >= N || std::mem::replace(&mut seen[j], true) {
+				panic!("{:?} is not a permutation of the variables", permutation)
+			}
+		}
+
+		MultiPoly::new(self.terms.iter().map(|(m, c)| {
+			let mut exponents = [0; N];
+			for (i, &j) in permutation.iter().enumerate() {
+				exponents[j] = m.exponents[i];
+			}
+			(Monomial::new(exponents), c.clone())
+		}).collect())
+	}
+
+	/// Applies f to every coefficient, such as a reduction modulo p or an inclusion into a larger
+	/// ring, dropping any terms whose coefficients become zero
+	pub fn map_coefficients<S: Ring>(&self, f: impl Fn(&R) -> S) -> MultiPoly<S, N> {
+		MultiPoly::new(self.terms.iter().map(|(m, c)| (*m, f(c))).collect())
+	}
+
+	/// Whether or not every term has the same total degree. Zero counts as homogeneous.
+	pub fn is_homogeneous(&self) -> bool {
+		let mut degrees = self.terms.keys().map(Monomial::degree);
+		degrees.next().is_none_or(|d| degrees.all(|e| e == d))
+	}
+
+	/// The homogeneous polynomial of the given degree which gives this one on setting x_var = 1,
+	/// multiplying each term by the power of x_var making up its degree. This polynomial must not
+	/// involve x_var and must have total degree at most the given degree.
+	pub fn homogenize_to_degree(&self, var: usize, degree: u32) -> MultiPoly<R, N> {
+		if self.degree_in(var) > 0 {
+			panic!("Cannot homogenize with x_{}, which already appears in the polynomial", var)
+		}
+		if self.total_degree() > degree {
+			panic!("Cannot homogenize a polynomial of degree {} to degree {}", self.total_degree(), degree)
+		}
+
+		MultiPoly::new(self.terms.iter().map(|(m, c)| {
+			let mut exponents = m.exponents;
+			exponents[var] = degree - m.degree();
+			(Monomial::new(exponents), c.clone())
+		}).collect())
+	}
+
+	/// The homogenization with respect to x_var, homogeneous of the same total degree as this
+	/// polynomial, which must not involve x_var
+	pub fn homogenize(&self, var: usize) -> MultiPoly<R, N> {
+		self.homogenize_to_degree(var, self.total_degree())
+	}
+
+	/// The polynomial with x_var set to 1, which undoes `homogenize`
+	pub fn dehomogenize(&self, var: usize) -> MultiPoly<R, N> {
+		self.specialize(var, &R::one())
+	}
+
+	/// Adds c m to this polynomial in place
+	fn add_term(&mut self, m: Monomial<N>, c: &R) {
+		let entry = self.terms.entry(m).or_insert_with(R::zero);
+		entry.add_assign_ref(c);
+		if entry.is_zero() {
+			self.terms.remove(&m);
+		}
+	}
+
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring, const N: usize> Add for MultiPoly<R, N> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self.add_assign_ref(&rhs);
+		self
+	}
+}
+
+impl<R: Ring, const N: usize> AddAssign for MultiPoly<R, N> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring, const N: usize> Neg for MultiPoly<R, N> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		MultiPoly { terms: self.terms.into_iter().map(|(m, c)| (m, -c)).collect() }
+	}
+}
+
+impl<R: Ring, const N: usize> Sub for MultiPoly<R, N> {
+	type Output = Self;
+
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self.sub_assign_ref(&rhs);
+		self
+	}
+}
+
+impl<R: Ring, const N: usize> SubAssign for MultiPoly<R, N> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.sub_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring, const N: usize> Mul for MultiPoly<R, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring, const N: usize> MulAssign for MultiPoly<R, N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring, const N: usize> Semiring for MultiPoly<R, N> {
+	fn one() -> Self {
+		MultiPoly::constant(R::one())
+	}
+
+	fn zero() -> Self {
+		MultiPoly { terms: BTreeMap::new() }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.terms.is_empty()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		for (m, c) in rhs.terms.iter() {
+			self.add_term(*m, c);
+		}
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		let mut product = MultiPoly::zero();
+		for (m, a) in self.terms.iter() {
+			for (n, b) in rhs.terms.iter() {
+				product.add_term(*m * *n, &a.mul_ref(b));
+			}
+		}
+		product
+	}
+}
+
+impl<R: Ring, const N: usize> Ring for MultiPoly<R, N> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+
+		let mut power = Self::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base);
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		for (m, c) in rhs.terms.iter() {
+			self.add_term(*m, &-c.clone());
+		}
+	}
+}
+
+impl<R: CommutativeRing, const N: usize> CommutativeRing for MultiPoly<R, N> { }
+
+/// Division by the leading term in the lexicographic order, which is the largest monomial in the
+/// map, dividing coefficients exactly in R
+impl<R: IntegralDomain, const N: usize> IntegralDomain for MultiPoly<R, N> {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		let (lead_monomial, lead) = divisor.terms.iter().next_back()?;
+		let mut quotient = MultiPoly::zero();
+		let mut remainder = self.clone();
+
+		while let Some((m, c)) = remainder.terms.iter().next_back() {
+			let m = m.checked_div(lead_monomial)?;
+			let q = c.divide_exact(lead)?;
+			remainder.sub_assign_ref(&divisor.mul_term(&m, &q));
+			quotient.add_term(m, &q);
+		}
+
+		Some(quotient)
+	}
+}
+
+// MARK: Binary Forms
+
+impl<R: Ring> Polynomial<R> {
+
+	/// The binary form y^d f(x / y) of degree d in x = x_0 and y = x_1, for d at least the degree
+	/// of this polynomial. Its zeros in the projective line are the roots of f, together with the
+	/// point at infinity y = 0 when d is more than the degree.
+	pub fn homogenize(&self, degree: usize) -> MultiPoly<R, 2> {
+		if !self.is_zero() && self.degree() > degree {
+			panic!("Cannot homogenize a polynomial of degree {} to degree {}", self.degree(), degree)
+		}
+
+		MultiPoly::new(self.coefficients().iter().enumerate().map(|(i, c)| {
+			(Monomial::new([i as u32, (degree - i) as u32]), c.clone())
+		}).collect())
+	}
+
+	/// The polynomial f(x) = F(x, 1) of a polynomial F in x = x_0 and y = x_1, which undoes
+	/// `homogenize`
+	pub fn dehomogenize(form: &MultiPoly<R, 2>) -> Polynomial<R> {
+		let degree = form.degree_in(0) as usize;
+		let mut coefficients = vec![R::zero(); degree + 1];
+		for (m, c) in form.terms() {
+			coefficients[m.exponents[0] as usize].add_assign_ref(c);
+		}
+		Polynomial::new(coefficients)
+	}
+
+}
@@ -0,0 +1,182 @@
+//
+// Perron-Frobenius theory for nonnegative matrices: irreducibility, periods, spectral radii, and
+// stationary distributions of Markov chains
+//
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+
+/// How many rounds of power iteration to run before settling for the current estimate
+const POWER_ITERATION_LIMIT: usize = 100_000;
+
+// MARK: Nonnegativity
+
+/// Whether or not every entry is at least zero
+pub fn is_nonnegative<R: PoRing>(a: &Matrix<R>) -> bool {
+	a.entries().iter().all(|x| *x >= R::zero())
+}
+
+/// Whether or not every entry is nonnegative and every row sums to one, so that a is the
+/// transition matrix of a Markov chain
+pub fn is_stochastic<R: PoRing>(a: &Matrix<R>) -> bool {
+	is_nonnegative(a) && (0..a.rows()).all(|i| a.row(i).iter().fold(R::zero(), |sum, x| sum.add_ref(x)) == R::one())
+}
+
+// MARK: Graph Structure
+
+/// The directed graph with an edge i -> j wherever a_ij is nonzero, as adjacency lists
+fn adjacency<R: Semiring>(a: &Matrix<R>) -> Vec<Vec<usize>> {
+	if !a.is_square() {
+		panic!("Only square matrices have an associated graph")
+	}
+
+	(0..a.rows()).map(|i| (0..a.cols()).filter(|&j| !a[(i, j)].is_zero()).collect()).collect()
+}
+
+/// The distance from vertex 0 to every vertex, or None for the unreachable ones
+fn distances_from_zero(graph: &[Vec<usize>]) -> Vec<Option<usize>> {
+	let mut distance = vec![None; graph.len()];
+	let mut queue = std::collections::VecDeque::from([0]);
+	distance[0] = Some(0);
+
+	while let Some(u) = queue.pop_front() {
+		for &v in graph[u].iter() {
+			if distance[v].is_none() {
+				distance[v] = Some(distance[u].unwrap() + 1);
+				queue.push_back(v);
+			}
+		}
+	}
+
+	distance
+}
+
+/// Whether or not this square matrix is irreducible, meaning its graph is strongly connected.
+///
+/// A 0x0 matrix is not irreducible, and a 1x1 matrix always is.
+pub fn is_irreducible<R: Semiring>(a: &Matrix<R>) -> bool {
+	let graph = adjacency(a);
+	if graph.is_empty() {
+		return false
+	}
+
+	// Strongly connected exactly when every vertex is reachable from 0, and 0 from every vertex
+	let reversed: Vec<Vec<usize>> = (0..graph.len()).map(|j| (0..graph.len()).filter(|&i| graph[i].contains(&j)).collect()).collect();
+	distances_from_zero(&graph).iter().all(Option::is_some) && distances_from_zero(&reversed).iter().all(Option::is_some)
+}
+
+/// The period of an irreducible matrix, the gcd of the lengths of the cycles in its graph, or None
+/// if the matrix is reducible
+pub fn period<R: Semiring>(a: &Matrix<R>) -> Option<usize> {
+	if !is_irreducible(a) {
+		return None
+	}
+
+	// Every edge u -> v closes cycles whose lengths differ from d(u) + 1 - d(v) by multiples of
+	// the period, and the gcd of these over all edges is exactly the period
+	let graph = adjacency(a);
+	let distance: Vec<usize> = distances_from_zero(&graph).into_iter().map(Option::unwrap).collect();
+
+	let mut period: i64 = 0;
+	for (u, targets) in graph.iter().enumerate() {
+		for &v in targets {
+			period = gcd(&period, &(distance[u] as i64 + 1 - distance[v] as i64));
+		}
+	}
+
+	Some(period as usize)
+}
+
+/// Whether or not this matrix is primitive, meaning irreducible with period one, so that some
+/// power of it has every entry nonzero
+pub fn is_primitive<R: Semiring>(a: &Matrix<R>) -> bool {
+	period(a) == Some(1)
+}
+
+// MARK: Spectral Radius
+
+/// The Collatz-Wielandt bounds min (Ax)_i / x_i <= r <= max (Ax)_i / x_i on the spectral radius r
+/// of a nonnegative matrix, for a vector x with positive entries
+pub fn collatz_wielandt_bounds<F: PoField>(a: &Matrix<F>, x: &[F]) -> (F, F) {
+	if x.len() != a.cols() || x.iter().any(|xi| *xi <= F::zero()) {
+		panic!("Collatz-Wielandt bounds need a positive vector of length {}", a.cols())
+	}
+
+	let ratios: Vec<F> = (0..a.rows()).map(|i| {
+		let ax = a.row(i).iter().zip(x.iter()).fold(F::zero(), |sum, (aij, xj)| sum.add_ref(&aij.mul_ref(xj)));
+		ax / x[i].clone()
+	}).collect();
+
+	let mut lower = ratios[0].clone();
+	let mut upper = ratios[0].clone();
+	for r in ratios.into_iter().skip(1) {
+		if r < lower {
+			lower = r
+		} else if r > upper {
+			upper = r
+		}
+	}
+
+	(lower, upper)
+}
+
+/// The spectral radius of an irreducible nonnegative matrix and its positive Perron vector,
+/// normalized to sum to one, by power iteration until the Collatz-Wielandt bounds are within
+/// the tolerance, or for at most `POWER_ITERATION_LIMIT` rounds.
+///
+/// Iterates with A + I, which has the same Perron vector and is primitive, so this converges even
+/// when A is periodic. Returns None if A is not irreducible and nonnegative.
+pub fn perron_vector(a: &Matrix<f64>, tolerance: f64) -> Option<(f64, Vec<f64>)> {
+	if !is_nonnegative(a) || !is_irreducible(a) {
+		return None
+	}
+
+	let n = a.rows();
+	let shifted = a.add_ref(&Matrix::identity(n));
+	let mut x = vec![1.0 / n as f64; n];
+
+	for _ in 0..POWER_ITERATION_LIMIT {
+		let (lower, upper) = collatz_wielandt_bounds(&shifted, &x);
+		if upper - lower <= tolerance {
+			break
+		}
+
+		let next: Vec<f64> = (0..n).map(|i| shifted.row(i).iter().zip(x.iter()).map(|(aij, xj)| aij * xj).sum()).collect();
+		let total: f64 = next.iter().sum();
+		x = next.into_iter().map(|xi| xi / total).collect();
+	}
+
+	let (lower, upper) = collatz_wielandt_bounds(&shifted, &x);
+	Some(((lower + upper) / 2.0 - 1.0, x))
+}
+
+/// The spectral radius of an irreducible nonnegative matrix, to within the tolerance
+pub fn spectral_radius(a: &Matrix<f64>, tolerance: f64) -> Option<f64> {
+	perron_vector(a, tolerance).map(|(r, _)| r)
+}
+
+// MARK: Markov Chains
+
+/// The stationary distribution pi of a Markov chain with transition matrix p, the probability
+/// vector with pi p = pi, found exactly by solving the linear system. Returns None if there is no
+/// unique one, which happens exactly when the chain has more than one closed class.
+pub fn stationary_distribution<F: Field>(p: &Matrix<F>) -> Option<Vec<F>> {
+	if !p.is_square() {
+		panic!("Only square matrices are transition matrices")
+	}
+
+	// pi (p - I) = 0 has one redundant equation, which we swap for pi_0 + ... + pi_(n-1) = 1
+	let n = p.rows();
+	let system = Matrix::from_fn(n, n, |i, j| {
+		if i == n - 1 {
+			F::one()
+		} else if i == j {
+			p[(j, i)].clone() - F::one()
+		} else {
+			p[(j, i)].clone()
+		}
+	});
+
+	let inverse = system.inverse()?;
+	Some((0..n).map(|i| inverse[(i, n - 1)].clone()).collect())
+}
@@ -0,0 +1,173 @@
+//
+// The Smith and Hermite normal forms of matrices over a Euclidean domain, together with the
+// invertible transformations that reach them
+//
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+
+// MARK: Row and Column Operations
+
+/// The associate of x that gcd(x, 0) picks, with the unit u making xu equal to it. Over the
+/// integers this is |x| and the sign of x.
+fn normalize<R: EuclideanDomain>(x: &R) -> (R, R) {
+	let normal = x.gcd(&R::zero());
+	let unit = normal.divide_exact(x).unwrap_or_else(R::one);
+	(normal, unit)
+}
+
+/// Row i minus q times row t
+fn sub_row_multiple<R: Ring>(m: &mut Matrix<R>, i: usize, t: usize, q: &R) {
+	for j in 0..m.cols() {
+		let delta = q.mul_ref(&m[(t, j)]);
+		m[(i, j)].sub_assign_ref(&delta);
+	}
+}
+
+/// Column j minus q times column t
+fn sub_column_multiple<R: Ring>(m: &mut Matrix<R>, j: usize, t: usize, q: &R) {
+	for i in 0..m.rows() {
+		let delta = m[(i, t)].mul_ref(q);
+		m[(i, j)].sub_assign_ref(&delta);
+	}
+}
+
+/// Row i times c
+fn scale_row<R: Ring>(m: &mut Matrix<R>, i: usize, c: &R) {
+	for j in 0..m.cols() {
+		m[(i, j)].mul_assign_ref(c);
+	}
+}
+
+// MARK: Normal Forms
+
+impl<R: EuclideanDomain> Matrix<R> {
+
+	/// The Smith normal form of this matrix, as (U, D, V) with U and V invertible and D = U M V
+	/// diagonal, whose nonzero diagonal entries d_1 | d_2 | ... | d_r come first. Each d_i is the
+	/// associate chosen by gcd(d_i, 0), so over the integers they are positive.
+	///
+	/// Over the integers, the cokernel Z^m / M Z^n is the sum of the Z / d_i Z and a free part of
+	/// rank m - r, which is the structure theorem for finitely generated abelian groups.
+	pub fn smith_normal_form(&self) -> (Matrix<R>, Matrix<R>, Matrix<R>) {
+		let (rows, cols) = (self.rows(), self.cols());
+		let mut d = self.clone();
+		let mut u = Matrix::identity(rows);
+		let mut v = Matrix::identity(cols);
+
+		for t in 0..rows.min(cols) {
+			loop {
+				// Move a nonzero entry of least size into the pivot position
+				let smallest = (t..rows).flat_map(|i| (t..cols).map(move |j| (i, j)))
+					.filter(|&(i, j)| !d[(i, j)].is_zero())
+					.min_by_key(|&(i, j)| d[(i, j)].euc_size());
+				let Some((i, j)) = smallest else {
+					return (u, d, v)
+				};
+				d.swap_rows(t, i);
+				u.swap_rows(t, i);
+				d.swap_columns(t, j);
+				v.swap_columns(t, j);
+
+				// Clear the pivot's column and row, stopping if a smaller remainder turns up
+				let mut cleared = true;
+				for i in t + 1..rows {
+					let q = d[(i, t)].quotient_and_remainder(&d[(t, t)]).0;
+					sub_row_multiple(&mut d, i, t, &q);
+					sub_row_multiple(&mut u, i, t, &q);
+					cleared &= d[(i, t)].is_zero();
+				}
+				for j in t + 1..cols {
+					let q = d[(t, j)].quotient_and_remainder(&d[(t, t)]).0;
+					sub_column_multiple(&mut d, j, t, &q);
+					sub_column_multiple(&mut v, j, t, &q);
+					cleared &= d[(t, j)].is_zero();
+				}
+				if !cleared {
+					continue
+				}
+
+				// The pivot must divide everything left, or it is not the next invariant factor
+				let indivisible = (t + 1..rows).find(|&i| (t + 1..cols).any(|j| !d[(i, j)].quotient_and_remainder(&d[(t, t)]).1.is_zero()));
+				match indivisible {
+					Some(i) => {
+						sub_row_multiple(&mut d, t, i, &-R::one());
+						sub_row_multiple(&mut u, t, i, &-R::one());
+					},
+					None => break
+				}
+			}
+
+			let (_, unit) = normalize(&d[(t, t)]);
+			scale_row(&mut d, t, &unit);
+			scale_row(&mut u, t, &unit);
+		}
+
+		(u, d, v)
+	}
+
+	/// The Hermite normal form of this matrix, as (U, H) with U invertible and H = U M in row
+	/// echelon form. Each pivot is the associate chosen by gcd(p, 0), and the entries above it are
+	/// remainders modulo it, so over the integers the pivots are positive and the entries above
+	/// each pivot p lie in [0, p).
+	///
+	/// The nonzero rows of H are a canonical basis of the row space of M, so two integer matrices
+	/// have the same row lattice exactly when their Hermite normal forms agree.
+	pub fn hermite_normal_form(&self) -> (Matrix<R>, Matrix<R>) {
+		let (rows, cols) = (self.rows(), self.cols());
+		let mut h = self.clone();
+		let mut u = Matrix::identity(rows);
+		let mut row = 0;
+
+		for col in 0..cols {
+			if row == rows {
+				break
+			}
+
+			// Run the Euclidean algorithm down the column until only the pivot is left
+			loop {
+				let smallest = (row..rows).filter(|&i| !h[(i, col)].is_zero()).min_by_key(|&i| h[(i, col)].euc_size());
+				let Some(pivot) = smallest else {
+					break
+				};
+				h.swap_rows(row, pivot);
+				u.swap_rows(row, pivot);
+
+				let mut cleared = true;
+				for i in row + 1..rows {
+					let q = h[(i, col)].quotient_and_remainder(&h[(row, col)]).0;
+					sub_row_multiple(&mut h, i, row, &q);
+					sub_row_multiple(&mut u, i, row, &q);
+					cleared &= h[(i, col)].is_zero();
+				}
+				if cleared {
+					break
+				}
+			}
+
+			if h[(row, col)].is_zero() {
+				continue
+			}
+
+			let (_, unit) = normalize(&h[(row, col)]);
+			scale_row(&mut h, row, &unit);
+			scale_row(&mut u, row, &unit);
+
+			// Reduce the entries above the pivot, taking the remainder that gcd leaves alone, which
+			// over the integers is the nonnegative one
+			for i in 0..row {
+				let (mut q, r) = h[(i, col)].quotient_and_remainder(&h[(row, col)]);
+				if normalize(&r).0 != r {
+					q.sub_assign_ref(&R::one());
+				}
+				sub_row_multiple(&mut h, i, row, &q);
+				sub_row_multiple(&mut u, i, row, &q);
+			}
+
+			row += 1;
+		}
+
+		(u, h)
+	}
+
+}
@@ -0,0 +1,218 @@
+//
+// Number-theoretic transforms over ZM<Q>, the finite field version of the fast Fourier transform,
+// in cyclic form for products modulo x^n - 1 and negacyclic form for products modulo x^n + 1
+//
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::algebra::*;
+use crate::std_impls::ZM;
+
+/// How many small bases to try raising to the power (Q - 1) / n when looking for a root of unity
+const ROOT_SEARCH_LIMIT: i64 = 100;
+
+// MARK: Roots of Unity
+
+/// A primitive n-th root of unity w in ZM<Q> with w^(n/2) = -1, for n a power of two, or None if
+/// none is found.
+///
+/// Such a root exists when Q is a prime with n dividing Q - 1, and is then z^((Q - 1) / n) for any
+/// non-residue z, so a few small candidates are tried and each one is checked directly. The check
+/// is all a transform of length n needs, so Q need not be prime.
+pub fn root_of_unity<const Q: i64>(n: usize) -> Option<ZM<Q>> {
+	if n == 1 {
+		return Some(ZM::one())
+	}
+
+	if !n.is_power_of_two() || Q % 2 == 0 || (Q - 1) % n as i64 != 0 {
+		return None
+	}
+
+	let minus_one = -ZM::<Q>::one();
+	(2..ROOT_SEARCH_LIMIT)
+		.map(|z| ZM::from_int(z).power((Q - 1) / n as i64))
+		.find(|w| w.power(n as i64 / 2) == minus_one)
+}
+
+/// Whether or not w is a root of unity a transform of length n can use, meaning n is a power of
+/// two invertible in ZM<Q> and w^(n/2) = -1, or w = 1 when n = 1. This makes w a primitive n-th
+/// root of unity whose powers sum to zero, which is what inverting the transform relies on.
+pub fn is_transform_root<const Q: i64>(w: &ZM<Q>, n: usize) -> bool {
+	if n == 1 {
+		return *w == ZM::one()
+	}
+
+	n.is_power_of_two() && Q % 2 == 1 && w.power(n as i64 / 2) == -ZM::<Q>::one()
+}
+
+// MARK: Transforms
+
+/// Replaces a, of length n a power of two, with its values a(1), a(w), ..., a(w^(n-1)) at the
+/// powers of a primitive n-th root of unity w, by the iterative Cooley-Tukey algorithm
+fn transform<const Q: i64>(a: &mut [ZM<Q>], w: ZM<Q>) {
+	let n = a.len();
+
+	// Put the coefficients in bit-reversed order, so the butterflies can work in place
+	let mut j = 0;
+	for i in 1..n {
+		let mut bit = n >> 1;
+		while j & bit != 0 {
+			j ^= bit;
+			bit >>= 1;
+		}
+		j |= bit;
+
+		if i < j {
+			a.swap(i, j);
+		}
+	}
+
+	let mut length = 2;
+	while length <= n {
+		let step = w.power((n / length) as i64);
+		for block in a.chunks_mut(length) {
+			let (low, high) = block.split_at_mut(length / 2);
+			let mut twiddle = ZM::one();
+			for (u, v) in low.iter_mut().zip(high.iter_mut()) {
+				let t = *v * twiddle;
+				*v = *u - t;
+				*u += t;
+				twiddle *= step;
+			}
+		}
+		length <<= 1;
+	}
+}
+
+/// The number-theoretic transform of a, whose length must be a power of two, the values
+/// a(1), a(w), ..., a(w^(n-1)) of a as a polynomial at the powers of the root of unity w found by
+/// `root_of_unity`, or None if ZM<Q> has none of order n
+pub fn ntt<const Q: i64>(a: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	ntt_with_root(a, root_of_unity(a.len())?)
+}
+
+/// The inverse of `ntt`, recovering the coefficients from the values
+pub fn intt<const Q: i64>(a: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	intt_with_root(a, root_of_unity(a.len())?)
+}
+
+/// The number-theoretic transform using the given root of unity, or None if it is not one
+/// `is_transform_root` accepts for the length of a
+pub fn ntt_with_root<const Q: i64>(a: &[ZM<Q>], w: ZM<Q>) -> Option<Vec<ZM<Q>>> {
+	if !a.len().is_power_of_two() {
+		panic!("Transforms need a power of two length, got {}", a.len())
+	}
+
+	if !is_transform_root(&w, a.len()) {
+		return None
+	}
+
+	let mut values = a.to_vec();
+	transform(&mut values, w);
+	Some(values)
+}
+
+/// The inverse transform for the given root of unity, which is the transform for its inverse
+/// scaled by 1/n, or None if it is not a valid root
+pub fn intt_with_root<const Q: i64>(a: &[ZM<Q>], w: ZM<Q>) -> Option<Vec<ZM<Q>>> {
+	let values = ntt_with_root(a, w.try_inverse()?)?;
+	let scale = ZM::from_int(a.len() as i64).inverse();
+	Some(values.into_iter().map(|x| x * scale).collect())
+}
+
+// MARK: Negacyclic Transforms
+
+/// The negacyclic transform of a, whose length n must be a power of two, the values
+/// a(psi), a(psi^3), ..., a(psi^(2n-1)) at the roots of x^n + 1, where psi is a primitive 2n-th
+/// root of unity, or None if ZM<Q> has none.
+///
+/// Multiplying two such transforms pointwise multiplies the polynomials modulo x^n + 1, as in
+/// the rings Z_q[x] / (x^n + 1) of lattice cryptography.
+pub fn negacyclic_ntt<const Q: i64>(a: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	negacyclic_ntt_with_root(a, root_of_unity(2 * a.len())?)
+}
+
+/// The inverse of `negacyclic_ntt`
+pub fn negacyclic_intt<const Q: i64>(a: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	negacyclic_intt_with_root(a, root_of_unity(2 * a.len())?)
+}
+
+/// The negacyclic transform using the given 2n-th root of unity psi, or None if it is not one
+/// `is_transform_root` accepts for length 2n.
+///
+/// This twists a_i by psi^i, turning reduction modulo x^n + 1 into reduction modulo x^n - 1, then
+/// takes the ordinary transform with the n-th root of unity psi^2.
+pub fn negacyclic_ntt_with_root<const Q: i64>(a: &[ZM<Q>], psi: ZM<Q>) -> Option<Vec<ZM<Q>>> {
+	if !is_transform_root(&psi, 2 * a.len()) {
+		return None
+	}
+
+	let mut twist = ZM::one();
+	let twisted: Vec<ZM<Q>> = a.iter().map(|&x| {
+		let y = x * twist;
+		twist *= psi;
+		y
+	}).collect();
+
+	ntt_with_root(&twisted, psi * psi)
+}
+
+/// The inverse negacyclic transform for the given 2n-th root of unity psi
+pub fn negacyclic_intt_with_root<const Q: i64>(a: &[ZM<Q>], psi: ZM<Q>) -> Option<Vec<ZM<Q>>> {
+	if !is_transform_root(&psi, 2 * a.len()) {
+		return None
+	}
+
+	let untwist = psi.inverse();
+	let mut twist = ZM::one();
+	Some(intt_with_root(a, psi * psi)?.into_iter().map(|x| {
+		let y = x * twist;
+		twist *= untwist;
+		y
+	}).collect())
+}
+
+/// The product of a and b modulo x^n + 1, for a and b of the same length n a power of two, by
+/// the negacyclic transform, or None if ZM<Q> has no primitive 2n-th root of unity
+pub fn negacyclic_multiply<const Q: i64>(a: &[ZM<Q>], b: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	if a.len() != b.len() {
+		panic!("Negacyclic products need factors of the same length")
+	}
+
+	let psi = root_of_unity(2 * a.len())?;
+	let (fa, fb) = (negacyclic_ntt_with_root(a, psi)?, negacyclic_ntt_with_root(b, psi)?);
+	let product: Vec<ZM<Q>> = fa.into_iter().zip(fb).map(|(x, y)| x * y).collect();
+	negacyclic_intt_with_root(&product, psi)
+}
+
+// MARK: Convolution
+
+/// The linear convolution of a and b by transforming both, multiplying pointwise, and
+/// transforming back, or None if ZM<Q> has no root of unity of a large enough power of two order
+pub(crate) fn convolve<const Q: i64>(a: &[ZM<Q>], b: &[ZM<Q>]) -> Option<Vec<ZM<Q>>> {
+	if a.is_empty() || b.is_empty() {
+		return Some(vec![])
+	}
+
+	let length = a.len() + b.len() - 1;
+	let n = length.next_power_of_two();
+	let w = root_of_unity::<Q>(n)?;
+
+	let mut fa = a.to_vec();
+	let mut fb = b.to_vec();
+	fa.resize(n, ZM::zero());
+	fb.resize(n, ZM::zero());
+
+	transform(&mut fa, w);
+	transform(&mut fb, w);
+	for (x, y) in fa.iter_mut().zip(fb.iter()) {
+		*x *= *y;
+	}
+
+	// Transforming with w^(-1) inverts the transform, up to a factor of n
+	transform(&mut fa, w.inverse());
+	let scale = ZM::from_int(n as i64).inverse();
+	fa.truncate(length);
+	Some(fa.into_iter().map(|x| x * scale).collect())
+}
@@ -0,0 +1,310 @@
+//
+// Bridges between the algebra traits and the num-traits ones, so that number types from the num
+// ecosystem can be used as rings here, and rings from here can be used by code written against num
+//
+
+use core::fmt::Display;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use num_traits::{Inv, Num, One, Zero};
+
+use crate::algebra::*;
+
+// MARK: Num Types as Rings
+
+/// A `num_traits::Num` type used as a `Ring`, like `num_rational::Ratio<i32>` or a fixed point or
+/// complex type from another crate.
+///
+/// The types which can also be inverted through `num_traits::Inv`, like floats and ratios, are
+/// fields as well.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumRing<T>(pub T);
+
+impl<T> NumRing<T> {
+
+	/// The underlying number
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+
+}
+
+impl<T> From<T> for NumRing<T> {
+	fn from(x: T) -> Self {
+		NumRing(x)
+	}
+}
+
+impl<T: Display> Display for NumRing<T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T: Num> Add for NumRing<T> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		NumRing(self.0 + rhs.0)
+	}
+}
+
+impl<T: Num + Clone> AddAssign for NumRing<T> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 = self.0.clone() + rhs.0
+	}
+}
+
+impl<T: Num> Sub for NumRing<T> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		NumRing(self.0 - rhs.0)
+	}
+}
+
+impl<T: Num + Clone> SubAssign for NumRing<T> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 = self.0.clone() - rhs.0
+	}
+}
+
+impl<T: Num + Neg<Output = T>> Neg for NumRing<T> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		NumRing(-self.0)
+	}
+}
+
+impl<T: Num> Mul for NumRing<T> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		NumRing(self.0 * rhs.0)
+	}
+}
+
+impl<T: Num + Clone> MulAssign for NumRing<T> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.0 = self.0.clone() * rhs.0
+	}
+}
+
+impl<T: Num> Div for NumRing<T> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		NumRing(self.0 / rhs.0)
+	}
+}
+
+impl<T: Num + Clone> DivAssign for NumRing<T> {
+	fn div_assign(&mut self, rhs: Self) {
+		self.0 = self.0.clone() / rhs.0
+	}
+}
+
+impl<T: Num + Clone + core::fmt::Debug> Semiring for NumRing<T> {
+	fn one() -> Self {
+		NumRing(T::one())
+	}
+
+	fn zero() -> Self {
+		NumRing(T::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+}
+
+/// Negative powers divide one by the positive power, and panic when that is not exact
+impl<T: Num + Clone + core::fmt::Debug + Neg<Output = T>> Ring for NumRing<T> {
+	fn power(&self, n: i64) -> Self {
+		let power = NumRing(num_traits::pow(self.0.clone(), n.unsigned_abs() as usize));
+		if n >= 0 {
+			return power
+		}
+
+		let inverse = NumRing(T::one() / power.0.clone());
+		if inverse.clone() * power != NumRing::one() {
+			panic!("Cannot invert ring element")
+		}
+		inverse
+	}
+}
+
+impl<T: Num + Clone + core::fmt::Debug + Neg<Output = T> + Inv<Output = T>> Field for NumRing<T> {
+	fn inverse(&self) -> Self {
+		if self.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+		NumRing(self.0.clone().inv())
+	}
+}
+
+// MARK: Rings as Num Types
+
+/// A ring from this crate used as a num-traits number, for code like `ndarray` which is generic
+/// over `Zero` and `One`, or over `Num`.
+///
+/// Every ring is `Zero` and `One`. Fields are also `Num`, with exact division and a remainder of
+/// zero, and parse integers in any radix by reading their digits in the field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NumWrapper<R: Ring>(pub R);
+
+impl<R: Ring> NumWrapper<R> {
+
+	/// The underlying ring element
+	pub fn into_inner(self) -> R {
+		self.0
+	}
+
+}
+
+impl<R: Ring> From<R> for NumWrapper<R> {
+	fn from(x: R) -> Self {
+		NumWrapper(x)
+	}
+}
+
+impl<R: Ring + Display> Display for NumWrapper<R> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		Display::fmt(&self.0, f)
+	}
+}
+
+impl<R: Ring> Add for NumWrapper<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		NumWrapper(self.0 + rhs.0)
+	}
+}
+
+impl<R: Ring> AddAssign for NumWrapper<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0
+	}
+}
+
+impl<R: Ring> Sub for NumWrapper<R> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		NumWrapper(self.0 - rhs.0)
+	}
+}
+
+impl<R: Ring> SubAssign for NumWrapper<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0
+	}
+}
+
+impl<R: Ring> Neg for NumWrapper<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		NumWrapper(-self.0)
+	}
+}
+
+impl<R: Ring> Mul for NumWrapper<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		NumWrapper(self.0 * rhs.0)
+	}
+}
+
+impl<R: Ring> MulAssign for NumWrapper<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.0 *= rhs.0
+	}
+}
+
+impl<F: Field> Div for NumWrapper<F> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		NumWrapper(self.0 / rhs.0)
+	}
+}
+
+impl<F: Field> DivAssign for NumWrapper<F> {
+	fn div_assign(&mut self, rhs: Self) {
+		self.0 /= rhs.0
+	}
+}
+
+/// Division in a field is exact, so the remainder is always zero
+impl<F: Field> Rem for NumWrapper<F> {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		if rhs.0.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+		NumWrapper(F::zero())
+	}
+}
+
+impl<F: Field> RemAssign for NumWrapper<F> {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = self.clone() % rhs
+	}
+}
+
+impl<R: Ring> Zero for NumWrapper<R> {
+	fn zero() -> Self {
+		NumWrapper(R::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+}
+
+impl<R: Ring> One for NumWrapper<R> {
+	fn one() -> Self {
+		NumWrapper(R::one())
+	}
+}
+
+impl<F: Field> Num for NumWrapper<F> {
+	type FromStrRadixErr = ParseRadixError;
+
+	/// Reads an integer, possibly negative, as its image in the field
+	fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+		let (negative, digits) = match s.strip_prefix('-') {
+			Some(digits) => (true, digits),
+			None => (false, s.strip_prefix('+').unwrap_or(s))
+		};
+		if digits.is_empty() {
+			return Err(ParseRadixError)
+		}
+
+		let base = (0..radix).fold(F::zero(), |sum, _| sum + F::one());
+		let mut n = F::zero();
+		for c in digits.chars() {
+			let d = c.to_digit(radix).ok_or(ParseRadixError)?;
+			n = n * base.clone() + (0..d).fold(F::zero(), |sum, _| sum + F::one());
+		}
+
+		Ok(NumWrapper(if negative { -n } else { n }))
+	}
+}
+
+/// Text which is not an integer in the radix it was read in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseRadixError;
+
+impl Display for ParseRadixError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "Not an integer in the given radix")
+	}
+}
+
+impl core::error::Error for ParseRadixError { }
@@ -0,0 +1,154 @@
+//
+// Classical versions of the pieces of Shor's algorithm: the period of a modulo n, the continued
+// fraction step recovering a period from a measurement of the quantum Fourier transform, and
+// factoring n from a period
+//
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::continued_fraction::{continued_fraction, convergents};
+use crate::std_impls::is_perfect_power;
+
+/// a^e modulo n, for 0 <= a < n
+fn pow_mod(a: i64, e: u64, n: i64) -> i64 {
+	let (mut power, mut base, mut e) = (1 % n as i128, a as i128, e);
+	while e > 0 {
+		if e & 1 == 1 {
+			power = power * base % n as i128;
+		}
+		base = base * base % n as i128;
+		e >>= 1;
+	}
+	power as i64
+}
+
+// MARK: Periods
+
+/// The multiplicative period of a modulo n, the least r > 0 with a^r = 1 modulo n, or None unless a
+/// is coprime to n > 1.
+///
+/// This steps through the powers of a one at a time, taking up to n steps, since bounding it by a
+/// multiple of the order of the unit group would need the factorization of n. Finding r quickly
+/// is the part of Shor's algorithm a quantum computer does.
+pub fn multiplicative_period(a: i64, n: i64) -> Option<u64> {
+	if n <= 1 {
+		panic!("The modulus must be greater than 1, got {}", n)
+	}
+
+	let a = a.rem_euclid(n);
+	if gcd(&a, &n) != 1 {
+		return None
+	}
+
+	let mut power = a;
+	let mut r = 1;
+	while power != 1 % n {
+		power = (power as i128 * a as i128 % n as i128) as i64;
+		r += 1;
+	}
+	Some(r)
+}
+
+/// A measurement y of the first register after the quantum Fourier transform in Shor's algorithm
+/// on a modulo n, over q = 2^t states, sampled from the ideal outcome round(s q / r) for a uniform
+/// s in [0, r), where r is the period. This stands in for the quantum computer, and finds r
+/// classically to do so. Needs a coprime to n.
+pub fn simulate_measurement_with_rng<G: Rng + ?Sized>(a: i64, n: i64, q: u64, rng: &mut G) -> u64 {
+	let r = multiplicative_period(a, n)
+		.unwrap_or_else(|| panic!("{} is not a unit modulo {}", a, n));
+	let s = rng.gen_range(0..r) as u128;
+	((2 * s * q as u128 + r as u128) / (2 * r as u128) % q as u128) as u64
+}
+
+/// The period of a modulo n, from a measurement y out of q states close to s q / r, or None if this
+/// measurement does not reveal it.
+///
+/// When q >= n^2 and |y / q - s / r| <= 1 / (2q), s / r is a convergent of the continued fraction
+/// of y / q, so its denominator is r divided by gcd(s, r). Each convergent's denominator d < n is
+/// tried, along with small multiples of it in case s and r share a factor, checking a^d = 1.
+pub fn period_from_measurement(a: i64, n: i64, measurement: u64, q: u64) -> Option<u64> {
+	let a = a.rem_euclid(n);
+	let quotients = continued_fraction(&(measurement as i128), &(q as i128));
+
+	for (_, d) in convergents(&quotients) {
+		if d <= 0 || d >= n as i128 {
+			continue
+		}
+
+		for multiple in 1..=4 {
+			let candidate = d as u64 * multiple;
+			if candidate < n as u64 && pow_mod(a, candidate, n) == 1 % n {
+				return Some(candidate)
+			}
+		}
+	}
+
+	None
+}
+
+// MARK: Factoring
+
+/// A nontrivial factorization (d, n / d) of n found from the period r of a modulo n, or None unless
+/// r is even and a^(r/2) is not -1 modulo n.
+///
+/// Then a^(r/2) is a square root of 1 other than 1 and -1, so n divides (a^(r/2) - 1)(a^(r/2) + 1)
+/// but neither factor, and gcd(a^(r/2) - 1, n) is a proper divisor.
+pub fn factor_from_period(a: i64, n: i64, r: u64) -> Option<(i64, i64)> {
+	if !r.is_multiple_of(2) {
+		return None
+	}
+
+	let half = pow_mod(a.rem_euclid(n), r / 2, n);
+	if half == n - 1 || half == 1 {
+		return None
+	}
+
+	let d = gcd(&(half - 1), &n);
+	(d > 1 && d < n).then(|| (d, n / d))
+}
+
+/// A nontrivial factorization (d, n / d) of the composite n > 1 by Shor's algorithm, with the
+/// quantum period finding simulated classically, using the given source of randomness, or None
+/// if n is prime or `attempts` random bases all fail.
+///
+/// Even numbers and perfect powers are split directly, as Shor's algorithm needs an odd n with two
+/// distinct prime factors. Otherwise, each attempt picks a random base a, which may share a factor
+/// with n outright, and else measures with q = 2^t >= n^2 states and recovers the period.
+pub fn shor_factor_with_rng<G: Rng + ?Sized>(n: i64, attempts: usize, rng: &mut G) -> Option<(i64, i64)> {
+	if n <= 3 {
+		return None
+	}
+	if n % 2 == 0 {
+		return Some((2, n / 2))
+	}
+	if let Some((base, _)) = is_perfect_power(n) {
+		return Some((base, n / base))
+	}
+
+	let q = (n as u128 * n as u128).next_power_of_two();
+	if q > u64::MAX as u128 {
+		panic!("{} is too large to simulate period finding for", n)
+	}
+
+	for _ in 0..attempts {
+		let a = rng.gen_range(2..n - 1);
+		let d = gcd(&a, &n);
+		if d > 1 {
+			return Some((d, n / d))
+		}
+
+		let measurement = simulate_measurement_with_rng(a, n, q as u64, rng);
+		if let Some(factors) = period_from_measurement(a, n, measurement, q as u64).and_then(|r| factor_from_period(a, n, r)) {
+			return Some(factors)
+		}
+	}
+
+	None
+}
+
+/// A nontrivial factorization (d, n / d) of the composite n > 1 by simulated Shor's algorithm, or
+/// None if n is prime or 20 random bases all fail
+pub fn shor_factor(n: i64) -> Option<(i64, i64)> {
+	shor_factor_with_rng(n, 20, &mut rand::thread_rng())
+}
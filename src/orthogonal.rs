@@ -0,0 +1,60 @@
+//
+// Orthogonal projection and Gram-Schmidt orthogonalization in inner product spaces, done exactly
+// over any field by never normalizing
+//
+
+use crate::algebra::*;
+
+// MARK: Projection
+
+/// The coefficient <v, u> / <u, u> of the projection of v onto the line through u, or None if u is
+/// zero. Panics if u is a nonzero vector with <u, u> = 0, which only happens over fields like
+/// ZM<Q> that are not ordered, and leaves no orthogonal projection onto u.
+fn projection_coefficient<F: Field, V: VectorSpace<F> + InnerProductSpace<F>>(v: &V, u: &V) -> Option<F> {
+	let length = u.inner_product(u.clone());
+	if length.is_zero() {
+		if *u != u.clone() - u.clone() {
+			panic!("Cannot project onto a nonzero vector orthogonal to itself")
+		}
+		return None
+	}
+
+	Some(v.inner_product(u.clone()) * length.inverse())
+}
+
+/// The orthogonal projection of v onto the span of the given pairwise orthogonal vectors, such as
+/// the output of `gram_schmidt`, which is the sum of its projections onto each of them. Zero
+/// vectors among them are ignored.
+pub fn project<F: Field, V: VectorSpace<F> + InnerProductSpace<F>>(v: &V, orthogonal_basis: &[V]) -> V {
+	orthogonal_basis.iter().fold(v.clone() - v.clone(), |sum, u| match projection_coefficient(v, u) {
+		Some(c) => sum + u.scale(&c),
+		None => sum
+	})
+}
+
+// MARK: Gram-Schmidt
+
+/// Pairwise orthogonal vectors u_0, ..., u_(k-1) with each u_i equal to v_i minus its projection
+/// onto the span of v_0, ..., v_(i-1), so that the first i of them span the same space as the
+/// first i of the v_i.
+///
+/// The u_i are not normalized, which would need square roots, so over the rationals they stay
+/// rational. When some v_i depends on the earlier ones, u_i is zero, so the outputs still line up
+/// with the inputs.
+pub fn gram_schmidt<F: Field, V: VectorSpace<F> + InnerProductSpace<F>>(basis: &[V]) -> Vec<V> {
+	let mut orthogonal: Vec<V> = Vec::with_capacity(basis.len());
+
+	for v in basis {
+		// Subtracting each projection from the running remainder in turn, as in modified
+		// Gram-Schmidt, loses less precision over floating point than projecting v all at once
+		let mut u = v.clone();
+		for w in orthogonal.iter() {
+			if let Some(c) = projection_coefficient(&u, w) {
+				u -= w.scale(&c);
+			}
+		}
+		orthogonal.push(u);
+	}
+
+	orthogonal
+}
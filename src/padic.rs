@@ -0,0 +1,318 @@
+//
+// p-adic numbers with capped relative precision, and Hensel lifting of roots mod p to p-adic roots
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+// MARK: p-adic Numbers
+
+/// A p-adic number p^v u, for a prime P, stored as its valuation v and a unit u known modulo P^N,
+/// so that every nonzero element carries N significant digits, like a floating point number with
+/// a base P mantissa. P^N must fit in an i64.
+///
+/// Digits beyond the precision are taken to be zero, and a sum whose first N digits cancel
+/// becomes zero, so results are exact only to the precision they are computed at.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Padic<const P: i64, const N: u32 = 16> {
+	/// In [0, P^N) and not divisible by P, or zero for the zero element
+	unit: i64,
+
+	/// The valuation, or zero for the zero element
+	valuation: i64
+}
+
+impl<const P: i64, const N: u32> Padic<P, N> {
+
+	/// P^N, the modulus the unit is known to
+	const MODULUS: i64 = {
+		assert!(P >= 2 && N >= 1, "A p-adic type needs a prime P and at least one digit of precision");
+		let mut m: i128 = 1;
+		let mut i = 0;
+		while i < N {
+			m *= P as i128;
+			assert!(m <= i64::MAX as i128, "P^N must fit in an i64");
+			i += 1;
+		}
+		m as i64
+	};
+
+	/// The number of significant digits, N
+	pub fn precision() -> u32 {
+		N
+	}
+
+	/// The modulus P^N that units are known to
+	pub fn modulus() -> i64 {
+		Self::MODULUS
+	}
+
+	/// p^valuation unit, reducing unit modulo P^N and moving any factors of P into the valuation
+	fn normalized(unit: i128, valuation: i64) -> Self {
+		let mut unit = unit.rem_euclid(Self::MODULUS as i128);
+		if unit == 0 {
+			return Padic { unit: 0, valuation: 0 }
+		}
+
+		let mut valuation = valuation;
+		while unit % P as i128 == 0 {
+			unit /= P as i128;
+			valuation += 1;
+		}
+		Padic { unit: unit as i64, valuation }
+	}
+
+	/// The integer n
+	pub fn from_int(n: i64) -> Self {
+		Self::normalized(n as i128, 0)
+	}
+
+	/// The rational number numerator / denominator, which needs a nonzero denominator
+	pub fn from_rational(numerator: i64, denominator: i64) -> Self {
+		Self::from_int(numerator) / Self::from_int(denominator)
+	}
+
+	/// The p-adic number p^k
+	pub fn p_power(k: i64) -> Self {
+		Padic { unit: 1, valuation: k }
+	}
+
+	/// The valuation v with this element equal to p^v times a unit, or None for zero
+	pub fn valuation(&self) -> Option<i64> {
+		(!self.is_zero()).then_some(self.valuation)
+	}
+
+	/// The unit u with this element equal to p^v u, in [0, P^N), or zero for zero
+	pub fn unit(&self) -> i64 {
+		self.unit
+	}
+
+	/// The p-adic absolute value p^-v
+	pub fn norm(&self) -> f64 {
+		match self.valuation() {
+			Some(v) => (P as f64).powi(-(v as i32)),
+			None => 0.0
+		}
+	}
+
+	/// Whether or not this element is a p-adic integer, with nonnegative valuation
+	pub fn is_integral(&self) -> bool {
+		self.valuation().is_none_or(|v| v >= 0)
+	}
+
+	/// The first N digits of this p-adic integer as an integer in [0, P^N), which is congruent to
+	/// it modulo P^N. Panics if this element is not integral.
+	pub fn to_integer(&self) -> i64 {
+		if !self.is_integral() {
+			panic!("{:?} is not a p-adic integer", self)
+		}
+		if self.valuation >= N as i64 {
+			return 0
+		}
+
+		let shift = (P as i128).pow(self.valuation as u32);
+		((self.unit as i128 * shift) % Self::MODULUS as i128) as i64
+	}
+
+	/// The reduction of this p-adic integer modulo p. Panics if it is not integral.
+	pub fn residue(&self) -> ZM<P> {
+		ZM::from_int(self.to_integer())
+	}
+
+	/// The digits d_0, d_1, ... in [0, P) of the unit, lowest first, so that this element is the
+	/// sum of d_i p^(v + i)
+	pub fn digits(&self) -> Vec<i64> {
+		let mut u = self.unit;
+		(0..N).map(|_| {
+			let d = u % P;
+			u /= P;
+			d
+		}).collect()
+	}
+
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const P: i64, const N: u32> Add for Padic<P, N> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		if self.is_zero() {
+			return rhs
+		} else if rhs.is_zero() {
+			return self
+		}
+
+		let (low, high) = if self.valuation <= rhs.valuation { (self, rhs) } else { (rhs, self) };
+		let gap = high.valuation - low.valuation;
+		if gap >= N as i64 {
+			return low
+		}
+
+		let shifted = high.unit as i128 * (P as i128).pow(gap as u32);
+		Self::normalized(low.unit as i128 + shifted, low.valuation)
+	}
+}
+
+impl<const P: i64, const N: u32> AddAssign for Padic<P, N> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<const P: i64, const N: u32> Neg for Padic<P, N> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Self::normalized(-(self.unit as i128), self.valuation)
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const P: i64, const N: u32> Sub for Padic<P, N> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + -rhs
+	}
+}
+
+impl<const P: i64, const N: u32> SubAssign for Padic<P, N> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const P: i64, const N: u32> Mul for Padic<P, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		if self.is_zero() || rhs.is_zero() {
+			return Self::zero()
+		}
+		Self::normalized(self.unit as i128 * rhs.unit as i128, self.valuation + rhs.valuation)
+	}
+}
+
+impl<const P: i64, const N: u32> MulAssign for Padic<P, N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const P: i64, const N: u32> Div for Padic<P, N> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const P: i64, const N: u32> DivAssign for Padic<P, N> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<const P: i64, const N: u32> Semiring for Padic<P, N> {
+	fn one() -> Self {
+		Self::from_int(1)
+	}
+
+	fn zero() -> Self {
+		Padic { unit: 0, valuation: 0 }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.unit == 0
+	}
+}
+
+impl<const P: i64, const N: u32> Ring for Padic<P, N> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
+
+		let mut power = Self::one();
+		let mut base = *self;
+		let mut e = n;
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base;
+			}
+			base *= base;
+			e >>= 1;
+		}
+		power
+	}
+}
+
+impl<const P: i64, const N: u32> Field for Padic<P, N> {
+	fn inverse(&self) -> Self {
+		if self.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+
+		let inverse = mod_inverse(&(self.unit as i128), &(Self::MODULUS as i128)).unwrap();
+		Self::normalized(inverse, -self.valuation)
+	}
+}
+
+// MARK: Hensel Lifting
+
+impl<const P: i64, const N: u32> Padic<P, N> {
+
+	/// The p-adic root of f congruent to the given approximate root, found by Newton's method, or
+	/// None unless the approximation is integral and a simple root of f modulo p, meaning that
+	/// f(a) = 0 and f'(a) != 0 modulo p, which Hensel's lemma needs for the root to lift uniquely.
+	/// The coefficients of f must be p-adic integers.
+	///
+	/// Each step of r <- r - f(r) / f'(r) doubles the number of correct digits.
+	pub fn hensel_lift(f: &Polynomial<Padic<P, N>>, approximation: &Padic<P, N>) -> Option<Padic<P, N>> {
+		let derivative = f.derivative();
+		let is_small = |x: &Padic<P, N>| x.valuation().is_none_or(|v| v > 0);
+
+		if !approximation.is_integral() || !is_small(&f.evaluate(approximation)) || is_small(&derivative.evaluate(approximation)) {
+			return None
+		}
+
+		let mut root = *approximation;
+		let mut correct = 1;
+		while correct < 2 * N {
+			let value = f.evaluate(&root);
+			if value.is_zero() {
+				break
+			}
+			root -= value / derivative.evaluate(&root);
+			correct *= 2;
+		}
+
+		Some(root)
+	}
+
+	/// The p-adic integer roots of f lifted from the simple roots of f modulo p, after scaling f
+	/// so that its coefficients are p-adic integers, not all divisible by p. Roots which are
+	/// repeated modulo p are left out, since they may lift to several p-adic roots or to none.
+	pub fn simple_roots(f: &Polynomial<Padic<P, N>>) -> Vec<Padic<P, N>> {
+		let Some(lowest) = f.coefficients().iter().filter_map(Padic::valuation).min() else {
+			return Vec::new()
+		};
+
+		let f = f.scale(&Padic::p_power(-lowest));
+		let reduced = Polynomial::new(f.coefficients().iter().map(Padic::residue).collect());
+		if reduced.degree() == 0 {
+			return Vec::new()
+		}
+
+		reduced.roots_mod_p().into_iter()
+			.filter_map(|a| Padic::hensel_lift(&f, &Padic::from_int(a.val)))
+			.collect()
+	}
+
+}
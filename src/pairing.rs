@@ -0,0 +1,354 @@
+//
+// The Weil and Tate pairings on elliptic curves, by Miller's algorithm
+//
+
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::elliptic_curve::ECPoint;
+use crate::extension_field::{ModulusPolynomial, GF};
+use crate::std_impls::ZM;
+
+// MARK: Points over Extension Fields
+
+/// A point on the elliptic curve y^2 = x^3 + Ax + B, which is defined over ZM<Q>, with
+/// coordinates in the extension field GF(Q^K)
+pub enum ExtensionPoint<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> {
+	/// The point at infinity, which is the group identity
+	Infinity,
+
+	/// A point (x, y) in the affine plane
+	Affine { x: GF<P, Q, K>, y: GF<P, Q, K> }
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> ExtensionPoint<P, Q, K, A, B> {
+
+	/// The coefficient A of the curve, in the extension field
+	fn a() -> GF<P, Q, K> {
+		GF::from_base(ZM::from_int(A))
+	}
+
+	/// The coefficient B of the curve, in the extension field
+	fn b() -> GF<P, Q, K> {
+		GF::from_base(ZM::from_int(B))
+	}
+
+	/// Whether or not the point (x, y) lies on the curve
+	pub fn contains(x: GF<P, Q, K>, y: GF<P, Q, K>) -> bool {
+		y * y == x * x * x + Self::a() * x + Self::b()
+	}
+
+	/// The affine point (x, y), if it lies on the curve
+	pub fn point(x: GF<P, Q, K>, y: GF<P, Q, K>) -> Option<Self> {
+		if Self::contains(x, y) {
+			Some(ExtensionPoint::Affine { x, y })
+		} else {
+			None
+		}
+	}
+
+	/// Whether or not this is the point at infinity
+	pub fn is_infinity(&self) -> bool {
+		matches!(self, ExtensionPoint::Infinity)
+	}
+
+	/// The affine coordinates of this point, if it is not the point at infinity
+	pub fn coordinates(&self) -> Option<(GF<P, Q, K>, GF<P, Q, K>)> {
+		match self {
+			ExtensionPoint::Infinity => None,
+			ExtensionPoint::Affine { x, y } => Some((*x, *y))
+		}
+	}
+
+	/// The image of this point under the Qth power Frobenius map
+	pub fn frobenius(&self) -> Self {
+		match self {
+			ExtensionPoint::Infinity => ExtensionPoint::Infinity,
+			ExtensionPoint::Affine { x, y } => ExtensionPoint::Affine { x: x.frobenius(), y: y.frobenius() }
+		}
+	}
+
+	/// Computes k * self using double-and-add
+	pub fn scalar_mul(&self, k: i64) -> Self {
+		if k < 0 {
+			return (-*self).scalar_mul(-k)
+		}
+
+		let mut result = ExtensionPoint::Infinity;
+		let mut addend = *self;
+		let mut k = k;
+
+		while k > 0 {
+			if k & 1 == 1 {
+				result += addend
+			}
+			addend += addend;
+			k >>= 1;
+		}
+
+		result
+	}
+
+	/// The slope of the line through self and other, or of the tangent line if they are equal,
+	/// or `None` if that line is vertical
+	fn slope(&self, other: &Self) -> Option<GF<P, Q, K>> {
+		let (x1, y1, x2, y2) = match (self, other) {
+			(ExtensionPoint::Affine { x: x1, y: y1 }, ExtensionPoint::Affine { x: x2, y: y2 }) => (*x1, *y1, *x2, *y2),
+			_ => return None
+		};
+
+		if x1 != x2 {
+			Some((y2 - y1) / (x2 - x1))
+		} else if y1 == y2 && !y1.is_zero() {
+			let three = GF::from_base(ZM::from_int(3));
+			Some((three * x1 * x1 + Self::a()) / (y1 + y1))
+		} else {
+			None
+		}
+	}
+
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> From<ECPoint<Q, A, B>> for ExtensionPoint<P, Q, K, A, B> {
+	fn from(point: ECPoint<Q, A, B>) -> Self {
+		match point {
+			ECPoint::Infinity => ExtensionPoint::Infinity,
+			ECPoint::Affine { x, y } => ExtensionPoint::Affine { x: GF::from_base(x), y: GF::from_base(y) }
+		}
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Clone for ExtensionPoint<P, Q, K, A, B> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Copy for ExtensionPoint<P, Q, K, A, B> { }
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Debug for ExtensionPoint<P, Q, K, A, B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ExtensionPoint::Infinity => write!(f, "Infinity"),
+			ExtensionPoint::Affine { x, y } => write!(f, "({:?}, {:?})", x, y)
+		}
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> PartialEq for ExtensionPoint<P, Q, K, A, B> {
+	fn eq(&self, other: &Self) -> bool {
+		self.coordinates() == other.coordinates()
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Eq for ExtensionPoint<P, Q, K, A, B> { }
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Hash for ExtensionPoint<P, Q, K, A, B> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.coordinates().hash(state)
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Add for ExtensionPoint<P, Q, K, A, B> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let (x1, y1, x2) = match (self, rhs) {
+			(ExtensionPoint::Infinity, _) => return rhs,
+			(_, ExtensionPoint::Infinity) => return self,
+			(ExtensionPoint::Affine { x: x1, y: y1 }, ExtensionPoint::Affine { x: x2, .. }) => (x1, y1, x2)
+		};
+
+		// A vertical line means P + (-P), or doubling a point of order 2
+		let Some(slope) = self.slope(&rhs) else {
+			return ExtensionPoint::Infinity
+		};
+
+		let x3 = slope * slope - x1 - x2;
+		let y3 = slope * (x1 - x3) - y1;
+
+		ExtensionPoint::Affine { x: x3, y: y3 }
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> AddAssign for ExtensionPoint<P, Q, K, A, B> {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Neg for ExtensionPoint<P, Q, K, A, B> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		match self {
+			ExtensionPoint::Infinity => ExtensionPoint::Infinity,
+			ExtensionPoint::Affine { x, y } => ExtensionPoint::Affine { x, y: -y }
+		}
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Sub for ExtensionPoint<P, Q, K, A, B> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self + (-rhs)
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> SubAssign for ExtensionPoint<P, Q, K, A, B> {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Mul for ExtensionPoint<P, Q, K, A, B> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> MulAssign for ExtensionPoint<P, Q, K, A, B> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self += rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Div for ExtensionPoint<P, Q, K, A, B> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self - rhs
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> DivAssign for ExtensionPoint<P, Q, K, A, B> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self -= rhs
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Monoid for ExtensionPoint<P, Q, K, A, B> {
+	fn identity() -> Self {
+		ExtensionPoint::Infinity
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> Group for ExtensionPoint<P, Q, K, A, B> {
+
+	fn inverse(&self) -> Self {
+		-*self
+	}
+
+	fn power(&self, n: i64) -> Self {
+		self.scalar_mul(n)
+	}
+}
+
+impl<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64> AbelianGroup for ExtensionPoint<P, Q, K, A, B> { }
+
+// MARK: Miller's Algorithm
+
+/// The line through t and u, or the tangent at t if they are equal, divided by the vertical line
+/// through t + u, evaluated at s. Returns `None` if s is a zero or pole of this function.
+fn line_over_vertical<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64>(
+	t: &ExtensionPoint<P, Q, K, A, B>,
+	u: &ExtensionPoint<P, Q, K, A, B>,
+	s: &ExtensionPoint<P, Q, K, A, B>
+) -> Option<GF<P, Q, K>> {
+	let (xt, yt) = t.coordinates()?;
+	let (xs, ys) = s.coordinates()?;
+
+	let (numerator, denominator) = match t.slope(u) {
+		// The vertical line x = xt, and t + u is infinity, where the vertical line is 1
+		None => (xs - xt, GF::one()),
+		Some(slope) => {
+			let (xv, _) = (*t + *u).coordinates()?;
+			(ys - yt - slope * (xs - xt), xs - xv)
+		}
+	};
+
+	if numerator.is_zero() || denominator.is_zero() {
+		None
+	} else {
+		Some(numerator / denominator)
+	}
+}
+
+/// The Miller function f_(r, p) evaluated at s, where f_(r, p) has divisor r(p) - r(O) when rp = O.
+///
+/// Returns `None` if s is a zero or pole of one of the lines along the way, which happens when s
+/// is too closely related to p, for example a multiple of it.
+pub fn miller_loop<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64>(
+	p: &ExtensionPoint<P, Q, K, A, B>,
+	s: &ExtensionPoint<P, Q, K, A, B>,
+	r: u64
+) -> Option<GF<P, Q, K>> {
+	if r == 0 || p.is_infinity() {
+		return None
+	}
+
+	let mut f = GF::one();
+	let mut t = *p;
+
+	for i in (0..63 - r.leading_zeros()).rev() {
+		f = f * f * line_over_vertical(&t, &t, s)?;
+		t += t;
+
+		if (r >> i) & 1 == 1 {
+			f *= line_over_vertical(&t, p, s)?;
+			t += *p;
+		}
+	}
+
+	Some(f)
+}
+
+// MARK: Pairings
+
+/// The reduced Tate pairing f_(r, p)(s)^((Q^K - 1) / r), for p in E(ZM<Q>)[r] and s in E(GF(Q^K)).
+///
+/// This is bilinear, and nondegenerate when K is the embedding degree of r, the smallest K with r
+/// dividing Q^K - 1. Returns `None` if Miller's algorithm hits a zero or pole of one of its lines,
+/// and panics unless r divides Q^K - 1 and rp = O.
+pub fn tate_pairing<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64>(
+	p: &ECPoint<Q, A, B>,
+	s: &ExtensionPoint<P, Q, K, A, B>,
+	r: u64
+) -> Option<GF<P, Q, K>> {
+	let exponent = GF::<P, Q, K>::order() - 1;
+	if !exponent.is_multiple_of(r as u128) {
+		panic!("{} does not divide {}^{} - 1", r, Q, K)
+	}
+	if !p.scalar_mul(r as i64).is_infinity() {
+		panic!("{:?} is not an {}-torsion point", p, r)
+	}
+
+	Some(miller_loop(&ExtensionPoint::from(*p), s, r)?.pow_u128(exponent / r as u128))
+}
+
+/// The Weil pairing (-1)^r f_(r, p)(s) / f_(r, s)(p) of two r-torsion points, which is an rth root of unity.
+///
+/// This is bilinear and alternating, and nondegenerate on E[r]. Returns `None` if Miller's algorithm
+/// hits a zero or pole of one of its lines, which happens when p and s generate the same subgroup,
+/// and panics unless rp = rs = O.
+pub fn weil_pairing<P: ModulusPolynomial<Q, K>, const Q: i64, const K: usize, const A: i64, const B: i64>(
+	p: &ExtensionPoint<P, Q, K, A, B>,
+	s: &ExtensionPoint<P, Q, K, A, B>,
+	r: u64
+) -> Option<GF<P, Q, K>> {
+	if !p.scalar_mul(r as i64).is_infinity() || !s.scalar_mul(r as i64).is_infinity() {
+		panic!("Both points must be {}-torsion points", r)
+	}
+
+	let quotient = miller_loop(p, s, r)? / miller_loop(s, p, r)?;
+	Some(if r % 2 == 1 { -quotient } else { quotient })
+}
@@ -0,0 +1,58 @@
+//
+// Parallel versions of the heavier matrix and polynomial kernels, spread over a rayon thread pool
+//
+
+use rayon::prelude::*;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::ntt;
+use crate::polynomial::Polynomial;
+use crate::std_impls::ZM;
+
+// MARK: Matrices
+
+impl<R: Semiring + Send + Sync> Matrix<R> {
+
+	/// The product of two borrowed matrices, as `mul_ref` computes it, with the rows of the result
+	/// computed in parallel
+	pub fn par_mul_ref(&self, rhs: &Matrix<R>) -> Matrix<R> {
+		if self.cols() != rhs.rows() {
+			panic!("Cannot multiply a {}x{} matrix by a {}x{} matrix", self.rows(), self.cols(), rhs.rows(), rhs.cols())
+		}
+
+		let entries: Vec<R> = (0..self.rows()).into_par_iter().flat_map_iter(|i| {
+			let mut row = vec![R::zero(); rhs.cols()];
+			for (k, a) in self.row(i).iter().enumerate() {
+				for (sum, b) in row.iter_mut().zip(rhs.row(k).iter()) {
+					sum.add_assign_ref(&a.mul_ref(b));
+				}
+			}
+			row
+		}).collect();
+
+		Matrix::new(self.rows(), rhs.cols(), entries)
+	}
+
+}
+
+// MARK: Transforms
+
+/// The number-theoretic transform `ntt::ntt` of each of the inputs, in parallel, or None if one
+/// of them has a length ZM<Q> has no root of unity for
+pub fn ntt_batch<const Q: i64>(inputs: &[Vec<ZM<Q>>]) -> Option<Vec<Vec<ZM<Q>>>> {
+	inputs.par_iter().map(|a| ntt::ntt(a)).collect()
+}
+
+/// The inverse transform `ntt::intt` of each of the inputs, in parallel
+pub fn intt_batch<const Q: i64>(inputs: &[Vec<ZM<Q>>]) -> Option<Vec<Vec<ZM<Q>>>> {
+	inputs.par_iter().map(|a| ntt::intt(a)).collect()
+}
+
+// MARK: Polynomial Evaluation
+
+/// The values of each polynomial at every one of the points, by `Polynomial::evaluate_many` on
+/// each polynomial in parallel, so the ith entry holds the values of the ith polynomial
+pub fn evaluate_polynomials<F: Field + Send + Sync>(polynomials: &[Polynomial<F>], points: &[F]) -> Vec<Vec<F>> {
+	polynomials.par_iter().map(|p| p.evaluate_many(points)).collect()
+}
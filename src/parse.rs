@@ -0,0 +1,234 @@
+//
+// Reading polynomials and matrices from the way they are written by hand, like "x^3 - 2x + 1" and
+// "[1 2; 3 4]", with integer coefficients taken into any ring
+//
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+
+// MARK: Errors
+
+/// Text which could not be read as a polynomial or matrix
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+	/// The byte offset into the text where the problem is
+	pub position: usize,
+
+	/// What was wrong there
+	pub reason: String
+}
+
+impl Display for ParseError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "At position {}: {}", self.position, self.reason)
+	}
+}
+
+impl std::error::Error for ParseError { }
+
+fn error<T>(position: usize, reason: impl Into<String>) -> Result<T, ParseError> {
+	Err(ParseError { position, reason: reason.into() })
+}
+
+// MARK: Integers
+
+/// The image of the decimal integer written by the given digits in R, found by Horner's rule in R
+/// itself, so integers of any size work in every ring, reduced as the ring reduces them
+pub fn integer<R: Ring>(digits: &str) -> Option<R> {
+	if digits.is_empty() {
+		return None
+	}
+
+	let ten = (0..10).fold(R::zero(), |sum, _| sum + R::one());
+	let mut n = R::zero();
+	for d in digits.chars() {
+		let d = d.to_digit(10)?;
+		n = n * ten.clone() + (0..d).fold(R::zero(), |sum, _| sum + R::one());
+	}
+	Some(n)
+}
+
+/// A position in text being read, which skips whitespace between tokens
+struct Cursor<'a> {
+	text: &'a str,
+	position: usize
+}
+
+impl<'a> Cursor<'a> {
+
+	/// The next character which is not whitespace, without moving past it
+	fn peek(&mut self) -> Option<char> {
+		let rest = &self.text[self.position..];
+		self.position += rest.len() - rest.trim_start().len();
+		self.text[self.position..].chars().next()
+	}
+
+	/// Moves past the next character if it is c
+	fn eat(&mut self, c: char) -> bool {
+		if self.peek() == Some(c) {
+			self.position += c.len_utf8();
+			true
+		} else {
+			false
+		}
+	}
+
+	/// The longest run of characters satisfying the predicate, starting at the next token
+	fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+		self.peek();
+		let rest = &self.text[self.position..];
+		let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+		self.position += length;
+		&rest[..length]
+	}
+
+}
+
+// MARK: Polynomials
+
+impl<R: Ring> Polynomial<R> {
+
+	/// Reads a polynomial written as a sum of terms like 3x^2, -x, or 7, in any one variable.
+	///
+	/// Coefficients are nonnegative integers, written before the variable and optionally separated
+	/// from it by `*`, and are taken into R by `integer`. Terms may repeat powers and come in any
+	/// order, and whitespace is ignored.
+	pub fn parse(text: &str) -> Result<Polynomial<R>, ParseError> {
+		let mut cursor = Cursor { text, position: 0 };
+		let mut coefficients: Vec<R> = Vec::new();
+		let mut variable: Option<&str> = None;
+
+		loop {
+			let negative = if coefficients.is_empty() && variable.is_none() && cursor.peek().is_some() {
+				let negative = cursor.eat('-');
+				if !negative {
+					cursor.eat('+');
+				}
+				negative
+			} else if cursor.eat('-') {
+				true
+			} else if cursor.eat('+') {
+				false
+			} else if cursor.peek().is_none() {
+				break
+			} else {
+				return error(cursor.position, "Expected + or - between terms")
+			};
+
+			let digits = cursor.take_while(|c| c.is_ascii_digit());
+			let times = cursor.eat('*');
+			let name = cursor.take_while(|c| c.is_alphanumeric() || c == '_');
+
+			if name.is_empty() && (digits.is_empty() || times) {
+				return error(cursor.position, if digits.is_empty() { "Expected a term" } else { "Expected a variable after *" })
+			}
+
+			let mut exponent = 0;
+			if !name.is_empty() {
+				if !name.starts_with(|c: char| c.is_alphabetic()) {
+					return error(cursor.position - name.len(), format!("{} is not a variable name", name))
+				}
+				match variable {
+					Some(v) if v != name => return error(cursor.position - name.len(), format!("Expected the variable {}, found {}", v, name)),
+					_ => variable = Some(name)
+				}
+
+				exponent = 1;
+				if cursor.eat('^') {
+					let power = cursor.take_while(|c| c.is_ascii_digit());
+					exponent = match power.parse() {
+						Ok(e) => e,
+						Err(_) => return error(cursor.position, "Expected a nonnegative integer exponent after ^")
+					};
+				}
+			}
+
+			let mut c = if digits.is_empty() { R::one() } else { integer(digits).unwrap() };
+			if negative {
+				c = -c;
+			}
+
+			if coefficients.len() <= exponent {
+				coefficients.resize(exponent + 1, R::zero());
+			}
+			coefficients[exponent].add_assign_ref(&c);
+		}
+
+		if coefficients.is_empty() && variable.is_none() {
+			return error(0, "Expected a polynomial, found nothing")
+		}
+
+		Ok(Polynomial::new(coefficients))
+	}
+
+}
+
+impl<R: Ring> FromStr for Polynomial<R> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Polynomial::parse(s)
+	}
+}
+
+// MARK: Matrices
+
+impl<R: Ring> Matrix<R> {
+
+	/// Reads a matrix literal like "[1 2; 3 -4]", with entries separated by commas or spaces and
+	/// rows separated by semicolons or new lines.
+	///
+	/// The surrounding brackets are optional, and entries are integers, taken into R by `integer`.
+	pub fn parse(text: &str) -> Result<Matrix<R>, ParseError> {
+		let offset = |s: &str| s.as_ptr() as usize - text.as_ptr() as usize;
+
+		let mut body = text.trim();
+		if let Some(inner) = body.strip_prefix('[') {
+			body = match inner.strip_suffix(']') {
+				Some(inner) => inner,
+				None => return error(text.len(), "Expected ] to close the matrix")
+			};
+		}
+
+		let mut rows: Vec<Vec<R>> = Vec::new();
+		for row in body.split([';', '\n']) {
+			if row.trim().is_empty() {
+				continue
+			}
+
+			let mut entries = Vec::new();
+			for entry in row.split([',', ' ', '\t', '\r']).filter(|e| !e.is_empty()) {
+				let (negative, digits) = match entry.strip_prefix('-') {
+					Some(digits) => (true, digits),
+					None => (false, entry.strip_prefix('+').unwrap_or(entry))
+				};
+				match integer::<R>(digits) {
+					Some(x) => entries.push(if negative { -x } else { x }),
+					None => return error(offset(entry), format!("{} is not an integer", entry))
+				}
+			}
+
+			if let Some(first) = rows.first() {
+				if first.len() != entries.len() {
+					return error(offset(row), format!("Expected {} entries in this row, found {}", first.len(), entries.len()))
+				}
+			}
+			rows.push(entries);
+		}
+
+		Ok(Matrix::from_rows(rows))
+	}
+
+}
+
+impl<R: Ring> FromStr for Matrix<R> {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Matrix::parse(s)
+	}
+}
@@ -0,0 +1,188 @@
+//
+// Integer partitions and Young diagrams, which index the conjugacy classes and irreducible
+// representations of the symmetric groups
+//
+
+use std::fmt::Display;
+
+use crate::permutation::Perm;
+use crate::std_impls::factor;
+
+// MARK: Partitions
+
+/// A partition of n, a nonincreasing list of positive parts adding up to n, drawn as a Young
+/// diagram with a row of each length.
+///
+/// Partitions compare lexicographically by their parts, so (3) > (2, 1) > (1, 1, 1).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Partition {
+	parts: Vec<usize>
+}
+
+impl Partition {
+
+	/// The partition with the given parts, ignoring zeros, or None if they are not nonincreasing
+	pub fn new(parts: Vec<usize>) -> Option<Partition> {
+		let parts: Vec<usize> = parts.into_iter().filter(|&p| p > 0).collect();
+		if parts.windows(2).any(|w| w[0] < w[1]) {
+			return None
+		}
+
+		Some(Partition { parts })
+	}
+
+	/// The cycle type of a permutation, the lengths of its cycles including fixed points, which
+	/// determines its conjugacy class in S_N
+	pub fn cycle_type<const N: usize>(sigma: &Perm<N>) -> Partition {
+		let cycles = sigma.cycle_decomposition();
+		let fixed = N - cycles.iter().map(Vec::len).sum::<usize>();
+
+		let mut parts: Vec<usize> = cycles.iter().map(Vec::len).chain(std::iter::repeat_n(1, fixed)).collect();
+		parts.sort_unstable_by(|a, b| b.cmp(a));
+		Partition { parts }
+	}
+
+	/// The parts, from largest to smallest
+	pub fn parts(&self) -> &[usize] {
+		&self.parts
+	}
+
+	/// The number n being partitioned, the number of cells of the diagram
+	pub fn size(&self) -> usize {
+		self.parts.iter().sum()
+	}
+
+	/// The number of parts, the number of rows of the diagram
+	pub fn length(&self) -> usize {
+		self.parts.len()
+	}
+
+	/// Whether or not the diagram has a cell in row i and column j, counting from zero
+	pub fn contains(&self, i: usize, j: usize) -> bool {
+		i < self.parts.len() && j < self.parts[i]
+	}
+
+	/// The cells (i, j) of the diagram, row by row
+	pub fn cells(&self) -> Vec<(usize, usize)> {
+		self.parts.iter().enumerate().flat_map(|(i, &p)| (0..p).map(move |j| (i, j))).collect()
+	}
+
+	/// The conjugate partition, whose diagram is the transpose of this one
+	pub fn conjugate(&self) -> Partition {
+		let columns = self.parts.first().copied().unwrap_or(0);
+		Partition { parts: (0..columns).map(|j| self.parts.iter().take_while(|&&p| p > j).count()).collect() }
+	}
+
+	/// The hook length of the cell (i, j), the number of cells to its right or below it, counting
+	/// the cell itself
+	pub fn hook_length(&self, i: usize, j: usize) -> usize {
+		if !self.contains(i, j) {
+			panic!("The cell ({}, {}) is not in the diagram", i, j)
+		}
+
+		let arm = self.parts[i] - j - 1;
+		let leg = self.parts[i + 1..].iter().take_while(|&&p| p > j).count();
+		arm + leg + 1
+	}
+
+	/// The dimension of the irreducible representation of S_n indexed by this partition, which is
+	/// the number of standard Young tableaux of this shape, n! divided by the product of the hook
+	/// lengths.
+	///
+	/// The primes in n! and the hook lengths are cancelled before multiplying, so this is exact
+	/// whenever the answer fits in a u128.
+	pub fn dimension(&self) -> u128 {
+		// The exponent of each prime p < n + 1, in n! minus in the hook lengths
+		let n = self.size();
+		let mut exponents = vec![0i64; n + 1];
+		for k in 2..=n {
+			for (p, e) in factor(k as i64) {
+				exponents[p as usize] += e as i64;
+			}
+		}
+		for (i, j) in self.cells() {
+			for (p, e) in factor(self.hook_length(i, j) as i64) {
+				exponents[p as usize] -= e as i64;
+			}
+		}
+
+		exponents.iter().enumerate().fold(1, |product, (p, &e)| product * (p as u128).pow(e as u32))
+	}
+
+	/// The order of the centralizer of a permutation with this cycle type, the product of
+	/// i^(m_i) m_i! where m_i parts equal i
+	pub fn centralizer_order(&self) -> u128 {
+		let mut order: u128 = 1;
+		let mut run = 0;
+
+		for (k, &part) in self.parts.iter().enumerate() {
+			run = if k > 0 && self.parts[k - 1] == part { run + 1 } else { 1 };
+			order *= part as u128 * run;
+		}
+
+		order
+	}
+
+	/// The number of permutations in S_n with this cycle type, n! divided by the centralizer order
+	pub fn class_size(&self) -> u128 {
+		(1..=self.size() as u128).product::<u128>() / self.centralizer_order()
+	}
+
+}
+
+impl Display for Partition {
+	/// Writes the Young diagram, with one row of boxes for each part
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let rows: Vec<String> = self.parts.iter().map(|&p| "□".repeat(p)).collect();
+		write!(f, "{}", rows.join("\n"))
+	}
+}
+
+// MARK: Enumeration
+
+/// Every partition of n, from largest to smallest, so starting with (n) and ending with (1, ..., 1)
+pub fn partitions(n: usize) -> Vec<Partition> {
+	let mut all = Vec::new();
+	extend_partitions(n, n, &mut Vec::new(), &mut all);
+	all
+}
+
+/// Pushes every partition which starts with `prefix` and continues with parts of size at most
+/// `largest` adding up to `remaining`
+fn extend_partitions(remaining: usize, largest: usize, prefix: &mut Vec<usize>, all: &mut Vec<Partition>) {
+	if remaining == 0 {
+		all.push(Partition { parts: prefix.clone() });
+		return
+	}
+
+	for part in (1..=largest.min(remaining)).rev() {
+		prefix.push(part);
+		extend_partitions(remaining - part, part, prefix, all);
+		prefix.pop();
+	}
+}
+
+/// The number of partitions of n, by Euler's pentagonal number recurrence
+/// p(n) = sum over k != 0 of (-1)^(k + 1) p(n - k(3k - 1)/2)
+pub fn partition_count(n: usize) -> u128 {
+	let mut counts: Vec<i128> = vec![1];
+
+	for m in 1..=n {
+		let mut count = 0;
+		for k in 1.. {
+			let first = k * (3 * k - 1) / 2;
+			if first > m {
+				break
+			}
+
+			let sign = if k % 2 == 1 { 1 } else { -1 };
+			count += sign * counts[m - first];
+			if first + k <= m {
+				count += sign * counts[m - first - k];
+			}
+		}
+		counts.push(count);
+	}
+
+	counts[n] as u128
+}
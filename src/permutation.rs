@@ -0,0 +1,367 @@
+//
+// Permutations of a finite set, the standard non-abelian groups
+//
+
+use std::fmt::Display;
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+
+/// A permutation of {0, 1, ..., N - 1}
+///
+/// Permutations compose like functions, so `(s * t).apply(i) == s.apply(t.apply(i))`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Perm<const N: usize> {
+	/// The image of each point, so i maps to images[i]
+	pub images: [usize; N]
+}
+
+impl<const N: usize> Perm<N> {
+
+	/// Creates a permutation from one-line notation, where i maps to images[i].
+	///
+	/// Returns `None` if `images` is not a rearrangement of 0..N.
+	pub fn from_one_line(images: [usize; N]) -> Option<Perm<N>> {
+		let mut seen = [false; N];
+
+		for &i in images.iter() {
+			if i >= N || seen[i] {
+				return None
+			}
+			seen[i] = true;
+		}
+
+		Some(Perm { images })
+	}
+
+	/// Creates a single cycle, sending cycle[0] to cycle[1], cycle[1] to cycle[2], and so on,
+	/// with the last point going back to cycle[0].
+	///
+	/// Returns `None` if a point is out of range or repeated.
+	pub fn cycle(cycle: &[usize]) -> Option<Perm<N>> {
+		let mut images = Self::identity().images;
+		let mut seen = [false; N];
+
+		for (k, &i) in cycle.iter().enumerate() {
+			if i >= N || seen[i] {
+				return None
+			}
+			seen[i] = true;
+			images[i] = cycle[(k + 1) % cycle.len()];
+		}
+
+		Some(Perm { images })
+	}
+
+	/// Creates a permutation from cycle notation, as the product of the given cycles.
+	///
+	/// The cycles need not be disjoint, in which case the rightmost cycle is applied first.
+	pub fn from_cycles(cycles: &[&[usize]]) -> Option<Perm<N>> {
+		cycles.iter().try_fold(Self::identity(), |product, c| Some(product * Self::cycle(c)?))
+	}
+
+	/// The image of the point i under this permutation
+	pub fn apply(&self, i: usize) -> usize {
+		self.images[i]
+	}
+
+	/// The disjoint cycles making up this permutation, each starting at its smallest point,
+	/// in order of their smallest points. Fixed points are left out.
+	pub fn cycle_decomposition(&self) -> Vec<Vec<usize>> {
+		let mut visited = [false; N];
+		let mut cycles = Vec::new();
+
+		for start in 0..N {
+			if visited[start] {
+				continue
+			}
+
+			let mut cycle = vec![start];
+			visited[start] = true;
+
+			let mut i = self.images[start];
+			while i != start {
+				cycle.push(i);
+				visited[i] = true;
+				i = self.images[i];
+			}
+
+			if cycle.len() > 1 {
+				cycles.push(cycle);
+			}
+		}
+
+		cycles
+	}
+
+	/// The sign of this permutation: 1 if it is even, -1 if it is odd
+	pub fn sign(&self) -> i64 {
+		// A k-cycle is a product of k - 1 transpositions
+		let transpositions: usize = self.cycle_decomposition().iter().map(|c| c.len() - 1).sum();
+
+		if transpositions.is_multiple_of(2) { 1 } else { -1 }
+	}
+
+	/// The order of this permutation, which is the lcm of its cycle lengths.
+	/// 
+	/// This is the same as `element_order`. For the order of the whole group, use
+	/// `<Perm<N> as FiniteGroup>::order()`.
+	pub fn order(&self) -> usize {
+		self.cycle_decomposition().iter().fold(1, |l, c| {
+			let g = gcd(&(l as i64), &(c.len() as i64)) as usize;
+			l / g * c.len()
+		})
+	}
+
+	/// The next permutation after this one in lexicographic order of one-line notation
+	fn next_lexicographic(&self) -> Option<Self> {
+		let mut images = self.images;
+
+		// Find the rightmost ascent images[i] < images[i + 1]
+		let i = (0..N.saturating_sub(1)).rev().find(|&i| images[i] < images[i + 1])?;
+
+		// Swap it with the rightmost larger element, then reverse the tail
+		let j = (i + 1..N).rev().find(|&j| images[j] > images[i])?;
+		images.swap(i, j);
+		images[i + 1..].reverse();
+
+		Some(Perm { images })
+	}
+
+}
+
+impl<const N: usize> Display for Perm<N> {
+	/// Writes this permutation in cycle notation, like (0 2 1)(3 4)
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let cycles = self.cycle_decomposition();
+
+		if cycles.is_empty() {
+			return write!(f, "()")
+		}
+
+		for cycle in cycles {
+			let points: Vec<String> = cycle.iter().map(|i| i.to_string()).collect();
+			write!(f, "({})", points.join(" "))?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<const N: usize> Mul for Perm<N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let mut images = [0; N];
+		for (i, image) in images.iter_mut().enumerate() {
+			*image = self.images[rhs.images[i]];
+		}
+		Perm { images }
+	}
+}
+
+impl<const N: usize> MulAssign for Perm<N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const N: usize> Div for Perm<N> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const N: usize> DivAssign for Perm<N> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<const N: usize> Monoid for Perm<N> {
+	fn identity() -> Self {
+		let mut images = [0; N];
+		for (i, image) in images.iter_mut().enumerate() {
+			*image = i;
+		}
+		Perm { images }
+	}
+}
+
+impl<const N: usize> Group for Perm<N> {
+	fn inverse(&self) -> Self {
+		let mut images = [0; N];
+		for (i, &image) in self.images.iter().enumerate() {
+			images[image] = i;
+		}
+		Perm { images }
+	}
+}
+
+impl<const N: usize> FiniteGroup for Perm<N> {
+
+	/// N factorial
+	fn order() -> usize {
+		(1..=N).product()
+	}
+
+	/// Every permutation, in lexicographic order of one-line notation
+	fn elements() -> impl Iterator<Item = Self> {
+		std::iter::successors(Some(Self::identity()), |p| p.next_lexicographic())
+	}
+
+	fn element_order(&self) -> usize {
+		self.order()
+	}
+}
+
+impl<const N: usize> RandomElement for Perm<N> {
+	/// A Fisher-Yates shuffle of the identity
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		let mut images = Self::identity().images;
+		images.shuffle(rng);
+		Perm { images }
+	}
+}
+
+impl<const N: usize> Distribution<Perm<N>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Perm<N> {
+		Perm::random(rng)
+	}
+}
+
+// MARK: Stabilizer Chains
+
+/// One level of a stabilizer chain: the stabilizer of the earlier base points, acting on its
+/// orbit of this level's base point
+#[derive(Clone, Debug)]
+struct StabilizerLevel<const N: usize> {
+	base_point: usize,
+	generators: Vec<Perm<N>>,
+
+	/// For each point x in the orbit of the base point, a group element sending the base point to x
+	transversal: Vec<Option<Perm<N>>>
+}
+
+/// A base and strong generating set for a permutation group, built by the Schreier-Sims algorithm.
+///
+/// This describes the group as a tower of point stabilizers, which makes the order, membership,
+/// and uniform random elements cheap to compute without listing the elements.
+#[derive(Clone, Debug)]
+pub struct StabilizerChain<const N: usize> {
+	levels: Vec<StabilizerLevel<N>>
+}
+
+impl<const N: usize> StabilizerChain<N> {
+
+	/// The stabilizer chain of the group generated by the given permutations
+	pub fn new(generators: &[Perm<N>]) -> StabilizerChain<N> {
+		let mut chain = StabilizerChain { levels: Vec::new() };
+		for g in generators {
+			chain.extend(0, *g);
+		}
+		chain
+	}
+
+	/// The base points b_0, b_1, ..., where only the identity fixes all of them
+	pub fn base(&self) -> Vec<usize> {
+		self.levels.iter().map(|level| level.base_point).collect()
+	}
+
+	/// A strong generating set, containing generators for every stabilizer in the chain
+	pub fn strong_generators(&self) -> Vec<Perm<N>> {
+		self.levels.iter().flat_map(|level| level.generators.iter().copied()).collect()
+	}
+
+	/// The sizes of the orbits of each base point under the stabilizer of the earlier ones
+	pub fn basic_orbit_sizes(&self) -> Vec<usize> {
+		self.levels.iter().map(|level| level.transversal.iter().flatten().count()).collect()
+	}
+
+	/// The number of elements in the group, which is the product of the basic orbit sizes
+	pub fn order(&self) -> u128 {
+		self.basic_orbit_sizes().iter().map(|&size| size as u128).product()
+	}
+
+	/// Whether or not g is in the group
+	pub fn contains(&self, g: &Perm<N>) -> bool {
+		self.sift(0, *g).is_some_and(|h| h == Perm::identity())
+	}
+
+	/// A uniformly random element of the group
+	///
+	/// NOT cryptographically secure!
+	pub fn random_element(&self) -> Perm<N> {
+		self.random_element_with_rng(&mut StdRng::from_entropy())
+	}
+
+	/// A uniformly random element of the group using the given source of randomness
+	pub fn random_element_with_rng<G: Rng + ?Sized>(&self, rng: &mut G) -> Perm<N> {
+		// Every element is uniquely u_0 u_1 ... u_k, with u_i from the ith transversal
+		self.levels.iter().fold(Perm::identity(), |product, level| {
+			let orbit: Vec<&Perm<N>> = level.transversal.iter().flatten().collect();
+			product * *orbit[rng.gen_range(0..orbit.len())]
+		})
+	}
+
+	/// Strips g through the levels starting at the given one, returning what is left
+	/// if g can be stripped all the way down, or None if it falls out of some orbit
+	fn sift(&self, start: usize, g: Perm<N>) -> Option<Perm<N>> {
+		let mut h = g;
+
+		for level in self.levels[start..].iter() {
+			let u = level.transversal[h.apply(level.base_point)]?;
+			h = u.inverse() * h;
+		}
+
+		Some(h)
+	}
+
+	/// Adds g, which fixes the base points before the given level, as a generator at that level,
+	/// then sifts the resulting Schreier generators into the levels below
+	fn extend(&mut self, level: usize, g: Perm<N>) {
+		if self.sift(level, g).is_some_and(|h| h == Perm::identity()) {
+			return
+		}
+
+		if level == self.levels.len() {
+			let base_point = (0..N).find(|&i| g.apply(i) != i).unwrap();
+			let mut transversal = vec![None; N];
+			transversal[base_point] = Some(Perm::identity());
+			self.levels.push(StabilizerLevel { base_point, generators: Vec::new(), transversal });
+		}
+
+		self.levels[level].generators.push(g);
+
+		// The new generator needs applying to the old orbit, and every generator to new points
+		let mut work: Vec<(usize, Perm<N>)> = (0..N)
+			.filter(|&x| self.levels[level].transversal[x].is_some())
+			.map(|x| (x, g))
+			.collect();
+
+		while let Some((x, s)) = work.pop() {
+			let y = s.apply(x);
+			let candidate = s * self.levels[level].transversal[x].unwrap();
+
+			match self.levels[level].transversal[y] {
+				None => {
+					self.levels[level].transversal[y] = Some(candidate);
+					work.extend(self.levels[level].generators.iter().map(|&t| (y, t)));
+				},
+				Some(u) => {
+					// A Schreier generator, which fixes this level's base point
+					self.extend(level + 1, u.inverse() * candidate);
+				}
+			}
+		}
+	}
+
+}
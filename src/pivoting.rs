@@ -0,0 +1,249 @@
+//
+// Gaussian elimination with a choice of pivoting strategy, reporting where the pivots fell
+// rather than just how many there were
+//
+
+use std::collections::BTreeMap;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::sparse_matrix::SparseMatrix;
+
+// MARK: Rank Profile
+
+/// The pivots chosen by Gaussian elimination, as (row, column) positions in the original matrix,
+/// in the order they were chosen. No two share a row or a column, and there are as many as the
+/// rank.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RankProfile {
+	pub pivots: Vec<(usize, usize)>
+}
+
+impl RankProfile {
+
+	/// The number of pivots, which is the rank of the matrix
+	pub fn rank(&self) -> usize {
+		self.pivots.len()
+	}
+
+	/// The rows of the pivots, in increasing order. These rows of the matrix are linearly
+	/// independent.
+	pub fn pivot_rows(&self) -> Vec<usize> {
+		let mut rows: Vec<usize> = self.pivots.iter().map(|&(i, _)| i).collect();
+		rows.sort_unstable();
+		rows
+	}
+
+	/// The columns of the pivots, in increasing order. These columns of the matrix are linearly
+	/// independent.
+	pub fn pivot_columns(&self) -> Vec<usize> {
+		let mut cols: Vec<usize> = self.pivots.iter().map(|&(_, j)| j).collect();
+		cols.sort_unstable();
+		cols
+	}
+
+}
+
+// MARK: Pivoting Strategies
+
+/// A rule for choosing the next pivot of Gaussian elimination
+pub trait PivotStrategy<F: Field> {
+
+	/// The position of a nonzero entry of m to pivot on, among the given rows and columns that no
+	/// pivot has used yet, or None if those entries are all zero
+	fn choose(&self, m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)>;
+
+}
+
+/// The first nonzero entry of the leftmost column that has one, which is exact and cheap, and
+/// chooses the pivot columns that make up the column rank profile
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FirstNonzero;
+
+impl<F: Field> PivotStrategy<F> for FirstNonzero {
+	fn choose(&self, m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)> {
+		cols.iter().find_map(|&j| rows.iter().find(|&&i| !m[(i, j)].is_zero()).map(|&i| (i, j)))
+	}
+}
+
+/// The entry of largest absolute value in the leftmost column that has a nonzero entry, which
+/// keeps floating point elimination stable in practice
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PartialPivoting;
+
+impl<F: PoField> PivotStrategy<F> for PartialPivoting {
+	fn choose(&self, m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)> {
+		cols.iter().find_map(|&j| largest(m, rows, &[j]))
+	}
+}
+
+/// The entry of largest absolute value among all the remaining rows and columns, which is more
+/// stable than partial pivoting and costs a search of the whole submatrix at every step
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FullPivoting;
+
+impl<F: PoField> PivotStrategy<F> for FullPivoting {
+	fn choose(&self, m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)> {
+		largest(m, rows, cols)
+	}
+}
+
+/// The nonzero entry minimizing the Markowitz count (r - 1)(c - 1), where r and c are the
+/// numbers of nonzero entries left in its row and column, which bounds the fill-in the step can
+/// cause, so that sparse matrices stay sparse while they are eliminated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Markowitz;
+
+impl<F: Field> PivotStrategy<F> for Markowitz {
+	fn choose(&self, m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)> {
+		let row_counts: Vec<usize> = rows.iter()
+			.map(|&i| cols.iter().filter(|&&j| !m[(i, j)].is_zero()).count())
+			.collect();
+		let col_counts: Vec<usize> = cols.iter()
+			.map(|&j| rows.iter().filter(|&&i| !m[(i, j)].is_zero()).count())
+			.collect();
+
+		let mut best: Option<(usize, (usize, usize))> = None;
+		for (a, &i) in rows.iter().enumerate() {
+			for (b, &j) in cols.iter().enumerate() {
+				if m[(i, j)].is_zero() {
+					continue
+				}
+				let cost = (row_counts[a] - 1) * (col_counts[b] - 1);
+				if best.is_none_or(|(c, _)| cost < c) {
+					best = Some((cost, (i, j)));
+				}
+			}
+		}
+
+		best.map(|(_, position)| position)
+	}
+}
+
+/// The position of the entry of largest absolute value among the given rows and columns, or None
+/// if they are all zero
+fn largest<F: PoField>(m: &Matrix<F>, rows: &[usize], cols: &[usize]) -> Option<(usize, usize)> {
+	let mut best: Option<(F, (usize, usize))> = None;
+	for &j in cols {
+		for &i in rows {
+			if m[(i, j)].is_zero() {
+				continue
+			}
+			let size = m[(i, j)].abs();
+			if best.as_ref().is_none_or(|(b, _)| size > *b) {
+				best = Some((size, (i, j)));
+			}
+		}
+	}
+
+	best.map(|(_, position)| position)
+}
+
+// MARK: Elimination
+
+impl<F: Field> Matrix<F> {
+
+	/// The pivots of Gaussian elimination choosing each pivot by the given strategy
+	pub fn rank_profile_with<P: PivotStrategy<F>>(&self, strategy: &P) -> RankProfile {
+		let mut m = self.clone();
+		let mut rows: Vec<usize> = (0..self.rows()).collect();
+		let mut cols: Vec<usize> = (0..self.cols()).collect();
+		let mut pivots = Vec::new();
+
+		while let Some((r, c)) = strategy.choose(&m, &rows, &cols) {
+			rows.retain(|&i| i != r);
+			cols.retain(|&j| j != c);
+
+			let pivot_inverse = m[(r, c)].inverse();
+			for &i in rows.iter() {
+				let factor = m[(i, c)].mul_ref(&pivot_inverse);
+				if factor.is_zero() {
+					continue
+				}
+				m[(i, c)] = F::zero();
+				for &j in cols.iter() {
+					let delta = factor.mul_ref(&m[(r, j)]);
+					m[(i, j)].sub_assign_ref(&delta);
+				}
+			}
+
+			pivots.push((r, c));
+		}
+
+		RankProfile { pivots }
+	}
+
+	/// The pivots of Gaussian elimination taking the first nonzero entry down the leftmost
+	/// possible column
+	pub fn rank_profile(&self) -> RankProfile {
+		self.rank_profile_with(&FirstNonzero)
+	}
+
+	/// The column rank profile, the lexicographically first set of columns forming a basis of the
+	/// column space, which are the pivot columns of the reduced row echelon form
+	pub fn column_rank_profile(&self) -> Vec<usize> {
+		self.rank_profile().pivot_columns()
+	}
+
+	/// The row rank profile, the lexicographically first set of rows forming a basis of the row
+	/// space
+	pub fn row_rank_profile(&self) -> Vec<usize> {
+		self.transpose().column_rank_profile()
+	}
+
+}
+
+impl<F: Field> SparseMatrix<F> {
+
+	/// The pivots of Gaussian elimination by the Markowitz strategy, working on the nonzero
+	/// entries alone so that the matrix is never made dense
+	pub fn rank_profile(&self) -> RankProfile {
+		let mut rows: BTreeMap<usize, BTreeMap<usize, F>> = (0..self.rows())
+			.map(|i| (i, self.row(i).map(|(j, x)| (j, x.clone())).collect::<BTreeMap<_, _>>()))
+			.filter(|(_, row)| !row.is_empty())
+			.collect();
+		let mut pivots = Vec::new();
+
+		loop {
+			// Every pivot column has been cleared out of the remaining rows, so these counts are
+			// over the remaining columns only
+			let mut col_counts: BTreeMap<usize, usize> = BTreeMap::new();
+			for row in rows.values() {
+				for &j in row.keys() {
+					*col_counts.entry(j).or_insert(0) += 1;
+				}
+			}
+
+			let Some((r, c)) = rows.iter()
+				.flat_map(|(&i, row)| row.keys().map(move |&j| (i, j, row.len())))
+				.min_by_key(|&(_, j, r)| (r - 1) * (col_counts[&j] - 1))
+				.map(|(i, j, _)| (i, j))
+			else {
+				break
+			};
+
+			let pivot_row = rows.remove(&r).unwrap();
+			let pivot_inverse = pivot_row[&c].inverse();
+
+			for row in rows.values_mut() {
+				let Some(x) = row.remove(&c) else {
+					continue
+				};
+				let factor = x.mul_ref(&pivot_inverse);
+				for (&j, y) in pivot_row.iter().filter(|(&j, _)| j != c) {
+					let entry = row.entry(j).or_insert_with(F::zero);
+					entry.sub_assign_ref(&factor.mul_ref(y));
+					if entry.is_zero() {
+						row.remove(&j);
+					}
+				}
+			}
+			rows.retain(|_, row| !row.is_empty());
+
+			pivots.push((r, c));
+		}
+
+		RankProfile { pivots }
+	}
+
+}
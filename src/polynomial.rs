@@ -0,0 +1,1413 @@
+//
+// Univariate polynomials over a ring
+//
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::ntt;
+use crate::random::RandomElement;
+use crate::std_impls::{factor, ZM};
+
+// MARK: Polynomial
+
+/// A polynomial with coefficients in a ring R, stored from the constant term up.
+///
+/// The coefficient list never ends in a zero, so the zero polynomial has no coefficients.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Polynomial<R: Ring> {
+	coefficients: Vec<R>
+}
+
+impl<R: Ring> Polynomial<R> {
+
+	/// Creates the polynomial c_0 + c_1 x + c_2 x^2 + ... from its coefficients
+	pub fn new(coefficients: Vec<R>) -> Polynomial<R> {
+		let mut p = Polynomial { coefficients };
+		p.normalize();
+		p
+	}
+
+	/// The constant polynomial c
+	pub fn constant(c: R) -> Polynomial<R> {
+		Polynomial::new(vec![c])
+	}
+
+	/// The polynomial x
+	pub fn x() -> Polynomial<R> {
+		Polynomial::monomial(R::one(), 1)
+	}
+
+	/// The polynomial c x^n
+	pub fn monomial(c: R, n: usize) -> Polynomial<R> {
+		let mut coefficients = vec![R::zero(); n + 1];
+		coefficients[n] = c;
+		Polynomial::new(coefficients)
+	}
+
+	/// The coefficients of this polynomial, from the constant term up
+	pub fn coefficients(&self) -> &[R] {
+		&self.coefficients
+	}
+
+	/// The coefficient of x^i
+	pub fn coefficient(&self, i: usize) -> R {
+		self.coefficients.get(i).cloned().unwrap_or_else(R::zero)
+	}
+
+	/// The degree of this polynomial. The zero polynomial is given degree 0.
+	pub fn degree(&self) -> usize {
+		self.check_normalized();
+		self.coefficients.len().saturating_sub(1)
+	}
+
+	/// The coefficient of the highest power of x, or zero for the zero polynomial
+	pub fn leading_coefficient(&self) -> R {
+		self.check_normalized();
+		self.coefficients.last().cloned().unwrap_or_else(R::zero)
+	}
+
+	/// Whether or not the leading coefficient is one
+	pub fn is_monic(&self) -> bool {
+		self.leading_coefficient() == R::one()
+	}
+
+	/// Evaluates this polynomial at x using Horner's method
+	pub fn evaluate(&self, x: &R) -> R {
+		let mut value = R::zero();
+		for c in self.coefficients.iter().rev() {
+			value.mul_assign_ref(x);
+			value.add_assign_ref(c);
+		}
+		value
+	}
+
+	/// Multiplies every coefficient by c
+	pub fn scale(&self, c: &R) -> Polynomial<R> {
+		Polynomial::new(self.coefficients.iter().map(|a| a.mul_ref(c)).collect())
+	}
+
+	/// The formal derivative of this polynomial
+	pub fn derivative(&self) -> Polynomial<R> {
+		Polynomial::new(self.coefficients.iter().enumerate().skip(1).map(|(i, c)| repeated_sum(c, i)).collect())
+	}
+
+	/// Multiplies this polynomial by x^k
+	pub fn mul_x_pow(&self, k: usize) -> Polynomial<R> {
+		if self.is_zero() {
+			return self.clone()
+		}
+
+		let mut coefficients = vec![R::zero(); k];
+		coefficients.extend(self.coefficients.iter().cloned());
+		Polynomial { coefficients }
+	}
+
+	/// Divides this polynomial by x^k, dropping the remainder
+	pub fn div_x_pow(&self, k: usize) -> Polynomial<R> {
+		Polynomial { coefficients: self.coefficients.iter().skip(k).cloned().collect() }
+	}
+
+	/// With the `debug-checks` feature, asserts that the leading coefficient is nonzero
+	#[inline(always)]
+	fn check_normalized(&self) {
+		debug_check!(!self.coefficients.last().is_some_and(R::is_zero), "Polynomial has a zero leading coefficient");
+	}
+
+	/// Removes trailing zero coefficients
+	fn normalize(&mut self) {
+		while self.coefficients.last().is_some_and(|c| c.is_zero()) {
+			self.coefficients.pop();
+		}
+	}
+
+}
+
+/// The sum c + c + ... + c of n copies of c
+pub(crate) fn repeated_sum<R: Ring>(c: &R, n: usize) -> R {
+	let mut sum = R::zero();
+	let mut addend = c.clone();
+	let mut n = n;
+
+	while n > 0 {
+		if n & 1 == 1 {
+			sum.add_assign_ref(&addend);
+		}
+		addend = addend.add_ref(&addend);
+		n >>= 1;
+	}
+
+	sum
+}
+
+impl<F: Field> Polynomial<F> {
+
+	/// This polynomial divided by its leading coefficient
+	pub fn monic(&self) -> Polynomial<F> {
+		if self.is_zero() {
+			return self.clone()
+		}
+
+		self.scale(&self.leading_coefficient().inverse())
+	}
+
+	/// The squarefree part of this nonzero polynomial, the monic product of its distinct irreducible
+	/// factors, which has the same roots without their multiplicities.
+	///
+	/// Over ZM<Q> this is `radical`. Over other fields it is f / gcd(f, f'), which is only right in
+	/// characteristic zero, or when the degree is less than the characteristic.
+	pub fn squarefree_part(&self) -> Polynomial<F> {
+		if self.is_zero() {
+			panic!("The zero polynomial has no squarefree part")
+		}
+
+		<F as SquarefreePart>::squarefree_part(self)
+	}
+
+	/// This polynomial to the power e, reduced modulo the given polynomial
+	fn pow_mod(&self, mut e: u64, modulus: &Polynomial<F>) -> Polynomial<F> {
+		let mut base = self.quotient_and_remainder(modulus).1;
+		let mut result = Polynomial::one().quotient_and_remainder(modulus).1;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				result = result.mul_ref(&base).quotient_and_remainder(modulus).1;
+			}
+			base = base.mul_ref(&base).quotient_and_remainder(modulus).1;
+			e >>= 1;
+		}
+
+		result
+	}
+
+}
+
+/// How a field finds squarefree parts, specialized for the fields where repeated factors can hide
+/// from the derivative
+trait SquarefreePart: Field {
+	fn squarefree_part(f: &Polynomial<Self>) -> Polynomial<Self>;
+}
+
+impl<F: Field> SquarefreePart for F {
+	default fn squarefree_part(f: &Polynomial<F>) -> Polynomial<F> {
+		f.quotient_and_remainder(&gcd(f, &f.derivative())).0.monic()
+	}
+}
+
+impl<const Q: i64> SquarefreePart for ZM<Q> {
+	fn squarefree_part(f: &Polynomial<ZM<Q>>) -> Polynomial<ZM<Q>> {
+		f.radical()
+	}
+}
+
+impl<const Q: i64> Polynomial<ZM<Q>> {
+
+	/// A random polynomial of exactly the given degree, using the given source of randomness
+	pub fn random_with_rng<G: Rng + ?Sized>(degree: usize, rng: &mut G) -> Polynomial<ZM<Q>> {
+		let mut coefficients: Vec<ZM<Q>> = (0..degree).map(|_| ZM::random(rng)).collect();
+		coefficients.push(ZM::from_int(rng.gen_range(1..Q)));
+		Polynomial::new(coefficients)
+	}
+
+	/// The radical of this polynomial, the monic product of its distinct irreducible factors.
+	///
+	/// This is its largest squarefree divisor. Q must be prime.
+	pub fn radical(&self) -> Polynomial<ZM<Q>> {
+		if self.is_zero() {
+			panic!("The zero polynomial has no radical")
+		}
+
+		if self.degree() == 0 {
+			return Polynomial::one()
+		}
+
+		let derivative = self.derivative();
+		if derivative.is_zero() {
+			return self.frobenius_root().radical()
+		}
+
+		// w collects the factors whose multiplicity is not divisible by Q
+		let mut rest = gcd(self, &derivative);
+		let w = self.quotient_and_remainder(&rest).0.monic();
+
+		loop {
+			let common = gcd(&rest, &w);
+			if common.degree() == 0 {
+				break
+			}
+			rest = rest.quotient_and_remainder(&common).0;
+		}
+
+		// Whatever is left has every multiplicity divisible by Q
+		w.mul_ref(&rest.frobenius_root().radical())
+	}
+
+	/// Whether or not this polynomial has no repeated irreducible factors. Q must be prime.
+	pub fn is_squarefree(&self) -> bool {
+		!self.is_zero() && gcd(self, &self.derivative()).degree() == 0
+	}
+
+	/// The distinct roots of this nonzero polynomial in ZM<Q>, in increasing order. Q must be prime.
+	///
+	/// The product of x - a over every a in ZM<Q> is x^Q - x, so the roots are those of
+	/// gcd(f, x^Q - x), which is split into its linear factors by Cantor-Zassenhaus.
+	#[cfg(feature = "std")]
+	pub fn roots_mod_p(&self) -> Vec<ZM<Q>> {
+		self.roots_mod_p_with_rng(&mut rand::thread_rng())
+	}
+
+	/// The distinct roots in ZM<Q>, using the given source of randomness
+	pub fn roots_mod_p_with_rng<G: Rng + ?Sized>(&self, rng: &mut G) -> Vec<ZM<Q>> {
+		if self.is_zero() {
+			panic!("Every element is a root of the zero polynomial")
+		}
+
+		if self.degree() == 0 {
+			return Vec::new()
+		}
+
+		let x = Polynomial::x();
+		let linear = gcd(self, &(x.pow_mod(Q as u64, self) - x)).monic();
+		if linear.degree() == 0 {
+			return Vec::new()
+		}
+
+		let mut roots: Vec<ZM<Q>> = linear.equal_degree_split(1, rng).into_iter().map(|f| -f.coefficient(0)).collect();
+		roots.sort_by_key(|r| r.val);
+		roots
+	}
+
+	/// A random monic irreducible polynomial of the given positive degree, which is also primitive
+	/// if `primitive` is set. Q must be prime.
+	#[cfg(feature = "std")]
+	pub fn random_irreducible(degree: usize, primitive: bool) -> Polynomial<ZM<Q>> {
+		Polynomial::random_irreducible_with_rng(degree, primitive, &mut rand::thread_rng())
+	}
+
+	/// A random monic irreducible polynomial of the given positive degree, which is also primitive
+	/// if `primitive` is set, using the given source of randomness. Q must be prime.
+	///
+	/// About one in every `degree` monic polynomials is irreducible, so this tries that many on average,
+	/// and the primitive ones are a fraction phi(Q^n - 1) / (Q^n - 1) of those.
+	pub fn random_irreducible_with_rng<G: Rng + ?Sized>(degree: usize, primitive: bool, rng: &mut G) -> Polynomial<ZM<Q>> {
+		if degree == 0 {
+			panic!("Irreducible polynomials have positive degree")
+		}
+
+		loop {
+			let candidate = Polynomial::random_with_rng(degree, rng).monic();
+			if candidate.is_irreducible() && (!primitive || candidate.is_primitive()) {
+				return candidate
+			}
+		}
+	}
+
+	/// The first monic irreducible polynomial of the given positive degree, which is also primitive
+	/// if `primitive` is set, taking x^n + c_(n-1) x^(n-1) + ... + c_0 in increasing order of
+	/// c_0 + c_1 Q + ... + c_(n-1) Q^(n-1) with each c_i in [0, Q). Q must be prime.
+	///
+	/// This gives the same modulus every time, for building GF(Q^n) reproducibly without tables of
+	/// Conway polynomials.
+	pub fn first_irreducible(degree: usize, primitive: bool) -> Polynomial<ZM<Q>> {
+		if degree == 0 {
+			panic!("Irreducible polynomials have positive degree")
+		}
+
+		(0u128..).map(|index| {
+			let mut index = index;
+			let mut coefficients: Vec<ZM<Q>> = (0..degree).map(|_| {
+				let digit = ZM::from((index % Q as u128) as i64);
+				index /= Q as u128;
+				digit
+			}).collect();
+			coefficients.push(ZM::one());
+			Polynomial::new(coefficients)
+		}).find(|candidate| candidate.is_irreducible() && (!primitive || candidate.is_primitive())).unwrap()
+	}
+
+	/// Whether or not this polynomial of degree n is primitive, meaning irreducible with x of
+	/// multiplicative order Q^n - 1 modulo it, so that x generates the unit group of the field
+	/// ZM<Q>[x] / (f). Q must be prime, and Q^n - 1 must fit in an i64.
+	///
+	/// For an irreducible f this checks x^((Q^n - 1) / r) != 1 for each prime r dividing Q^n - 1.
+	pub fn is_primitive(&self) -> bool {
+		if !self.is_irreducible() {
+			return false
+		}
+
+		let order = Q.checked_pow(self.degree() as u32).and_then(|size| size.checked_sub(1))
+			.expect("Q^n - 1 is too large to factor");
+		let x = Polynomial::x();
+		let one = Polynomial::one().quotient_and_remainder(self).1;
+
+		factor(order).into_iter().all(|(r, _)| x.pow_mod((order / r) as u64, self) != one)
+	}
+
+	/// Whether or not this polynomial is irreducible, by Rabin's test. Q must be prime.
+	///
+	/// A polynomial f of degree n is irreducible exactly when it divides x^(Q^n) - x, and is coprime
+	/// to x^(Q^(n/r)) - x for each prime r dividing n.
+	pub fn is_irreducible(&self) -> bool {
+		if self.degree() == 0 {
+			return false
+		}
+
+		let n = self.degree();
+		let x = Polynomial::x();
+
+		// x^(Q^k) mod self, for k = 0, ..., n
+		let mut frobenius = vec![x.quotient_and_remainder(self).1];
+		for k in 0..n {
+			frobenius.push(frobenius[k].pow_mod(Q as u64, self));
+		}
+
+		let coprime = factor(n as i64).into_iter().all(|(r, _)| {
+			gcd(self, &frobenius[n / r as usize].sub_ref(&x)).degree() == 0
+		});
+
+		coprime && frobenius[n] == frobenius[0]
+	}
+
+	/// The factorization into monic irreducible polynomials, as (factor, multiplicity) pairs sorted
+	/// by degree, leaving out the leading coefficient. Q must be prime.
+	///
+	/// Splits off repeated factors by squarefree decomposition, groups the factors of each
+	/// squarefree part by degree with distinct-degree factorization, and separates factors of the
+	/// same degree with the randomized Cantor-Zassenhaus algorithm.
+	#[cfg(feature = "std")]
+	pub fn factor(&self) -> Vec<(Polynomial<ZM<Q>>, usize)> {
+		self.factor_with_rng(&mut rand::thread_rng())
+	}
+
+	/// The factorization into monic irreducible polynomials, using the given source of randomness
+	pub fn factor_with_rng<G: Rng + ?Sized>(&self, rng: &mut G) -> Vec<(Polynomial<ZM<Q>>, usize)> {
+		if self.is_zero() {
+			panic!("The zero polynomial has no factorization")
+		}
+
+		let mut factors = Vec::new();
+		for (part, multiplicity) in self.monic().squarefree_decomposition() {
+			for (product, degree) in part.distinct_degree_factorization() {
+				factors.extend(product.equal_degree_split(degree, rng).into_iter().map(|f| (f, multiplicity)));
+			}
+		}
+
+		factors.sort_by_key(|(f, _)| (f.degree(), f.coefficients.iter().rev().map(|c| c.val).collect::<Vec<i64>>()));
+		factors
+	}
+
+	/// Pairs (f_i, i) of squarefree, pairwise coprime, monic polynomials whose product of f_i^i is
+	/// this monic polynomial
+	fn squarefree_decomposition(&self) -> Vec<(Polynomial<ZM<Q>>, usize)> {
+		if self.degree() == 0 {
+			return Vec::new()
+		}
+
+		let derivative = self.derivative();
+		if derivative.is_zero() {
+			return self.frobenius_root().squarefree_decomposition().into_iter().map(|(f, i)| (f, i * Q as usize)).collect()
+		}
+
+		// Each round peels off the factors of multiplicity exactly i, among those not divisible by Q
+		let mut parts = Vec::new();
+		let mut rest = gcd(self, &derivative).monic();
+		let mut w = self.quotient_and_remainder(&rest).0;
+		let mut i = 1;
+
+		while w.degree() > 0 {
+			let y = gcd(&w, &rest).monic();
+			let part = w.quotient_and_remainder(&y).0;
+			if part.degree() > 0 {
+				parts.push((part.monic(), i));
+			}
+
+			rest = rest.quotient_and_remainder(&y).0;
+			w = y;
+			i += 1;
+		}
+
+		// Whatever is left has every multiplicity divisible by Q
+		if rest.degree() > 0 {
+			parts.extend(rest.frobenius_root().squarefree_decomposition().into_iter().map(|(f, i)| (f, i * Q as usize)));
+		}
+
+		parts
+	}
+
+	/// Pairs (g_d, d) where g_d is the product of the irreducible factors of degree d of this
+	/// squarefree monic polynomial, using that x^(Q^d) - x is the product of all monic irreducibles
+	/// whose degree divides d
+	fn distinct_degree_factorization(&self) -> Vec<(Polynomial<ZM<Q>>, usize)> {
+		let x = Polynomial::x();
+		let mut products = Vec::new();
+		let mut rest = self.clone();
+		let mut frobenius = x.clone();
+		let mut d = 0;
+
+		while rest.degree() >= 2 * (d + 1) {
+			d += 1;
+			frobenius = frobenius.pow_mod(Q as u64, &rest);
+
+			let g = gcd(&rest, &frobenius.sub_ref(&x)).monic();
+			if g.degree() > 0 {
+				rest = rest.quotient_and_remainder(&g).0;
+				frobenius = frobenius.quotient_and_remainder(&rest).1;
+				products.push((g, d));
+			}
+		}
+
+		// A leftover with no factor of degree at most half its own is irreducible
+		if rest.degree() > 0 {
+			let degree = rest.degree();
+			products.push((rest, degree));
+		}
+
+		products
+	}
+
+	/// The irreducible factors of a squarefree monic polynomial whose factors all have degree d, by
+	/// splitting it with gcd(f, a^((Q^d - 1) / 2) - 1) for random a, or with the trace
+	/// a + a^2 + ... + a^(2^(d - 1)) when Q is 2
+	fn equal_degree_split<G: Rng + ?Sized>(&self, d: usize, rng: &mut G) -> Vec<Polynomial<ZM<Q>>> {
+		let n = self.degree();
+		if n <= d {
+			return vec![self.clone()]
+		}
+
+		loop {
+			let a = Polynomial::new((0..n).map(|_| ZM::random(rng)).collect());
+
+			let splitter = if Q == 2 {
+				let mut power = a.clone();
+				let mut trace = a;
+				for _ in 1..d {
+					power = power.pow_mod(2, self);
+					trace.add_assign_ref(&power);
+				}
+				trace
+			} else {
+				// (Q^d - 1) / 2 = (1 + Q + ... + Q^(d - 1)) (Q - 1) / 2
+				let mut power = a.clone();
+				let mut norm = a;
+				for _ in 1..d {
+					power = power.pow_mod(Q as u64, self);
+					norm = norm.mul_ref(&power).quotient_and_remainder(self).1;
+				}
+				norm.pow_mod((Q as u64 - 1) / 2, self) - Polynomial::one()
+			};
+
+			let g = gcd(self, &splitter).monic();
+			if g.degree() > 0 && g.degree() < n {
+				let mut factors = g.equal_degree_split(d, rng);
+				factors.extend(self.quotient_and_remainder(&g).0.equal_degree_split(d, rng));
+				return factors
+			}
+		}
+	}
+
+	/// The polynomial g with g^Q = self, assuming only powers of x^Q appear in self
+	fn frobenius_root(&self) -> Polynomial<ZM<Q>> {
+		// Every element of ZM<Q> is its own Q-th power
+		Polynomial::new(self.coefficients.iter().step_by(Q as usize).cloned().collect())
+	}
+
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring> Add for Polynomial<R> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self += rhs;
+		self
+	}
+}
+
+impl<R: Ring> AddAssign for Polynomial<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Neg for Polynomial<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Polynomial { coefficients: self.coefficients.into_iter().map(|c| -c).collect() }
+	}
+}
+
+impl<R: Ring> Sub for Polynomial<R> {
+	type Output = Self;
+
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self -= rhs;
+		self
+	}
+}
+
+impl<R: Ring> SubAssign for Polynomial<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.sub_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Mul for Polynomial<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring> MulAssign for Polynomial<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.mul_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Mul<R> for Polynomial<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: R) -> Self::Output {
+		self.scale(&rhs)
+	}
+}
+
+impl<R: Ring> MulAssign<R> for Polynomial<R> {
+	fn mul_assign(&mut self, rhs: R) {
+		*self = self.scale(&rhs)
+	}
+}
+
+/// Scalar multiplication on the left, c * p, for the commutative scalar types the orphan rules
+/// allow, since a blanket impl over every ring would not be allowed
+macro_rules! impl_left_scale_polynomial {
+	($($t:ty),*) => {$(
+		impl Mul<Polynomial<$t>> for $t {
+			type Output = Polynomial<$t>;
+
+			fn mul(self, rhs: Polynomial<$t>) -> Self::Output {
+				rhs.scale(&self)
+			}
+		}
+	)*};
+}
+
+impl_left_scale_polynomial!(i8, i16, i32, i64, i128, f32, f64);
+
+impl<const Q: i64> Mul<Polynomial<ZM<Q>>> for ZM<Q> {
+	type Output = Polynomial<ZM<Q>>;
+
+	fn mul(self, rhs: Polynomial<ZM<Q>>) -> Self::Output {
+		rhs.scale(&self)
+	}
+}
+
+impl<R: Ring> Semiring for Polynomial<R> {
+	fn one() -> Self {
+		Polynomial::constant(R::one())
+	}
+
+	fn zero() -> Self {
+		Polynomial { coefficients: vec![] }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.coefficients.is_empty()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		if self.coefficients.len() < rhs.coefficients.len() {
+			self.coefficients.resize(rhs.coefficients.len(), R::zero());
+		}
+		for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+			a.add_assign_ref(b);
+		}
+		self.normalize();
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		Polynomial::new(convolve(&self.coefficients, &rhs.coefficients))
+	}
+}
+
+impl<R: Ring> Ring for Polynomial<R> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+
+		let mut power = Self::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base);
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		if self.coefficients.len() < rhs.coefficients.len() {
+			self.coefficients.resize(rhs.coefficients.len(), R::zero());
+		}
+		for (a, b) in self.coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+			a.sub_assign_ref(b);
+		}
+		self.normalize();
+	}
+}
+
+/// Polynomials are a module over their coefficients, which multiply every coefficient
+impl<R: Ring> Module<R> for Polynomial<R> {
+	fn scale(&self, r: &R) -> Self {
+		Polynomial::scale(self, r)
+	}
+}
+
+impl<R: CommutativeRing> CommutativeRing for Polynomial<R> { }
+
+/// Long division, dividing each leading coefficient exactly in R, so Z[x] and the like have exact
+/// division too
+impl<R: IntegralDomain> IntegralDomain for Polynomial<R> {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		if divisor.is_zero() {
+			return None
+		}
+
+		if self.coefficients.len() < divisor.coefficients.len() {
+			return self.is_zero().then(Self::zero)
+		}
+
+		let d = divisor.degree();
+		let lead = divisor.leading_coefficient();
+		let mut remainder = self.coefficients.clone();
+		let mut quotient = vec![R::zero(); self.degree() - d + 1];
+
+		for i in (0..quotient.len()).rev() {
+			let q = remainder[i + d].divide_exact(&lead)?;
+			for (j, c) in divisor.coefficients.iter().enumerate() {
+				remainder[i + j].sub_assign_ref(&q.mul_ref(c));
+			}
+			quotient[i] = q;
+		}
+
+		remainder.iter().all(|c| c.is_zero()).then(|| Polynomial::new(quotient))
+	}
+}
+
+// MARK: Convolution
+
+/// Below this many coefficients in the shorter factor, multiplication is done term by term
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// From this many coefficients in the shorter factor, multiplication over ZM<Q> uses a
+/// number-theoretic transform when Q allows one
+const NTT_THRESHOLD: usize = 64;
+
+/// The (linear) convolution of two sequences, c_k = sum of a_i b_j over i + j = k.
+///
+/// This is the coefficient list of the product of the polynomials with coefficients a and b. Short
+/// inputs are multiplied term by term and longer ones by Karatsuba's algorithm. Over ZM<Q> where
+/// Q - 1 is divisible by a large enough power of two, as for the prime 998244353 = 119 2^23 + 1,
+/// long inputs are instead multiplied with a number-theoretic transform in O(n log n) time.
+pub fn convolve<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+	<R as Convolution>::convolve(a, b)
+}
+
+/// How a ring multiplies coefficient lists, specialized for the rings with faster methods
+trait Convolution: Ring {
+	fn convolve(a: &[Self], b: &[Self]) -> Vec<Self>;
+}
+
+impl<R: Ring> Convolution for R {
+	default fn convolve(a: &[R], b: &[R]) -> Vec<R> {
+		karatsuba(a, b)
+	}
+}
+
+impl<const Q: i64> Convolution for ZM<Q> {
+	fn convolve(a: &[ZM<Q>], b: &[ZM<Q>]) -> Vec<ZM<Q>> {
+		if a.len().min(b.len()) >= NTT_THRESHOLD {
+			if let Some(c) = ntt::convolve(a, b) {
+				return c
+			}
+		}
+
+		karatsuba(a, b)
+	}
+}
+
+/// The convolution by the schoolbook method, multiplying every pair of terms
+fn schoolbook<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+	if a.is_empty() || b.is_empty() {
+		return vec![]
+	}
+
+	let mut c = vec![R::zero(); a.len() + b.len() - 1];
+	for (i, x) in a.iter().enumerate() {
+		for (j, y) in b.iter().enumerate() {
+			c[i + j].add_assign_ref(&x.mul_ref(y));
+		}
+	}
+
+	c
+}
+
+/// The convolution by Karatsuba's algorithm, which splits a = a_0 + x^m a_1 and b = b_0 + x^m b_1
+/// and gets the middle term a_0 b_1 + a_1 b_0 from the single product (a_0 + a_1)(b_0 + b_1),
+/// taking three half-size products instead of four. The order of factors is kept, so this works
+/// over noncommutative rings too.
+fn karatsuba<R: Ring>(a: &[R], b: &[R]) -> Vec<R> {
+	if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+		return schoolbook(a, b)
+	}
+
+	let m = a.len().max(b.len()) / 2;
+	let (a0, a1) = a.split_at(m.min(a.len()));
+	let (b0, b1) = b.split_at(m.min(b.len()));
+
+	let sum = |low: &[R], high: &[R]| -> Vec<R> {
+		(0..low.len().max(high.len())).map(|i| match (low.get(i), high.get(i)) {
+			(Some(x), Some(y)) => x.add_ref(y),
+			(Some(x), None) | (None, Some(x)) => x.clone(),
+			(None, None) => unreachable!()
+		}).collect()
+	};
+
+	let low = karatsuba(a0, b0);
+	let high = karatsuba(a1, b1);
+	let middle = karatsuba(&sum(a0, a1), &sum(b0, b1));
+
+	// The padded middle product can be longer than the true one, but only by zeros
+	let length = (a.len() + b.len() - 1).max(m + middle.len()).max(2 * m + high.len());
+	let mut c = vec![R::zero(); length];
+	for (i, x) in low.iter().enumerate() {
+		c[i].add_assign_ref(x);
+		c[i + m].sub_assign_ref(x);
+	}
+	for (i, x) in high.iter().enumerate() {
+		c[i + 2 * m].add_assign_ref(x);
+		c[i + m].sub_assign_ref(x);
+	}
+	for (i, x) in middle.iter().enumerate() {
+		c[i + m].add_assign_ref(x);
+	}
+
+	c.truncate(a.len() + b.len() - 1);
+	c
+}
+
+/// The cyclic convolution of length n, c_k = sum of a_i b_j over i + j = k (mod n).
+///
+/// This is the product of the polynomials with coefficients a and b modulo x^n - 1.
+pub fn cyclic_convolve<R: Ring>(a: &[R], b: &[R], n: usize) -> Vec<R> {
+	wrap_convolution(convolve(a, b), n, false)
+}
+
+/// The negacyclic convolution of length n, where terms wrapping past x^n pick up a sign.
+///
+/// This is the product of the polynomials with coefficients a and b modulo x^n + 1.
+pub fn negacyclic_convolve<R: Ring>(a: &[R], b: &[R], n: usize) -> Vec<R> {
+	wrap_convolution(convolve(a, b), n, true)
+}
+
+/// Folds a linear convolution down to length n, negating every other wrap if `negate`
+fn wrap_convolution<R: Ring>(linear: Vec<R>, n: usize, negate: bool) -> Vec<R> {
+	if n == 0 {
+		panic!("Cannot take a convolution of length zero")
+	}
+
+	let mut c = vec![R::zero(); n];
+	for (k, x) in linear.into_iter().enumerate() {
+		if negate && (k / n) % 2 == 1 {
+			c[k % n].sub_assign_ref(&x);
+		} else {
+			c[k % n].add_assign_ref(&x);
+		}
+	}
+
+	c
+}
+
+// MARK: Division
+
+impl<F: Field> Div for Polynomial<F> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl<F: Field> DivAssign for Polynomial<F> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl<F: Field> Rem for Polynomial<F> {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl<F: Field> RemAssign for Polynomial<F> {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl<F: Field> UniqueFactorizationDomain for Polynomial<F> {
+	fn gcd(&self, other: &Self) -> Self {
+		euclidean_gcd(self, other)
+	}
+}
+
+impl<F: Field> PrincipalIdealDomain for Polynomial<F> { }
+
+impl<F: Field> EuclideanDomain for Polynomial<F> {
+	type SizeType = usize;
+
+	fn euc_size(&self) -> usize {
+		self.degree()
+	}
+
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() {
+			panic!("Cannot divide by the zero polynomial")
+		}
+
+		if self.coefficients.len() < divisor.coefficients.len() {
+			return (Self::zero(), self.clone())
+		}
+
+		let d = divisor.degree();
+		let lead_inverse = divisor.leading_coefficient().inverse();
+		let mut remainder = self.coefficients.clone();
+		let mut quotient = vec![F::zero(); self.degree() - d + 1];
+
+		for i in (0..quotient.len()).rev() {
+			let q = remainder[i + d].mul_ref(&lead_inverse);
+			for (j, c) in divisor.coefficients.iter().enumerate() {
+				remainder[i + j].sub_assign_ref(&q.mul_ref(c));
+			}
+			quotient[i] = q;
+		}
+
+		remainder.truncate(d);
+		(Polynomial::new(quotient), Polynomial::new(remainder))
+	}
+}
+
+// MARK: Half GCD
+
+/// A 2x2 matrix of polynomials, acting on pairs of polynomials
+pub type PolynomialTransform<F> = [[Polynomial<F>; 2]; 2];
+
+fn transform_identity<F: Field>() -> PolynomialTransform<F> {
+	[[Polynomial::one(), Polynomial::zero()], [Polynomial::zero(), Polynomial::one()]]
+}
+
+fn transform_mul<F: Field>(m: &PolynomialTransform<F>, n: &PolynomialTransform<F>) -> PolynomialTransform<F> {
+	let entry = |i: usize, j: usize| m[i][0].mul_ref(&n[0][j]).add_ref(&m[i][1].mul_ref(&n[1][j]));
+	[[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]]
+}
+
+fn transform_apply<F: Field>(m: &PolynomialTransform<F>, a: &Polynomial<F>, b: &Polynomial<F>) -> (Polynomial<F>, Polynomial<F>) {
+	(
+		m[0][0].mul_ref(a).add_ref(&m[0][1].mul_ref(b)),
+		m[1][0].mul_ref(a).add_ref(&m[1][1].mul_ref(b))
+	)
+}
+
+/// Below this degree, the plain Euclidean algorithm beats the divide and conquer
+const HALF_GCD_THRESHOLD: usize = 32;
+
+/// The half-GCD of a and b, where deg a > deg b.
+///
+/// Returns a matrix M such that M (a, b) = (c, d) are consecutive remainders in the
+/// Euclidean remainder sequence of a and b, with deg c >= ceil(deg a / 2) > deg d.
+/// The matrix has determinant +/- 1, so gcd(a, b) = gcd(c, d).
+pub fn half_gcd<F: Field>(a: &Polynomial<F>, b: &Polynomial<F>) -> PolynomialTransform<F> {
+	let m = a.degree().div_ceil(2);
+
+	if b.is_zero() || b.degree() < m {
+		return transform_identity()
+	}
+
+	// Reduce the top halves, which determine the first half of the quotient sequence
+	let r = half_gcd(&a.div_x_pow(m), &b.div_x_pow(m));
+	let (c, d) = transform_apply(&r, a, b);
+
+	if d.is_zero() || d.degree() < m {
+		return r
+	}
+
+	// One Euclidean step
+	let (q, e) = c.quotient_and_remainder(&d);
+	let step = [[Polynomial::zero(), Polynomial::one()], [Polynomial::one(), -q]];
+	let r = transform_mul(&step, &r);
+
+	if e.is_zero() || e.degree() < m {
+		return r
+	}
+
+	let k = 2 * m - d.degree();
+	let s = half_gcd(&d.div_x_pow(k), &e.div_x_pow(k));
+
+	transform_mul(&s, &r)
+}
+
+/// The GCD of two polynomials over a field using half-GCD steps, normalized to be monic.
+///
+/// This does the same thing as `gcd`, but is asymptotically faster for large degrees.
+pub fn half_gcd_gcd<F: Field>(a: &Polynomial<F>, b: &Polynomial<F>) -> Polynomial<F> {
+	let (mut a, mut b) = if a.degree() >= b.degree() { (a.clone(), b.clone()) } else { (b.clone(), a.clone()) };
+
+	while !b.is_zero() {
+		if a.degree() > b.degree() && a.degree() >= HALF_GCD_THRESHOLD {
+			let m = half_gcd(&a, &b);
+			(a, b) = transform_apply(&m, &a, &b);
+			if b.is_zero() {
+				break
+			}
+		}
+
+		let r = a.quotient_and_remainder(&b).1;
+		a = core::mem::replace(&mut b, r);
+	}
+
+	a.monic()
+}
+
+// MARK: Content and Primitive Parts
+
+impl<R: CommutativeRing> Polynomial<R> {
+
+	/// The pseudo-remainder of this polynomial by a nonzero divisor g, the remainder r of
+	/// lc(g)^(m - n + 1) f = q g + r, where m and n are the degrees of f and g, which needs no
+	/// division of coefficients
+	pub fn pseudo_remainder(&self, divisor: &Polynomial<R>) -> Polynomial<R> {
+		if divisor.is_zero() {
+			panic!("Cannot divide by the zero polynomial")
+		}
+
+		if self.coefficients.len() < divisor.coefficients.len() {
+			return self.clone()
+		}
+
+		let d = divisor.degree();
+		let lead = divisor.leading_coefficient();
+		let mut remainder = self.coefficients.clone();
+
+		for i in (0..=self.degree() - d).rev() {
+			// remainder <- lc(g) remainder - r_(i+d) x^i g, which clears the coefficient of x^(i+d)
+			let q = remainder[i + d].clone();
+			for c in remainder.iter_mut() {
+				c.mul_assign_ref(&lead);
+			}
+			for (j, c) in divisor.coefficients.iter().enumerate() {
+				remainder[i + j].sub_assign_ref(&q.mul_ref(c));
+			}
+		}
+
+		remainder.truncate(d);
+		Polynomial::new(remainder)
+	}
+
+}
+
+impl<R: EuclideanDomain> Polynomial<R> {
+
+	/// The content of this polynomial, a greatest common divisor of its coefficients
+	pub fn content(&self) -> R {
+		self.coefficients.iter().fold(R::zero(), |g, c| gcd(&g, c))
+	}
+
+	/// This polynomial divided by its content, so that its coefficients have no common factor
+	pub fn primitive_part(&self) -> Polynomial<R> {
+		if self.is_zero() {
+			return self.clone()
+		}
+
+		let content = self.content();
+		Polynomial::new(self.coefficients.iter().map(|c| c.quotient_and_remainder(&content).0).collect())
+	}
+
+	/// A greatest common divisor in R[x], defined up to multiplication by a unit of R.
+	///
+	/// R[x] has no division with remainder when R is not a field, so this is the gcd of the
+	/// contents times the last nonzero primitive part in the sequence of pseudo-remainders, each
+	/// one made primitive to keep the coefficients from growing.
+	pub fn primitive_gcd(&self, other: &Polynomial<R>) -> Polynomial<R> {
+		if self.is_zero() {
+			return other.clone()
+		}
+
+		if other.is_zero() {
+			return self.clone()
+		}
+
+		let content = gcd(&self.content(), &other.content());
+		let (mut a, mut b) = (self.primitive_part(), other.primitive_part());
+		if a.degree() < b.degree() {
+			core::mem::swap(&mut a, &mut b);
+		}
+
+		while !b.is_zero() {
+			let r = a.pseudo_remainder(&b).primitive_part();
+			a = core::mem::replace(&mut b, r);
+		}
+
+		a.scale(&content)
+	}
+
+}
+
+// MARK: Resultants
+
+/// The resultant of f and g, the determinant of their Sylvester matrix.
+///
+/// Over a field, this is zero exactly when f and g have a common root in an algebraic closure, and
+/// it always lies in the ideal (f, g), so it eliminates x from f = g = 0. It is zero when f or g is.
+pub fn resultant<R: CommutativeRing>(f: &Polynomial<R>, g: &Polynomial<R>) -> R {
+	if f.is_zero() || g.is_zero() {
+		return R::zero()
+	}
+
+	Matrix::sylvester(f, g).berkowitz_determinant()
+}
+
+/// The discriminant (-1)^(n(n - 1)/2) Res(f, f') / a_n of a polynomial f of degree n >= 1 with
+/// leading coefficient a_n. Over a field, this is zero exactly when f has a repeated root.
+///
+/// The division is done exactly, by dividing a_n out of the first column of the Sylvester matrix.
+pub fn discriminant<R: CommutativeRing>(f: &Polynomial<R>) -> R {
+	if f.degree() == 0 {
+		panic!("Only polynomials of positive degree have discriminants")
+	}
+
+	// The derivative keeps degree n - 1 even when n a_n is zero in R
+	let n = f.degree();
+	let derivative: Vec<R> = (1..=n).map(|i| repeated_sum(&f.coefficients[i], i)).collect();
+	let mut m = Matrix::sylvester_of_coefficients(&f.coefficients, &derivative);
+
+	// The first column is a_n (1, 0, ..., 0, n, 0, ..., 0), with the n in the first row of f'
+	m[(0, 0)] = R::one();
+	m[(n - 1, 0)] = repeated_sum(&R::one(), n);
+
+	let determinant = m.berkowitz_determinant();
+	if (n * (n - 1) / 2).is_multiple_of(2) { determinant } else { -determinant }
+}
+
+// MARK: Interpolation
+
+/// The products of x - a_i over a binary tree of ranges of the points, which lets polynomials be
+/// evaluated at all the points, or built from values at them, by divide and conquer
+struct SubproductTree<F: Field> {
+	product: Polynomial<F>,
+	children: Option<Box<(SubproductTree<F>, SubproductTree<F>)>>
+}
+
+impl<F: Field> SubproductTree<F> {
+
+	/// The tree over the given nonempty list of points
+	fn new(points: &[F]) -> SubproductTree<F> {
+		if points.len() == 1 {
+			return SubproductTree { product: Polynomial::new(vec![-points[0].clone(), F::one()]), children: None }
+		}
+
+		let (left, right) = points.split_at(points.len() / 2);
+		let (left, right) = (SubproductTree::new(left), SubproductTree::new(right));
+		SubproductTree { product: left.product.mul_ref(&right.product), children: Some(Box::new((left, right))) }
+	}
+
+	/// The values of f at the points under this node, reducing f modulo the product at each node
+	/// on the way down
+	fn evaluate(&self, f: &Polynomial<F>, values: &mut Vec<F>) {
+		let f = f.quotient_and_remainder(&self.product).1;
+		match &self.children {
+			None => values.push(f.coefficient(0)),
+			Some(children) => {
+				children.0.evaluate(&f, values);
+				children.1.evaluate(&f, values);
+			}
+		}
+	}
+
+	/// The sum of c_i times the product of x - a_j over the points a_j under this node other than
+	/// a_i, for the given c_i, one for each point under this node
+	fn combine(&self, c: &[F]) -> Polynomial<F> {
+		match &self.children {
+			None => Polynomial::constant(c[0].clone()),
+			Some(children) => {
+				let (left, right) = &**children;
+				let (c_left, c_right) = c.split_at(c.len() / 2);
+				left.combine(c_left).mul_ref(&right.product) + right.combine(c_right).mul_ref(&left.product)
+			}
+		}
+	}
+
+}
+
+impl<F: Field> Polynomial<F> {
+
+	/// The unique polynomial of degree less than k through the k points (a_i, v_i), or None if two
+	/// of the a_i coincide.
+	///
+	/// Uses Lagrange's formula, the sum of v_i / Z'(a_i) Z(x) / (x - a_i) where Z is the product
+	/// of x - a_i, with the weights Z'(a_i) found by multipoint evaluation and the sum combined
+	/// over the subproduct tree.
+	pub fn interpolate(points: &[(F, F)]) -> Option<Polynomial<F>> {
+		if points.is_empty() {
+			return Some(Polynomial::zero())
+		}
+
+		let xs: Vec<F> = points.iter().map(|(a, _)| a.clone()).collect();
+		let tree = SubproductTree::new(&xs);
+
+		// Z'(a_i) is the product of a_i - a_j over j != i, which is zero exactly for a repeated point
+		let mut weights = Vec::with_capacity(points.len());
+		tree.evaluate(&tree.product.derivative(), &mut weights);
+
+		let c: Option<Vec<F>> = points.iter().zip(weights).map(|((_, v), w)| Some(v.mul_ref(&w.try_inverse()?))).collect();
+		Some(tree.combine(&c?))
+	}
+
+	/// The values of this polynomial at each of the given points, by reducing it down a
+	/// subproduct tree of the points
+	pub fn evaluate_many(&self, points: &[F]) -> Vec<F> {
+		let mut values = Vec::with_capacity(points.len());
+		if !points.is_empty() {
+			SubproductTree::new(points).evaluate(self, &mut values);
+		}
+		values
+	}
+
+}
+
+// MARK: Chinese Remainder
+
+/// The isomorphism F[x]/(f) -> F[x]/(f_1) x ... x F[x]/(f_k) for pairwise coprime moduli f_i
+/// with product f, together with its inverse
+#[derive(Clone, Debug)]
+pub struct PolynomialCRT<F: Field> {
+	moduli: Vec<Polynomial<F>>,
+	modulus: Polynomial<F>,
+	idempotents: Vec<Polynomial<F>>
+}
+
+impl<F: Field> PolynomialCRT<F> {
+
+	/// Sets up the isomorphism for the given moduli, or None if they are not pairwise coprime
+	/// or one of them is zero
+	pub fn new(moduli: Vec<Polynomial<F>>) -> Option<PolynomialCRT<F>> {
+		if moduli.iter().any(|m| m.is_zero()) {
+			return None
+		}
+
+		let modulus = moduli.iter().fold(Polynomial::one(), |acc, m| acc.mul_ref(m));
+
+		// e_i is 1 mod f_i and 0 mod every other f_j
+		let mut idempotents = Vec::with_capacity(moduli.len());
+		for m in moduli.iter() {
+			let cofactor = modulus.quotient_and_remainder(m).0;
+			let (g, s, _) = ext_gcd(&cofactor, m);
+
+			if g.degree() != 0 {
+				return None
+			}
+
+			let e = s.scale(&g.leading_coefficient().inverse()).mul_ref(&cofactor);
+			idempotents.push(e.quotient_and_remainder(&modulus).1);
+		}
+
+		Some(PolynomialCRT { moduli, modulus, idempotents })
+	}
+
+	/// The moduli f_i
+	pub fn moduli(&self) -> &[Polynomial<F>] {
+		&self.moduli
+	}
+
+	/// The product f of all the moduli
+	pub fn modulus(&self) -> &Polynomial<F> {
+		&self.modulus
+	}
+
+	/// The residues of p modulo each f_i
+	pub fn to_residues(&self, p: &Polynomial<F>) -> Vec<Polynomial<F>> {
+		self.moduli.iter().map(|m| p.quotient_and_remainder(m).1).collect()
+	}
+
+	/// The unique polynomial of degree less than deg f with the given residue modulo each f_i
+	pub fn from_residues(&self, residues: &[Polynomial<F>]) -> Polynomial<F> {
+		if residues.len() != self.moduli.len() {
+			panic!("Expected {} residues, got {}", self.moduli.len(), residues.len())
+		}
+
+		let combined = residues.iter().zip(self.idempotents.iter())
+			.fold(Polynomial::zero(), |acc, (r, e)| acc.add_ref(&r.mul_ref(e)));
+
+		combined.quotient_and_remainder(&self.modulus).1
+	}
+
+}
+
+// MARK: Linear Recurrences
+
+/// The kth term of the linear recurrence a_n = c_1 a_(n-1) + c_2 a_(n-2) + ... + c_d a_(n-d),
+/// where `coeffs` is c_1, ..., c_d and `init` is a_0, ..., a_(d-1).
+///
+/// Writes a_k as a combination of the initial terms by computing x^k modulo the characteristic
+/// polynomial x^d - c_1 x^(d-1) - ... - c_d, in O(d^2 log k) ring operations.
+///
+/// For example, the 10^18th Fibonacci number modulo 998244353:
+///
+/// ```
+/// use algebra_kit::polynomial::kth_term_of_recurrence;
+/// use algebra_kit::std_impls::ZM;
+///
+/// let (zero, one) = (ZM::<998244353>::from_int(0), ZM::<998244353>::from_int(1));
+/// let f = kth_term_of_recurrence(&[one, one], &[zero, one], 1_000_000_000_000_000_000);
+/// assert_eq!(f.val, 23849548);
+/// ```
+pub fn kth_term_of_recurrence<R: CommutativeRing>(coeffs: &[R], init: &[R], k: u64) -> R {
+	let d = coeffs.len();
+	if init.len() != d {
+		panic!("A recurrence of order {} needs {} initial terms, got {}", d, d, init.len())
+	}
+
+	if d == 0 {
+		return R::zero()
+	}
+
+	if k < d as u64 {
+		return init[k as usize].clone()
+	}
+
+	// Powers of x reduced modulo the characteristic polynomial, as coefficient lists of length d
+	let mut power = vec![R::zero(); d];
+	power[0] = R::one();
+	let mut base = vec![R::zero(); d];
+	if d == 1 {
+		base[0] = coeffs[0].clone();
+	} else {
+		base[1] = R::one();
+	}
+
+	let mut e = k;
+	while e > 0 {
+		if e & 1 == 1 {
+			power = reduce_by_recurrence(convolve(&power, &base), coeffs);
+		}
+		base = reduce_by_recurrence(convolve(&base, &base), coeffs);
+		e >>= 1;
+	}
+
+	power.iter().zip(init.iter()).fold(R::zero(), |sum, (r, a)| sum.add_ref(&r.mul_ref(a)))
+}
+
+/// Reduces a coefficient list modulo x^d - c_1 x^(d-1) - ... - c_d, leaving d coefficients
+fn reduce_by_recurrence<R: Ring>(mut p: Vec<R>, coeffs: &[R]) -> Vec<R> {
+	let d = coeffs.len();
+
+	// x^t = c_1 x^(t-1) + ... + c_d x^(t-d)
+	for t in (d..p.len()).rev() {
+		let top = core::mem::replace(&mut p[t], R::zero());
+		for (i, c) in coeffs.iter().enumerate() {
+			p[t - 1 - i].add_assign_ref(&top.mul_ref(c));
+		}
+	}
+
+	p.resize(d, R::zero());
+	p
+}
+
+/// The coefficients of the connection polynomial C(x) = 1 - c_1 x - ... - c_d x^d of the shortest
+/// linear recurrence a_n = c_1 a_(n-1) + ... + c_d a_(n-d) satisfied by the sequence from a_d on,
+/// padded to length d + 1, by the Berlekamp-Massey algorithm in O(n^2) field operations
+fn connection_coefficients<F: Field>(sequence: &[F]) -> Vec<F> {
+	// C, and B the connection polynomial from before the last change in length, with b its
+	// discrepancy and m the steps since then
+	let mut c = vec![F::one()];
+	let mut b = vec![F::one()];
+	let mut length = 0;
+	let mut last_discrepancy = F::one();
+	let mut m = 1;
+
+	for n in 0..sequence.len() {
+		let discrepancy = (0..=length).fold(F::zero(), |sum, i| {
+			sum.add_ref(&c.get(i).cloned().unwrap_or_else(F::zero).mul_ref(&sequence[n - i]))
+		});
+
+		if discrepancy.is_zero() {
+			m += 1;
+			continue
+		}
+
+		// C <- C - (d / b) x^m B
+		let factor = discrepancy.mul_ref(&last_discrepancy.inverse());
+		let previous = c.clone();
+		if c.len() < b.len() + m {
+			c.resize(b.len() + m, F::zero());
+		}
+		for (i, x) in b.iter().enumerate() {
+			c[i + m].sub_assign_ref(&factor.mul_ref(x));
+		}
+
+		if 2 * length <= n {
+			length = n + 1 - length;
+			b = previous;
+			last_discrepancy = discrepancy;
+			m = 1;
+		} else {
+			m += 1;
+		}
+	}
+
+	c.resize(length + 1, F::zero());
+	c
+}
+
+/// The minimal connection polynomial C(x) = 1 - c_1 x - ... - c_d x^d of a sequence, for the
+/// shortest linear recurrence a_n = c_1 a_(n-1) + ... + c_d a_(n-d) satisfied by every term from
+/// a_d on, by the Berlekamp-Massey algorithm.
+///
+/// When the sequence satisfies a recurrence of order d and has at least 2d terms, this finds it.
+/// The order d can exceed the degree of C when c_d = 0, so use `linear_complexity` for it.
+pub fn berlekamp_massey<F: Field>(sequence: &[F]) -> Polynomial<F> {
+	Polynomial::new(connection_coefficients(sequence))
+}
+
+/// The shortest linear recurrence a_n = c_1 a_(n-1) + ... + c_d a_(n-d) satisfied by every term
+/// of the sequence from a_d on, as c_1, ..., c_d, from `berlekamp_massey`
+pub fn linear_recurrence<F: Field>(sequence: &[F]) -> Vec<F> {
+	connection_coefficients(sequence)[1..].iter().map(|x| -x.clone()).collect()
+}
+
+/// The linear complexity of a sequence, the order d of the shortest linear recurrence it
+/// satisfies, which is the length of the shortest linear feedback shift register generating it
+pub fn linear_complexity<F: Field>(sequence: &[F]) -> usize {
+	connection_coefficients(sequence).len() - 1
+}
@@ -0,0 +1,132 @@
+//
+// Matrices over the polynomial ring F[x]: the Smith normal form, invariant factors, and the
+// rational canonical form of a matrix over F
+//
+
+use crate::algebra::*;
+use crate::matrix::Matrix;
+use crate::polynomial::Polynomial;
+
+// MARK: Smith Normal Form
+
+/// The Smith normal form of a matrix over F[x], the unique diagonal matrix D = UMV for invertible
+/// U and V whose diagonal entries d_1 | d_2 | ... | d_r are monic, followed by zeros.
+///
+/// Each pivot is a nonzero entry of least degree, which is used to clear its row and column by
+/// division with remainder, moving any smaller remainder into the pivot position and starting
+/// again. Once they are cleared, a row of the rest not divisible by the pivot is added to the
+/// pivot's row, so the divisibility d_i | d_(i+1) holds at the end.
+pub fn smith_form<F: Field>(m: &Matrix<Polynomial<F>>) -> Matrix<Polynomial<F>> {
+	let mut m = m.clone();
+	let (rows, cols) = (m.rows(), m.cols());
+
+	for t in 0..rows.min(cols) {
+		loop {
+			// Move a nonzero entry of least degree into the pivot position
+			let smallest = (t..rows).flat_map(|i| (t..cols).map(move |j| (i, j)))
+				.filter(|&(i, j)| !m[(i, j)].is_zero())
+				.min_by_key(|&(i, j)| m[(i, j)].degree());
+			let Some((i, j)) = smallest else {
+				return m
+			};
+			m.swap_rows(t, i);
+			m.swap_columns(t, j);
+
+			// Clear the pivot's column and row, stopping if a smaller remainder turns up
+			let mut cleared = true;
+			for i in t + 1..rows {
+				let q = m[(i, t)].quotient_and_remainder(&m[(t, t)]).0;
+				for j in t..cols {
+					let delta = q.mul_ref(&m[(t, j)]);
+					m[(i, j)].sub_assign_ref(&delta);
+				}
+				cleared &= m[(i, t)].is_zero();
+			}
+			for j in t + 1..cols {
+				let q = m[(t, j)].quotient_and_remainder(&m[(t, t)]).0;
+				for i in t..rows {
+					let delta = m[(i, t)].mul_ref(&q);
+					m[(i, j)].sub_assign_ref(&delta);
+				}
+				cleared &= m[(t, j)].is_zero();
+			}
+			if !cleared {
+				continue
+			}
+
+			// The pivot must divide everything left, or it is not the next invariant factor
+			let indivisible = (t + 1..rows).find(|&i| (t + 1..cols).any(|j| !m[(i, j)].quotient_and_remainder(&m[(t, t)]).1.is_zero()));
+			match indivisible {
+				Some(i) => {
+					for j in t..cols {
+						let entry = m[(i, j)].clone();
+						m[(t, j)].add_assign_ref(&entry);
+					}
+				},
+				None => break
+			}
+		}
+
+		m[(t, t)] = m[(t, t)].monic();
+	}
+
+	m
+}
+
+/// The invariant factors d_1 | d_2 | ... | d_r of a matrix over F[x], the nonzero diagonal
+/// entries of its Smith normal form, which classify it up to row and column operations
+pub fn invariant_factors<F: Field>(m: &Matrix<Polynomial<F>>) -> Vec<Polynomial<F>> {
+	let smith = smith_form(m);
+	(0..m.rows().min(m.cols())).map(|i| smith[(i, i)].clone()).filter(|d| !d.is_zero()).collect()
+}
+
+// MARK: Rational Canonical Form
+
+/// The characteristic matrix xI - A of a square matrix over F
+pub fn characteristic_matrix<F: Field>(a: &Matrix<F>) -> Matrix<Polynomial<F>> {
+	if !a.is_square() {
+		panic!("Only square matrices have characteristic matrices")
+	}
+
+	Matrix::from_fn(a.rows(), a.cols(), |i, j| {
+		let entry = Polynomial::constant(-a[(i, j)].clone());
+		if i == j { entry + Polynomial::x() } else { entry }
+	})
+}
+
+/// The similarity invariants of a square matrix over F, the invariant factors of xI - A other
+/// than 1. Their product is the characteristic polynomial, the last is the minimal polynomial, and
+/// two matrices are similar exactly when they have the same ones.
+pub fn similarity_invariants<F: Field>(a: &Matrix<F>) -> Vec<Polynomial<F>> {
+	invariant_factors(&characteristic_matrix(a)).into_iter().filter(|d| d.degree() > 0).collect()
+}
+
+/// Whether or not two square matrices over F are similar, meaning B = PAP^(-1) for some invertible P
+pub fn are_similar<F: Field>(a: &Matrix<F>, b: &Matrix<F>) -> bool {
+	a.rows() == b.rows() && similarity_invariants(a) == similarity_invariants(b)
+}
+
+/// The rational canonical form of a square matrix over F, which is similar to it and is block
+/// diagonal with one block for each similarity invariant d, the companion matrix
+/// `Matrix::companion` whose characteristic polynomial is d
+pub fn rational_canonical_form<F: Field>(a: &Matrix<F>) -> Matrix<F> {
+	let mut form = Matrix::zero(a.rows(), a.cols());
+	let mut offset = 0;
+
+	for d in similarity_invariants(a) {
+		// x^k + p_(k-1) x^(k-1) + ... + p_0 is the characteristic polynomial of the recurrence
+		// a_n = -p_(k-1) a_(n-1) - ... - p_0 a_(n-k)
+		let k = d.degree();
+		let coeffs: Vec<F> = (1..=k).map(|i| -d.coefficient(k - i)).collect();
+		let block = Matrix::companion(&coeffs);
+
+		for i in 0..k {
+			for j in 0..k {
+				form[(offset + i, offset + j)] = block[(i, j)].clone();
+			}
+		}
+		offset += k;
+	}
+
+	form
+}
@@ -0,0 +1,256 @@
+//
+// Solving systems of polynomial equations, by triangularizing with a lexicographic Gröbner basis
+// and back substituting one variable at a time
+//
+
+use crate::algebra::*;
+use crate::groebner::groebner_basis;
+use crate::multivariate::{Lex, MonomialOrder, MultiPoly};
+use crate::polynomial::{resultant, Polynomial};
+use crate::std_impls::ZM;
+
+#[cfg(feature = "bigint")]
+use num_rational::BigRational;
+#[cfg(feature = "bigint")]
+use num_traits::ToPrimitive;
+
+#[cfg(feature = "bigint")]
+use crate::complex::Complex;
+
+/// The most rounds of Durand-Kerner iteration when finding complex roots
+#[cfg(feature = "bigint")]
+const DURAND_KERNER_LIMIT: usize = 10_000;
+
+// MARK: Zero-Dimensional Ideals
+
+/// Whether or not the ideal with this Gröbner basis is zero-dimensional, meaning it has finitely
+/// many solutions over the algebraic closure. This is when every variable has a pure power among
+/// the leading monomials.
+pub fn is_zero_dimensional<F: Field, const N: usize, O: MonomialOrder>(basis: &[MultiPoly<F, N>], order: &O) -> bool {
+	let leads: Vec<_> = basis.iter().filter_map(|g| g.leading_monomial(order)).collect();
+	(0..N).all(|i| leads.iter().any(|m| m.exponents[i] > 0 && m.degree() == m.exponents[i]))
+}
+
+/// The elements of a lexicographic Gröbner basis in only the variables x_k, ..., x_(N-1) and
+/// involving x_k, which are the equations deciding x_k once the later variables are known
+fn level<F: Field, const N: usize>(basis: &[MultiPoly<F, N>], k: usize) -> Vec<MultiPoly<F, N>> {
+	basis.iter().filter(|g| (0..k).all(|i| g.degree_in(i) == 0) && g.degree_in(k) > 0).cloned().collect()
+}
+
+// MARK: Finite Fields
+
+/// Every solution in ZM<Q>^N of the system f_1 = ... = f_k = 0, in no particular order.
+///
+/// Solves for x_(N-1), then x_(N-2), and so on, trying every value of each variable against the
+/// equations of the lexicographic Gröbner basis which involve only it and later variables. This
+/// takes time proportional to Q for each partial solution, so it suits small fields; a variable
+/// left free by the equations takes all Q values.
+pub fn solve_zm<const Q: i64, const N: usize>(system: &[MultiPoly<ZM<Q>, N>]) -> Vec<[ZM<Q>; N]> {
+	let basis = groebner_basis(system, &Lex);
+	if basis.iter().any(|g| g.is_constant()) {
+		return Vec::new()
+	}
+
+	let mut solutions = vec![[ZM::zero(); N]];
+	for k in (0..N).rev() {
+		let equations = level(&basis, k);
+		solutions = solutions.into_iter().flat_map(|partial| {
+			let equations = &equations;
+			(0..Q).filter_map(move |v| {
+				let mut point = partial;
+				point[k] = ZM::from_int(v);
+				equations.iter().all(|g| g.evaluate(&point).is_zero()).then_some(point)
+			})
+		}).collect();
+	}
+
+	solutions
+}
+
+// MARK: Complex Numbers
+
+/// The complex roots of a polynomial of positive degree, with repeated roots repeated, by the
+/// Durand-Kerner iteration
+#[cfg(feature = "bigint")]
+fn complex_roots(p: &Polynomial<Complex<f64>>) -> Vec<Complex<f64>> {
+	let p = p.monic();
+	let n = p.degree();
+
+	// Start from powers of a number which is neither real nor a root of unity
+	let seed = Complex::new(0.4, 0.9);
+	let mut roots: Vec<Complex<f64>> = (0..n).map(|k| seed.power(k as i64)).collect();
+
+	for _ in 0..DURAND_KERNER_LIMIT {
+		let mut change: f64 = 0.0;
+		for k in 0..n {
+			let denominator = (0..n).filter(|&j| j != k).fold(Complex::one(), |d, j| d * (roots[k] - roots[j]));
+			if denominator.is_zero() {
+				continue
+			}
+
+			let step = p.evaluate(&roots[k]) / denominator;
+			roots[k] -= step;
+			change = change.max(step.abs());
+		}
+
+		if change < 1e-15 {
+			break
+		}
+	}
+
+	roots
+}
+
+/// The solutions in C^N of a system with rational coefficients and finitely many solutions,
+/// approximately, or None if it has infinitely many.
+///
+/// Triangularizes the system exactly with a lexicographic Gröbner basis. For each variable from
+/// x_(N-1) down, one basis element has a pure power of it as its leading term, so after
+/// substituting the later variables it is a univariate polynomial of the same degree, whose roots
+/// are found numerically and kept if every other equation at that level is within the tolerance
+/// of zero there. Solutions closer together than the tolerance are merged.
+#[cfg(feature = "bigint")]
+pub fn solve_complex<const N: usize>(system: &[MultiPoly<BigRational, N>], tolerance: f64) -> Option<Vec<[Complex<f64>; N]>> {
+	let basis = groebner_basis(system, &Lex);
+	if basis.iter().any(|g| g.is_constant()) {
+		return Some(Vec::new())
+	}
+
+	if !is_zero_dimensional(&basis, &Lex) {
+		return None
+	}
+
+	let to_complex = |c: &BigRational| Complex::from_real(c.to_f64().unwrap_or(f64::NAN));
+
+	let mut solutions = vec![[Complex::zero(); N]];
+	for k in (0..N).rev() {
+		let equations = level(&basis, k);
+
+		// x_k as a polynomial once the later variables are known
+		let substitute = |g: &MultiPoly<BigRational, N>, point: &[Complex<f64>; N]| {
+			let mut coefficients = vec![Complex::zero(); g.degree_in(k) as usize + 1];
+			for (m, c) in g.terms() {
+				let value = (k + 1..N).fold(to_complex(c), |value, i| value * point[i].power(m.exponents[i] as i64));
+				coefficients[m.exponents[k] as usize] += value;
+			}
+			Polynomial::new(coefficients)
+		};
+
+		// The element whose leading monomial is a pure power of x_k keeps its degree
+		let pivot = equations.iter().find(|g| {
+			let m = g.leading_monomial(&Lex).unwrap();
+			m.degree() == m.exponents[k]
+		}).unwrap();
+
+		let mut extended = Vec::new();
+		for partial in solutions {
+			for root in complex_roots(&substitute(pivot, &partial)) {
+				let mut point = partial;
+				point[k] = root;
+
+				let satisfied = equations.iter().all(|g| substitute(g, &partial).evaluate(&root).abs() <= tolerance);
+				let repeated = extended.iter().any(|other: &[Complex<f64>; N]| (k..N).all(|i| (other[i] - point[i]).abs() <= tolerance));
+				if satisfied && !repeated {
+					extended.push(point);
+				}
+			}
+		}
+		solutions = extended;
+	}
+
+	Some(solutions)
+}
+
+// MARK: Bivariate Systems
+
+/// f as a polynomial in x_0 whose coefficients are polynomials in x_1
+fn in_first_variable<F: Field>(f: &MultiPoly<F, 2>) -> Polynomial<Polynomial<F>> {
+	let mut coefficients = vec![Polynomial::zero(); f.degree_in(0) as usize + 1];
+	for (m, c) in f.terms() {
+		coefficients[m.exponents[0] as usize] += Polynomial::monomial(c.clone(), m.exponents[1] as usize);
+	}
+	Polynomial::new(coefficients)
+}
+
+/// The resultant of f and g with respect to x_0, a polynomial in x_1 which vanishes at the x_1
+/// coordinate of every common solution.
+///
+/// It is zero exactly when f and g share a factor involving x_0.
+pub fn eliminate<F: Field>(f: &MultiPoly<F, 2>, g: &MultiPoly<F, 2>) -> Polynomial<F> {
+	resultant(&in_first_variable(f), &in_first_variable(g))
+}
+
+/// Every solution in ZM<Q>^2 of f = g = 0, or None if f and g share a factor, so that there are
+/// infinitely many solutions over the algebraic closure. Q must be prime.
+///
+/// Finds the possible x_1 coordinates as roots of the resultant, then the x_0 coordinates above
+/// each as roots of the gcd of f and g there. This avoids computing a Gröbner basis, and finds
+/// roots with `Polynomial::roots_mod_p` rather than by search, so it suits large fields.
+pub fn solve_bivariate_zm<const Q: i64>(f: &MultiPoly<ZM<Q>, 2>, g: &MultiPoly<ZM<Q>, 2>) -> Option<Vec<[ZM<Q>; 2]>> {
+	let resultant = eliminate(f, g);
+	if resultant.is_zero() {
+		return None
+	}
+
+	let (f, g) = (in_first_variable(f), in_first_variable(g));
+	let mut solutions = Vec::new();
+
+	for y in resultant.roots_mod_p() {
+		let at = |p: &Polynomial<Polynomial<ZM<Q>>>| Polynomial::new(p.coefficients().iter().map(|c| c.evaluate(&y)).collect());
+		let common = gcd(&at(&f), &at(&g));
+
+		// Both vanish identically only on a shared line y = c, which the resultant rules out
+		solutions.extend(common.roots_mod_p().into_iter().map(|x| [x, y]));
+	}
+
+	Some(solutions)
+}
+
+/// The solutions in C^2 of f = g = 0 for f and g with rational coefficients, approximately, or
+/// None if f and g share a factor, so that there are infinitely many.
+///
+/// Finds the roots of the resultant's squarefree part numerically as the possible x_1 coordinates,
+/// then above each the roots of f or g in x_0, keeping those where both are within the tolerance
+/// of zero. Solutions closer together than the tolerance are merged.
+#[cfg(feature = "bigint")]
+pub fn solve_bivariate_complex(f: &MultiPoly<BigRational, 2>, g: &MultiPoly<BigRational, 2>, tolerance: f64) -> Option<Vec<[Complex<f64>; 2]>> {
+	let resultant = eliminate(f, g);
+	if resultant.is_zero() {
+		return None
+	}
+
+	// Repeated roots are only found to about half precision, so find those of the squarefree part
+	let resultant = resultant.quotient_and_remainder(&gcd(&resultant, &resultant.derivative())).0;
+
+	let to_complex = |c: &BigRational| Complex::from_real(c.to_f64().unwrap_or(f64::NAN));
+	let resultant = Polynomial::new(resultant.coefficients().iter().map(to_complex).collect());
+	let (f, g) = (in_first_variable(f), in_first_variable(g));
+
+	let mut solutions: Vec<[Complex<f64>; 2]> = Vec::new();
+	let ys = if resultant.degree() == 0 { Vec::new() } else { complex_roots(&resultant) };
+
+	for y in ys {
+		// Coefficients which vanish at y up to rounding are dropped, or they would give huge roots
+		let at = |p: &Polynomial<Polynomial<BigRational>>| {
+			let mut coefficients: Vec<Complex<f64>> = p.coefficients().iter().map(|c| {
+				Polynomial::new(c.coefficients().iter().map(to_complex).collect()).evaluate(&y)
+			}).collect();
+			while coefficients.last().is_some_and(|c| c.abs() <= tolerance) {
+				coefficients.pop();
+			}
+			Polynomial::new(coefficients)
+		};
+		let (fy, gy) = (at(&f), at(&g));
+
+		let candidates = if fy.degree() > 0 { complex_roots(&fy) } else if gy.degree() > 0 { complex_roots(&gy) } else { Vec::new() };
+		for x in candidates {
+			let satisfied = fy.evaluate(&x).abs() <= tolerance && gy.evaluate(&x).abs() <= tolerance;
+			let repeated = solutions.iter().any(|s| (s[0] - x).abs() <= tolerance && (s[1] - y).abs() <= tolerance);
+			if satisfied && !repeated {
+				solutions.push([x, y]);
+			}
+		}
+	}
+
+	Some(solutions)
+}
@@ -0,0 +1,247 @@
+//
+// Formal power series over a ring, with lazily computed and memoized coefficients
+//
+
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::rc::Rc;
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+
+/// How many coefficients `Debug` shows
+const DEBUG_TERMS: usize = 8;
+
+/// A rule giving the nth coefficient from n and the coefficients before it
+type Rule<R> = Box<dyn Fn(usize, &[R]) -> R>;
+
+/// The rule for a series, and the coefficients found so far
+struct Lazy<R: Ring> {
+	rule: Rule<R>,
+	cache: RefCell<Vec<R>>
+}
+
+// MARK: Power Series
+
+/// A formal power series a_0 + a_1 x + a_2 x^2 + ... over a ring R.
+///
+/// Coefficients are only computed when asked for, and each is computed once, so series can be
+/// defined by rules or recurrences and combined freely. Cloning a series is cheap, and the clone
+/// shares the coefficients already computed.
+///
+/// Since two series can only be compared up to some number of terms, this is not a `Ring`, but it
+/// has the ring operators and its coefficients can be read off with `coefficient` or `truncate`.
+pub struct PowerSeries<R: Ring> {
+	inner: Rc<Lazy<R>>
+}
+
+impl<R: Ring + 'static> PowerSeries<R> {
+
+	/// The series whose nth coefficient is computed from n and the coefficients before it
+	pub fn from_recurrence<F: Fn(usize, &[R]) -> R + 'static>(rule: F) -> PowerSeries<R> {
+		PowerSeries { inner: Rc::new(Lazy { rule: Box::new(rule), cache: RefCell::new(Vec::new()) }) }
+	}
+
+	/// The series whose nth coefficient is f(n)
+	pub fn from_fn<F: Fn(usize) -> R + 'static>(f: F) -> PowerSeries<R> {
+		PowerSeries::from_recurrence(move |n, _| f(n))
+	}
+
+	/// A polynomial, as a series with finitely many nonzero terms
+	pub fn from_polynomial(p: Polynomial<R>) -> PowerSeries<R> {
+		PowerSeries::from_fn(move |n| p.coefficient(n))
+	}
+
+	/// The constant series c
+	pub fn constant(c: R) -> PowerSeries<R> {
+		PowerSeries::from_polynomial(Polynomial::constant(c))
+	}
+
+	/// The series x
+	pub fn x() -> PowerSeries<R> {
+		PowerSeries::from_polynomial(Polynomial::x())
+	}
+
+	/// The zero series
+	pub fn zero() -> PowerSeries<R> {
+		PowerSeries::from_fn(|_| R::zero())
+	}
+
+	/// The series 1
+	pub fn one() -> PowerSeries<R> {
+		PowerSeries::constant(R::one())
+	}
+
+	/// The geometric series 1 + x + x^2 + ... = 1 / (1 - x)
+	pub fn geometric() -> PowerSeries<R> {
+		PowerSeries::from_fn(|_| R::one())
+	}
+
+	/// The coefficient of x^n, computing it and every coefficient before it if need be
+	pub fn coefficient(&self, n: usize) -> R {
+		let known = self.inner.cache.borrow().len();
+
+		// The rule may read other series, but only reads this one through the slice it is given
+		for i in known..=n {
+			let next = (self.inner.rule)(i, &self.inner.cache.borrow());
+			self.inner.cache.borrow_mut().push(next);
+		}
+
+		self.inner.cache.borrow()[n].clone()
+	}
+
+	/// The coefficients of x^0, ..., x^(n-1)
+	pub fn coefficients(&self, n: usize) -> Vec<R> {
+		if n > 0 {
+			self.coefficient(n - 1);
+		}
+		self.inner.cache.borrow()[..n].to_vec()
+	}
+
+	/// The polynomial made of the terms up to x^degree
+	pub fn truncate(&self, degree: usize) -> Polynomial<R> {
+		Polynomial::new(self.coefficients(degree + 1))
+	}
+
+	/// Multiplies every coefficient by c
+	pub fn scale(&self, c: &R) -> PowerSeries<R> {
+		let (series, c) = (self.clone(), c.clone());
+		PowerSeries::from_fn(move |n| series.coefficient(n).mul_ref(&c))
+	}
+
+	/// Multiplies this series by x^k
+	pub fn mul_x_pow(&self, k: usize) -> PowerSeries<R> {
+		let series = self.clone();
+		PowerSeries::from_fn(move |n| if n < k { R::zero() } else { series.coefficient(n - k) })
+	}
+
+	/// The formal derivative
+	pub fn derivative(&self) -> PowerSeries<R> {
+		let series = self.clone();
+		PowerSeries::from_fn(move |n| {
+			let c = series.coefficient(n + 1);
+			(0..n).fold(c.clone(), |sum, _| sum.add_ref(&c))
+		})
+	}
+
+	/// The multiplicative inverse, given the inverse u of the constant term, or None if u is not
+	/// its inverse
+	pub fn inverse_given(&self, u: R) -> Option<PowerSeries<R>> {
+		let a0 = self.coefficient(0);
+		if a0.mul_ref(&u) != R::one() || u.mul_ref(&a0) != R::one() {
+			return None
+		}
+
+		// b_0 = u and b_n = -u (a_1 b_(n-1) + ... + a_n b_0)
+		let series = self.clone();
+		Some(PowerSeries::from_recurrence(move |n, b| {
+			if n == 0 {
+				return u.clone()
+			}
+
+			let sum = (1..=n).fold(R::zero(), |sum, k| sum.add_ref(&series.coefficient(k).mul_ref(&b[n - k])));
+			-u.mul_ref(&sum)
+		}))
+	}
+
+	/// The composition f(g(x)), which is only defined when g has no constant term
+	pub fn compose(&self, g: &PowerSeries<R>) -> Option<PowerSeries<R>> {
+		if !g.coefficient(0).is_zero() {
+			return None
+		}
+
+		// Since g^k starts at x^k, the nth coefficient is f_0 [x^n] g^0 + ... + f_n [x^n] g^n
+		let (f, g) = (self.clone(), g.clone());
+		let powers = RefCell::new(vec![PowerSeries::one()]);
+
+		Some(PowerSeries::from_fn(move |n| {
+			let mut powers = powers.borrow_mut();
+			while powers.len() <= n {
+				let next = powers[powers.len() - 1].clone() * g.clone();
+				powers.push(next);
+			}
+
+			(0..=n).fold(R::zero(), |sum, k| sum.add_ref(&f.coefficient(k).mul_ref(&powers[k].coefficient(n))))
+		}))
+	}
+
+}
+
+impl<F: Field + 'static> PowerSeries<F> {
+
+	/// The multiplicative inverse, or None if the constant term is zero
+	pub fn inverse(&self) -> Option<PowerSeries<F>> {
+		self.inverse_given(self.coefficient(0).try_inverse()?)
+	}
+
+	/// The formal integral with constant term zero. Computing the coefficient of x^n divides by n,
+	/// so this panics at the first such coefficient where n is zero in F.
+	pub fn integral(&self) -> PowerSeries<F> {
+		let series = self.clone();
+		PowerSeries::from_fn(move |n| {
+			if n == 0 {
+				return F::zero()
+			}
+
+			let n_in_f = (1..n).fold(F::one(), |sum, _| sum.add_ref(&F::one()));
+			series.coefficient(n - 1) * n_in_f.inverse()
+		})
+	}
+
+}
+
+impl<R: Ring> Clone for PowerSeries<R> {
+	fn clone(&self) -> Self {
+		PowerSeries { inner: Rc::clone(&self.inner) }
+	}
+}
+
+impl<R: Ring + 'static> Debug for PowerSeries<R> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "PowerSeries {:?} + ...", self.coefficients(DEBUG_TERMS))
+	}
+}
+
+impl<R: Ring + 'static> From<Polynomial<R>> for PowerSeries<R> {
+	fn from(value: Polynomial<R>) -> Self {
+		PowerSeries::from_polynomial(value)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring + 'static> Add for PowerSeries<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		PowerSeries::from_fn(move |n| self.coefficient(n) + rhs.coefficient(n))
+	}
+}
+
+impl<R: Ring + 'static> Neg for PowerSeries<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		PowerSeries::from_fn(move |n| -self.coefficient(n))
+	}
+}
+
+impl<R: Ring + 'static> Sub for PowerSeries<R> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		PowerSeries::from_fn(move |n| self.coefficient(n) - rhs.coefficient(n))
+	}
+}
+
+/// The Cauchy product, c_n = a_0 b_n + a_1 b_(n-1) + ... + a_n b_0
+impl<R: Ring + 'static> Mul for PowerSeries<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		PowerSeries::from_fn(move |n| {
+			(0..=n).fold(R::zero(), |sum, k| sum.add_ref(&self.coefficient(k).mul_ref(&rhs.coefficient(n - k))))
+		})
+	}
+}
@@ -0,0 +1,355 @@
+//
+// Groups built out of other groups
+//
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::finite_set::FiniteSet;
+use crate::permutation::Perm;
+use crate::random::RandomElement;
+use crate::std_impls::AdditiveGroupZM;
+
+// MARK: Actions by Automorphisms
+
+/// A homomorphism H -> Aut(N), describing how H acts on N.
+///
+/// This is given at the type level so that the identity of a semidirect product
+/// can be written down without any extra context.
+pub trait AutomorphismAction<N: Group, H: Group> {
+
+	/// The image of n under the automorphism corresponding to h
+	fn act(h: &H, n: &N) -> N;
+
+}
+
+/// The trivial action, where every element of H acts as the identity on N.
+///
+/// The semidirect product with this action is the direct product N x H.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TrivialAction;
+
+impl<N: Group, H: Group> AutomorphismAction<N, H> for TrivialAction {
+	fn act(_: &H, n: &N) -> N {
+		n.clone()
+	}
+}
+
+/// The action of Z/(M), for even M, on an abelian group by inversion, where h acts as n -> n^((-1)^h).
+///
+/// Acting on Z/(N) with M = 2, the semidirect product is the dihedral group of order 2N.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InversionAction;
+
+impl<N: AbelianGroup, const M: i64> AutomorphismAction<N, AdditiveGroupZM<M>> for InversionAction {
+	fn act(h: &AdditiveGroupZM<M>, n: &N) -> N {
+		if M % 2 != 0 {
+			panic!("Inversion is only an action of Z/(M) for even M")
+		}
+
+		if h.0.val.rem_euclid(2) == 0 { n.clone() } else { n.inverse() }
+	}
+}
+
+// MARK: Semidirect Products
+
+/// An element (n, h) of the semidirect product N x| H, where H acts on N through A.
+///
+/// Multiplication is (n1, h1)(n2, h2) = (n1 A(h1)(n2), h1 h2).
+pub struct SemidirectProduct<N: Group, H: Group, A: AutomorphismAction<N, H>> {
+	pub normal: N,
+	pub acting: H,
+	_action: PhantomData<fn() -> A>
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> SemidirectProduct<N, H, A> {
+
+	/// The element (n, h)
+	pub fn new(normal: N, acting: H) -> Self {
+		SemidirectProduct { normal, acting, _action: PhantomData }
+	}
+
+	/// Checks that A really is a homomorphism H -> Aut(N), by testing every element
+	pub fn is_valid_action() -> bool where N: FiniteGroup, H: FiniteGroup {
+		let ns: Vec<N> = N::elements().collect();
+		let hs: Vec<H> = H::elements().collect();
+
+		let respects_n = hs.iter().all(|h| {
+			ns.iter().all(|a| ns.iter().all(|b| {
+				A::act(h, &(a.clone() * b.clone())) == A::act(h, a) * A::act(h, b)
+			}))
+		});
+
+		let respects_h = hs.iter().all(|g| hs.iter().all(|h| {
+			ns.iter().all(|n| A::act(&(g.clone() * h.clone()), n) == A::act(g, &A::act(h, n)))
+		}));
+
+		let is_bijective = hs.iter().all(|h| {
+			ns.iter().all(|n| ns.iter().any(|m| A::act(h, m) == *n))
+		});
+
+		respects_n && respects_h && is_bijective
+	}
+
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Clone for SemidirectProduct<N, H, A> {
+	fn clone(&self) -> Self {
+		SemidirectProduct::new(self.normal.clone(), self.acting.clone())
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Debug for SemidirectProduct<N, H, A> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "({:?}, {:?})", self.normal, self.acting)
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> PartialEq for SemidirectProduct<N, H, A> {
+	fn eq(&self, other: &Self) -> bool {
+		self.normal == other.normal && self.acting == other.acting
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Mul for SemidirectProduct<N, H, A> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let twisted = A::act(&self.acting, &rhs.normal);
+		SemidirectProduct::new(self.normal * twisted, self.acting * rhs.acting)
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> MulAssign for SemidirectProduct<N, H, A> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.clone() * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Div for SemidirectProduct<N, H, A> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> DivAssign for SemidirectProduct<N, H, A> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Monoid for SemidirectProduct<N, H, A> {
+	fn identity() -> Self {
+		SemidirectProduct::new(N::identity(), H::identity())
+	}
+}
+
+impl<N: Group, H: Group, A: AutomorphismAction<N, H>> Group for SemidirectProduct<N, H, A> {
+	fn inverse(&self) -> Self {
+		// (n, h)^(-1) = (A(h^(-1))(n^(-1)), h^(-1))
+		let h_inverse = self.acting.inverse();
+		SemidirectProduct::new(A::act(&h_inverse, &self.normal.inverse()), h_inverse)
+	}
+}
+
+impl<N: FiniteGroup, H: FiniteGroup, A: AutomorphismAction<N, H>> FiniteGroup for SemidirectProduct<N, H, A> {
+
+	fn order() -> usize {
+		N::order() * H::order()
+	}
+
+	fn elements() -> impl Iterator<Item = Self> {
+		H::elements().flat_map(|h| N::elements().map(move |n| SemidirectProduct::new(n, h.clone())))
+	}
+}
+
+impl<N: Group + RandomElement, H: Group + RandomElement, A: AutomorphismAction<N, H>> RandomElement for SemidirectProduct<N, H, A> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		SemidirectProduct::new(N::random(rng), H::random(rng))
+	}
+}
+
+impl<N: Group + RandomElement, H: Group + RandomElement, A: AutomorphismAction<N, H>> Distribution<SemidirectProduct<N, H, A>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> SemidirectProduct<N, H, A> {
+		SemidirectProduct::random(rng)
+	}
+}
+
+/// (n, h) is numbered like the pair (n, h)
+impl<N: Group + FiniteSet, H: Group + FiniteSet, A: AutomorphismAction<N, H>> FiniteSet for SemidirectProduct<N, H, A> {
+	fn size() -> u128 {
+		<(N, H)>::size()
+	}
+
+	fn to_index(&self) -> u128 {
+		(self.normal.clone(), self.acting.clone()).to_index()
+	}
+
+	fn from_index(index: u128) -> Self {
+		let (normal, acting) = <(N, H)>::from_index(index);
+		SemidirectProduct::new(normal, acting)
+	}
+}
+
+// MARK: Direct Powers
+
+/// An element of the direct power G^N, multiplied coordinate by coordinate
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectPower<G: Group, const N: usize> {
+	pub coordinates: [G; N]
+}
+
+impl<G: Group, const N: usize> DirectPower<G, N> {
+
+	/// The element with the given coordinates
+	pub fn new(coordinates: [G; N]) -> Self {
+		DirectPower { coordinates }
+	}
+
+}
+
+impl<G: Group, const N: usize> Mul for DirectPower<G, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let mut coordinates = self.coordinates;
+		for (a, b) in coordinates.iter_mut().zip(rhs.coordinates) {
+			*a *= b;
+		}
+		DirectPower { coordinates }
+	}
+}
+
+impl<G: Group, const N: usize> MulAssign for DirectPower<G, N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.clone() * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<G: Group, const N: usize> Div for DirectPower<G, N> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<G: Group, const N: usize> DivAssign for DirectPower<G, N> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<G: Group, const N: usize> Monoid for DirectPower<G, N> {
+	fn identity() -> Self {
+		DirectPower { coordinates: std::array::from_fn(|_| G::identity()) }
+	}
+}
+
+impl<G: Group, const N: usize> Group for DirectPower<G, N> {
+	fn inverse(&self) -> Self {
+		DirectPower { coordinates: std::array::from_fn(|i| self.coordinates[i].inverse()) }
+	}
+}
+
+impl<G: AbelianGroup, const N: usize> AbelianGroup for DirectPower<G, N> { }
+
+impl<G: FiniteGroup, const N: usize> FiniteGroup for DirectPower<G, N> {
+
+	fn order() -> usize {
+		G::order().pow(N as u32)
+	}
+
+	fn elements() -> impl Iterator<Item = Self> {
+		let elements: Vec<G> = G::elements().collect();
+
+		// Count through every N digit number in base |G|
+		(0..Self::order()).map(move |mut k| {
+			DirectPower {
+				coordinates: std::array::from_fn(|_| {
+					let digit = k % elements.len();
+					k /= elements.len();
+					elements[digit].clone()
+				})
+			}
+		})
+	}
+}
+
+impl<G: Group + RandomElement, const N: usize> RandomElement for DirectPower<G, N> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		DirectPower { coordinates: std::array::from_fn(|_| G::random(rng)) }
+	}
+}
+
+impl<G: Group + RandomElement, const N: usize> Distribution<DirectPower<G, N>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DirectPower<G, N> {
+		DirectPower::random(rng)
+	}
+}
+
+impl<G: Group + FiniteSet, const N: usize> FiniteSet for DirectPower<G, N> {
+	fn size() -> u128 {
+		<[G; N]>::size()
+	}
+
+	fn to_index(&self) -> u128 {
+		self.coordinates.to_index()
+	}
+
+	fn from_index(index: u128) -> Self {
+		DirectPower::new(<[G; N]>::from_index(index))
+	}
+}
+
+// MARK: Wreath Products
+
+/// The action of S_N on G^N by permuting coordinates, so that s sends the coordinate at i to s(i)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CoordinatePermutation;
+
+impl<G: Group, const N: usize> AutomorphismAction<DirectPower<G, N>, Perm<N>> for CoordinatePermutation {
+	fn act(s: &Perm<N>, f: &DirectPower<G, N>) -> DirectPower<G, N> {
+		let s_inverse = s.inverse();
+		DirectPower { coordinates: std::array::from_fn(|i| f.coordinates[s_inverse.apply(i)].clone()) }
+	}
+}
+
+/// The wreath product G wr S_N = G^N x| S_N, with S_N permuting the N copies of G
+pub type WreathProduct<G, const N: usize> = SemidirectProduct<DirectPower<G, N>, Perm<N>, CoordinatePermutation>;
+
+impl<G: FiniteGroup, const N: usize> WreathProduct<G, N> {
+
+	/// This element as a permutation of the M = N|G| points (i, g), where i < N and g is in G.
+	///
+	/// The element (f, s) sends (i, g) to (s(i), f(s(i)) g), so the N blocks {i} x G are permuted
+	/// among themselves. The point (i, g) is numbered i|G| + k, where g is the kth element of
+	/// `G::elements()`. This panics unless M = N|G|.
+	pub fn imprimitive_permutation<const M: usize>(&self) -> Perm<M> {
+		let elements: Vec<G> = G::elements().collect();
+		let size = elements.len();
+
+		if M != N * size {
+			panic!("The wreath product acts on {} points, not {}", N * size, M)
+		}
+
+		let images = std::array::from_fn(|point| {
+			let (i, g) = (point / size, &elements[point % size]);
+			let j = self.acting.apply(i);
+			let image = self.normal.coordinates[j].clone() * g.clone();
+			j * size + elements.iter().position(|h| *h == image).unwrap()
+		});
+
+		Perm { images }
+	}
+
+}
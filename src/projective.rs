@@ -0,0 +1,104 @@
+//
+// Points of projective space, as homogeneous coordinates up to scaling
+//
+
+use std::hash::{Hash, Hasher};
+
+use crate::algebra::*;
+use crate::std_impls::ZM;
+
+// MARK: Projective Points
+
+/// A point (x_0 : x_1 : ... : x_(N - 1)) of the projective space P^(N - 1)(F).
+///
+/// The coordinates are never all zero, and two points are equal when one is a nonzero
+/// multiple of the other.
+#[derive(Clone, Copy, Debug)]
+pub struct ProjectivePoint<F: Field, const N: usize> {
+	coordinates: [F; N]
+}
+
+impl<F: Field, const N: usize> ProjectivePoint<F, N> {
+
+	/// The point with the given homogeneous coordinates, or `None` if they are all zero
+	pub fn new(coordinates: [F; N]) -> Option<ProjectivePoint<F, N>> {
+		if coordinates.iter().all(|c| c.is_zero()) {
+			None
+		} else {
+			Some(ProjectivePoint { coordinates })
+		}
+	}
+
+	/// The homogeneous coordinates this point was created with, which are only defined up to scaling
+	pub fn coordinates(&self) -> &[F; N] {
+		&self.coordinates
+	}
+
+	/// The same point, scaled so that its last nonzero coordinate is 1
+	pub fn normalized(&self) -> ProjectivePoint<F, N> {
+		let last = self.coordinates.iter().rposition(|c| !c.is_zero()).unwrap();
+		let scale = self.coordinates[last].inverse();
+		ProjectivePoint { coordinates: std::array::from_fn(|i| self.coordinates[i].mul_ref(&scale)) }
+	}
+
+	/// Whether or not this point lies off the hyperplane at infinity, where the last coordinate is zero
+	pub fn is_finite(&self) -> bool {
+		!self.coordinates[N - 1].is_zero()
+	}
+
+}
+
+impl<F: Field, const N: usize> PartialEq for ProjectivePoint<F, N> {
+	fn eq(&self, other: &Self) -> bool {
+		// Two points are equal when every 2x2 minor of their coordinates vanishes
+		(0..N).all(|i| (0..i).all(|j| {
+			self.coordinates[i].mul_ref(&other.coordinates[j]) == self.coordinates[j].mul_ref(&other.coordinates[i])
+		}))
+	}
+}
+
+impl<F: Field + Eq, const N: usize> Eq for ProjectivePoint<F, N> { }
+
+/// Hashes the normalized coordinates, so that equal points hash alike however they are scaled
+impl<F: Field + Hash, const N: usize> Hash for ProjectivePoint<F, N> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.normalized().coordinates.hash(state)
+	}
+}
+
+impl<const Q: i64, const N: usize> ProjectivePoint<ZM<Q>, N> {
+
+	/// The number of points of P^(N - 1) over ZM<Q>, which is (Q^N - 1) / (Q - 1)
+	pub fn count() -> usize {
+		(0..N).map(|i| (Q as usize).pow(i as u32)).sum()
+	}
+
+	/// Every point of P^(N - 1) over ZM<Q>, each one normalized.
+	///
+	/// This lists all of them, so it is only reasonable when Q^N is small.
+	pub fn points() -> Vec<ProjectivePoint<ZM<Q>, N>> {
+		let mut points = Vec::with_capacity(Self::count());
+
+		// The points whose last nonzero coordinate is at position k are 1 at k,
+		// zero after k, and anything before k
+		for k in 0..N {
+			for mut digits in 0..(Q as usize).pow(k as u32) {
+				let coordinates = std::array::from_fn(|i| {
+					if i < k {
+						let digit = digits % Q as usize;
+						digits /= Q as usize;
+						ZM::from_int(digit as i64)
+					} else if i == k {
+						ZM::one()
+					} else {
+						ZM::zero()
+					}
+				});
+				points.push(ProjectivePoint { coordinates });
+			}
+		}
+
+		points
+	}
+
+}
@@ -0,0 +1,248 @@
+//
+// Positive definite binary quadratic forms ax^2 + bxy + cy^2 of a negative discriminant, and the
+// class group they form under Gauss composition
+//
+
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+
+use crate::algebra::*;
+
+// MARK: Reduction
+
+/// The form equivalent to (a, b, c) with -a < b <= a, by the substitution x -> x + ry
+fn normalize(a: i64, b: i64, c: i64) -> (i64, i64, i64) {
+	let r = (a - b).div_euclid(2 * a);
+	let (a128, b128, r128) = (a as i128, b as i128, r as i128);
+	let c = a128 * r128 * r128 + b128 * r128 + c as i128;
+	(a, b + 2 * r * a, c as i64)
+}
+
+/// The unique reduced form equivalent to the positive definite form (a, b, c), with
+/// |b| <= a <= c, and b >= 0 if either inequality is an equality
+fn reduce(a: i64, b: i64, c: i64) -> (i64, i64, i64) {
+	let (mut a, mut b, mut c) = normalize(a, b, c);
+
+	// Swapping (a, b, c) -> (c, -b, a) is the substitution (x, y) -> (-y, x), and each swap
+	// strictly decreases a
+	while a > c {
+		(a, b, c) = normalize(c, -b, a);
+	}
+
+	if a == c && b < 0 {
+		b = -b;
+	}
+	(a, b, c)
+}
+
+/// Returns (g, x, y) with g = gcd(a, b) >= 0 and ax + by = g
+fn positive_ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+	let (g, x, y) = ext_gcd(&a, &b);
+	if g < 0 { (-g, -x, -y) } else { (g, x, y) }
+}
+
+/// Every primitive reduced form (a, b, c) of discriminant d, so |b| <= a <= sqrt(|d| / 3), in
+/// increasing order of a then b
+fn reduced_forms(d: i64) -> Vec<(i64, i64, i64)> {
+	let mut forms = Vec::new();
+	let mut a = 1;
+
+	while 3 * a * a <= -d {
+		for b in -a + 1..=a {
+			if (b - d).rem_euclid(2) != 0 || (b * b - d) % (4 * a) != 0 {
+				continue
+			}
+
+			let c = (b * b - d) / (4 * a);
+			if c < a || (c == a && b < 0) || gcd(&gcd(&a, &b), &c) != 1 {
+				continue
+			}
+			forms.push((a, b, c));
+		}
+		a += 1;
+	}
+
+	forms
+}
+
+/// Panics unless d is a discriminant of positive definite forms: negative, and 0 or 1 modulo 4
+fn check_discriminant(d: i64) {
+	if d >= 0 || d.rem_euclid(4) > 1 {
+		panic!("{} is not a negative discriminant, which is 0 or 1 modulo 4", d)
+	}
+}
+
+/// The class number h(d), the number of classes of primitive positive definite forms of the
+/// negative discriminant d, counted as the number of reduced forms
+pub fn class_number(d: i64) -> usize {
+	check_discriminant(d);
+	reduced_forms(d).len()
+}
+
+// MARK: Binary Quadratic Forms
+
+/// The class of a primitive positive definite binary quadratic form ax^2 + bxy + cy^2 of
+/// discriminant D = b^2 - 4ac, stored as the unique reduced form in it. D must be negative, and
+/// 0 or 1 modulo 4.
+///
+/// Two forms are equivalent when a change of variables in SL2(Z) takes one to the other, and
+/// the classes form a finite abelian group under Gauss composition, the class group, whose
+/// order is the class number h(D). For a fundamental discriminant D it is isomorphic to the
+/// ideal class group of the imaginary quadratic field of discriminant D.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BinaryQuadraticForm<const D: i64> {
+	a: i64,
+	b: i64,
+	c: i64
+}
+
+impl<const D: i64> BinaryQuadraticForm<D> {
+
+	/// The discriminant D, checked to be a valid negative discriminant
+	const DISCRIMINANT: i64 = {
+		assert!(D < 0 && (D % 4 == 0 || D % 4 == -3), "The discriminant must be negative, and 0 or 1 modulo 4");
+		D
+	};
+
+	/// The class of the form ax^2 + bxy + cy^2, or None unless it has discriminant D, is positive
+	/// definite, and is primitive, with gcd(a, b, c) = 1
+	pub fn new(a: i64, b: i64, c: i64) -> Option<BinaryQuadraticForm<D>> {
+		let discriminant = b as i128 * b as i128 - 4 * a as i128 * c as i128;
+		if discriminant != Self::DISCRIMINANT as i128 || a <= 0 || gcd(&gcd(&a, &b), &c) != 1 {
+			return None
+		}
+
+		let (a, b, c) = reduce(a, b, c);
+		Some(BinaryQuadraticForm { a, b, c })
+	}
+
+	/// The class of the form with leading coefficient a and middle coefficient b, or None unless
+	/// b^2 - D is divisible by 4a and the form is primitive and positive definite
+	pub fn from_ab(a: i64, b: i64) -> Option<BinaryQuadraticForm<D>> {
+		let numerator = b as i128 * b as i128 - Self::DISCRIMINANT as i128;
+		if a <= 0 || numerator % (4 * a as i128) != 0 {
+			return None
+		}
+		Self::new(a, b, i64::try_from(numerator / (4 * a as i128)).ok()?)
+	}
+
+	/// The discriminant D
+	pub fn discriminant() -> i64 {
+		Self::DISCRIMINANT
+	}
+
+	/// The class number h(D), the order of the class group
+	pub fn class_number() -> usize {
+		class_number(Self::DISCRIMINANT)
+	}
+
+	/// The coefficients (a, b, c) of the reduced form in this class
+	pub fn coefficients(&self) -> (i64, i64, i64) {
+		(self.a, self.b, self.c)
+	}
+
+	/// The value ax^2 + bxy + cy^2 of the reduced form in this class
+	pub fn evaluate(&self, x: i64, y: i64) -> i128 {
+		let (x, y) = (x as i128, y as i128);
+		self.a as i128 * x * x + self.b as i128 * x * y + self.c as i128 * y * y
+	}
+
+	/// Whether or not this is an ambiguous class, its own inverse, which is when the reduced form
+	/// has b = 0, a = b, or a = c
+	pub fn is_ambiguous(&self) -> bool {
+		self.b == 0 || self.a == self.b || self.a == self.c
+	}
+
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const D: i64> Mul for BinaryQuadraticForm<D> {
+	type Output = Self;
+
+	/// Gauss composition, by Dirichlet's method of finding united forms (Cohen, Algorithm 5.4.7)
+	fn mul(self, rhs: Self) -> Self::Output {
+		let (f1, f2) = if self.a > rhs.a { (rhs, self) } else { (self, rhs) };
+		let (a1, b1) = (f1.a as i128, f1.b as i128);
+		let (a2, b2, c2) = (f2.a as i128, f2.b as i128, f2.c as i128);
+
+		let s = (b1 + b2) / 2;
+		let n = b2 - s;
+
+		let (d, y1) = if a2 % a1 == 0 {
+			(a1, 0)
+		} else {
+			let (d, u, _) = positive_ext_gcd(f2.a, f1.a);
+			(d as i128, u as i128)
+		};
+
+		let (d1, x2, y2) = if s % d == 0 {
+			(d, 0, -1)
+		} else {
+			let (d1, u, v) = positive_ext_gcd(s as i64, d as i64);
+			(d1 as i128, u as i128, -v as i128)
+		};
+
+		let v1 = a1 / d1;
+		let v2 = a2 / d1;
+		let r = (y1 * y2 * n - x2 * c2).rem_euclid(v1);
+		let b3 = b2 + 2 * v2 * r;
+		let a3 = v1 * v2;
+		let c3 = (c2 * d1 + r * (b2 + v2 * r)) / v1;
+
+		let (a, b, c) = reduce(a3 as i64, b3 as i64, c3 as i64);
+		BinaryQuadraticForm { a, b, c }
+	}
+}
+
+impl<const D: i64> MulAssign for BinaryQuadraticForm<D> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const D: i64> Div for BinaryQuadraticForm<D> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const D: i64> DivAssign for BinaryQuadraticForm<D> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<const D: i64> Monoid for BinaryQuadraticForm<D> {
+
+	/// The principal form x^2 + bxy + cy^2, with b = 0 or 1 matching D modulo 4
+	fn identity() -> Self {
+		let b = Self::DISCRIMINANT.rem_euclid(2);
+		BinaryQuadraticForm { a: 1, b, c: (b - Self::DISCRIMINANT) / 4 }
+	}
+}
+
+impl<const D: i64> Group for BinaryQuadraticForm<D> {
+
+	/// The opposite form (a, -b, c)
+	fn inverse(&self) -> Self {
+		let (a, b, c) = reduce(self.a, -self.b, self.c);
+		BinaryQuadraticForm { a, b, c }
+	}
+}
+
+impl<const D: i64> AbelianGroup for BinaryQuadraticForm<D> { }
+
+impl<const D: i64> FiniteGroup for BinaryQuadraticForm<D> {
+
+	/// The class number h(D)
+	fn order() -> usize {
+		Self::class_number()
+	}
+
+	/// The reduced forms of discriminant D, starting with the principal form
+	fn elements() -> impl Iterator<Item = Self> {
+		reduced_forms(Self::DISCRIMINANT).into_iter().map(|(a, b, c)| BinaryQuadraticForm { a, b, c })
+	}
+}
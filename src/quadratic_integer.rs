@@ -0,0 +1,435 @@
+//
+// The Gaussian integers Z[i] and the Eisenstein integers Z[w], the classic Euclidean domains
+// beyond Z and F[x]
+//
+
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use crate::algebra::*;
+
+/// n / d rounded to the nearest integer, with halves rounded up, for d > 0
+fn div_round(n: i128, d: i128) -> i128 {
+	(2 * n + d).div_euclid(2 * d)
+}
+
+// MARK: Gaussian Integers
+
+/// The Gaussian integer re + im i, where i^2 = -1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaussianInt {
+	pub re: i64,
+	pub im: i64
+}
+
+impl GaussianInt {
+
+	/// The Gaussian integer re + im i
+	pub fn new(re: i64, im: i64) -> GaussianInt {
+		GaussianInt { re, im }
+	}
+
+	/// The unit i
+	pub fn i() -> GaussianInt {
+		GaussianInt::new(0, 1)
+	}
+
+	/// The four units 1, i, -1, -i
+	pub fn units() -> [GaussianInt; 4] {
+		[GaussianInt::new(1, 0), GaussianInt::new(0, 1), GaussianInt::new(-1, 0), GaussianInt::new(0, -1)]
+	}
+
+	/// The complex conjugate re - im i
+	pub fn conj(&self) -> GaussianInt {
+		GaussianInt::new(self.re, -self.im)
+	}
+
+	/// The norm re^2 + im^2, which is multiplicative
+	pub fn norm(&self) -> i128 {
+		let (re, im) = (self.re as i128, self.im as i128);
+		re * re + im * im
+	}
+
+	/// Whether or not this is one of the four units
+	pub fn is_unit(&self) -> bool {
+		self.norm() == 1
+	}
+
+}
+
+impl From<i64> for GaussianInt {
+	fn from(value: i64) -> Self {
+		GaussianInt::new(value, 0)
+	}
+}
+
+impl Add for GaussianInt {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		GaussianInt::new(self.re + rhs.re, self.im + rhs.im)
+	}
+}
+
+impl AddAssign for GaussianInt {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl Neg for GaussianInt {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		GaussianInt::new(-self.re, -self.im)
+	}
+}
+
+impl Sub for GaussianInt {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		GaussianInt::new(self.re - rhs.re, self.im - rhs.im)
+	}
+}
+
+impl SubAssign for GaussianInt {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl Mul for GaussianInt {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		GaussianInt::new(self.re * rhs.re - self.im * rhs.im, self.re * rhs.im + self.im * rhs.re)
+	}
+}
+
+impl MulAssign for GaussianInt {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+impl Div for GaussianInt {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl DivAssign for GaussianInt {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl Rem for GaussianInt {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl RemAssign for GaussianInt {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = *self % rhs
+	}
+}
+
+impl Semiring for GaussianInt {
+	fn one() -> Self {
+		GaussianInt::new(1, 0)
+	}
+
+	fn zero() -> Self {
+		GaussianInt::new(0, 0)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.re == 0 && self.im == 0
+	}
+}
+
+impl Ring for GaussianInt {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			if !self.is_unit() {
+				panic!("{:?} is not a unit, so it has no negative powers", self)
+			}
+			return self.conj().power(-n)
+		}
+
+		let mut power = GaussianInt::one();
+		let mut base = *self;
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base
+			}
+			base *= base;
+			e >>= 1;
+		}
+
+		power
+	}
+}
+
+impl CommutativeRing for GaussianInt { }
+impl IntegralDomain for GaussianInt {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		euclidean_divide_exact(self, divisor)
+	}
+}
+
+impl PrincipalIdealDomain for GaussianInt { }
+
+impl UniqueFactorizationDomain for GaussianInt {
+	fn gcd(&self, other: &Self) -> Self {
+		euclidean_gcd(self, other)
+	}
+}
+
+impl EuclideanDomain for GaussianInt {
+	type SizeType = i128;
+
+	fn euc_size(&self) -> i128 {
+		self.norm()
+	}
+
+	/// Rounds each coordinate of self / divisor to the nearest integer, so the remainder has
+	/// norm at most half that of the divisor
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+
+		let n = divisor.norm();
+		let (a, b) = (self.re as i128, self.im as i128);
+		let (c, d) = (divisor.re as i128, divisor.im as i128);
+
+		// self / divisor = self * conj(divisor) / N(divisor)
+		let q = GaussianInt::new(div_round(a * c + b * d, n) as i64, div_round(b * c - a * d, n) as i64);
+		(q, *self - *divisor * q)
+	}
+}
+
+// MARK: Eisenstein Integers
+
+/// The Eisenstein integer a + b w, where w = (-1 + sqrt(-3)) / 2 is a primitive cube root of
+/// unity, so w^2 = -1 - w
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EisensteinInt {
+	pub a: i64,
+	pub b: i64
+}
+
+impl EisensteinInt {
+
+	/// The Eisenstein integer a + b w
+	pub fn new(a: i64, b: i64) -> EisensteinInt {
+		EisensteinInt { a, b }
+	}
+
+	/// The cube root of unity w
+	pub fn omega() -> EisensteinInt {
+		EisensteinInt::new(0, 1)
+	}
+
+	/// The six units, the sixth roots of unity 1, -w^2, w, -1, w^2, -w
+	pub fn units() -> [EisensteinInt; 6] {
+		[
+			EisensteinInt::new(1, 0), EisensteinInt::new(1, 1), EisensteinInt::new(0, 1),
+			EisensteinInt::new(-1, 0), EisensteinInt::new(-1, -1), EisensteinInt::new(0, -1)
+		]
+	}
+
+	/// The complex conjugate a + b w^2 = (a - b) - b w
+	pub fn conj(&self) -> EisensteinInt {
+		EisensteinInt::new(self.a - self.b, -self.b)
+	}
+
+	/// The norm a^2 - ab + b^2, which is multiplicative
+	pub fn norm(&self) -> i128 {
+		let (a, b) = (self.a as i128, self.b as i128);
+		a * a - a * b + b * b
+	}
+
+	/// Whether or not this is one of the six units
+	pub fn is_unit(&self) -> bool {
+		self.norm() == 1
+	}
+
+}
+
+impl From<i64> for EisensteinInt {
+	fn from(value: i64) -> Self {
+		EisensteinInt::new(value, 0)
+	}
+}
+
+impl Add for EisensteinInt {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		EisensteinInt::new(self.a + rhs.a, self.b + rhs.b)
+	}
+}
+
+impl AddAssign for EisensteinInt {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+impl Neg for EisensteinInt {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		EisensteinInt::new(-self.a, -self.b)
+	}
+}
+
+impl Sub for EisensteinInt {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		EisensteinInt::new(self.a - rhs.a, self.b - rhs.b)
+	}
+}
+
+impl SubAssign for EisensteinInt {
+	fn sub_assign(&mut self, rhs: Self) {
+		*self = *self - rhs
+	}
+}
+
+impl Mul for EisensteinInt {
+	type Output = Self;
+
+	/// (a + b w)(c + d w) = ac + (ad + bc) w + bd w^2 = (ac - bd) + (ad + bc - bd) w
+	fn mul(self, rhs: Self) -> Self::Output {
+		let bd = self.b * rhs.b;
+		EisensteinInt::new(self.a * rhs.a - bd, self.a * rhs.b + self.b * rhs.a - bd)
+	}
+}
+
+impl MulAssign for EisensteinInt {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+impl Div for EisensteinInt {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl DivAssign for EisensteinInt {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl Rem for EisensteinInt {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl RemAssign for EisensteinInt {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = *self % rhs
+	}
+}
+
+impl Semiring for EisensteinInt {
+	fn one() -> Self {
+		EisensteinInt::new(1, 0)
+	}
+
+	fn zero() -> Self {
+		EisensteinInt::new(0, 0)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.a == 0 && self.b == 0
+	}
+}
+
+impl Ring for EisensteinInt {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			if !self.is_unit() {
+				panic!("{:?} is not a unit, so it has no negative powers", self)
+			}
+			return self.conj().power(-n)
+		}
+
+		let mut power = EisensteinInt::one();
+		let mut base = *self;
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base
+			}
+			base *= base;
+			e >>= 1;
+		}
+
+		power
+	}
+}
+
+impl CommutativeRing for EisensteinInt { }
+impl IntegralDomain for EisensteinInt {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		euclidean_divide_exact(self, divisor)
+	}
+}
+
+impl PrincipalIdealDomain for EisensteinInt { }
+
+impl UniqueFactorizationDomain for EisensteinInt {
+	fn gcd(&self, other: &Self) -> Self {
+		euclidean_gcd(self, other)
+	}
+}
+
+impl EuclideanDomain for EisensteinInt {
+	type SizeType = i128;
+
+	fn euc_size(&self) -> i128 {
+		self.norm()
+	}
+
+	/// Rounds each coordinate of self / divisor in the basis 1, w to the nearest integer, so the
+	/// remainder has norm at most three quarters that of the divisor
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() {
+			panic!("Cannot divide by zero")
+		}
+
+		let n = divisor.norm();
+		let conj = divisor.conj();
+		let (a, b) = (self.a as i128, self.b as i128);
+		let (c, d) = (conj.a as i128, conj.b as i128);
+
+		// self / divisor = self * conj(divisor) / N(divisor)
+		let bd = b * d;
+		let q = EisensteinInt::new(div_round(a * c - bd, n) as i64, div_round(a * d + b * c - bd, n) as i64);
+		(q, *self - *divisor * q)
+	}
+}
@@ -0,0 +1,269 @@
+//
+// Quaternions a + bi + cj + dk over any field, where i^2 = j^2 = k^2 = ijk = -1
+//
+
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::random::RandomElement;
+
+// MARK: Quaternions
+
+/// The quaternion a + bi + cj + dk in the Hamilton quaternion algebra over F.
+///
+/// Multiplication is not commutative, so this is only a `Ring`. Over the reals, and any other
+/// field where a sum of four squares is zero only when all of them are, every nonzero quaternion
+/// is invertible and the quaternions form a division ring.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quaternion<F: Field> {
+	pub a: F,
+	pub b: F,
+	pub c: F,
+	pub d: F
+}
+
+impl<F: Field> Quaternion<F> {
+
+	/// The quaternion a + bi + cj + dk
+	pub fn new(a: F, b: F, c: F, d: F) -> Quaternion<F> {
+		Quaternion { a, b, c, d }
+	}
+
+	/// The image of x under the inclusion of F as the scalars
+	pub fn from_scalar(x: F) -> Quaternion<F> {
+		Quaternion::new(x, F::zero(), F::zero(), F::zero())
+	}
+
+	/// The pure quaternion xi + yj + zk
+	pub fn from_vector(v: [F; 3]) -> Quaternion<F> {
+		let [x, y, z] = v;
+		Quaternion::new(F::zero(), x, y, z)
+	}
+
+	/// The unit i
+	pub fn i() -> Quaternion<F> {
+		Quaternion::new(F::zero(), F::one(), F::zero(), F::zero())
+	}
+
+	/// The unit j
+	pub fn j() -> Quaternion<F> {
+		Quaternion::new(F::zero(), F::zero(), F::one(), F::zero())
+	}
+
+	/// The unit k
+	pub fn k() -> Quaternion<F> {
+		Quaternion::new(F::zero(), F::zero(), F::zero(), F::one())
+	}
+
+	/// The scalar part a
+	pub fn scalar(&self) -> &F {
+		&self.a
+	}
+
+	/// The vector part (b, c, d)
+	pub fn vector(&self) -> [F; 3] {
+		[self.b.clone(), self.c.clone(), self.d.clone()]
+	}
+
+	/// The conjugate a - bi - cj - dk
+	pub fn conj(&self) -> Quaternion<F> {
+		Quaternion::new(self.a.clone(), -self.b.clone(), -self.c.clone(), -self.d.clone())
+	}
+
+	/// The reduced norm q * conj(q) = a^2 + b^2 + c^2 + d^2
+	pub fn norm(&self) -> F {
+		[&self.a, &self.b, &self.c, &self.d].iter().fold(F::zero(), |sum, x| sum + x.mul_ref(x))
+	}
+
+	/// The reduced trace q + conj(q) = 2a
+	pub fn trace(&self) -> F {
+		self.a.add_ref(&self.a)
+	}
+
+	/// Multiplies every component by c
+	pub fn scale(&self, c: &F) -> Quaternion<F> {
+		Quaternion::new(self.a.mul_ref(c), self.b.mul_ref(c), self.c.mul_ref(c), self.d.mul_ref(c))
+	}
+
+	/// The two-sided inverse conj(q) / N(q), or None if the norm is not invertible
+	pub fn try_inverse(&self) -> Option<Quaternion<F>> {
+		self.norm().try_inverse().map(|n| self.conj().scale(&n))
+	}
+
+	/// The two-sided inverse conj(q) / N(q), which panics if the norm is not invertible
+	pub fn inverse(&self) -> Quaternion<F> {
+		match self.try_inverse() {
+			Some(inverse) => inverse,
+			None => panic!("{:?} has norm zero, so it is not invertible", self)
+		}
+	}
+
+}
+
+impl Quaternion<f64> {
+
+	/// The unit quaternion rotating by angle theta about the given axis, which need not be normalized
+	pub fn from_axis_angle(axis: [f64; 3], theta: f64) -> Quaternion<f64> {
+		let length = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+		let s = (theta / 2.0).sin() / length;
+		Quaternion::new((theta / 2.0).cos(), axis[0] * s, axis[1] * s, axis[2] * s)
+	}
+
+	/// Rotates v by this quaternion, as q v q^(-1)
+	pub fn rotate(&self, v: [f64; 3]) -> [f64; 3] {
+		(*self * Quaternion::from_vector(v) * self.inverse()).vector()
+	}
+
+	/// This quaternion scaled to norm one
+	pub fn normalized(&self) -> Quaternion<f64> {
+		self.scale(&(1.0 / self.norm().sqrt()))
+	}
+
+}
+
+impl<F: Field> From<F> for Quaternion<F> {
+	fn from(value: F) -> Self {
+		Quaternion::from_scalar(value)
+	}
+}
+
+impl<F: Field + RandomElement> RandomElement for Quaternion<F> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		Quaternion::new(F::random(rng), F::random(rng), F::random(rng), F::random(rng))
+	}
+}
+
+impl<F: Field + RandomElement> Distribution<Quaternion<F>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quaternion<F> {
+		Quaternion::random(rng)
+	}
+}
+
+// MARK: Arithmetic
+
+impl<F: Field> Add for Quaternion<F> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Quaternion::new(self.a + rhs.a, self.b + rhs.b, self.c + rhs.c, self.d + rhs.d)
+	}
+}
+
+impl<F: Field> AddAssign for Quaternion<F> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.a += rhs.a;
+		self.b += rhs.b;
+		self.c += rhs.c;
+		self.d += rhs.d;
+	}
+}
+
+impl<F: Field> Neg for Quaternion<F> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Quaternion::new(-self.a, -self.b, -self.c, -self.d)
+	}
+}
+
+impl<F: Field> Sub for Quaternion<F> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		Quaternion::new(self.a - rhs.a, self.b - rhs.b, self.c - rhs.c, self.d - rhs.d)
+	}
+}
+
+impl<F: Field> SubAssign for Quaternion<F> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.a -= rhs.a;
+		self.b -= rhs.b;
+		self.c -= rhs.c;
+		self.d -= rhs.d;
+	}
+}
+
+/// The Hamilton product
+impl<F: Field> Mul for Quaternion<F> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<F: Field> MulAssign for Quaternion<F> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl<F: Field> Semiring for Quaternion<F> {
+	fn one() -> Self {
+		Quaternion::from_scalar(F::one())
+	}
+
+	fn zero() -> Self {
+		Quaternion::from_scalar(F::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.a.is_zero() && self.b.is_zero() && self.c.is_zero() && self.d.is_zero()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		self.a.add_assign_ref(&rhs.a);
+		self.b.add_assign_ref(&rhs.b);
+		self.c.add_assign_ref(&rhs.c);
+		self.d.add_assign_ref(&rhs.d);
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		let (a1, b1, c1, d1) = (&self.a, &self.b, &self.c, &self.d);
+		let (a2, b2, c2, d2) = (&rhs.a, &rhs.b, &rhs.c, &rhs.d);
+
+		Quaternion::new(
+			a1.mul_ref(a2) - b1.mul_ref(b2) - c1.mul_ref(c2) - d1.mul_ref(d2),
+			a1.mul_ref(b2) + b1.mul_ref(a2) + c1.mul_ref(d2) - d1.mul_ref(c2),
+			a1.mul_ref(c2) - b1.mul_ref(d2) + c1.mul_ref(a2) + d1.mul_ref(b2),
+			a1.mul_ref(d2) + b1.mul_ref(c2) - c1.mul_ref(b2) + d1.mul_ref(a2)
+		)
+	}
+}
+
+impl<F: Field> Ring for Quaternion<F> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
+
+		let mut power = Quaternion::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base)
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		self.a.sub_assign_ref(&rhs.a);
+		self.b.sub_assign_ref(&rhs.b);
+		self.c.sub_assign_ref(&rhs.c);
+		self.d.sub_assign_ref(&rhs.d);
+	}
+}
@@ -0,0 +1,18 @@
+//
+// Uniform sampling of elements, with a caller supplied source of randomness
+//
+
+use rand::Rng;
+
+/// A type whose elements can be drawn uniformly at random.
+///
+/// The randomness comes from the caller, so a seeded rng makes results reproducible, and a
+/// cryptographically secure one like `rand::rngs::OsRng` makes them suitable for cryptography.
+/// Implementors also implement `Distribution<Self>` for `rand::distributions::Standard`, so
+/// `rng.gen()` works too.
+pub trait RandomElement: Sized {
+
+	/// A uniformly random element, using the given source of randomness
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+}
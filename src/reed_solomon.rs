@@ -0,0 +1,204 @@
+//
+// Reed-Solomon codes, which correct errors in unknown positions rather than only erasures, by
+// Berlekamp-Massey on the syndromes, a Chien search for the error positions, and Forney's formula
+// for the error values
+//
+
+use crate::algebra::*;
+use crate::binary_field::GF256;
+use crate::polynomial::{berlekamp_massey, Polynomial};
+
+// MARK: Reed-Solomon Codes
+
+/// An (n, k) Reed-Solomon code over a field F: a message of k symbols is the coefficients of a
+/// polynomial m of degree less than k, and its codeword is m(x_0), ..., m(x_(n-1)) for n fixed,
+/// distinct, nonzero evaluation points. Two codewords differ in at least n - k + 1 symbols, so up
+/// to (n - k) / 2 symbols in unknown positions can be corrected.
+///
+/// With the points 1, a, a^2, ..., a^(n-1) for an element a of order n this is the classical
+/// Reed-Solomon code, and other points give the generalized code, which decodes the same way.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReedSolomon<F: Field> {
+	message_length: usize,
+	points: Vec<F>,
+
+	/// The column multipliers v_i = 1 / (product of x_i - x_j over j != i), for which the sum of
+	/// v_i c_i x_i^j vanishes for every codeword c and every j < n - k
+	weights: Vec<F>
+}
+
+impl<F: Field> ReedSolomon<F> {
+
+	/// A code with messages of length k evaluated at the given points, or None unless
+	/// 0 < k <= n and the points are distinct and nonzero
+	pub fn new(message_length: usize, points: Vec<F>) -> Option<ReedSolomon<F>> {
+		if message_length == 0 || message_length > points.len() || points.iter().any(F::is_zero) {
+			return None
+		}
+
+		// A repeated point makes its product zero, with no inverse
+		let mut weights = Vec::with_capacity(points.len());
+		for (i, a) in points.iter().enumerate() {
+			let mut product = F::one();
+			for (_, b) in points.iter().enumerate().filter(|&(j, _)| j != i) {
+				product *= a.sub_ref(b);
+			}
+			weights.push(product.try_inverse()?);
+		}
+
+		Some(ReedSolomon { message_length, points, weights })
+	}
+
+	/// The classical code of length n with messages of length k, evaluated at 1, a, ..., a^(n-1),
+	/// or None unless 0 < k <= n and a has multiplicative order at least n
+	pub fn with_generator(message_length: usize, generator: &F, length: usize) -> Option<ReedSolomon<F>> {
+		let mut points = Vec::with_capacity(length);
+		let mut power = F::one();
+		for _ in 0..length {
+			points.push(power.clone());
+			power.mul_assign_ref(generator);
+		}
+
+		ReedSolomon::new(message_length, points)
+	}
+
+	/// The number k of symbols in a message
+	pub fn message_length(&self) -> usize {
+		self.message_length
+	}
+
+	/// The number n of symbols in a codeword
+	pub fn length(&self) -> usize {
+		self.points.len()
+	}
+
+	/// The evaluation points x_0, ..., x_(n-1)
+	pub fn points(&self) -> &[F] {
+		&self.points
+	}
+
+	/// The minimum distance n - k + 1 between two codewords
+	pub fn minimum_distance(&self) -> usize {
+		self.length() - self.message_length + 1
+	}
+
+	/// The number (n - k) / 2 of errors in unknown positions that decoding always corrects
+	pub fn correctable_errors(&self) -> usize {
+		(self.length() - self.message_length) / 2
+	}
+
+	/// The codeword of a message of length k, the values of the polynomial with the message as
+	/// its coefficients at each point
+	pub fn encode(&self, message: &[F]) -> Vec<F> {
+		if message.len() != self.message_length {
+			panic!("Expected a message of length {}, got {}", self.message_length, message.len())
+		}
+
+		Polynomial::new(message.to_vec()).evaluate_many(&self.points)
+	}
+
+	/// The n - k syndromes S_j, the sum of v_i r_i x_i^j, which are all zero exactly when the
+	/// received word is a codeword
+	pub fn syndromes(&self, received: &[F]) -> Vec<F> {
+		if received.len() != self.length() {
+			panic!("Expected a word of length {}, got {}", self.length(), received.len())
+		}
+
+		let mut terms: Vec<F> = received.iter().zip(self.weights.iter()).map(|(r, v)| r.mul_ref(v)).collect();
+		(0..self.length() - self.message_length).map(|_| {
+			let sum = terms.iter().fold(F::zero(), |sum, t| sum + t.clone());
+			for (t, x) in terms.iter_mut().zip(self.points.iter()) {
+				t.mul_assign_ref(x);
+			}
+			sum
+		}).collect()
+	}
+
+	/// The codeword nearest the received word, or None if the errors could not be corrected. Up
+	/// to (n - k) / 2 errors are always corrected, and more are usually detected, but may instead
+	/// lead to a different codeword.
+	///
+	/// An error e_l at point X_l contributes Y_l X_l^j to each syndrome, with Y_l = v_l e_l, so the
+	/// syndromes follow a linear recurrence whose connection polynomial, found by Berlekamp-Massey,
+	/// is the error locator, the product of 1 - X_l z. Its roots, found by trying the inverse of
+	/// every point, locate the errors, and with the error evaluator Ω = S Λ mod z^(n - k), Forney's
+	/// formula gives Y_l = -X_l Ω(1 / X_l) / Λ'(1 / X_l).
+	pub fn correct(&self, received: &[F]) -> Option<Vec<F>> {
+		let syndromes = self.syndromes(received);
+		if syndromes.iter().all(F::is_zero) {
+			return Some(received.to_vec())
+		}
+
+		let locator = berlekamp_massey(&syndromes);
+		let errors = locator.degree();
+		if errors > self.correctable_errors() {
+			return None
+		}
+
+		// Chien search: the errors are at the points whose inverses are roots of the locator
+		let inverses: Vec<F> = self.points.iter().map(F::inverse).collect();
+		let positions: Vec<usize> = locator.evaluate_many(&inverses).iter().enumerate()
+			.filter(|(_, value)| value.is_zero())
+			.map(|(i, _)| i)
+			.collect();
+		if positions.len() != errors {
+			return None
+		}
+
+		let mut evaluator = Polynomial::new(syndromes.clone()) * locator.clone();
+		evaluator = Polynomial::new(evaluator.coefficients().iter().take(syndromes.len()).cloned().collect());
+		let derivative = locator.derivative();
+
+		let mut corrected = received.to_vec();
+		for i in positions {
+			let z = &inverses[i];
+			let y = -(self.points[i].mul_ref(&evaluator.evaluate(z)) / derivative.evaluate(z));
+			corrected[i].sub_assign_ref(&(y / self.weights[i].clone()));
+		}
+
+		self.syndromes(&corrected).iter().all(F::is_zero).then_some(corrected)
+	}
+
+	/// The message whose codeword is nearest the received word, or None if the errors could not
+	/// be corrected
+	pub fn decode(&self, received: &[F]) -> Option<Vec<F>> {
+		let corrected = self.correct(received)?;
+		let k = self.message_length;
+
+		// The message polynomial is determined by its values at any k of the points
+		let points: Vec<(F, F)> = self.points.iter().cloned().zip(corrected).take(k).collect();
+		let mut message = Polynomial::interpolate(&points)?.coefficients().to_vec();
+		message.resize(k, F::zero());
+		Some(message)
+	}
+
+}
+
+// MARK: Bytes
+
+impl ReedSolomon<GF256> {
+
+	/// A code over GF(2^8) with messages of k bytes and codewords of n bytes, evaluated at the
+	/// powers of the generator x + 1, or None unless 0 < k <= n <= 255
+	pub fn for_bytes(message_length: usize, length: usize) -> Option<Self> {
+		if length > 255 {
+			return None
+		}
+
+		ReedSolomon::with_generator(message_length, &GF256::generator(), length)
+	}
+
+	/// The codeword of a message of k bytes
+	pub fn encode_bytes(&self, message: &[u8]) -> Vec<u8> {
+		let message: Vec<GF256> = message.iter().map(|&b| GF256(b)).collect();
+		self.encode(&message).iter().map(|b| b.0).collect()
+	}
+
+	/// The message of k bytes whose codeword is nearest the received bytes, or None if the errors
+	/// could not be corrected
+	pub fn decode_bytes(&self, received: &[u8]) -> Option<Vec<u8>> {
+		let received: Vec<GF256> = received.iter().map(|&b| GF256(b)).collect();
+		Some(self.decode(&received)?.iter().map(|b| b.0).collect())
+	}
+
+}
@@ -0,0 +1,161 @@
+//
+// The distributions of lattice cryptography, uniform on small integers, centered binomial, and
+// discrete Gaussian, sampling integers, elements of ZM<Q>, and elements of cyclotomic rings
+//
+
+use rand::distributions::Distribution;
+use rand::Rng;
+
+use crate::cyclotomic_ring::CyclotomicRing;
+use crate::std_impls::ZM;
+use crate::vector::Vector;
+
+// MARK: Distributions
+
+/// The uniform distribution on the integers in [-bound, bound].
+///
+/// For the uniform distribution on all of ZM<Q>, use `rand::distributions::Standard`, or
+/// `RandomElement`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UniformBounded {
+	bound: i64
+}
+
+impl UniformBounded {
+
+	/// The uniform distribution on [-bound, bound], for bound nonnegative
+	pub fn new(bound: i64) -> UniformBounded {
+		if bound < 0 {
+			panic!("The bound of a uniform distribution must be nonnegative, got {}", bound)
+		}
+		UniformBounded { bound }
+	}
+
+}
+
+impl Distribution<i64> for UniformBounded {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> i64 {
+		rng.gen_range(-self.bound..=self.bound)
+	}
+}
+
+/// The centered binomial distribution with parameter eta, the difference of two sums of eta
+/// random bits, which lies in [-eta, eta] with variance eta / 2. This is how Kyber samples
+/// secrets and errors, since it needs only random bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CenteredBinomial {
+	eta: u32
+}
+
+impl CenteredBinomial {
+
+	/// The centered binomial distribution with parameter eta, at most 32
+	pub fn new(eta: u32) -> CenteredBinomial {
+		if eta > 32 {
+			panic!("Centered binomial parameters above 32 are not supported, got {}", eta)
+		}
+		CenteredBinomial { eta }
+	}
+
+}
+
+impl Distribution<i64> for CenteredBinomial {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> i64 {
+		let bits: u64 = rng.gen();
+		let mask = ((1u64 << self.eta) - 1) as u32;
+		(bits as u32 & mask).count_ones() as i64 - ((bits >> 32) as u32 & mask).count_ones() as i64
+	}
+}
+
+/// The discrete Gaussian distribution on the integers with parameter sigma, where x has
+/// probability proportional to exp(-x^2 / (2 sigma^2)), cut off at tail * sigma.
+///
+/// Samples are drawn by rejection from the uniform distribution on the cut off range, so this is
+/// neither constant time nor suitable for very small sigma and large tails together, but it is
+/// exact up to floating point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiscreteGaussian {
+	sigma: f64,
+	bound: i64
+}
+
+impl DiscreteGaussian {
+
+	/// How many standard deviations out the default distribution is cut off, past which the
+	/// probability is below 2^-100
+	pub const DEFAULT_TAIL: f64 = 12.0;
+
+	/// The discrete Gaussian with parameter sigma, cut off at `DEFAULT_TAIL` * sigma
+	pub fn new(sigma: f64) -> DiscreteGaussian {
+		DiscreteGaussian::with_tail(sigma, DiscreteGaussian::DEFAULT_TAIL)
+	}
+
+	/// The discrete Gaussian with parameter sigma, cut off at tail * sigma
+	pub fn with_tail(sigma: f64, tail: f64) -> DiscreteGaussian {
+		if !(sigma.is_finite() && sigma > 0.0) {
+			panic!("The parameter of a discrete Gaussian must be positive and finite, got {}", sigma)
+		}
+		if !(tail.is_finite() && tail > 0.0) {
+			panic!("The tail cut of a discrete Gaussian must be positive and finite, got {}", tail)
+		}
+		DiscreteGaussian { sigma, bound: (tail * sigma).ceil() as i64 }
+	}
+
+	/// The parameter sigma
+	pub fn sigma(&self) -> f64 {
+		self.sigma
+	}
+
+	/// The largest absolute value a sample can have
+	pub fn bound(&self) -> i64 {
+		self.bound
+	}
+
+}
+
+impl Distribution<i64> for DiscreteGaussian {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> i64 {
+		let denominator = 2.0 * self.sigma * self.sigma;
+		loop {
+			let x = rng.gen_range(-self.bound..=self.bound);
+			if rng.gen::<f64>() < (-((x * x) as f64) / denominator).exp() {
+				return x
+			}
+		}
+	}
+}
+
+/// Samples elements of ZM<Q> as the reductions of integer samples, and elements of cyclotomic
+/// rings coefficient by coefficient
+macro_rules! reduced_distribution {
+	($($distribution:ty),*) => {
+		$(
+			impl<const Q: i64> Distribution<ZM<Q>> for $distribution {
+				fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> ZM<Q> {
+					ZM::from_int(Distribution::<i64>::sample(self, rng))
+				}
+			}
+
+			impl<const Q: i64, const N: usize> Distribution<CyclotomicRing<Q, N>> for $distribution {
+				fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> CyclotomicRing<Q, N> {
+					CyclotomicRing::new(std::array::from_fn(|_| self.sample(rng)))
+				}
+			}
+		)*
+	};
+}
+
+reduced_distribution!(UniformBounded, CenteredBinomial, DiscreteGaussian);
+
+// MARK: Batches
+
+/// K independent samples from the distribution, like the secret of module LWE as an array of
+/// cyclotomic ring elements
+pub fn batch<T, D: Distribution<T>, G: Rng + ?Sized, const K: usize>(distribution: &D, rng: &mut G) -> [T; K] {
+	std::array::from_fn(|_| distribution.sample(rng))
+}
+
+/// A vector of N independent samples from the distribution, like the error of plain LWE
+pub fn vector<const Q: i64, const N: usize, D: Distribution<ZM<Q>>, G: Rng + ?Sized>(distribution: &D, rng: &mut G) -> Vector<ZM<Q>, N> {
+	Vector::new(batch(distribution, rng))
+}
@@ -0,0 +1,144 @@
+//
+// Shamir secret sharing: a secret split into n shares, any t of which recover it by interpolation,
+// while t - 1 of them reveal nothing about it
+//
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::binary_field::GF256;
+use crate::erasure::Share;
+use crate::polynomial::Polynomial;
+use crate::random::RandomElement;
+
+// MARK: Shares
+
+/// One share of a secret, the value at a nonzero point of the sharing polynomial
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SecretShare<F: Field> {
+	pub point: F,
+	pub value: F
+}
+
+// MARK: Sharing
+
+/// Splits a secret into shares at the given distinct nonzero points, any `threshold` of which
+/// recover it, using the given source of randomness.
+///
+/// The shares are the values of a random polynomial of degree less than the threshold whose
+/// constant term is the secret. Any threshold of them determine it, but the values at fewer
+/// points than that are uniformly random whatever the secret is, so the randomness must be
+/// cryptographically secure to keep the secret.
+pub fn share_at_with_rng<F: Field + RandomElement, G: Rng + ?Sized>(secret: F, threshold: usize, points: &[F], rng: &mut G) -> Vec<SecretShare<F>> {
+	if threshold == 0 || threshold > points.len() {
+		panic!("The threshold must be between 1 and the number of shares {}, got {}", points.len(), threshold)
+	}
+
+	for (i, a) in points.iter().enumerate() {
+		if a.is_zero() || points[..i].contains(a) {
+			panic!("Shares must be at distinct nonzero points")
+		}
+	}
+
+	let mut coefficients = vec![secret];
+	coefficients.extend((1..threshold).map(|_| F::random(rng)));
+	let f = Polynomial::new(coefficients);
+
+	points.iter().cloned().zip(f.evaluate_many(points))
+		.map(|(point, value)| SecretShare { point, value })
+		.collect()
+}
+
+/// Splits a secret into shares at the points 1, 2, ..., n, any `threshold` of which recover it,
+/// using the given source of randomness. Those points are only distinct and nonzero when n is less
+/// than the characteristic of the field, so in a small field like GF(2^8) use `share_at_with_rng`.
+pub fn share_with_rng<F: Field + RandomElement, G: Rng + ?Sized>(secret: F, threshold: usize, shares: usize, rng: &mut G) -> Vec<SecretShare<F>> {
+	let mut points = Vec::with_capacity(shares);
+	let mut point = F::zero();
+	for _ in 0..shares {
+		point.add_assign_ref(&F::one());
+		if point.is_zero() {
+			panic!("The field has too small a characteristic for {} shares at 1, 2, ..., n", shares)
+		}
+		points.push(point.clone());
+	}
+
+	share_at_with_rng(secret, threshold, &points, rng)
+}
+
+/// Splits a secret into shares at the points 1, 2, ..., n, any `threshold` of which recover it
+pub fn share<F: Field + RandomElement>(secret: F, threshold: usize, shares: usize) -> Vec<SecretShare<F>> {
+	share_with_rng(secret, threshold, shares, &mut rand::thread_rng())
+}
+
+/// The weights w_i with f(0) equal to the sum of w_i f(x_i) for every polynomial f of degree
+/// less than the number of points, the Lagrange basis polynomials evaluated at zero, or None if
+/// two points coincide
+fn weights_at_zero<F: Field>(points: &[F]) -> Option<Vec<F>> {
+	points.iter().enumerate().map(|(i, a)| {
+		let mut numerator = F::one();
+		let mut denominator = F::one();
+		for (_, b) in points.iter().enumerate().filter(|&(j, _)| j != i) {
+			numerator.mul_assign_ref(b);
+			denominator *= b.sub_ref(a);
+		}
+		numerator.checked_div(&denominator)
+	}).collect()
+}
+
+/// The secret shared among at least threshold shares, the value at zero of the polynomial through
+/// them, or None if there are no shares or two are at the same point. With fewer than the
+/// threshold the result is meaningless.
+pub fn reconstruct<F: Field>(shares: &[SecretShare<F>]) -> Option<F> {
+	if shares.is_empty() {
+		return None
+	}
+
+	let points: Vec<F> = shares.iter().map(|s| s.point.clone()).collect();
+	let weights = weights_at_zero(&points)?;
+	Some(shares.iter().zip(weights).fold(F::zero(), |sum, (s, w)| sum + s.value.mul_ref(&w)))
+}
+
+// MARK: Bytes
+
+/// Splits a byte string into n shares, any `threshold` of which recover it, sharing each byte
+/// separately over GF(2^8) at the points 1, 2, ..., n. Each share's index is its point, so needs
+/// 0 < threshold <= n <= 255.
+pub fn share_bytes_with_rng<G: Rng + ?Sized>(secret: &[u8], threshold: usize, shares: usize, rng: &mut G) -> Vec<Share<u8>> {
+	if shares > 255 {
+		panic!("Bytes can be shared among at most 255 shares, got {}", shares)
+	}
+
+	let points: Vec<GF256> = (1..=shares).map(|i| GF256(i as u8)).collect();
+	let mut result: Vec<Share<u8>> = (1..=shares).map(|index| Share { index, symbols: Vec::with_capacity(secret.len()) }).collect();
+
+	for &byte in secret {
+		for (share, s) in result.iter_mut().zip(share_at_with_rng(GF256(byte), threshold, &points, rng)) {
+			share.symbols.push(s.value.0);
+		}
+	}
+
+	result
+}
+
+/// Splits a byte string into n shares, any `threshold` of which recover it
+pub fn share_bytes(secret: &[u8], threshold: usize, shares: usize) -> Vec<Share<u8>> {
+	share_bytes_with_rng(secret, threshold, shares, &mut rand::thread_rng())
+}
+
+/// The byte string shared among at least threshold byte shares, or None if two shares have the
+/// same index, an index is not in [1, 255], or the shares have different lengths
+pub fn reconstruct_bytes(shares: &[Share<u8>]) -> Option<Vec<u8>> {
+	let length = shares.first()?.symbols.len();
+	if shares.iter().any(|s| s.symbols.len() != length || s.index == 0 || s.index > 255) {
+		return None
+	}
+
+	// Every byte was shared at the same points, so the weights only need finding once
+	let points: Vec<GF256> = shares.iter().map(|s| GF256(s.index as u8)).collect();
+	let weights = weights_at_zero(&points)?;
+
+	Some((0..length).map(|b| {
+		shares.iter().zip(weights.iter()).fold(GF256(0), |sum, (s, w)| sum + GF256(s.symbols[b]) * *w).0
+	}).collect())
+}
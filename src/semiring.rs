@@ -0,0 +1,128 @@
+//
+// Semirings which are not rings: the tropical and boolean semirings
+//
+
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+
+use crate::algebra::*;
+
+// MARK: Tropical Semirings
+
+/// The max-plus tropical semiring, where addition is max and multiplication is +.
+///
+/// Zero is negative infinity and one is 0. Matrix products over this semiring
+/// find the heaviest paths in a weighted graph.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MaxPlus(pub f64);
+
+/// The min-plus tropical semiring, where addition is min and multiplication is +.
+///
+/// Zero is positive infinity and one is 0. Matrix products over this semiring
+/// find the shortest paths in a weighted graph.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinPlus(pub f64);
+
+macro_rules! impl_tropical {
+	($t:ident, $add:ident, $zero:expr) => {
+		impl Add for $t {
+			type Output = Self;
+
+			fn add(self, rhs: Self) -> Self::Output {
+				$t(self.0.$add(rhs.0))
+			}
+		}
+
+		impl AddAssign for $t {
+			fn add_assign(&mut self, rhs: Self) {
+				*self = *self + rhs
+			}
+		}
+
+		#[allow(clippy::suspicious_arithmetic_impl)]
+		impl Mul for $t {
+			type Output = Self;
+
+			fn mul(self, rhs: Self) -> Self::Output {
+				$t(self.0 + rhs.0)
+			}
+		}
+
+		impl MulAssign for $t {
+			fn mul_assign(&mut self, rhs: Self) {
+				*self = *self * rhs
+			}
+		}
+
+		impl Semiring for $t {
+			fn one() -> Self {
+				$t(0.0)
+			}
+
+			fn zero() -> Self {
+				$t($zero)
+			}
+
+			fn is_zero(&self) -> bool {
+				self.0 == $zero
+			}
+		}
+	};
+}
+
+impl_tropical!(MaxPlus, max, f64::NEG_INFINITY);
+impl_tropical!(MinPlus, min, f64::INFINITY);
+
+// MARK: Boolean Semiring
+
+/// The boolean semiring, where addition is "or" and multiplication is "and".
+///
+/// Matrix products over this semiring compute reachability in a graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Boolean(pub bool);
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Add for Boolean {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		Boolean(self.0 || rhs.0)
+	}
+}
+
+impl AddAssign for Boolean {
+	fn add_assign(&mut self, rhs: Self) {
+		*self = *self + rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl Mul for Boolean {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Boolean(self.0 && rhs.0)
+	}
+}
+
+impl MulAssign for Boolean {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+impl Semiring for Boolean {
+	fn one() -> Self {
+		Boolean(true)
+	}
+
+	fn zero() -> Self {
+		Boolean(false)
+	}
+
+	fn is_zero(&self) -> bool {
+		!self.0
+	}
+}
@@ -0,0 +1,226 @@
+//
+// Serialization of elements with serde, for the `serde` feature
+//
+// Every element is written as its canonical representative, and checked when it is read back,
+// so a deserialized value always satisfies the same invariants as a constructed one. Elements
+// which carry a shared runtime context, like finite algebras and cosets, are not serializable.
+//
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::algebra::*;
+use crate::big_zm::{BigModulus, BigZM};
+use crate::elliptic_curve::{ECPoint, EllipticCurve, JacobianPoint};
+use crate::matrix::Matrix;
+use crate::matrix_group::{GL, SL};
+use crate::permutation::Perm;
+use crate::polynomial::Polynomial;
+use crate::product::{AutomorphismAction, DirectPower, SemidirectProduct};
+use crate::projective::ProjectivePoint;
+use crate::std_impls::{Additive, Multiplicative, ZM};
+
+/// Reads a sequence of exactly N values
+fn deserialize_array<'de, T: Deserialize<'de>, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[T; N], D::Error> {
+	let values = Vec::<T>::deserialize(deserializer)?;
+	let length = values.len();
+	values.try_into().map_err(|_| D::Error::custom(format!("expected {} values, got {}", N, length)))
+}
+
+// MARK: Integers Modulo N
+
+impl<R: Ring + Serialize> Serialize for Additive<R> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.0.serialize(serializer)
+	}
+}
+
+impl<'de, R: Ring + Deserialize<'de>> Deserialize<'de> for Additive<R> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(Additive(R::deserialize(deserializer)?))
+	}
+}
+
+impl<F: Field + Serialize> Serialize for Multiplicative<F> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.value().serialize(serializer)
+	}
+}
+
+impl<'de, F: Field + Deserialize<'de>> Deserialize<'de> for Multiplicative<F> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let x = F::deserialize(deserializer)?;
+		Multiplicative::new(x.clone()).ok_or_else(|| D::Error::custom(format!("{:?} is not invertible", x)))
+	}
+}
+
+impl<const Q: i64> Serialize for ZM<Q> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.val.rem_euclid(Q).serialize(serializer)
+	}
+}
+
+impl<'de, const Q: i64> Deserialize<'de> for ZM<Q> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(ZM::from_int(i64::deserialize(deserializer)?))
+	}
+}
+
+impl<M: BigModulus<LIMBS>, const LIMBS: usize> Serialize for BigZM<M, LIMBS> {
+	/// The limbs of the value, least significant first, not its Montgomery form
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.to_limbs().as_slice().serialize(serializer)
+	}
+}
+
+impl<'de, M: BigModulus<LIMBS>, const LIMBS: usize> Deserialize<'de> for BigZM<M, LIMBS> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let limbs = deserialize_array(deserializer)?;
+		BigZM::from_limbs(limbs).ok_or_else(|| D::Error::custom("value is not less than the modulus"))
+	}
+}
+
+// MARK: Permutations
+
+impl<const N: usize> Serialize for Perm<N> {
+	/// The one-line notation of this permutation
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.images.as_slice().serialize(serializer)
+	}
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Perm<N> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let images = deserialize_array(deserializer)?;
+		Perm::from_one_line(images).ok_or_else(|| D::Error::custom(format!("not a permutation of 0..{}", N)))
+	}
+}
+
+// MARK: Elliptic Curves
+
+impl<const Q: i64, const A: i64, const B: i64> Serialize for ECPoint<Q, A, B> {
+	/// The affine coordinates (x, y), or nothing for the point at infinity
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.coordinates().serialize(serializer)
+	}
+}
+
+impl<'de, const Q: i64, const A: i64, const B: i64> Deserialize<'de> for ECPoint<Q, A, B> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		match Option::<(ZM<Q>, ZM<Q>)>::deserialize(deserializer)? {
+			None => Ok(ECPoint::Infinity),
+			Some((x, y)) => EllipticCurve::<Q, A, B>::point(x, y).ok_or_else(|| D::Error::custom("point is not on the curve"))
+		}
+	}
+}
+
+impl<const Q: i64, const A: i64, const B: i64> Serialize for JacobianPoint<Q, A, B> {
+	/// The same as the affine point, since Jacobian coordinates are not unique
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.to_affine().serialize(serializer)
+	}
+}
+
+impl<'de, const Q: i64, const A: i64, const B: i64> Deserialize<'de> for JacobianPoint<Q, A, B> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(ECPoint::deserialize(deserializer)?.into())
+	}
+}
+
+impl<F: Field + Serialize, const N: usize> Serialize for ProjectivePoint<F, N> {
+	/// The coordinates scaled so that the last nonzero one is 1
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.normalized().coordinates().as_slice().serialize(serializer)
+	}
+}
+
+impl<'de, F: Field + Deserialize<'de>, const N: usize> Deserialize<'de> for ProjectivePoint<F, N> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let coordinates = deserialize_array(deserializer)?;
+		ProjectivePoint::new(coordinates).ok_or_else(|| D::Error::custom("coordinates are all zero"))
+	}
+}
+
+// MARK: Polynomials and Matrices
+
+impl<R: Ring + Serialize> Serialize for Polynomial<R> {
+	/// The coefficients, from the constant term up
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.coefficients().serialize(serializer)
+	}
+}
+
+impl<'de, R: Ring + Deserialize<'de>> Deserialize<'de> for Polynomial<R> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(Polynomial::new(Vec::deserialize(deserializer)?))
+	}
+}
+
+impl<R: Semiring + Serialize> Serialize for Matrix<R> {
+	/// The number of rows, the number of columns, and the entries row by row
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		(self.rows(), self.cols(), self.entries()).serialize(serializer)
+	}
+}
+
+impl<'de, R: Semiring + Deserialize<'de>> Deserialize<'de> for Matrix<R> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let (rows, cols, entries) = <(usize, usize, Vec<R>)>::deserialize(deserializer)?;
+		if entries.len() != rows * cols {
+			return Err(D::Error::custom(format!("a {}x{} matrix needs {} entries, got {}", rows, cols, rows * cols, entries.len())))
+		}
+		Ok(Matrix::new(rows, cols, entries))
+	}
+}
+
+impl<const N: usize, const Q: i64> Serialize for GL<N, Q> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.matrix().serialize(serializer)
+	}
+}
+
+impl<'de, const N: usize, const Q: i64> Deserialize<'de> for GL<N, Q> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		GL::new(Matrix::deserialize(deserializer)?).ok_or_else(|| D::Error::custom(format!("not an element of GL({}, {})", N, Q)))
+	}
+}
+
+impl<const N: usize, const Q: i64> Serialize for SL<N, Q> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.matrix().serialize(serializer)
+	}
+}
+
+impl<'de, const N: usize, const Q: i64> Deserialize<'de> for SL<N, Q> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		SL::new(Matrix::deserialize(deserializer)?).ok_or_else(|| D::Error::custom(format!("not an element of SL({}, {})", N, Q)))
+	}
+}
+
+// MARK: Products
+
+impl<G: Group + Serialize, const N: usize> Serialize for DirectPower<G, N> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.coordinates.as_slice().serialize(serializer)
+	}
+}
+
+impl<'de, G: Group + Deserialize<'de>, const N: usize> Deserialize<'de> for DirectPower<G, N> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		Ok(DirectPower::new(deserialize_array(deserializer)?))
+	}
+}
+
+impl<N: Group + Serialize, H: Group + Serialize, A: AutomorphismAction<N, H>> Serialize for SemidirectProduct<N, H, A> {
+	/// The pair (n, h)
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		(&self.normal, &self.acting).serialize(serializer)
+	}
+}
+
+impl<'de, N: Group + Deserialize<'de>, H: Group + Deserialize<'de>, A: AutomorphismAction<N, H>> Deserialize<'de> for SemidirectProduct<N, H, A> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let (normal, acting) = <(N, H)>::deserialize(deserializer)?;
+		Ok(SemidirectProduct::new(normal, acting))
+	}
+}
@@ -0,0 +1,179 @@
+//
+// Sieves for bulk number theory: primes in a range by a segmented sieve of Eratosthenes, and
+// tables of smallest prime factors, totients, and Möbius values by a linear sieve
+//
+
+/// The length of each block of the segmented sieve, small enough for the block to stay in cache
+const SEGMENT_SIZE: i64 = 1 << 15;
+
+// MARK: Primes
+
+/// The largest r with r^2 <= n, for n >= 0
+fn isqrt(n: i64) -> i64 {
+	let mut r = (n as f64).sqrt() as i64;
+	while r * r > n {
+		r -= 1;
+	}
+	while (r + 1) * (r + 1) <= n {
+		r += 1;
+	}
+	r
+}
+
+/// Every prime p with low <= p <= high, in increasing order, by a segmented sieve of Eratosthenes.
+///
+/// Only the primes up to the square root of high and one block of the range are held at once, so
+/// a window far out can be sieved without a table reaching all the way up to it.
+pub fn primes_between(low: i64, high: i64) -> Vec<i64> {
+	let low = low.max(2);
+	if high < low {
+		return Vec::new()
+	}
+
+	let base: Vec<i64> = smallest_prime_factors(isqrt(high) as usize).into_iter().enumerate()
+		.filter(|&(m, p)| m >= 2 && p == m)
+		.map(|(m, _)| m as i64)
+		.collect();
+
+	let mut primes = Vec::new();
+	let mut start = low;
+	while start <= high {
+		let end = high.min(start + SEGMENT_SIZE - 1);
+		let mut composite = vec![false; (end - start + 1) as usize];
+
+		for &p in base.iter() {
+			if p * p > end {
+				break
+			}
+			let first = (p * p).max((start + p - 1) / p * p);
+			for m in (first..=end).step_by(p as usize) {
+				composite[(m - start) as usize] = true;
+			}
+		}
+
+		primes.extend(composite.iter().enumerate().filter(|(_, c)| !**c).map(|(i, _)| start + i as i64));
+		start = end + 1;
+	}
+
+	primes
+}
+
+/// Every prime up to n, in increasing order
+pub fn primes_up_to(n: i64) -> Vec<i64> {
+	primes_between(2, n)
+}
+
+// MARK: Tables
+
+/// The smallest prime factor of each of 0, 1, ..., n, with 0 in place of one for 0 and 1, by a
+/// linear sieve, which marks each composite exactly once from its smallest prime factor
+pub fn smallest_prime_factors(n: usize) -> Vec<usize> {
+	let mut smallest = vec![0; n + 1];
+	let mut primes = Vec::new();
+
+	for m in 2..=n {
+		if smallest[m] == 0 {
+			smallest[m] = m;
+			primes.push(m);
+		}
+		for &p in primes.iter() {
+			if p > smallest[m] || m * p > n {
+				break
+			}
+			smallest[m * p] = p;
+		}
+	}
+
+	smallest
+}
+
+/// Euler's totient of each of 0, 1, ..., n, with 0 as a placeholder for 0
+pub fn totients_up_to(n: usize) -> Vec<i64> {
+	let smallest = smallest_prime_factors(n);
+	let mut phi = vec![0; n + 1];
+	if n >= 1 {
+		phi[1] = 1;
+	}
+
+	for m in 2..=n {
+		let p = smallest[m];
+		let r = m / p;
+		phi[m] = if r.is_multiple_of(p) { phi[r] * p as i64 } else { phi[r] * (p as i64 - 1) };
+	}
+
+	phi
+}
+
+/// The Möbius function of each of 0, 1, ..., n, with 0 as a placeholder for 0
+pub fn mobius_up_to(n: usize) -> Vec<i64> {
+	let smallest = smallest_prime_factors(n);
+	let mut mu = vec![0; n + 1];
+	if n >= 1 {
+		mu[1] = 1;
+	}
+
+	for m in 2..=n {
+		let p = smallest[m];
+		let r = m / p;
+		mu[m] = if r.is_multiple_of(p) { 0 } else { -mu[r] };
+	}
+
+	mu
+}
+
+// MARK: Factor Tables
+
+/// A table of smallest prime factors up to a bound, which factors any number in range with one
+/// division per prime factor instead of by trial division
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FactorTable {
+	smallest: Vec<usize>
+}
+
+impl FactorTable {
+
+	/// The table for the numbers up to n
+	pub fn new(n: usize) -> FactorTable {
+		FactorTable { smallest: smallest_prime_factors(n) }
+	}
+
+	/// The largest number this table covers
+	pub fn bound(&self) -> usize {
+		self.smallest.len() - 1
+	}
+
+	/// The smallest prime factor of m, which must be between 2 and the bound
+	pub fn smallest_prime_factor(&self, m: usize) -> usize {
+		if m < 2 || m > self.bound() {
+			panic!("{} is not between 2 and the bound {} of the factor table", m, self.bound())
+		}
+		self.smallest[m]
+	}
+
+	/// Whether or not m is prime, for m up to the bound
+	pub fn is_prime(&self, m: usize) -> bool {
+		m >= 2 && self.smallest_prime_factor(m) == m
+	}
+
+	/// The prime factorization of m, between 1 and the bound, as (prime, exponent) pairs in
+	/// increasing order, the same as `std_impls::factor`
+	pub fn factor(&self, m: usize) -> Vec<(i64, u32)> {
+		if m == 0 || m > self.bound() {
+			panic!("{} is not between 1 and the bound {} of the factor table", m, self.bound())
+		}
+
+		let mut factors: Vec<(i64, u32)> = Vec::new();
+		let mut m = m;
+		while m > 1 {
+			let p = self.smallest[m];
+			m /= p;
+			match factors.last_mut() {
+				Some((q, e)) if *q == p as i64 => *e += 1,
+				_ => factors.push((p as i64, 1))
+			}
+		}
+
+		factors
+	}
+
+}
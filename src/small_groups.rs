@@ -0,0 +1,329 @@
+//
+// The cyclic, dihedral, and symmetric groups, with their standard presentations by generators
+// and relations
+//
+
+use std::fmt::Display;
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::permutation::Perm;
+use crate::random::RandomElement;
+use crate::subgroup::generate_subgroup;
+
+// MARK: Presentations
+
+/// A word in the generators of a group, as (generator index, exponent) pairs read left to right,
+/// so [(0, 2), (1, -1)] is g_0^2 g_1^-1
+pub type Word = Vec<(usize, i64)>;
+
+/// A finite group with named generators and defining relations, each a word equal to the identity
+pub trait Presentation: FiniteGroup {
+
+	/// The generators, each with its name
+	fn generators() -> Vec<(String, Self)>;
+
+	/// The defining relations, in the order of `generators`
+	fn relations() -> Vec<Word>;
+
+	/// The generator with the given name, if there is one
+	fn generator(name: &str) -> Option<Self> {
+		Self::generators().into_iter().find(|(n, _)| n == name).map(|(_, g)| g)
+	}
+
+	/// The element the word evaluates to
+	fn evaluate(word: &[(usize, i64)]) -> Self {
+		let generators = Self::generators();
+		word.iter().fold(Self::identity(), |product, &(i, e)| product * generators[i].1.power(e))
+	}
+
+	/// Whether or not every relation evaluates to the identity
+	fn check_relations() -> bool {
+		Self::relations().iter().all(|r| Self::evaluate(r) == Self::identity())
+	}
+
+	/// Whether or not the generators generate the whole group. Together with `check_relations`
+	/// this shows the group is a quotient of the presented one, and they are the same exactly
+	/// when the presented group has no more elements.
+	fn check_generators() -> bool {
+		let generators: Vec<Self> = Self::generators().into_iter().map(|(_, g)| g).collect();
+		generate_subgroup(&generators).order() == Self::order()
+	}
+
+}
+
+// MARK: Cyclic Groups
+
+/// The element r^power of the cyclic group of order N, written multiplicatively with generator r
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CyclicGroup<const N: usize> {
+	pub power: usize
+}
+
+impl<const N: usize> CyclicGroup<N> {
+
+	/// The element r^k
+	pub fn new(k: i64) -> CyclicGroup<N> {
+		CyclicGroup { power: k.rem_euclid(N as i64) as usize }
+	}
+
+	/// The generator r
+	pub fn r() -> CyclicGroup<N> {
+		CyclicGroup::new(1)
+	}
+
+	/// The rotation of the points 0, 1, ..., N - 1 this element is, sending i to i + power
+	pub fn to_perm(&self) -> Perm<N> {
+		Perm { images: std::array::from_fn(|i| (i + self.power) % N) }
+	}
+
+}
+
+impl<const N: usize> Display for CyclicGroup<N> {
+	/// Writes this element like e, r, or r^3
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.power {
+			0 => write!(f, "e"),
+			1 => write!(f, "r"),
+			k => write!(f, "r^{}", k)
+		}
+	}
+}
+
+impl<const N: usize> Mul for CyclicGroup<N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		CyclicGroup { power: (self.power + rhs.power) % N }
+	}
+}
+
+impl<const N: usize> MulAssign for CyclicGroup<N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const N: usize> Div for CyclicGroup<N> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const N: usize> DivAssign for CyclicGroup<N> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<const N: usize> Monoid for CyclicGroup<N> {
+	fn identity() -> Self {
+		CyclicGroup { power: 0 }
+	}
+}
+
+impl<const N: usize> Group for CyclicGroup<N> {
+	fn inverse(&self) -> Self {
+		CyclicGroup { power: (N - self.power) % N }
+	}
+}
+
+impl<const N: usize> AbelianGroup for CyclicGroup<N> { }
+
+impl<const N: usize> FiniteGroup for CyclicGroup<N> {
+	fn order() -> usize {
+		N
+	}
+
+	/// Every power of r, in increasing order
+	fn elements() -> impl Iterator<Item = Self> {
+		(0..N).map(|power| CyclicGroup { power })
+	}
+}
+
+/// The presentation <r | r^N>
+impl<const N: usize> Presentation for CyclicGroup<N> {
+	fn generators() -> Vec<(String, Self)> {
+		vec![("r".to_string(), CyclicGroup::r())]
+	}
+
+	fn relations() -> Vec<Word> {
+		vec![vec![(0, N as i64)]]
+	}
+}
+
+impl<const N: usize> RandomElement for CyclicGroup<N> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		CyclicGroup { power: rng.gen_range(0..N) }
+	}
+}
+
+impl<const N: usize> Distribution<CyclicGroup<N>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> CyclicGroup<N> {
+		CyclicGroup::random(rng)
+	}
+}
+
+// MARK: Dihedral Groups
+
+/// The element r^rotation s^reflected of the dihedral group of order 2N, the symmetries of a
+/// regular N-gon, where r is a rotation by one vertex and s a reflection, so s r s = r^-1
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DihedralGroup<const N: usize> {
+	pub rotation: usize,
+	pub reflected: bool
+}
+
+impl<const N: usize> DihedralGroup<N> {
+
+	/// The rotation r^k
+	pub fn rotation(k: i64) -> DihedralGroup<N> {
+		DihedralGroup { rotation: k.rem_euclid(N as i64) as usize, reflected: false }
+	}
+
+	/// The reflection r^k s
+	pub fn reflection(k: i64) -> DihedralGroup<N> {
+		DihedralGroup { rotation: k.rem_euclid(N as i64) as usize, reflected: true }
+	}
+
+	/// The generator r, a rotation by one vertex
+	pub fn r() -> DihedralGroup<N> {
+		DihedralGroup::rotation(1)
+	}
+
+	/// The generator s, the reflection fixing vertex 0
+	pub fn s() -> DihedralGroup<N> {
+		DihedralGroup::reflection(0)
+	}
+
+	/// The permutation of the vertices 0, 1, ..., N - 1 of the N-gon this element is, sending i to
+	/// rotation + i, or to rotation - i when reflected
+	pub fn to_perm(&self) -> Perm<N> {
+		Perm { images: std::array::from_fn(|i| if self.reflected { (self.rotation + N - i) % N } else { (self.rotation + i) % N }) }
+	}
+
+}
+
+impl<const N: usize> Display for DihedralGroup<N> {
+	/// Writes this element like e, r^2, s, or r^2 s
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (self.rotation, self.reflected) {
+			(0, false) => write!(f, "e"),
+			(0, true) => write!(f, "s"),
+			(k, reflected) => {
+				write!(f, "{}", CyclicGroup::<N> { power: k })?;
+				if reflected { write!(f, " s") } else { Ok(()) }
+			}
+		}
+	}
+}
+
+/// (r^a s^x)(r^b s^y) = r^(a + (-1)^x b) s^(x + y), since s r^b = r^-b s
+impl<const N: usize> Mul for DihedralGroup<N> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		let b = if self.reflected { (N - rhs.rotation) % N } else { rhs.rotation };
+		DihedralGroup { rotation: (self.rotation + b) % N, reflected: self.reflected ^ rhs.reflected }
+	}
+}
+
+impl<const N: usize> MulAssign for DihedralGroup<N> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = *self * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<const N: usize> Div for DihedralGroup<N> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<const N: usize> DivAssign for DihedralGroup<N> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = *self / rhs
+	}
+}
+
+impl<const N: usize> Monoid for DihedralGroup<N> {
+	fn identity() -> Self {
+		DihedralGroup { rotation: 0, reflected: false }
+	}
+}
+
+impl<const N: usize> Group for DihedralGroup<N> {
+	/// Reflections are their own inverses
+	fn inverse(&self) -> Self {
+		if self.reflected { *self } else { DihedralGroup::rotation(-(self.rotation as i64)) }
+	}
+}
+
+impl<const N: usize> FiniteGroup for DihedralGroup<N> {
+	fn order() -> usize {
+		2 * N
+	}
+
+	/// The rotations, then the reflections, each in increasing order of rotation
+	fn elements() -> impl Iterator<Item = Self> {
+		[false, true].into_iter().flat_map(|reflected| (0..N).map(move |rotation| DihedralGroup { rotation, reflected }))
+	}
+}
+
+/// The presentation <r, s | r^N, s^2, (s r)^2>
+impl<const N: usize> Presentation for DihedralGroup<N> {
+	fn generators() -> Vec<(String, Self)> {
+		vec![("r".to_string(), DihedralGroup::r()), ("s".to_string(), DihedralGroup::s())]
+	}
+
+	fn relations() -> Vec<Word> {
+		vec![vec![(0, N as i64)], vec![(1, 2)], vec![(1, 1), (0, 1), (1, 1), (0, 1)]]
+	}
+}
+
+impl<const N: usize> RandomElement for DihedralGroup<N> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		DihedralGroup { rotation: rng.gen_range(0..N), reflected: rng.gen() }
+	}
+}
+
+impl<const N: usize> Distribution<DihedralGroup<N>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> DihedralGroup<N> {
+		DihedralGroup::random(rng)
+	}
+}
+
+// MARK: Symmetric Groups
+
+/// The symmetric group on N points, whose elements are permutations
+pub type SymmetricGroup<const N: usize> = Perm<N>;
+
+/// The Coxeter presentation by the adjacent transpositions s1, ..., s(N - 1), where si swaps i - 1
+/// and i, with relations si^2, (si s(i + 1))^3, and (si sj)^2 for |i - j| > 1
+impl<const N: usize> Presentation for Perm<N> {
+	fn generators() -> Vec<(String, Self)> {
+		(1..N).map(|i| (format!("s{}", i), Perm::cycle(&[i - 1, i]).unwrap())).collect()
+	}
+
+	fn relations() -> Vec<Word> {
+		let n = N.saturating_sub(1);
+		let mut relations = Vec::new();
+		for i in 0..n {
+			relations.push(vec![(i, 2)]);
+			for j in i + 1..n {
+				let exponent = if j == i + 1 { 3 } else { 2 };
+				relations.push([(i, 1), (j, 1)].repeat(exponent));
+			}
+		}
+		relations
+	}
+}
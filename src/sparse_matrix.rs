@@ -0,0 +1,235 @@
+//
+// Sparse matrices in compressed sparse row form, and Wiedemann's black box solver for large
+// sparse systems over finite fields
+//
+
+use rand::Rng;
+
+use crate::algebra::*;
+use crate::linear_operator::LinearOperator;
+use crate::matrix::Matrix;
+use crate::polynomial::linear_recurrence;
+use crate::random::RandomElement;
+
+// MARK: Sparse Matrix
+
+/// A rows x cols matrix over R storing only its nonzero entries, row by row: the entries of row i
+/// are `values[row_starts[i]..row_starts[i + 1]]`, in columns given by the same range of
+/// `columns`, in increasing order
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMatrix<R: Ring> {
+	rows: usize,
+	cols: usize,
+	row_starts: Vec<usize>,
+	columns: Vec<usize>,
+	values: Vec<R>
+}
+
+impl<R: Ring> SparseMatrix<R> {
+
+	/// The matrix with the given (row, column, value) entries, adding up repeated positions and
+	/// leaving out zeros
+	pub fn new(rows: usize, cols: usize, entries: Vec<(usize, usize, R)>) -> SparseMatrix<R> {
+		let mut entries = entries;
+		entries.sort_by_key(|&(i, j, _)| (i, j));
+
+		let mut row_starts = vec![0; rows + 1];
+		let mut columns: Vec<usize> = Vec::with_capacity(entries.len());
+		let mut values: Vec<R> = Vec::with_capacity(entries.len());
+		let mut last: Option<(usize, usize)> = None;
+
+		for (i, j, x) in entries {
+			if i >= rows || j >= cols {
+				panic!("Entry ({}, {}) is outside a {}x{} matrix", i, j, rows, cols)
+			}
+
+			if last == Some((i, j)) {
+				values.last_mut().unwrap().add_assign_ref(&x);
+				continue
+			}
+
+			// The previous position is complete, so drop it if it summed to zero
+			if values.last().is_some_and(|v| v.is_zero()) {
+				values.pop();
+				columns.pop();
+				row_starts[last.unwrap().0 + 1] -= 1;
+			}
+
+			columns.push(j);
+			values.push(x);
+			row_starts[i + 1] += 1;
+			last = Some((i, j));
+		}
+
+		if values.last().is_some_and(|v| v.is_zero()) {
+			values.pop();
+			columns.pop();
+			row_starts[last.unwrap().0 + 1] -= 1;
+		}
+
+		for i in 0..rows {
+			row_starts[i + 1] += row_starts[i];
+		}
+
+		SparseMatrix { rows, cols, row_starts, columns, values }
+	}
+
+	/// The sparse form of a dense matrix
+	pub fn from_dense(m: &Matrix<R>) -> SparseMatrix<R> {
+		let entries = (0..m.rows()).flat_map(|i| (0..m.cols()).map(move |j| (i, j)))
+			.filter(|&(i, j)| !m[(i, j)].is_zero())
+			.map(|(i, j)| (i, j, m[(i, j)].clone()))
+			.collect();
+		SparseMatrix::new(m.rows(), m.cols(), entries)
+	}
+
+	/// The dense form of this matrix
+	pub fn to_dense(&self) -> Matrix<R> {
+		let mut m = Matrix::zero(self.rows, self.cols);
+		for i in 0..self.rows {
+			for (j, x) in self.row(i) {
+				m[(i, j)] = x.clone();
+			}
+		}
+		m
+	}
+
+	/// The n x n identity matrix
+	pub fn identity(n: usize) -> SparseMatrix<R> {
+		SparseMatrix::new(n, n, (0..n).map(|i| (i, i, R::one())).collect())
+	}
+
+	/// The number of rows
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	/// The number of columns
+	pub fn cols(&self) -> usize {
+		self.cols
+	}
+
+	/// The number of nonzero entries
+	pub fn nonzero_count(&self) -> usize {
+		self.values.len()
+	}
+
+	/// The nonzero entries of row i, as (column, value) pairs in increasing order of column
+	pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, &R)> {
+		let range = self.row_starts[i]..self.row_starts[i + 1];
+		self.columns[range.clone()].iter().copied().zip(self.values[range].iter())
+	}
+
+	/// The entry in row i and column j
+	pub fn get(&self, i: usize, j: usize) -> R {
+		let range = self.row_starts[i]..self.row_starts[i + 1];
+		match self.columns[range.clone()].binary_search(&j) {
+			Ok(k) => self.values[range.start + k].clone(),
+			Err(_) => R::zero()
+		}
+	}
+
+	/// The transpose of this matrix
+	pub fn transpose(&self) -> SparseMatrix<R> {
+		let entries = (0..self.rows).flat_map(|i| self.row(i).map(move |(j, x)| (j, i, x.clone()))).collect();
+		SparseMatrix::new(self.cols, self.rows, entries)
+	}
+
+}
+
+impl<R: Ring> LinearOperator<R> for SparseMatrix<R> {
+	fn input_dimension(&self) -> usize {
+		self.cols
+	}
+
+	fn output_dimension(&self) -> usize {
+		self.rows
+	}
+
+	/// Ax, in time proportional to the number of nonzero entries
+	fn apply(&self, x: &[R]) -> Vec<R> {
+		if x.len() != self.cols {
+			panic!("Cannot apply a {}x{} matrix to a vector of length {}", self.rows, self.cols, x.len())
+		}
+
+		(0..self.rows).map(|i| self.row(i).fold(R::zero(), |sum, (j, a)| sum.add_ref(&a.mul_ref(&x[j])))).collect()
+	}
+
+	fn to_matrix(&self) -> Matrix<R> {
+		self.to_dense()
+	}
+}
+
+// MARK: Wiedemann
+
+/// How many random projections the Wiedemann solver tries before giving up
+const WIEDEMANN_ATTEMPTS: usize = 16;
+
+/// The solution of Ax = b for a square nonsingular A over a finite field, by Wiedemann's
+/// algorithm, or None if none was found, which happens when A is singular
+pub fn wiedemann<F: Field + RandomElement, A: LinearOperator<F>>(a: &A, b: &[F]) -> Option<Vec<F>> {
+	wiedemann_with_rng(a, b, &mut rand::thread_rng())
+}
+
+/// Wiedemann's algorithm, projecting with vectors drawn from the given source of randomness.
+///
+/// A only needs to be applied to vectors, about 3n times per attempt, so this takes O(n) products
+/// with A, O(n^2) field operations and O(n) memory, and never fills in a sparse A. For a random u, the shortest
+/// recurrence of u A^i b found by Berlekamp-Massey is usually the minimal polynomial
+/// x^d - c_1 x^(d-1) - ... - c_d of A on b, and then x = (A^(d-1) b - c_1 A^(d-2) b - ... -
+/// c_(d-1) b) / c_d. Each candidate is checked, and a new u is tried if it is wrong.
+pub fn wiedemann_with_rng<F: Field + RandomElement, A: LinearOperator<F>, G: Rng + ?Sized>(a: &A, b: &[F], rng: &mut G) -> Option<Vec<F>> {
+	let n = a.input_dimension();
+	if a.output_dimension() != n {
+		panic!("Wiedemann's algorithm needs a square operator, got {}x{}", a.output_dimension(), n)
+	}
+
+	if b.len() != n {
+		panic!("The system has {} rows but {} right hand sides", n, b.len())
+	}
+
+	if b.iter().all(|x| x.is_zero()) {
+		return Some(vec![F::zero(); n])
+	}
+
+	let dot = |u: &[F], v: &[F]| u.iter().zip(v.iter()).fold(F::zero(), |sum, (x, y)| sum.add_ref(&x.mul_ref(y)));
+
+	for _ in 0..WIEDEMANN_ATTEMPTS {
+		// u A^i b for i < 2n, walking along the Krylov vectors without keeping them
+		let u: Vec<F> = (0..n).map(|_| F::random(rng)).collect();
+		let mut v = b.to_vec();
+		let mut sequence = Vec::with_capacity(2 * n);
+		for _ in 0..2 * n {
+			sequence.push(dot(&u, &v));
+			v = a.apply(&v);
+		}
+
+		let c = linear_recurrence(&sequence);
+		let Some(constant) = c.last().and_then(|c_d| c_d.try_inverse()) else {
+			continue
+		};
+
+		// x = (A^(d-1) b - c_1 A^(d-2) b - ... - c_(d-1) b) / c_d, accumulated from b up
+		let d = c.len();
+		let mut x = vec![F::zero(); n];
+		let mut v = b.to_vec();
+		for i in 0..d {
+			let weight = if i == d - 1 { F::one() } else { -c[d - 2 - i].clone() };
+			for (xj, vj) in x.iter_mut().zip(v.iter()) {
+				xj.add_assign_ref(&weight.mul_ref(vj));
+			}
+			if i + 1 < d {
+				v = a.apply(&v);
+			}
+		}
+		for xj in x.iter_mut() {
+			xj.mul_assign_ref(&constant);
+		}
+
+		if a.apply(&x) == b {
+			return Some(x)
+		}
+	}
+
+	None
+}
@@ -0,0 +1,353 @@
+//
+// Univariate polynomials stored as maps from exponents to coefficients, for polynomials like
+// x^100000 + 1 with few terms of high degree
+//
+
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::polynomial::{repeated_sum, Polynomial};
+
+/// A product uses dense multiplication when both factors have at least one term in this many
+/// possible degrees
+const DENSITY_CROSSOVER: usize = 8;
+
+// MARK: Sparse Polynomial
+
+/// A polynomial with coefficients in a ring R, stored as a map from exponents to their nonzero
+/// coefficients, so that its size depends on its number of terms rather than its degree.
+///
+/// Products of two fairly dense polynomials are handed to `Polynomial`, whose multiplication is
+/// asymptotically faster, and products involving a sparse one are done term by term.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SparsePolynomial<R: Ring> {
+	terms: BTreeMap<usize, R>
+}
+
+impl<R: Ring> SparsePolynomial<R> {
+
+	/// The sum of the terms c x^e for the given pairs (e, c), combining any with the same exponent
+	pub fn new(terms: Vec<(usize, R)>) -> SparsePolynomial<R> {
+		let mut p = SparsePolynomial { terms: BTreeMap::new() };
+		for (e, c) in terms {
+			p.add_term(e, &c);
+		}
+		p
+	}
+
+	/// The constant polynomial c
+	pub fn constant(c: R) -> SparsePolynomial<R> {
+		SparsePolynomial::monomial(c, 0)
+	}
+
+	/// The polynomial x
+	pub fn x() -> SparsePolynomial<R> {
+		SparsePolynomial::monomial(R::one(), 1)
+	}
+
+	/// The single term c x^n
+	pub fn monomial(c: R, n: usize) -> SparsePolynomial<R> {
+		SparsePolynomial::new(vec![(n, c)])
+	}
+
+	/// The terms with nonzero coefficients, as (exponent, coefficient) pairs in increasing order of
+	/// exponent
+	pub fn terms(&self) -> impl Iterator<Item = (&usize, &R)> {
+		self.terms.iter()
+	}
+
+	/// The number of nonzero terms
+	pub fn term_count(&self) -> usize {
+		self.terms.len()
+	}
+
+	/// The coefficient of x^i
+	pub fn coefficient(&self, i: usize) -> R {
+		self.terms.get(&i).cloned().unwrap_or_else(R::zero)
+	}
+
+	/// The degree of this polynomial. The zero polynomial is given degree 0.
+	pub fn degree(&self) -> usize {
+		self.terms.keys().next_back().copied().unwrap_or(0)
+	}
+
+	/// The coefficient of the highest power of x, or zero for the zero polynomial
+	pub fn leading_coefficient(&self) -> R {
+		self.terms.values().next_back().cloned().unwrap_or_else(R::zero)
+	}
+
+	/// Evaluates this polynomial at x, raising x to each exponent by repeated squaring
+	pub fn evaluate(&self, x: &R) -> R {
+		self.terms.iter().fold(R::zero(), |sum, (&e, c)| sum.add_ref(&c.mul_ref(&x.power(e as i64))))
+	}
+
+	/// Multiplies every coefficient by c
+	pub fn scale(&self, c: &R) -> SparsePolynomial<R> {
+		SparsePolynomial::new(self.terms.iter().map(|(&e, a)| (e, a.mul_ref(c))).collect())
+	}
+
+	/// The formal derivative
+	pub fn derivative(&self) -> SparsePolynomial<R> {
+		SparsePolynomial::new(self.terms.iter().filter(|&(&e, _)| e > 0).map(|(&e, c)| (e - 1, repeated_sum(c, e))).collect())
+	}
+
+	/// The same polynomial with its coefficients stored densely
+	pub fn to_dense(&self) -> Polynomial<R> {
+		let mut coefficients = vec![R::zero(); self.terms.keys().next_back().map_or(0, |d| d + 1)];
+		for (&e, c) in self.terms.iter() {
+			coefficients[e] = c.clone();
+		}
+		Polynomial::new(coefficients)
+	}
+
+	/// Whether or not at least one in every `DENSITY_CROSSOVER` coefficients up to the degree is
+	/// nonzero, so that dense arithmetic is cheaper
+	fn is_dense(&self) -> bool {
+		DENSITY_CROSSOVER * self.terms.len() > self.degree() + 1
+	}
+
+	/// Adds c x^e to this polynomial in place
+	fn add_term(&mut self, e: usize, c: &R) {
+		let entry = self.terms.entry(e).or_insert_with(R::zero);
+		entry.add_assign_ref(c);
+		if entry.is_zero() {
+			self.terms.remove(&e);
+		}
+	}
+
+}
+
+impl<R: Ring> From<Polynomial<R>> for SparsePolynomial<R> {
+	fn from(value: Polynomial<R>) -> Self {
+		SparsePolynomial::new(value.coefficients().iter().cloned().enumerate().collect())
+	}
+}
+
+impl<R: Ring> From<SparsePolynomial<R>> for Polynomial<R> {
+	fn from(value: SparsePolynomial<R>) -> Self {
+		value.to_dense()
+	}
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring> Add for SparsePolynomial<R> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self.add_assign_ref(&rhs);
+		self
+	}
+}
+
+impl<R: Ring> AddAssign for SparsePolynomial<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.add_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Neg for SparsePolynomial<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		SparsePolynomial { terms: self.terms.into_iter().map(|(e, c)| (e, -c)).collect() }
+	}
+}
+
+impl<R: Ring> Sub for SparsePolynomial<R> {
+	type Output = Self;
+
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self.sub_assign_ref(&rhs);
+		self
+	}
+}
+
+impl<R: Ring> SubAssign for SparsePolynomial<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.sub_assign_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Mul for SparsePolynomial<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring> MulAssign for SparsePolynomial<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.mul_ref(&rhs)
+	}
+}
+
+impl<R: Ring> Semiring for SparsePolynomial<R> {
+	fn one() -> Self {
+		SparsePolynomial::constant(R::one())
+	}
+
+	fn zero() -> Self {
+		SparsePolynomial { terms: BTreeMap::new() }
+	}
+
+	fn is_zero(&self) -> bool {
+		self.terms.is_empty()
+	}
+
+	fn add_assign_ref(&mut self, rhs: &Self) {
+		for (&e, c) in rhs.terms.iter() {
+			self.add_term(e, c);
+		}
+	}
+
+	fn mul_assign_ref(&mut self, rhs: &Self) {
+		*self = self.mul_ref(rhs)
+	}
+
+	fn mul_ref(&self, rhs: &Self) -> Self {
+		if self.is_dense() && rhs.is_dense() {
+			return self.to_dense().mul_ref(&rhs.to_dense()).into()
+		}
+
+		let mut product = SparsePolynomial::zero();
+		for (&e, a) in self.terms.iter() {
+			for (&f, b) in rhs.terms.iter() {
+				product.add_term(e + f, &a.mul_ref(b));
+			}
+		}
+		product
+	}
+}
+
+impl<R: Ring> Ring for SparsePolynomial<R> {
+	fn power(&self, n: i64) -> Self {
+		if n < 0 {
+			panic!("Cannot invert ring element")
+		}
+
+		let mut power = Self::one();
+		let mut base = self.clone();
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power.mul_assign_ref(&base);
+			}
+			base = base.mul_ref(&base);
+			e >>= 1;
+		}
+
+		power
+	}
+
+	fn sub_assign_ref(&mut self, rhs: &Self) {
+		for (&e, c) in rhs.terms.iter() {
+			self.add_term(e, &-c.clone());
+		}
+	}
+}
+
+impl<R: CommutativeRing> CommutativeRing for SparsePolynomial<R> { }
+
+/// Long division, dividing each leading coefficient exactly in R
+impl<R: IntegralDomain> IntegralDomain for SparsePolynomial<R> {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		if divisor.is_zero() {
+			return None
+		}
+
+		let d = divisor.degree();
+		let lead = divisor.leading_coefficient();
+		let mut quotient = SparsePolynomial::zero();
+		let mut remainder = self.clone();
+
+		while let Some((&e, c)) = remainder.terms.iter().next_back() {
+			if e < d {
+				return None
+			}
+
+			let q = c.divide_exact(&lead)?;
+			for (&f, a) in divisor.terms.iter() {
+				remainder.add_term(e - d + f, &-q.mul_ref(a));
+			}
+			quotient.add_term(e - d, &q);
+		}
+
+		Some(quotient)
+	}
+}
+
+// MARK: Division
+
+impl<F: Field> Div for SparsePolynomial<F> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl<F: Field> DivAssign for SparsePolynomial<F> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).0
+	}
+}
+
+impl<F: Field> Rem for SparsePolynomial<F> {
+	type Output = Self;
+
+	fn rem(self, rhs: Self) -> Self::Output {
+		self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl<F: Field> RemAssign for SparsePolynomial<F> {
+	fn rem_assign(&mut self, rhs: Self) {
+		*self = self.quotient_and_remainder(&rhs).1
+	}
+}
+
+impl<F: Field> UniqueFactorizationDomain for SparsePolynomial<F> {
+	fn gcd(&self, other: &Self) -> Self {
+		euclidean_gcd(self, other)
+	}
+}
+
+impl<F: Field> PrincipalIdealDomain for SparsePolynomial<F> { }
+
+impl<F: Field> EuclideanDomain for SparsePolynomial<F> {
+	type SizeType = usize;
+
+	fn euc_size(&self) -> usize {
+		self.degree()
+	}
+
+	/// Long division, which only touches the terms present
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if divisor.is_zero() {
+			panic!("Cannot divide by the zero polynomial")
+		}
+
+		let d = divisor.degree();
+		let lead_inverse = divisor.leading_coefficient().inverse();
+		let mut quotient = SparsePolynomial::zero();
+		let mut remainder = self.clone();
+
+		while let Some((&e, c)) = remainder.terms.iter().next_back() {
+			if e < d {
+				break
+			}
+
+			let q = c.mul_ref(&lead_inverse);
+			for (&f, a) in divisor.terms.iter() {
+				remainder.add_term(e - d + f, &-q.mul_ref(a));
+			}
+			quotient.add_term(e - d, &q);
+		}
+
+		(quotient, remainder)
+	}
+}
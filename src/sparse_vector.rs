@@ -0,0 +1,271 @@
+//
+// Sparse vectors storing only their nonzero entries, in a map from indices to values, for the long
+// vectors with few nonzero entries which come up in index calculus, sieving, and sparse codes
+//
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::sparse_matrix::SparseMatrix;
+
+// MARK: Sparse Vector
+
+/// A vector in R^dimension storing only its nonzero entries, hashed by index, so that reading,
+/// writing, and adding to an entry take constant time regardless of the dimension
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseVector<R: Ring> {
+	dimension: usize,
+	entries: HashMap<usize, R>
+}
+
+impl<R: Ring> SparseVector<R> {
+
+	/// The vector with the given (index, value) entries, adding up repeated indices and leaving
+	/// out zeros
+	pub fn new(dimension: usize, entries: Vec<(usize, R)>) -> SparseVector<R> {
+		let mut v = SparseVector::zero(dimension);
+		for (i, x) in entries {
+			v.add_to(i, &x);
+		}
+		v
+	}
+
+	/// The zero vector
+	pub fn zero(dimension: usize) -> SparseVector<R> {
+		SparseVector { dimension, entries: HashMap::new() }
+	}
+
+	/// The standard basis vector e_i
+	pub fn basis(dimension: usize, i: usize) -> SparseVector<R> {
+		SparseVector::new(dimension, vec![(i, R::one())])
+	}
+
+	/// The sparse form of a dense vector
+	pub fn from_dense(v: &[R]) -> SparseVector<R> {
+		SparseVector {
+			dimension: v.len(),
+			entries: v.iter().enumerate().filter(|(_, x)| !x.is_zero()).map(|(i, x)| (i, x.clone())).collect()
+		}
+	}
+
+	/// The dense form of this vector
+	pub fn to_dense(&self) -> Vec<R> {
+		let mut v = vec![R::zero(); self.dimension];
+		for (&i, x) in &self.entries {
+			v[i] = x.clone();
+		}
+		v
+	}
+
+	/// The dimension of the space this vector lies in
+	pub fn dimension(&self) -> usize {
+		self.dimension
+	}
+
+	/// The number of nonzero entries
+	pub fn nonzero_count(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Whether or not this is the zero vector
+	pub fn is_zero(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// The nonzero entries, as (index, value) pairs in no particular order
+	pub fn entries(&self) -> impl Iterator<Item = (usize, &R)> {
+		self.entries.iter().map(|(&i, x)| (i, x))
+	}
+
+	/// The indices of the nonzero entries, in increasing order
+	pub fn support(&self) -> Vec<usize> {
+		let mut indices: Vec<usize> = self.entries.keys().copied().collect();
+		indices.sort_unstable();
+		indices
+	}
+
+	/// The entry at index i
+	pub fn get(&self, i: usize) -> R {
+		self.check_index(i);
+		self.entries.get(&i).cloned().unwrap_or_else(R::zero)
+	}
+
+	/// Sets the entry at index i to x
+	pub fn set(&mut self, i: usize, x: R) {
+		self.check_index(i);
+		if x.is_zero() {
+			self.entries.remove(&i);
+		} else {
+			self.entries.insert(i, x);
+		}
+	}
+
+	/// Adds x to the entry at index i
+	pub fn add_to(&mut self, i: usize, x: &R) {
+		self.check_index(i);
+		if x.is_zero() {
+			return
+		}
+
+		let entry = self.entries.entry(i).or_insert_with(R::zero);
+		entry.add_assign_ref(x);
+		if entry.is_zero() {
+			self.entries.remove(&i);
+		}
+	}
+
+	/// Adds c times other to this vector, in time proportional to the nonzero entries of other
+	pub fn add_scaled(&mut self, c: &R, other: &SparseVector<R>) {
+		self.check_dimension(other);
+		for (&i, x) in &other.entries {
+			self.add_to(i, &x.mul_ref(c));
+		}
+	}
+
+	/// This vector multiplied by the scalar c
+	pub fn scale(&self, c: &R) -> SparseVector<R> {
+		SparseVector {
+			dimension: self.dimension,
+			entries: self.entries.iter().map(|(&i, x)| (i, x.mul_ref(c))).filter(|(_, x)| !x.is_zero()).collect()
+		}
+	}
+
+	/// The dot product with another sparse vector, in time proportional to the smaller number of
+	/// nonzero entries
+	pub fn dot(&self, other: &SparseVector<R>) -> R {
+		self.check_dimension(other);
+		if self.entries.len() <= other.entries.len() {
+			self.entries.iter().filter_map(|(i, x)| other.entries.get(i).map(|y| x.mul_ref(y)))
+				.fold(R::zero(), |sum, p| sum.add_ref(&p))
+		} else {
+			other.entries.iter().filter_map(|(i, y)| self.entries.get(i).map(|x| x.mul_ref(y)))
+				.fold(R::zero(), |sum, p| sum.add_ref(&p))
+		}
+	}
+
+	/// The dot product with a dense vector, in time proportional to the nonzero entries
+	pub fn dot_dense(&self, other: &[R]) -> R {
+		if other.len() != self.dimension {
+			panic!("Cannot take the dot product of vectors of dimensions {} and {}", self.dimension, other.len())
+		}
+		self.entries.iter().fold(R::zero(), |sum, (&i, x)| sum.add_ref(&x.mul_ref(&other[i])))
+	}
+
+	fn check_index(&self, i: usize) {
+		if i >= self.dimension {
+			panic!("Index {} is outside a vector of dimension {}", i, self.dimension)
+		}
+	}
+
+	fn check_dimension(&self, other: &SparseVector<R>) {
+		if self.dimension != other.dimension {
+			panic!("Dimension mismatch: {} and {}", self.dimension, other.dimension)
+		}
+	}
+
+}
+
+/// Hashes the entries in order of index, since the map itself has no fixed order
+impl<R: Ring + Hash> Hash for SparseVector<R> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.dimension.hash(state);
+		for i in self.support() {
+			i.hash(state);
+			self.entries[&i].hash(state);
+		}
+	}
+}
+
+// MARK: Arithmetic
+
+impl<R: Ring> Add for SparseVector<R> {
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		let mut sum = self;
+		sum += rhs;
+		sum
+	}
+}
+
+impl<R: Ring> AddAssign for SparseVector<R> {
+	fn add_assign(&mut self, rhs: Self) {
+		self.check_dimension(&rhs);
+		for (i, x) in rhs.entries {
+			self.add_to(i, &x);
+		}
+	}
+}
+
+impl<R: Ring> Neg for SparseVector<R> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		SparseVector {
+			dimension: self.dimension,
+			entries: self.entries.into_iter().map(|(i, x)| (i, -x)).collect()
+		}
+	}
+}
+
+impl<R: Ring> Sub for SparseVector<R> {
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		let mut difference = self;
+		difference -= rhs;
+		difference
+	}
+}
+
+impl<R: Ring> SubAssign for SparseVector<R> {
+	fn sub_assign(&mut self, rhs: Self) {
+		self.add_scaled(&-R::one(), &rhs)
+	}
+}
+
+/// Scalar multiplication
+impl<R: Ring> Mul<R> for SparseVector<R> {
+	type Output = Self;
+
+	fn mul(self, rhs: R) -> Self::Output {
+		self.scale(&rhs)
+	}
+}
+
+impl<R: Ring> MulAssign<R> for SparseVector<R> {
+	fn mul_assign(&mut self, rhs: R) {
+		*self = self.scale(&rhs)
+	}
+}
+
+impl<R: Ring> Module<R> for SparseVector<R> {
+	fn scale(&self, r: &R) -> Self {
+		SparseVector::scale(self, r)
+	}
+}
+
+// MARK: Sparse Matrices
+
+impl<R: Ring> SparseMatrix<R> {
+
+	/// Row i as a sparse vector
+	pub fn row_vector(&self, i: usize) -> SparseVector<R> {
+		SparseVector::new(self.cols(), self.row(i).map(|(j, x)| (j, x.clone())).collect())
+	}
+
+	/// Ax for a sparse vector x, in time proportional to the number of nonzero entries of A
+	pub fn apply_sparse(&self, x: &SparseVector<R>) -> SparseVector<R> {
+		if x.dimension() != self.cols() {
+			panic!("Cannot apply a {}x{} matrix to a vector of dimension {}", self.rows(), self.cols(), x.dimension())
+		}
+
+		let entries = (0..self.rows()).map(|i| {
+			(i, self.row(i).filter_map(|(j, a)| x.entries.get(&j).map(|y| a.mul_ref(y))).fold(R::zero(), |sum, p| sum.add_ref(&p)))
+		}).collect();
+		SparseVector::new(self.rows(), entries)
+	}
+
+}
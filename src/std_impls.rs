@@ -1,120 +1,492 @@
-use rand::rngs;
-use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use alloc::vec::Vec;
+use rand::distributions::uniform::{SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::num::Wrapping;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::algebra::*;
+use crate::finite_set::{check_index, FiniteSet};
+use crate::random::RandomElement;
 
 // MARK: Groups
 
-/// The additive group of the integers modulo an integer N
-#[derive(Clone, Copy, Default, Debug)]
-pub struct AdditiveGroupZM<const N: i64> {
-	pub val: i64
-}							
+/// The additive group of a ring, as a `Group` whose operation is the ring's addition
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub struct Additive<R: Ring>(pub R);
 
-impl<const N: i64> Add for AdditiveGroupZM<N> {
-	type Output = Self;
+impl<R: Ring> Additive<R> {
+
+	/// The group element x
+	pub fn new(x: R) -> Additive<R> {
+		Additive(x)
+	}
+
+	/// The underlying ring element
+	pub fn value(&self) -> &R {
+		&self.0
+	}
+
+	/// Unwraps the underlying ring element
+	pub fn into_inner(self) -> R {
+		self.0
+	}
+
+}
 
-	fn add(self, rhs: Self) -> Self::Output {
-		AdditiveGroupZM { val: (self.val + rhs.val).rem_euclid(N) }
+impl<R: Ring> From<R> for Additive<R> {
+	fn from(x: R) -> Self {
+		Additive(x)
 	}
 }
 
-impl<const N: i64> AddAssign for AdditiveGroupZM<N> {
-	fn add_assign(&mut self, rhs: Self) {
-		self.val -= rhs.val;
-		self.val = self.val.rem_euclid(N);
+/// A group element equals the ring element it wraps
+impl<R: Ring> PartialEq<R> for Additive<R> {
+	fn eq(&self, other: &R) -> bool {
+		self.0 == *other
 	}
 }
 
-impl<const N: i64> Neg for AdditiveGroupZM<N> {
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<R: Ring> Mul for Additive<R> {
 	type Output = Self;
 
-	fn neg(self) -> Self::Output {
-		AdditiveGroupZM { val: (-self.val).rem_euclid(N) }
+	fn mul(self, rhs: Self) -> Self::Output {
+		Additive(self.0 + rhs.0)
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<R: Ring> MulAssign for Additive<R> {
+	fn mul_assign(&mut self, rhs: Self) {
+		self.0 += rhs.0
 	}
 }
 
-impl<const N: i64> Sub for AdditiveGroupZM<N> {
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<R: Ring> Div for Additive<R> {
 	type Output = Self;
 
-	fn sub(self, rhs: Self) -> Self::Output {
-		AdditiveGroupZM { val: (self.val - rhs.val).rem_euclid(N) }
+	fn div(self, rhs: Self) -> Self::Output {
+		Additive(self.0 - rhs.0)
+	}
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<R: Ring> DivAssign for Additive<R> {
+	fn div_assign(&mut self, rhs: Self) {
+		self.0 -= rhs.0
+	}
+}
+
+impl<R: Ring> Monoid for Additive<R> {
+	fn identity() -> Self {
+		Additive(R::zero())
+	}
+}
+
+impl<R: Ring> Group for Additive<R> {
+	fn inverse(&self) -> Self {
+		Additive(-self.0.clone())
+	}
+}
+
+impl<R: Ring> AbelianGroup for Additive<R> { }
+
+impl<R: Ring + RandomElement> RandomElement for Additive<R> {
+	fn random<G: Rng + ?Sized>(rng: &mut G) -> Self {
+		Additive(R::random(rng))
+	}
+}
+
+impl<R: Ring + RandomElement> Distribution<Additive<R>> for Standard {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> Additive<R> {
+		Additive::random(rng)
+	}
+}
+
+impl<R: Ring + FiniteSet> FiniteSet for Additive<R> {
+	fn size() -> u128 {
+		R::size()
+	}
+
+	fn to_index(&self) -> u128 {
+		self.0.to_index()
+	}
+
+	fn from_index(index: u128) -> Self {
+		Additive(R::from_index(index))
 	}
 }
 
-impl<const N: i64> SubAssign for AdditiveGroupZM<N> {
-	fn sub_assign(&mut self, rhs: Self) {
-		self.val -= rhs.val;
-		self.val = self.val.rem_euclid(N)
+/// The multiplicative group of a field, made up of its invertible elements.
+///
+/// For ZM<N> with N composite these are exactly the units modulo N, so this is the unit group
+/// of Z/(N) even when Z/(N) is not a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Multiplicative<F: Field>(F);
+
+impl<F: Field> Multiplicative<F> {
+
+	/// The group element x, if x is invertible
+	pub fn new(x: F) -> Option<Multiplicative<F>> {
+		x.try_inverse().map(|_| Multiplicative(x))
+	}
+
+	/// The underlying field element
+	pub fn value(&self) -> &F {
+		&self.0
+	}
+
+	/// Unwraps the underlying field element
+	pub fn into_inner(self) -> F {
+		self.0
 	}
+
 }
 
-impl<const N: i64> Mul for AdditiveGroupZM<N> {
+impl<F: Field> Mul for Multiplicative<F> {
 	type Output = Self;
 
 	fn mul(self, rhs: Self) -> Self::Output {
-		self + rhs
+		Multiplicative(self.0 * rhs.0)
 	}
 }
 
-impl<const N: i64> MulAssign for AdditiveGroupZM<N> {
+impl<F: Field> MulAssign for Multiplicative<F> {
 	fn mul_assign(&mut self, rhs: Self) {
-		*self += rhs
+		self.0 *= rhs.0
 	}
 }
 
-impl<const N: i64> Div for AdditiveGroupZM<N> {
+impl<F: Field> Div for Multiplicative<F> {
 	type Output = Self;
 
 	fn div(self, rhs: Self) -> Self::Output {
-		AdditiveGroupZM { val: self.val - rhs.val }
+		Multiplicative(self.0 / rhs.0)
 	}
 }
 
-impl<const N: i64> DivAssign for AdditiveGroupZM<N> {
+impl<F: Field> DivAssign for Multiplicative<F> {
 	fn div_assign(&mut self, rhs: Self) {
-		*self -= rhs
+		self.0 /= rhs.0
 	}
 }
 
-impl<const N: i64> PartialEq for AdditiveGroupZM<N> {
-	fn eq(&self, other: &Self) -> bool {
-		self.val.rem_euclid(N) == other.val.rem_euclid(N)
+impl<F: Field> Monoid for Multiplicative<F> {
+	fn identity() -> Self {
+		Multiplicative(F::one())
+	}
+}
+
+impl<F: Field> Group for Multiplicative<F> {
+	fn inverse(&self) -> Self {
+		Multiplicative(self.0.inverse())
 	}
 }
 
-impl<const N: i64> Group for AdditiveGroupZM<N> {
+impl<F: Field> AbelianGroup for Multiplicative<F> { }
 
-	fn identity() -> Self {
-		AdditiveGroupZM { val: 0 }
+impl<F: Field + RandomElement> RandomElement for Multiplicative<F> {
+	/// Rejection sampling from the whole field, until an invertible element comes up
+	fn random<G: Rng + ?Sized>(rng: &mut G) -> Self {
+		loop {
+			if let Some(u) = Multiplicative::new(F::random(rng)) {
+				return u
+			}
+		}
 	}
+}
 
-	fn inverse(&self) -> Self {
-		-(*self)
+impl<F: Field + RandomElement> Distribution<Multiplicative<F>> for Standard {
+	fn sample<G: Rng + ?Sized>(&self, rng: &mut G) -> Multiplicative<F> {
+		Multiplicative::random(rng)
 	}
 }
 
+/// The additive group of the integers modulo an integer N
+pub type AdditiveGroupZM<const N: i64> = Additive<ZM<N>>;
+
 impl<const N: i64> AdditiveGroupZM<N> {
 
 	/// Creates a group element in Z/(N) from the integer x
 	pub fn from_int(x: i64) -> AdditiveGroupZM<N> {
-		AdditiveGroupZM { val: x.rem_euclid(N) }
+		Additive(ZM::from_int(x))
+	}
+
+	/// The image of x under the reduction Z/(M) -> Z/(N), which is a homomorphism exactly when N
+	/// divides M, and `None` otherwise
+	pub fn reduce<const M: i64>(x: AdditiveGroupZM<M>) -> Option<AdditiveGroupZM<N>> {
+		ZM::try_convert(x.0).map(Additive)
+	}
+
+}
+
+impl<const N: i64> From<AdditiveGroupZM<N>> for ZM<N> {
+	fn from(x: AdditiveGroupZM<N>) -> Self {
+		x.0
+	}
+}
+
+impl<const N: i64> PartialEq<AdditiveGroupZM<N>> for ZM<N> {
+	fn eq(&self, other: &AdditiveGroupZM<N>) -> bool {
+		*self == other.0
+	}
+}
+
+impl<const N: i64> FiniteGroup for AdditiveGroupZM<N> {
+
+	fn order() -> usize {
+		N as usize
+	}
+
+	fn elements() -> impl Iterator<Item = Self> {
+		(0..N).map(AdditiveGroupZM::from_int)
+	}
+
+	/// The order of x in Z/(N), which is N / gcd(x, N)
+	fn element_order(&self) -> usize {
+		(N / gcd(&self.0.val, &N)) as usize
+	}
+}
+
+/// The multiplicative group of units of the integers modulo an integer N
+pub type UnitsZM<const N: i64> = Multiplicative<ZM<N>>;
+
+impl<const N: i64> UnitsZM<N> {
+
+	/// Creates a unit of Z/(N) from the integer x, if x is coprime to N
+	pub fn from_int(x: i64) -> Option<UnitsZM<N>> {
+		Multiplicative::new(ZM::from_int(x))
 	}
 
-	/// Generates a random group element
+	/// The smallest generator of the unit group, if the group is cyclic
 	/// 
-	/// NOT cryptographically secure!
-	pub fn random() -> AdditiveGroupZM<N> {
-		AdditiveGroupZM { val: rand::thread_rng().gen_range(0..N) }
+	/// The unit group is cyclic exactly when N is 1, 2, 4, p^k, or 2p^k for an odd prime p.
+	pub fn find_primitive_root() -> Option<UnitsZM<N>> {
+		let order = Self::order() as i64;
+		let prime_divisors: Vec<i64> = factor(order).into_iter().map(|(p, _)| p).collect();
+
+		Self::elements().find(|g| {
+			prime_divisors.iter().all(|p| g.power(order / p) != Self::identity())
+		})
+	}
+
+}
+
+impl<const N: i64> FiniteGroup for UnitsZM<N> {
+
+	/// The number of units modulo N, which is Euler's totient of N
+	fn order() -> usize {
+		totient(N) as usize
+	}
+
+	/// All units modulo N, in increasing order of representative
+	fn elements() -> impl Iterator<Item = UnitsZM<N>> {
+		(0..N).filter_map(UnitsZM::from_int)
+	}
+}
+
+// MARK: Number Theory
+
+/// The prime factorization of a positive integer n, as (prime, exponent) pairs in increasing order
+pub fn factor(n: i64) -> Vec<(i64, u32)> {
+	if n <= 0 {
+		panic!("Can only factor positive integers")
+	}
+
+	let mut factors = Vec::new();
+	let mut n = n;
+	let mut p = 2;
+
+	while p * p <= n {
+		if n % p == 0 {
+			let mut e = 0;
+			while n % p == 0 {
+				n /= p;
+				e += 1;
+			}
+			factors.push((p, e));
+		}
+		p += if p == 2 { 1 } else { 2 };
+	}
+
+	if n > 1 {
+		factors.push((n, 1));
+	}
+
+	factors
+}
+
+/// Euler's totient function: the number of integers in [1, n] coprime to n
+pub fn totient(n: i64) -> i64 {
+	factor(n).iter().fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+/// The radical of a positive integer n, the product of its distinct prime factors.
+///
+/// This is the largest squarefree divisor of n.
+pub fn radical(n: i64) -> i64 {
+	factor(n).iter().map(|(p, _)| p).product()
+}
+
+/// Whether or not no square of a prime divides the positive integer n
+pub fn is_squarefree(n: i64) -> bool {
+	factor(n).iter().all(|(_, e)| *e == 1)
+}
+
+/// Whether or not n is prime, by Miller-Rabin with the first twelve primes as bases, which is
+/// deterministic for every i64. This is a `const fn`, so it can check moduli at compile time.
+pub const fn is_prime(n: i64) -> bool {
+	const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+	if n < 2 {
+		return false
+	}
+
+	let n = n as u64;
+	let mut i = 0;
+	while i < BASES.len() {
+		if n.is_multiple_of(BASES[i]) {
+			return n == BASES[i]
+		}
+		i += 1;
+	}
+
+	// Write n - 1 = d * 2^s with d odd
+	let s = (n - 1).trailing_zeros();
+	let d = (n - 1) >> s;
+
+	let mut i = 0;
+	while i < BASES.len() {
+		let mut x = power_mod(BASES[i], d, n);
+		let mut r = 1;
+		let mut witness = x != 1 && x != n - 1;
+		while witness && r < s {
+			x = multiply_mod(x, x, n);
+			witness = x != n - 1;
+			r += 1;
+		}
+		if witness {
+			return false
+		}
+		i += 1;
+	}
+
+	true
+}
+
+/// a * b mod n, without overflow
+const fn multiply_mod(a: u64, b: u64, n: u64) -> u64 {
+	((a as u128 * b as u128) % n as u128) as u64
+}
+
+/// b^e mod n, by repeated squaring
+const fn power_mod(b: u64, e: u64, n: u64) -> u64 {
+	let (mut power, mut base, mut e) = (1, b % n, e);
+	while e > 0 {
+		if e & 1 == 1 {
+			power = multiply_mod(power, base, n);
+		}
+		base = multiply_mod(base, base, n);
+		e >>= 1;
+	}
+	power
+}
+
+/// Writes n as base^exp with the largest possible exp >= 2, if that can be done with |base| >= 2
+pub fn is_perfect_power(n: i64) -> Option<(i64, u32)> {
+	if n.unsigned_abs() < 4 {
+		return None
+	}
+
+	let magnitude = n.unsigned_abs();
+
+	for exp in (2..=magnitude.ilog2()).rev() {
+		// A negative number is only an odd power
+		if n < 0 && exp % 2 == 0 {
+			continue
+		}
+
+		let base = integer_root(magnitude, exp);
+		if base.checked_pow(exp) == Some(magnitude) {
+			let base = base as i64;
+			return Some((if n < 0 { -base } else { base }, exp))
+		}
+	}
+
+	None
+}
+
+/// The largest r with r^k <= n, by binary search so that no floating point roots are needed
+fn integer_root(n: u64, k: u32) -> u64 {
+	// r < 2^(floor(log2 n) / k + 1)
+	let bits = 64 - n.leading_zeros();
+	let (mut low, mut high) = (0u64, 1u64 << (bits / k + 1).min(63));
+
+	while low < high {
+		let middle = low + (high - low).div_ceil(2);
+		if middle.checked_pow(k).is_some_and(|x| x <= n) {
+			low = middle
+		} else {
+			high = middle - 1
+		}
 	}
 
+	low
 }
 
 // MARK: Rings and Fields
 
-impl Ring for f64 {
+/// x^y for a float and an integer y, which without `std` and its `powf` is found by repeated
+/// squaring
+#[cfg(feature = "std")]
+macro_rules! float_power {
+	($x:expr, $y:expr, $t:ty) => { <$t>::powf($x, $y) };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! float_power {
+	($x:expr, $y:expr, $t:ty) => {{
+		let (x, y): ($t, $t) = ($x, $y);
+		let mut base = if y < 0.0 { 1.0 / x } else { x };
+		let mut e = <$t>::abs(y) as u64;
+		let mut power = 1.0;
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base
+			}
+			base *= base;
+			e >>= 1;
+		}
+		power
+	}};
+}
+
+/// The floor of a float, which without `std` is found by truncating to an integer
+#[cfg(feature = "std")]
+macro_rules! float_floor {
+	($x:expr, $t:ty) => { <$t>::floor($x) };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! float_floor {
+	($x:expr, $t:ty) => {{
+		let x: $t = $x;
+		// Floats this large have no fractional part, and neither do infinities
+		if x.is_nan() || <$t>::abs(x) >= (1u64 << (<$t>::MANTISSA_DIGITS - 1)) as $t {
+			x
+		} else {
+			let truncated = x as i64 as $t;
+			if truncated > x { truncated - 1.0 } else { truncated }
+		}
+	}};
+}
+
+impl Semiring for f64 {
 	fn one() -> Self {
 		1.0
 	}
@@ -126,9 +498,11 @@ impl Ring for f64 {
 	fn zero() -> Self {
 		0.0
 	}
+}
 
+impl Ring for f64 {
 	fn power(&self, n: i64) -> Self {
-		self.powf(n as f64)
+		float_power!(*self, n as f64, f64)
 	}
 }
 
@@ -144,10 +518,13 @@ impl Field for f64 {
 	}
 }
 
-impl PoField for f64 { }
-
-impl Ring for f32 {
+impl PoField for f64 {
+	fn floor(&self) -> Self {
+		float_floor!(*self, f64)
+	}
+}
 
+impl Semiring for f32 {
 	fn one() -> Self {
 		1.0
 	}
@@ -159,9 +536,11 @@ impl Ring for f32 {
 	fn is_zero(&self) -> bool {
 		*self == 0.0
 	}
+}
 
+impl Ring for f32 {
 	fn power(&self, n: i64) -> Self {
-		self.powf(n as f32)
+		float_power!(*self, n as f32, f32)
 	}
 }
 
@@ -177,10 +556,13 @@ impl Field for f32 {
 	}
 }
 
-impl PoField for f32 { /* f64 already satisfies this! */ }
+impl PoField for f32 {
+	fn floor(&self) -> Self {
+		float_floor!(*self, f32)
+	}
+}
 
-impl Ring for i8 {
-	
+impl Semiring for i8 {
 	fn one() -> Self {
 		1
 	}
@@ -192,20 +574,21 @@ impl Ring for i8 {
 	fn is_zero(&self) -> bool {
 		*self == 0
 	}
+}
 
+impl Ring for i8 {
 	fn power(&self, n: i64) -> Self {
 		if n < 0 {
 			panic!("Cannot invert ring element")
 		}
 		self.pow(n as u32)
 	}
-	
 }
 
 impl PoRing for i8 { }
 impl OrderedRing for i8 { }
 
-impl Ring for i16 {
+impl Semiring for i16 {
 	fn one() -> Self {
 		1
 	}
@@ -214,22 +597,24 @@ impl Ring for i16 {
 		0
 	}
 
+	fn is_zero(&self) -> bool {
+		*self == 0
+	}
+}
+
+impl Ring for i16 {
 	fn power(&self, n: i64) -> Self {
 		if n < 0 {
 			panic!("Cannot invert ring element")
 		}
 		self.pow(n as u32)
 	}
-	
-	fn is_zero(&self) -> bool {
-		*self == 0
-	}
 }
 
 impl PoRing for i16 { }
 impl OrderedRing for i16 { }
 
-impl Ring for i32 {
+impl Semiring for i32 {
 	fn one() -> Self {
 		1
 	}
@@ -241,7 +626,9 @@ impl Ring for i32 {
 	fn is_zero(&self) -> bool {
 		*self == 0
 	}
+}
 
+impl Ring for i32 {
 	fn power(&self, n: i64) -> Self {
 		if n < 0 {
 			panic!("Cannot invert ring element")
@@ -253,7 +640,7 @@ impl Ring for i32 {
 impl PoRing for i32 { }
 impl OrderedRing for i32 { }
 
-impl Ring for i64 {
+impl Semiring for i64 {
 	fn one() -> Self {
 		1
 	}
@@ -265,7 +652,9 @@ impl Ring for i64 {
 	fn is_zero(&self) -> bool {
 		*self == 0
 	}
+}
 
+impl Ring for i64 {
 	fn power(&self, n: i64) -> Self {
 		if n < 0 {
 			panic!("Cannot invert ring element")
@@ -277,7 +666,7 @@ impl Ring for i64 {
 impl PoRing for i64 { }
 impl OrderedRing for i64 { }
 
-impl Ring for i128 {
+impl Semiring for i128 {
 	fn one() -> Self {
 		1
 	}
@@ -289,7 +678,9 @@ impl Ring for i128 {
 	fn is_zero(&self) -> bool {
 		*self == 0
 	}
+}
 
+impl Ring for i128 {
 	fn power(&self, n: i64) -> Self {
 		if n < 0 {
 			panic!("Cannot invert ring element")
@@ -301,7 +692,60 @@ impl Ring for i128 {
 impl PoRing for i128 { }
 impl OrderedRing for i128 { }
 
-/// The field of integers modulo a HUGE prime Q
+/// The unsigned machine integers are semirings, though not rings, since they have no negatives.
+/// For arithmetic modulo 2^k, wrap them in `Wrapping`.
+macro_rules! impl_unsigned_semiring {
+	($($t:ty),*) => {$(
+		impl Semiring for $t {
+			fn one() -> Self {
+				1
+			}
+
+			fn zero() -> Self {
+				0
+			}
+
+			fn is_zero(&self) -> bool {
+				*self == 0
+			}
+		}
+	)*};
+}
+
+impl_unsigned_semiring!(u8, u16, u32, u64, u128, usize);
+
+/// The k bit machine integers with wrapping arithmetic form the commutative ring Z/(2^k), whether
+/// their representatives are read as signed or unsigned
+macro_rules! impl_wrapping_ring {
+	($($t:ty),*) => {$(
+		impl Semiring for Wrapping<$t> {
+			fn one() -> Self {
+				Wrapping(1)
+			}
+
+			fn zero() -> Self {
+				Wrapping(0)
+			}
+
+			fn is_zero(&self) -> bool {
+				self.0 == 0
+			}
+		}
+
+		impl Ring for Wrapping<$t> {
+			fn power(&self, n: i64) -> Self {
+				if n < 0 {
+					panic!("Cannot invert ring element")
+				}
+				Wrapping(self.0.wrapping_pow(n as u32))
+			}
+		}
+
+		impl CommutativeRing for Wrapping<$t> { }
+	)*};
+}
+
+impl_wrapping_ring!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
 /// The field of the integers modulo a prime Q
 #[derive(Clone, Copy, Default)]
@@ -309,36 +753,160 @@ pub struct ZM<const Q: i64> {
 	pub val: i64
 }
 
-impl<const Q: i64> ZM<Q> {
-	pub fn rnd() -> ZM<Q> {
-		ZM::<Q> { val: StdRng::from_entropy().gen::<i64>().rem_euclid(Q) }
+impl<const Q: i64> RandomElement for ZM<Q> {
+	fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+		ZM::<Q> { val: rng.gen_range(0..Q) }
+	}
+}
+
+impl<const Q: i64> Distribution<ZM<Q>> for Standard {
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ZM<Q> {
+		ZM::random(rng)
+	}
+}
+
+/// Samples elements of ZM<Q> uniformly from a range of representatives, for `rand::distributions::Uniform`
+#[derive(Clone, Copy, Debug)]
+pub struct UniformZM<const Q: i64>(UniformInt<i64>);
+
+impl<const Q: i64> UniformSampler for UniformZM<Q> {
+	type X = ZM<Q>;
+
+	fn new<B1: SampleBorrow<ZM<Q>> + Sized, B2: SampleBorrow<ZM<Q>> + Sized>(low: B1, high: B2) -> Self {
+		UniformZM(UniformInt::new(low.borrow().val, high.borrow().val))
+	}
+
+	fn new_inclusive<B1: SampleBorrow<ZM<Q>> + Sized, B2: SampleBorrow<ZM<Q>> + Sized>(low: B1, high: B2) -> Self {
+		UniformZM(UniformInt::new_inclusive(low.borrow().val, high.borrow().val))
+	}
+
+	fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ZM<Q> {
+		ZM::<Q> { val: self.0.sample(rng) }
+	}
+}
+
+impl<const Q: i64> SampleUniform for ZM<Q> {
+	type Sampler = UniformZM<Q>;
+}
+
+/// Each element is numbered by its representative in [0, Q)
+impl<const Q: i64> FiniteSet for ZM<Q> {
+	fn size() -> u128 {
+		Q as u128
+	}
+
+	fn to_index(&self) -> u128 {
+		self.val as u128
+	}
+
+	fn from_index(index: u128) -> Self {
+		check_index::<Self>(index);
+		ZM { val: index as i64 }
+	}
+}
+
+impl<const Q: i64> FiniteRing for ZM<Q> {
+	fn characteristic() -> u128 {
+		Q as u128
+	}
+}
+
+/// A field when Q is prime, which `degree` checks at compile time
+impl<const Q: i64> FiniteField for ZM<Q> {
+	fn degree() -> u32 {
+		let () = Self::ASSERT_PRIME;
+		1
+	}
+
+	/// Every element of a prime field is fixed
+	fn frobenius(&self) -> Self {
+		*self
 	}
 }
 
 impl<const Q: i64> Debug for ZM<Q> {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		self.val.fmt(f)
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		Debug::fmt(&self.val, f)
+	}
+}
+
+/// The representative in 0..Q with the modulus, like "3 mod 17"
+impl<const Q: i64> Display for ZM<Q> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{} mod {}", self.val.rem_euclid(Q), Q)
 	}
 }
 
 impl<const Q: i64> PartialEq for ZM<Q> {
 	fn eq(&self, other: &Self) -> bool {
+		self.check_reduced();
+		other.check_reduced();
 		self.val == other.val
 	}
+}
+
+impl<const Q: i64> Eq for ZM<Q> { }
+
+impl<const Q: i64> Hash for ZM<Q> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.val.hash(state)
+	}
+}
+
+/// Ordered by representative in [0, Q), which is a total order for sorting and searching but is
+/// not compatible with the arithmetic
+impl<const Q: i64> PartialOrd for ZM<Q> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
 
-	fn ne(&self, other: &Self) -> bool {
-		self.val != other.val
+impl<const Q: i64> Ord for ZM<Q> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.check_reduced();
+		other.check_reduced();
+		self.val.cmp(&other.val)
 	}
 }
 
 impl<const Q: i64> ZM<Q> {
+
+	/// Evaluating this fails to compile when Q is not prime, so `const _: () = ZM::<Q>::ASSERT_PRIME;`
+	/// checks a modulus before its inverses are relied on, and the methods which need a prime
+	/// modulus check it themselves
+	pub const ASSERT_PRIME: () = assert!(is_prime(Q), "The modulus of ZM<Q> must be prime for it to be a field");
+
+	/// The residue of val modulo Q, usable in constants like `const G: ZM<17> = ZM::new(3);`
+	pub const fn new(val: i64) -> ZM<Q> {
+		ZM { val: val.rem_euclid(Q) }
+	}
+
+	/// The residue of val modulo Q, which fails to compile unless Q is prime
+	pub const fn new_prime(val: i64) -> ZM<Q> {
+		let () = Self::ASSERT_PRIME;
+		ZM::new(val)
+	}
+
 	pub fn convert<const P: i64>(other: ZM<P>) -> ZM<Q> {
 		other.val.into()
 	}
 
+	/// The reduction of other modulo Q, when Q divides P so that reducing is a ring homomorphism,
+	/// and `None` otherwise
+	pub fn try_convert<const P: i64>(other: ZM<P>) -> Option<ZM<Q>> {
+		if P % Q == 0 { Some(ZM::convert(other)) } else { None }
+	}
+
 	pub fn from_int(x: i64) -> ZM<Q> {
 		x.into()
 	}
+
+	/// With the `debug-checks` feature, asserts that the representative is in [0, Q), which every
+	/// operation assumes but a value built directly from `val` might not satisfy
+	#[inline(always)]
+	fn check_reduced(&self) {
+		debug_check!((0..Q).contains(&self.val), "{} is not a representative in [0, {}) of ZM<{}>", self.val, Q, Q);
+	}
 }
 
 impl<const Q: i64> From<i64> for ZM<Q> {
@@ -363,7 +931,12 @@ impl<const Q: i64> Add<ZM<Q>> for ZM<Q> {
 	type Output = ZM<Q>;
 
 	fn add(self, rhs: ZM<Q>) -> Self::Output {
-		ZM::<Q> { val: (self.val + rhs.val) % Q }
+		self.check_reduced();
+		rhs.check_reduced();
+
+		// Comparing against Q - rhs first keeps the sum below Q, so it cannot overflow
+		let val = if self.val >= Q - rhs.val { self.val - (Q - rhs.val) } else { self.val + rhs.val };
+		ZM::<Q> { val }
 	}
 }
 
@@ -377,7 +950,11 @@ impl<const Q: i64> Sub<ZM<Q>> for ZM<Q> {
 	type Output = ZM<Q>;
 
 	fn sub(self, rhs: ZM<Q>) -> Self::Output {
-		ZM::<Q> { val: (self.val - rhs.val + Q) % Q }
+		self.check_reduced();
+		rhs.check_reduced();
+
+		let val = if self.val >= rhs.val { self.val - rhs.val } else { self.val + (Q - rhs.val) };
+		ZM::<Q> { val }
 	}
 }
 
@@ -390,9 +967,10 @@ impl<const Q: i64> SubAssign<ZM<Q>> for ZM<Q> {
 impl<const Q: i64> Mul<ZM<Q>> for ZM<Q> {
 	type Output = ZM<Q>;
 
+	/// The product is taken in i128, so it cannot overflow for any modulus
 	fn mul(self, rhs: ZM<Q>) -> ZM<Q> {
-		let product = self.val.rem_euclid(Q) * rhs.val.rem_euclid(Q);
-		ZM::<Q> { val: product % Q }
+		let product = self.val.rem_euclid(Q) as i128 * rhs.val.rem_euclid(Q) as i128;
+		ZM::<Q> { val: (product % Q as i128) as i64 }
 	}
 }
 
@@ -406,11 +984,12 @@ impl<const Q: i64> Neg for ZM<Q> {
 	type Output = Self;
 
 	fn neg(self) -> Self::Output {
+		self.check_reduced();
 		(Q - self.val).into()
 	}
 }
 
-impl<const Q: i64> Ring for ZM<Q> {
+impl<const Q: i64> Semiring for ZM<Q> {
 	fn one() -> Self {
 		ZM::<Q> { val: 1 }
 	}
@@ -422,40 +1001,103 @@ impl<const Q: i64> Ring for ZM<Q> {
 	fn is_zero(&self) -> bool {
 		self.val == 0
 	}
+}
 
+impl<const Q: i64> Ring for ZM<Q> {
 	fn power(&self, n: i64) -> Self {
-		// TODO: Make this WAYY more efficient... Double and add, yeah?
-		let mut power = ZM::<Q>::one();
+		if n < 0 {
+			return self.inverse().power(-n)
+		}
 
-		for _ in 1..=n {
-			power *= *self
+		// Square and multiply
+		let mut power = ZM::<Q>::one();
+		let mut base = *self;
+		let mut e = n;
+
+		while e > 0 {
+			if e & 1 == 1 {
+				power *= base
+			}
+			base *= base;
+			e >>= 1;
 		}
 
 		power
 	}
 }
 
-impl EuclideanDomain for i64 {
-	type SizeType = usize;
+/// The machine integers are Euclidean domains, sized by absolute value, with GCDs found
+/// by binary (Stein) GCD
+macro_rules! impl_integer_domain {
+	($($t:ty => $u:ty),*) => {$(
+		impl CommutativeRing for $t { }
+		impl IntegralDomain for $t {
+			fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+				euclidean_divide_exact(self, divisor)
+			}
+		}
 
-	fn euc_size(&self) -> usize {
-		self.abs().try_into().unwrap()
-	}
+		impl PrincipalIdealDomain for $t { }
 
-	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
-		(self / divisor, self % divisor)
-	}
-}
+		impl EuclideanDomain for $t {
+			type SizeType = $u;
+
+			fn euc_size(&self) -> $u {
+				self.unsigned_abs()
+			}
 
-pub fn mod_inv<R: EuclideanDomain>(x: R, m: R) -> R {
-	match ext_gcd(x, m) { (_, i, _) => i }
+			/// Truncating division, so the remainder has the sign of self
+			fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+				(self / divisor, self % divisor)
+			}
+		}
+
+		impl UniqueFactorizationDomain for $t {
+			/// The nonnegative gcd, which panics when it is 2^(k - 1) for a k bit type, as for
+			/// gcd(MIN, 0) or gcd(MIN, MIN), since that does not fit
+			fn gcd(&self, other: &Self) -> Self {
+				let mut u: $u = self.unsigned_abs();
+				let mut v: $u = other.unsigned_abs();
+
+				let g = if u == 0 {
+					v
+				} else if v == 0 {
+					u
+				} else {
+					let shift = (u | v).trailing_zeros();
+					u >>= u.trailing_zeros();
+
+					loop {
+						v >>= v.trailing_zeros();
+						if u > v {
+							core::mem::swap(&mut u, &mut v);
+						}
+						v -= u;
+						if v == 0 {
+							break
+						}
+					}
+
+					u << shift
+				};
+
+				match <$t>::try_from(g) {
+					Ok(g) => g,
+					Err(_) => panic!("gcd({}, {}) = {} does not fit in {}", self, other, g, stringify!($t))
+				}
+			}
+		}
+	)*};
 }
 
+impl_integer_domain!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+#[allow(clippy::suspicious_arithmetic_impl)]
 impl<const Q: i64> Div<ZM<Q>> for ZM<Q> {
 	type Output = ZM<Q>;
 
 	fn div(self, rhs: ZM<Q>) -> Self::Output {
-		self * mod_inv(rhs.val, Q).into()
+		self * rhs.inverse()
 	}
 }
 
@@ -465,8 +1107,186 @@ impl<const Q: i64> DivAssign<ZM<Q>> for ZM<Q> {
 	}
 }
 
+/// Inverses come from the extended Euclidean algorithm, which is correct for any modulus, so a
+/// composite Q panics on a non-unit rather than giving a wrong inverse. The constant time `CtZM`
+/// of the `ct` feature inverts by Fermat's little theorem instead, which checks that its modulus
+/// is prime at compile time.
 impl<const Q: i64> Field for ZM<Q> {
 	fn inverse(&self) -> Self {
-		mod_inv(self.val, Q).into()
+		match self.try_inverse() {
+			Some(inverse) => inverse,
+			None => panic!("{} is not invertible modulo {}", self.val, Q)
+		}
+	}
+
+	/// The inverse modulo Q, which exists exactly when gcd(self, Q) = 1, even if Q is composite
+	fn try_inverse(&self) -> Option<Self> {
+		mod_inverse(&self.val, &Q).map(ZM::from)
+	}
+}
+
+impl<const Q: i64> ZM<Q> {
+
+	/// Whether or not this element has a multiplicative inverse modulo Q
+	pub fn is_unit(&self) -> bool {
+		gcd(&self.val, &Q) == 1
+	}
+
+	/// Every x with x^n = 1 modulo Q, for n positive, in increasing order of representative.
+	///
+	/// When the units modulo Q are cyclic of order m, as for Q prime, these are the gcd(n, m) powers
+	/// of g^(m / gcd(n, m)) for a primitive root g. Otherwise every unit is checked.
+	pub fn roots_of_unity(n: i64) -> Vec<ZM<Q>> {
+		if n <= 0 {
+			panic!("Roots of unity have positive order, got {}", n)
+		}
+
+		// Every unit has x^m = 1, so x^n = 1 exactly when x^gcd(n, m) = 1
+		let m = UnitsZM::<Q>::order() as i64;
+		let d = gcd(&n, &m);
+
+		let mut roots: Vec<ZM<Q>> = match UnitsZM::<Q>::find_primitive_root() {
+			Some(g) => g.power(m / d).cyclic_subgroup().into_iter().map(UnitsZM::into_inner).collect(),
+			None => UnitsZM::<Q>::elements().map(UnitsZM::into_inner).filter(|x| x.power(d) == ZM::one()).collect()
+		};
+		roots.sort_by_key(|x| x.val);
+		roots
+	}
+
+	/// Every x of order exactly n modulo Q, for n positive, in increasing order of representative.
+	///
+	/// For Q a prime not dividing n these are the roots of the n-th cyclotomic polynomial, and there
+	/// are totient(n) of them when n divides Q - 1 and none otherwise.
+	pub fn primitive_roots_of_unity(n: i64) -> Vec<ZM<Q>> {
+		let roots = Self::roots_of_unity(n);
+		let primes: Vec<i64> = factor(n).into_iter().map(|(p, _)| p).collect();
+		roots.into_iter().filter(|x| primes.iter().all(|p| x.power(n / p) != ZM::one())).collect()
+	}
+
+	/// Whether or not this element generates the multiplicative group modulo the prime Q, meaning
+	/// x^((Q - 1) / p) is not 1 for any prime p dividing Q - 1
+	pub fn is_primitive_root(&self) -> bool {
+		let () = Self::ASSERT_PRIME;
+		!self.is_zero() && factor(Q - 1).into_iter().all(|(p, _)| self.power((Q - 1) / p) != ZM::one())
+	}
+
+	/// The smallest primitive root modulo the prime Q, a generator of its multiplicative group.
+	///
+	/// Candidates are checked against the prime factors of Q - 1, which is factored once. A
+	/// primitive root is always small in practice, so only a few candidates are tried.
+	pub fn primitive_root() -> ZM<Q> {
+		let () = Self::ASSERT_PRIME;
+		if Q == 2 {
+			return ZM::one()
+		}
+
+		let primes: Vec<i64> = factor(Q - 1).into_iter().map(|(p, _)| p).collect();
+		(2..Q).map(ZM::from_int)
+			.find(|g: &ZM<Q>| primes.iter().all(|p| g.power((Q - 1) / p) != ZM::one()))
+			.unwrap()
+	}
+
+}
+
+// MARK: Quadratic Residues
+
+/// The Jacobi symbol (a/n) for an odd positive integer n
+pub fn jacobi(a: i64, n: i64) -> i64 {
+	if n <= 0 || n % 2 == 0 {
+		panic!("The Jacobi symbol is only defined for odd positive n")
+	}
+
+	let mut a = a.rem_euclid(n);
+	let mut n = n;
+	let mut result = 1;
+
+	while a != 0 {
+		while a % 2 == 0 {
+			a /= 2;
+			if n % 8 == 3 || n % 8 == 5 {
+				result = -result
+			}
+		}
+
+		core::mem::swap(&mut a, &mut n);
+
+		if a % 4 == 3 && n % 4 == 3 {
+			result = -result
+		}
+
+		a %= n;
+	}
+
+	if n == 1 { result } else { 0 }
+}
+
+impl<const Q: i64> ZM<Q> {
+
+	/// The Legendre symbol of this element, for Q prime, which is just the value when Q = 2. A
+	/// composite Q fails to compile.
+	/// 
+	/// Returns 1 if this is a nonzero square, -1 if it is not a square, and 0 if it is zero.
+	pub fn legendre(&self) -> i64 {
+		let () = Self::ASSERT_PRIME;
+
+		// Modulo 2 every element is its own square
+		if Q == 2 {
+			return self.val
+		}
+
+		jacobi(self.val, Q)
+	}
+
+	/// Finds both square roots of this element using Tonelli-Shanks, for Q prime, where modulo 2
+	/// every element is its own square root. A composite Q fails to compile.
+	/// 
+	/// Returns `None` if this element is not a square.
+	pub fn sqrt(&self) -> Option<(Self, Self)> {
+		let () = Self::ASSERT_PRIME;
+		if Q == 2 || self.is_zero() {
+			return Some((*self, *self))
+		}
+
+		if self.legendre() != 1 {
+			return None
+		}
+
+		// Write Q - 1 = q * 2^s with q odd
+		let mut q = Q - 1;
+		let mut s = 0;
+		while q % 2 == 0 {
+			q /= 2;
+			s += 1;
+		}
+
+		// Find any non-residue z
+		let mut z = ZM::<Q>::from_int(2);
+		while z.legendre() != -1 {
+			z += ZM::one()
+		}
+
+		let mut m = s;
+		let mut c = z.power(q);
+		let mut t = self.power(q);
+		let mut r = self.power((q + 1) / 2);
+
+		while t != ZM::one() {
+			// Find the least i with t^(2^i) = 1
+			let mut i = 0;
+			let mut t2i = t;
+			while t2i != ZM::one() {
+				t2i *= t2i;
+				i += 1;
+			}
+
+			let b = c.power(1 << (m - i - 1));
+			m = i;
+			c = b * b;
+			t *= c;
+			r *= b;
+		}
+
+		Some((r, -r))
 	}
+
 }
\ No newline at end of file
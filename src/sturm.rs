@@ -0,0 +1,147 @@
+//
+// Sturm sequences over an ordered field, for counting and isolating the real roots of a
+// polynomial exactly
+//
+
+use crate::algebra::*;
+use crate::polynomial::Polynomial;
+
+/// One, minus one, or zero, as x is positive, negative, or zero
+fn sign<F: OrderedField>(x: &F) -> i8 {
+	if x.is_positive() { 1 } else if x.is_negative() { -1 } else { 0 }
+}
+
+/// The number of sign changes in a sequence of signs, skipping zeros
+fn variations(signs: impl Iterator<Item = i8>) -> usize {
+	let mut previous = 0;
+	let mut count = 0;
+	for s in signs.filter(|&s| s != 0) {
+		if s != previous && previous != 0 {
+			count += 1;
+		}
+		previous = s;
+	}
+	count
+}
+
+/// The midpoint of a and b
+fn midpoint<F: OrderedField>(a: &F, b: &F) -> F {
+	a.add_ref(b).div_ref(&(F::one() + F::one()))
+}
+
+impl<F: OrderedField> Polynomial<F> {
+
+	/// The Sturm sequence of this nonzero polynomial: p_0 = f, p_1 = f', and each following
+	/// p_(i+1) = -(p_(i-1) mod p_i), up to the last nonzero one, which is a gcd of f and f'
+	pub fn sturm_sequence(&self) -> Vec<Polynomial<F>> {
+		if self.is_zero() {
+			panic!("The zero polynomial has no Sturm sequence")
+		}
+
+		let mut sequence = vec![self.clone()];
+		let mut next = self.derivative();
+		while !next.is_zero() {
+			let remainder = sequence.last().unwrap().clone() % next.clone();
+			sequence.push(next);
+			next = -remainder;
+		}
+		sequence
+	}
+
+	/// The number of distinct real roots in (a, b], for a < b, by Sturm's theorem: the number of
+	/// sign changes in the Sturm sequence at a minus the number at b
+	pub fn count_real_roots_in(&self, a: &F, b: &F) -> usize {
+		if a >= b {
+			return 0
+		}
+		let sequence = self.sturm_chain();
+		Self::variations_at(&sequence, a) - Self::variations_at(&sequence, b)
+	}
+
+	/// The number of distinct real roots of this nonzero polynomial, from the signs of the Sturm
+	/// sequence at minus and plus infinity, which are those of the leading terms
+	pub fn count_real_roots(&self) -> usize {
+		let sequence = self.sturm_chain();
+		let at_minus_infinity = variations(sequence.iter().map(|p| {
+			let s = sign(&p.leading_coefficient());
+			if p.degree() % 2 == 0 { s } else { -s }
+		}));
+		let at_plus_infinity = variations(sequence.iter().map(|p| sign(&p.leading_coefficient())));
+		at_minus_infinity - at_plus_infinity
+	}
+
+	/// Cauchy's bound 1 + max |c_i / c_n|, which is larger than the absolute value of every root
+	pub fn root_bound(&self) -> F {
+		let lead = self.leading_coefficient();
+		self.coefficients()[..self.degree()].iter()
+			.map(|c| c.div_ref(&lead).abs())
+			.fold(F::zero(), |max, c| if c > max { c } else { max })
+			+ F::one()
+	}
+
+	/// Disjoint intervals (a, b], in increasing order, each containing exactly one of the
+	/// distinct real roots of this nonzero polynomial, found by bisecting (-M, M] for the root
+	/// bound M until each piece holds at most one root according to the Sturm sequence
+	pub fn isolate_real_roots(&self) -> Vec<(F, F)> {
+		if self.degree() == 0 {
+			return Vec::new()
+		}
+
+		let sequence = self.sturm_chain();
+		let bound = self.root_bound();
+		let mut pending = vec![(-bound.clone(), bound)];
+		let mut intervals = Vec::new();
+
+		while let Some((a, b)) = pending.pop() {
+			let count = Self::variations_at(&sequence, &a) - Self::variations_at(&sequence, &b);
+			match count {
+				0 => { },
+				1 => intervals.push((a, b)),
+				_ => {
+					let m = midpoint(&a, &b);
+					pending.push((m.clone(), b));
+					pending.push((a, m));
+				}
+			}
+		}
+
+		intervals.sort_by(|(a, _), (b, _)| a.cmp(b));
+		intervals
+	}
+
+	/// Shrinks an interval (a, b] holding exactly one real root to one of width at most the
+	/// given width which still holds it, by bisection
+	pub fn refine_root(&self, interval: &(F, F), width: &F) -> (F, F) {
+		let sequence = self.sturm_chain();
+		let (mut a, mut b) = interval.clone();
+
+		while b.sub_ref(&a) > *width {
+			let m = midpoint(&a, &b);
+			if Self::variations_at(&sequence, &a) > Self::variations_at(&sequence, &m) {
+				b = m;
+			} else {
+				a = m;
+			}
+		}
+
+		(a, b)
+	}
+
+	/// The Sturm sequence with every term divided by the last, gcd(f, f'). This changes no sign
+	/// variations away from the roots of the gcd, the repeated roots of f, but at those every
+	/// term of the Sturm sequence itself vanishes, while these terms do not.
+	fn sturm_chain(&self) -> Vec<Polynomial<F>> {
+		let sequence = self.sturm_sequence();
+		let gcd = sequence.last().unwrap().clone();
+		if gcd.degree() == 0 {
+			return sequence
+		}
+		sequence.into_iter().map(|p| p / gcd.clone()).collect()
+	}
+
+	/// The number of sign changes in a Sturm sequence at x
+	fn variations_at(sequence: &[Polynomial<F>], x: &F) -> usize {
+		variations(sequence.iter().map(|p| sign(&p.evaluate(x))))
+	}
+
+}
@@ -0,0 +1,400 @@
+//
+// Subgroups of finite groups, their cosets, and quotient groups
+//
+
+use std::fmt::Debug;
+use std::ops::{Div, DivAssign, Mul, MulAssign};
+use std::rc::Rc;
+
+use crate::algebra::*;
+
+// MARK: Subgroups
+
+/// A subgroup of a finite group, stored as its full list of elements
+#[derive(Clone, Debug)]
+pub struct Subgroup<G: Group> {
+	generators: Vec<G>,
+	elements: Vec<G>
+}
+
+/// The subgroup generated by the given elements, which must all have finite order
+pub fn generate_subgroup<G: Group>(generators: &[G]) -> Subgroup<G> {
+	let mut elements = vec![G::identity()];
+	let mut frontier = vec![G::identity()];
+
+	// In a finite group, closing up under multiplication by generators gives the whole subgroup
+	while let Some(g) = frontier.pop() {
+		for s in generators {
+			let product = g.clone() * s.clone();
+			if !elements.contains(&product) {
+				elements.push(product.clone());
+				frontier.push(product);
+			}
+		}
+	}
+
+	Subgroup { generators: generators.to_vec(), elements }
+}
+
+impl<G: Group> Subgroup<G> {
+
+	/// The subgroup with the given elements, which must already be closed under multiplication
+	/// and start with the identity, generated by all of them
+	pub(crate) fn from_elements(elements: Vec<G>) -> Subgroup<G> {
+		Subgroup { generators: elements.clone(), elements }
+	}
+
+	/// The trivial subgroup
+	pub fn trivial() -> Subgroup<G> {
+		generate_subgroup(&[])
+	}
+
+	/// The generators this subgroup was built from
+	pub fn generators(&self) -> &[G] {
+		&self.generators
+	}
+
+	/// Every element of this subgroup, starting with the identity
+	pub fn elements(&self) -> &[G] {
+		&self.elements
+	}
+
+	/// The number of elements in this subgroup
+	pub fn order(&self) -> usize {
+		self.elements.len()
+	}
+
+	/// Whether or not g is in this subgroup
+	pub fn contains(&self, g: &G) -> bool {
+		self.elements.contains(g)
+	}
+
+	/// Whether or not every element of this subgroup is in `other`
+	pub fn is_subgroup_of(&self, other: &Subgroup<G>) -> bool {
+		self.generators.iter().all(|g| other.contains(g))
+	}
+
+	/// The left coset gH
+	pub fn left_coset(&self, g: &G) -> Vec<G> {
+		self.elements.iter().map(|h| g.clone() * h.clone()).collect()
+	}
+
+	/// The right coset Hg
+	pub fn right_coset(&self, g: &G) -> Vec<G> {
+		self.elements.iter().map(|h| h.clone() * g.clone()).collect()
+	}
+
+	/// Whether or not a and b lie in the same left coset, meaning a^(-1) b is in this subgroup
+	pub fn same_left_coset(&self, a: &G, b: &G) -> bool {
+		self.contains(&(a.inverse() * b.clone()))
+	}
+
+	/// Whether or not this subgroup is normalized by g, meaning g H g^(-1) = H
+	pub fn is_normalized_by(&self, g: &G) -> bool {
+		let g_inverse = g.inverse();
+		self.generators.iter().all(|h| self.contains(&(g.clone() * h.clone() * g_inverse.clone())))
+	}
+
+	/// The conjugate subgroup g H g^(-1)
+	pub fn conjugate(&self, g: &G) -> Subgroup<G> {
+		let g_inverse = g.inverse();
+		let conjugate = |h: &G| g.clone() * h.clone() * g_inverse.clone();
+
+		Subgroup {
+			generators: self.generators.iter().map(conjugate).collect(),
+			elements: self.elements.iter().map(conjugate).collect()
+		}
+	}
+
+	/// The subgroup generated by this subgroup and another
+	pub fn join(&self, other: &Subgroup<G>) -> Subgroup<G> {
+		let mut generators = self.generators.clone();
+		generators.extend(other.generators.iter().filter(|g| !self.contains(g)).cloned());
+		generate_subgroup(&generators)
+	}
+
+}
+
+impl<G: FiniteGroup> Subgroup<G> {
+
+	/// The whole group as a subgroup of itself
+	pub fn whole_group() -> Subgroup<G> {
+		let elements: Vec<G> = G::elements().collect();
+		Subgroup { generators: elements.clone(), elements }
+	}
+
+	/// The number of cosets of this subgroup in the whole group
+	pub fn index(&self) -> usize {
+		G::order() / self.order()
+	}
+
+	/// The distinct left cosets gH, each listed once
+	pub fn left_cosets(&self) -> Vec<Vec<G>> {
+		self.coset_representatives().iter().map(|g| self.left_coset(g)).collect()
+	}
+
+	/// The distinct right cosets Hg, each listed once
+	pub fn right_cosets(&self) -> Vec<Vec<G>> {
+		let mut cosets: Vec<Vec<G>> = Vec::new();
+		for g in G::elements() {
+			if !cosets.iter().any(|c| c.contains(&g)) {
+				cosets.push(self.right_coset(&g));
+			}
+		}
+		cosets
+	}
+
+	/// One element from each left coset, in the order the whole group lists its elements
+	pub fn coset_representatives(&self) -> Vec<G> {
+		let mut representatives: Vec<G> = Vec::new();
+		for g in G::elements() {
+			if !representatives.iter().any(|r| self.same_left_coset(r, &g)) {
+				representatives.push(g);
+			}
+		}
+		representatives
+	}
+
+	/// Whether or not g H g^(-1) = K for some g in the whole group
+	pub fn is_conjugate_to(&self, other: &Subgroup<G>) -> bool {
+		self.order() == other.order() && G::elements().any(|g| self.conjugate(&g) == *other)
+	}
+
+	/// Whether or not this is a normal subgroup of the whole group
+	pub fn is_normal(&self) -> bool {
+		G::elements().all(|g| self.is_normalized_by(&g))
+	}
+
+	/// The quotient group G/H, if this subgroup H is normal
+	pub fn quotient(&self) -> Option<QuotientGroup<G>> {
+		if !self.is_normal() {
+			return None
+		}
+
+		let subgroup = Rc::new(self.clone());
+		let cosets = self.coset_representatives().into_iter().map(|representative| {
+			Coset { subgroup: Some(subgroup.clone()), representative }
+		}).collect();
+
+		Some(QuotientGroup { subgroup, cosets })
+	}
+
+}
+
+impl<G: Group> PartialEq for Subgroup<G> {
+	fn eq(&self, other: &Self) -> bool {
+		self.order() == other.order() && self.is_subgroup_of(other)
+	}
+}
+
+// MARK: Subgroup Lattices
+
+/// Every subgroup of a finite group, ordered by size, starting with the trivial subgroup
+#[derive(Clone, Debug)]
+pub struct SubgroupLattice<G: Group> {
+	subgroups: Vec<Subgroup<G>>
+}
+
+/// The lattice of all subgroups of the finite group G.
+///
+/// Every subgroup is a join of cyclic subgroups, so this starts from the cyclic subgroups and
+/// takes joins until nothing new appears. It is meant for groups with at most a few hundred elements.
+pub fn subgroup_lattice<G: FiniteGroup>() -> SubgroupLattice<G> {
+	let mut cyclic: Vec<Subgroup<G>> = Vec::new();
+	for g in G::elements() {
+		let subgroup = generate_subgroup(&[g]);
+		if !cyclic.contains(&subgroup) {
+			cyclic.push(subgroup);
+		}
+	}
+
+	let mut subgroups = cyclic.clone();
+	let mut frontier = cyclic.clone();
+
+	while !frontier.is_empty() {
+		let mut next = Vec::new();
+
+		for h in frontier.iter() {
+			for c in cyclic.iter().filter(|c| !c.is_subgroup_of(h)) {
+				let joined = h.join(c);
+				if !subgroups.contains(&joined) {
+					subgroups.push(joined.clone());
+					next.push(joined);
+				}
+			}
+		}
+
+		frontier = next;
+	}
+
+	subgroups.sort_by_key(|h| h.order());
+	SubgroupLattice { subgroups }
+}
+
+impl<G: FiniteGroup> SubgroupLattice<G> {
+
+	/// Every subgroup, ordered by size
+	pub fn subgroups(&self) -> &[Subgroup<G>] {
+		&self.subgroups
+	}
+
+	/// The position of the given subgroup in this lattice
+	pub fn index_of(&self, subgroup: &Subgroup<G>) -> Option<usize> {
+		self.subgroups.iter().position(|h| h == subgroup)
+	}
+
+	/// Whether or not subgroup i is contained in subgroup j
+	pub fn is_contained_in(&self, i: usize, j: usize) -> bool {
+		self.subgroups[i].is_subgroup_of(&self.subgroups[j])
+	}
+
+	/// The maximal proper subgroups of subgroup j, meaning those with nothing in between
+	pub fn maximal_subgroups_of(&self, j: usize) -> Vec<usize> {
+		let proper: Vec<usize> = (0..self.subgroups.len()).filter(|&i| i != j && self.is_contained_in(i, j)).collect();
+
+		proper.iter().copied().filter(|&i| {
+			!proper.iter().any(|&k| k != i && self.is_contained_in(i, k))
+		}).collect()
+	}
+
+	/// The subgroups grouped into conjugacy classes
+	pub fn conjugacy_classes(&self) -> Vec<Vec<usize>> {
+		let mut classes: Vec<Vec<usize>> = Vec::new();
+
+		for i in 0..self.subgroups.len() {
+			match classes.iter_mut().find(|class| self.subgroups[class[0]].is_conjugate_to(&self.subgroups[i])) {
+				Some(class) => class.push(i),
+				None => classes.push(vec![i])
+			}
+		}
+
+		classes
+	}
+
+	/// The normal subgroups, which are exactly those alone in their conjugacy class
+	pub fn normal_subgroups(&self) -> Vec<usize> {
+		(0..self.subgroups.len()).filter(|&i| self.subgroups[i].is_normal()).collect()
+	}
+
+}
+
+// MARK: Quotient Groups
+
+/// The quotient of a finite group by a normal subgroup
+#[derive(Clone, Debug)]
+pub struct QuotientGroup<G: Group> {
+	subgroup: Rc<Subgroup<G>>,
+	cosets: Vec<Coset<G>>
+}
+
+impl<G: Group> QuotientGroup<G> {
+
+	/// The normal subgroup being quotiented by
+	pub fn subgroup(&self) -> &Subgroup<G> {
+		&self.subgroup
+	}
+
+	/// Every element of the quotient group, starting with the identity coset
+	pub fn elements(&self) -> &[Coset<G>] {
+		&self.cosets
+	}
+
+	/// The number of elements of the quotient group
+	pub fn order(&self) -> usize {
+		self.cosets.len()
+	}
+
+	/// The coset containing g
+	pub fn coset_of(&self, g: &G) -> Coset<G> {
+		Coset { subgroup: Some(self.subgroup.clone()), representative: g.clone() }
+	}
+
+}
+
+/// A coset gN of a normal subgroup N, as an element of the quotient group.
+///
+/// Since `Group::identity` can't know the subgroup, the identity coset may be stored without
+/// one, and takes on the subgroup of whatever it is combined with.
+#[derive(Clone)]
+pub struct Coset<G: Group> {
+	subgroup: Option<Rc<Subgroup<G>>>,
+	representative: G
+}
+
+impl<G: Group> Coset<G> {
+
+	/// Some element of this coset
+	pub fn representative(&self) -> &G {
+		&self.representative
+	}
+
+	/// Every element of this coset
+	pub fn elements(&self) -> Vec<G> {
+		match &self.subgroup {
+			Some(subgroup) => subgroup.left_coset(&self.representative),
+			None => vec![self.representative.clone()]
+		}
+	}
+
+}
+
+impl<G: Group> Debug for Coset<G> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}N", self.representative)
+	}
+}
+
+impl<G: Group> PartialEq for Coset<G> {
+	fn eq(&self, other: &Self) -> bool {
+		match self.subgroup.as_ref().or(other.subgroup.as_ref()) {
+			Some(subgroup) => subgroup.same_left_coset(&self.representative, &other.representative),
+			None => self.representative == other.representative
+		}
+	}
+}
+
+impl<G: Group> Mul for Coset<G> {
+	type Output = Self;
+
+	fn mul(self, rhs: Self) -> Self::Output {
+		Coset {
+			subgroup: self.subgroup.or(rhs.subgroup),
+			representative: self.representative * rhs.representative
+		}
+	}
+}
+
+impl<G: Group> MulAssign for Coset<G> {
+	fn mul_assign(&mut self, rhs: Self) {
+		*self = self.clone() * rhs
+	}
+}
+
+#[allow(clippy::suspicious_arithmetic_impl)]
+impl<G: Group> Div for Coset<G> {
+	type Output = Self;
+
+	fn div(self, rhs: Self) -> Self::Output {
+		self * rhs.inverse()
+	}
+}
+
+impl<G: Group> DivAssign for Coset<G> {
+	fn div_assign(&mut self, rhs: Self) {
+		*self = self.clone() / rhs
+	}
+}
+
+impl<G: Group> Monoid for Coset<G> {
+	fn identity() -> Self {
+		Coset { subgroup: None, representative: G::identity() }
+	}
+}
+
+impl<G: Group> Group for Coset<G> {
+	fn inverse(&self) -> Self {
+		Coset { subgroup: self.subgroup.clone(), representative: self.representative.inverse() }
+	}
+}
+
+impl<G: AbelianGroup> AbelianGroup for Coset<G> { }
@@ -0,0 +1,146 @@
+//
+// Symmetric polynomials, and rewriting symmetric systems in terms of the elementary symmetric
+// polynomials before solving them
+//
+
+use crate::algebra::*;
+use crate::multivariate::{Lex, Monomial, MultiPoly};
+use crate::polynomial::Polynomial;
+use crate::polynomial_system::solve_zm;
+use crate::std_impls::ZM;
+
+// MARK: Elementary Symmetric Polynomials
+
+/// The elementary symmetric polynomial e_k, the sum of all products of k distinct variables.
+/// e_0 is 1, and e_k is 0 for k > N.
+pub fn elementary_symmetric<R: Ring, const N: usize>(k: usize) -> MultiPoly<R, N> {
+	let terms = (0..1usize << N).filter(|subset| subset.count_ones() as usize == k).map(|subset| {
+		let mut exponents = [0; N];
+		for (i, e) in exponents.iter_mut().enumerate() {
+			*e = (subset >> i & 1) as u32;
+		}
+		(Monomial::new(exponents), R::one())
+	}).collect();
+
+	MultiPoly::new(terms)
+}
+
+/// Whether or not f is unchanged by every permutation of its variables. It is enough to check
+/// the swaps of neighbouring variables, since they generate every permutation.
+pub fn is_symmetric<R: Ring, const N: usize>(f: &MultiPoly<R, N>) -> bool {
+	(1..N).all(|i| {
+		let swapped = MultiPoly::new(f.terms().map(|(m, c)| {
+			let mut exponents = m.exponents;
+			exponents.swap(i - 1, i);
+			(Monomial::new(exponents), c.clone())
+		}).collect());
+		swapped == *f
+	})
+}
+
+/// The polynomial g with f(x_0, ..., x_(N-1)) = g(e_1, ..., e_N), where the variable y_i of g stands
+/// for e_(i+1), or None if f is not symmetric.
+///
+/// Repeatedly cancels the lexicographically leading term c x^a of f, whose exponents a_0 >= a_1 >=
+/// ... decrease when f is symmetric, with c e_1^(a_0 - a_1) e_2^(a_1 - a_2) ... e_N^(a_(N-1)),
+/// which has the same leading term. A term of weighted degree d in the y_i comes from terms of
+/// degree d in the x_i, but its ordinary degree is usually much smaller.
+pub fn to_elementary<R: CommutativeRing, const N: usize>(f: &MultiPoly<R, N>) -> Option<MultiPoly<R, N>> {
+	let elementary: Vec<MultiPoly<R, N>> = (1..=N).map(elementary_symmetric).collect();
+	let mut rest = f.clone();
+	let mut g = MultiPoly::zero();
+
+	while let Some((m, c)) = rest.leading_term(&Lex) {
+		let mut exponents = [0; N];
+		for (i, e) in exponents.iter_mut().enumerate() {
+			let next = if i + 1 < N { m.exponents[i + 1] } else { 0 };
+			if m.exponents[i] < next {
+				return None
+			}
+			*e = m.exponents[i] - next;
+		}
+
+		let product = (0..N).fold(MultiPoly::constant(c.clone()), |p, i| p * elementary[i].power(exponents[i] as i64));
+		rest -= product;
+		g += MultiPoly::monomial(c, Monomial::new(exponents));
+	}
+
+	Some(g)
+}
+
+/// The polynomial g(e_1, ..., e_N) in the x_i, undoing `to_elementary`
+pub fn from_elementary<R: CommutativeRing, const N: usize>(g: &MultiPoly<R, N>) -> MultiPoly<R, N> {
+	let elementary: Vec<MultiPoly<R, N>> = (1..=N).map(elementary_symmetric).collect();
+	g.terms().fold(MultiPoly::zero(), |sum, (m, c)| {
+		sum + (0..N).fold(MultiPoly::constant(c.clone()), |p, i| p * elementary[i].power(m.exponents[i] as i64))
+	})
+}
+
+/// The polynomial (t - x_0) ... (t - x_(N-1)) = t^N - e_1 t^(N-1) + ... + (-1)^N e_N, given the
+/// values e_1, ..., e_N, whose roots are the values of the variables in some order
+pub fn polynomial_with_elementary<R: Ring>(elementary: &[R]) -> Polynomial<R> {
+	let n = elementary.len();
+	let coefficients = (0..=n).map(|i| {
+		// The coefficient of t^i is (-1)^(n - i) e_(n - i)
+		if i == n {
+			R::one()
+		} else if (n - i).is_multiple_of(2) {
+			elementary[n - i - 1].clone()
+		} else {
+			-elementary[n - i - 1].clone()
+		}
+	}).collect();
+
+	Polynomial::new(coefficients)
+}
+
+// MARK: Symmetric Systems
+
+/// Rewrites every equation of a symmetric system in terms of the elementary symmetric
+/// polynomials, or None if one is not symmetric
+pub fn reduce_symmetric_system<R: CommutativeRing, const N: usize>(system: &[MultiPoly<R, N>]) -> Option<Vec<MultiPoly<R, N>>> {
+	system.iter().map(to_elementary).collect()
+}
+
+/// Every solution in ZM<Q>^N of a system of symmetric equations, or None if one is not symmetric.
+/// Q must be prime.
+///
+/// Solves the reduced system for the values of e_1, ..., e_N, then recovers the variables as the
+/// roots of `polynomial_with_elementary`, keeping the values of e_i for which it splits into linear
+/// factors over ZM<Q>, and returning each distinct ordering of its roots.
+pub fn solve_symmetric_zm<const Q: i64, const N: usize>(system: &[MultiPoly<ZM<Q>, N>]) -> Option<Vec<[ZM<Q>; N]>> {
+	let reduced = reduce_symmetric_system(system)?;
+	let mut solutions = Vec::new();
+
+	for elementary in solve_zm(&reduced) {
+		let p = polynomial_with_elementary(&elementary);
+		let mut roots: Vec<(ZM<Q>, usize)> = p.factor().into_iter()
+			.filter(|(f, _)| f.degree() == 1)
+			.map(|(f, multiplicity)| (-f.coefficient(0), multiplicity))
+			.collect();
+
+		if roots.iter().map(|(_, multiplicity)| multiplicity).sum::<usize>() == N {
+			orderings(&mut roots, &mut [ZM::zero(); N], 0, &mut solutions);
+		}
+	}
+
+	Some(solutions)
+}
+
+/// Pushes every distinct ordering of the multiset of values, with their multiplicities, that
+/// fills the point from position i on
+fn orderings<const Q: i64, const N: usize>(values: &mut [(ZM<Q>, usize)], point: &mut [ZM<Q>; N], i: usize, out: &mut Vec<[ZM<Q>; N]>) {
+	if i == N {
+		out.push(*point);
+		return
+	}
+
+	for k in 0..values.len() {
+		if values[k].1 > 0 {
+			values[k].1 -= 1;
+			point[i] = values[k].0;
+			orderings(values, point, i + 1, out);
+			values[k].1 += 1;
+		}
+	}
+}
@@ -0,0 +1,202 @@
+//
+// Toeplitz matrices stored by their first column and row, and solving Toeplitz and Hankel systems
+// in O(n^2) operations by Levinson recursion, instead of the O(n^3) of Gaussian elimination
+//
+
+use crate::algebra::*;
+use crate::complex::Complex;
+use crate::linear_operator::{AdjointOperator, LinearOperator};
+use crate::polynomial::convolve;
+
+// MARK: Symmetric Toeplitz Systems
+
+/// The solution x of T x = b, where T is the symmetric Toeplitz matrix with entry (i, j) equal to
+/// r_|i-j|, by the Levinson-Durbin recursion, or None if a leading principal submatrix of T is
+/// singular, which the recursion cannot pass through.
+///
+/// The recursion keeps the solution f of T_k f = e_1 for the leading k by k submatrix T_k, whose
+/// reverse solves T_k g = e_k by symmetry, and grows both it and x one row at a time.
+pub fn solve_symmetric_toeplitz<F: Field>(r: &[F], b: &[F]) -> Option<Vec<F>> {
+	if r.len() != b.len() {
+		panic!("The system has {} rows but {} right hand sides", r.len(), b.len())
+	}
+
+	if r.is_empty() {
+		return Some(vec![])
+	}
+
+	let r0_inverse = r[0].try_inverse()?;
+	let mut f = vec![r0_inverse.clone()];
+	let mut x = vec![b[0].mul_ref(&r0_inverse)];
+
+	for n in 1..r.len() {
+		// The last entry of T_(n+1) (f, 0), which is also the first entry of T_(n+1) (0, reverse f)
+		let error = (0..n).fold(F::zero(), |sum, i| sum.add_ref(&r[n - i].mul_ref(&f[i])));
+		let scale = (F::one() - error.mul_ref(&error)).try_inverse()?;
+
+		f.push(F::zero());
+		let reversed: Vec<F> = f.iter().rev().cloned().collect();
+		for (fi, gi) in f.iter_mut().zip(reversed.iter()) {
+			*fi = fi.sub_ref(&error.mul_ref(gi)).mul_ref(&scale);
+		}
+
+		// T_(n+1) (x, 0) differs from b only in its last entry, which the reverse of f corrects
+		let residual = (0..n).fold(b[n].clone(), |sum, i| sum.sub_ref(&r[n - i].mul_ref(&x[i])));
+		x.push(F::zero());
+		for (xi, gi) in x.iter_mut().zip(f.iter().rev()) {
+			xi.add_assign_ref(&residual.mul_ref(gi));
+		}
+	}
+
+	Some(x)
+}
+
+// MARK: General Toeplitz and Hankel Systems
+
+/// The solution x of T x = b, where T is the square Toeplitz matrix `Matrix::toeplitz(column, row)`,
+/// by Levinson recursion, or None if a leading principal submatrix of T is singular.
+///
+/// Without symmetry, the solutions of T_k f = e_1 and T_k g = e_k are kept separately.
+pub fn solve_toeplitz<F: Field>(column: &[F], row: &[F], b: &[F]) -> Option<Vec<F>> {
+	let n = column.len();
+	if row.len() != n || b.len() != n {
+		panic!("A square Toeplitz system needs a column, row, and right hand side of the same length")
+	}
+
+	if column.first() != row.first() {
+		panic!("The first column and row of a Toeplitz matrix must start with the same entry")
+	}
+
+	if n == 0 {
+		return Some(vec![])
+	}
+
+	let t0_inverse = column[0].try_inverse()?;
+	let mut f = vec![t0_inverse.clone()];
+	let mut g = vec![t0_inverse.clone()];
+	let mut x = vec![b[0].mul_ref(&t0_inverse)];
+
+	for k in 1..n {
+		// The last entry of T_(k+1) (f, 0) and the first entry of T_(k+1) (0, g)
+		let forward_error = (0..k).fold(F::zero(), |sum, i| sum.add_ref(&column[k - i].mul_ref(&f[i])));
+		let backward_error = (0..k).fold(F::zero(), |sum, i| sum.add_ref(&row[i + 1].mul_ref(&g[i])));
+		let scale = (F::one() - forward_error.mul_ref(&backward_error)).try_inverse()?;
+
+		f.push(F::zero());
+		g.insert(0, F::zero());
+		let (next_f, next_g) = f.iter().zip(g.iter()).map(|(fi, gi)| (
+			fi.sub_ref(&forward_error.mul_ref(gi)).mul_ref(&scale),
+			gi.sub_ref(&backward_error.mul_ref(fi)).mul_ref(&scale)
+		)).unzip();
+		f = next_f;
+		g = next_g;
+
+		let residual = (0..k).fold(b[k].clone(), |sum, i| sum.sub_ref(&column[k - i].mul_ref(&x[i])));
+		x.push(F::zero());
+		for (xi, gi) in x.iter_mut().zip(g.iter()) {
+			xi.add_assign_ref(&residual.mul_ref(gi));
+		}
+	}
+
+	Some(x)
+}
+
+/// The solution x of H x = b, where H is the square Hankel matrix `Matrix::hankel(h, n, n)`, or None
+/// if Levinson recursion fails on it.
+///
+/// Reversing the rows of H gives a Toeplitz matrix, so this solves that system with b reversed.
+pub fn solve_hankel<F: Field>(h: &[F], b: &[F]) -> Option<Vec<F>> {
+	let n = b.len();
+	if h.len() + 1 != 2 * n {
+		panic!("An {} by {} Hankel system needs {} entries, got {}", n, n, 2 * n - 1, h.len())
+	}
+
+	if n == 0 {
+		return Some(vec![])
+	}
+
+	// Row i of the reversed matrix is row n - 1 - i of H, so its entry (i, j) is h_(n-1-i+j)
+	let column: Vec<F> = (0..n).map(|i| h[n - 1 - i].clone()).collect();
+	let row: Vec<F> = (0..n).map(|j| h[n - 1 + j].clone()).collect();
+	let reversed: Vec<F> = b.iter().rev().cloned().collect();
+	solve_toeplitz(&column, &row, &reversed)
+}
+
+// MARK: Toeplitz Operators
+
+/// A Toeplitz matrix kept as just its first column and first row, which multiplies vectors by a
+/// single convolution instead of an entry by entry product
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toeplitz<R: Ring> {
+	column: Vec<R>,
+	row: Vec<R>
+}
+
+impl<R: Ring> Toeplitz<R> {
+
+	/// The Toeplitz matrix with the given first column and first row, as in `Matrix::toeplitz`
+	pub fn new(column: Vec<R>, row: Vec<R>) -> Toeplitz<R> {
+		if column.first() != row.first() {
+			panic!("The first column and row of a Toeplitz matrix must start with the same entry")
+		}
+
+		Toeplitz { column, row }
+	}
+
+	/// The first column
+	pub fn column(&self) -> &[R] {
+		&self.column
+	}
+
+	/// The first row
+	pub fn row(&self) -> &[R] {
+		&self.row
+	}
+
+	/// The transpose, which swaps the first column and row
+	pub fn transpose(&self) -> Toeplitz<R> {
+		Toeplitz { column: self.row.clone(), row: self.column.clone() }
+	}
+
+}
+
+impl<R: Ring> LinearOperator<R> for Toeplitz<R> {
+	fn input_dimension(&self) -> usize {
+		self.row.len()
+	}
+
+	fn output_dimension(&self) -> usize {
+		self.column.len()
+	}
+
+	/// Entry i of the image is the sum of t_(i-j) x_j, which is entry i + n - 1 of the convolution
+	/// of x with t_(-(n-1)), ..., t_(m-1), where t_k is column_k or row_(-k)
+	fn apply(&self, x: &[R]) -> Vec<R> {
+		let (m, n) = (self.column.len(), self.row.len());
+		if x.len() != n {
+			panic!("Cannot apply a {}x{} Toeplitz matrix to a vector of length {}", m, n, x.len())
+		}
+
+		if m == 0 || n == 0 {
+			return vec![R::zero(); m]
+		}
+
+		let diagonals: Vec<R> = self.row[1..].iter().rev().chain(self.column.iter()).cloned().collect();
+		convolve(&diagonals, x)[n - 1..n - 1 + m].to_vec()
+	}
+}
+
+impl<R: Ring> AdjointOperator<R> for Toeplitz<R> {
+	/// The transpose applied to y
+	default fn apply_adjoint(&self, y: &[R]) -> Vec<R> {
+		self.transpose().apply(y)
+	}
+}
+
+impl<F: Field> AdjointOperator<Complex<F>> for Toeplitz<Complex<F>> {
+	/// The conjugate transpose applied to y
+	fn apply_adjoint(&self, y: &[Complex<F>]) -> Vec<Complex<F>> {
+		let conjugated: Vec<Complex<F>> = y.iter().map(Complex::conj).collect();
+		self.transpose().apply(&conjugated).iter().map(Complex::conj).collect()
+	}
+}
@@ -0,0 +1,214 @@
+//
+// Fixed size vectors over a field, with the dot product and Euclidean norm
+//
+
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::algebra::*;
+use crate::complex::Complex;
+use crate::std_impls::ZM;
+
+// MARK: Vector
+
+/// A vector in F^N
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Vector<F: Field, const N: usize> {
+	pub entries: [F; N]
+}
+
+impl<F: Field, const N: usize> Vector<F, N> {
+
+	/// The vector with the given entries
+	pub fn new(entries: [F; N]) -> Vector<F, N> {
+		Vector { entries }
+	}
+
+	/// The vector whose ith entry is f(i)
+	pub fn from_fn<G: Fn(usize) -> F>(f: G) -> Vector<F, N> {
+		Vector { entries: std::array::from_fn(f) }
+	}
+
+	/// The zero vector
+	pub fn zero() -> Vector<F, N> {
+		Vector::from_fn(|_| F::zero())
+	}
+
+	/// The ith standard basis vector e_i
+	pub fn basis(i: usize) -> Vector<F, N> {
+		if i >= N {
+			panic!("F^{} has no basis vector e_{}", N, i)
+		}
+
+		Vector::from_fn(|j| if i == j { F::one() } else { F::zero() })
+	}
+
+	/// Whether or not every entry is zero
+	pub fn is_zero(&self) -> bool {
+		self.entries.iter().all(|x| x.is_zero())
+	}
+
+	/// Multiplies every entry by c
+	pub fn scale(&self, c: &F) -> Vector<F, N> {
+		Vector::from_fn(|i| self.entries[i].mul_ref(c))
+	}
+
+	/// The dot product, the sum of a_i b_i
+	pub fn dot(&self, other: &Vector<F, N>) -> F {
+		self.entries.iter().zip(other.entries.iter()).fold(F::zero(), |sum, (a, b)| sum.add_ref(&a.mul_ref(b)))
+	}
+
+	/// The dot product of this vector with itself. Over an ordered field this is the square of the
+	/// Euclidean norm, and needs no square root.
+	pub fn norm_squared(&self) -> F {
+		self.dot(self)
+	}
+
+}
+
+impl<F: Field, const N: usize> From<[F; N]> for Vector<F, N> {
+	fn from(entries: [F; N]) -> Self {
+		Vector::new(entries)
+	}
+}
+
+impl<F: Field, const N: usize> Index<usize> for Vector<F, N> {
+	type Output = F;
+
+	fn index(&self, i: usize) -> &F {
+		&self.entries[i]
+	}
+}
+
+impl<F: Field, const N: usize> IndexMut<usize> for Vector<F, N> {
+	fn index_mut(&mut self, i: usize) -> &mut F {
+		&mut self.entries[i]
+	}
+}
+
+// MARK: Arithmetic
+
+impl<F: Field, const N: usize> Add for Vector<F, N> {
+	type Output = Self;
+
+	fn add(mut self, rhs: Self) -> Self::Output {
+		self += rhs;
+		self
+	}
+}
+
+impl<F: Field, const N: usize> AddAssign for Vector<F, N> {
+	fn add_assign(&mut self, rhs: Self) {
+		for (a, b) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+			a.add_assign_ref(b);
+		}
+	}
+}
+
+impl<F: Field, const N: usize> Neg for Vector<F, N> {
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		Vector { entries: self.entries.map(|x| -x) }
+	}
+}
+
+impl<F: Field, const N: usize> Sub for Vector<F, N> {
+	type Output = Self;
+
+	fn sub(mut self, rhs: Self) -> Self::Output {
+		self -= rhs;
+		self
+	}
+}
+
+impl<F: Field, const N: usize> SubAssign for Vector<F, N> {
+	fn sub_assign(&mut self, rhs: Self) {
+		for (a, b) in self.entries.iter_mut().zip(rhs.entries.iter()) {
+			a.sub_assign_ref(b);
+		}
+	}
+}
+
+/// Scalar multiplication
+impl<F: Field, const N: usize> Mul<F> for Vector<F, N> {
+	type Output = Self;
+
+	fn mul(self, rhs: F) -> Self::Output {
+		self.scale(&rhs)
+	}
+}
+
+impl<F: Field, const N: usize> MulAssign<F> for Vector<F, N> {
+	fn mul_assign(&mut self, rhs: F) {
+		for a in self.entries.iter_mut() {
+			a.mul_assign_ref(&rhs);
+		}
+	}
+}
+
+/// Scalar multiplication on the left, c * v, for the commutative fields the orphan rules allow
+macro_rules! impl_left_scale_vector {
+	($($t:ty),*) => {$(
+		impl<const N: usize> Mul<Vector<$t, N>> for $t {
+			type Output = Vector<$t, N>;
+
+			fn mul(self, rhs: Vector<$t, N>) -> Self::Output {
+				rhs.scale(&self)
+			}
+		}
+	)*};
+}
+
+impl_left_scale_vector!(f32, f64);
+
+impl<const Q: i64, const N: usize> Mul<Vector<ZM<Q>, N>> for ZM<Q> {
+	type Output = Vector<ZM<Q>, N>;
+
+	fn mul(self, rhs: Vector<ZM<Q>, N>) -> Self::Output {
+		rhs.scale(&self)
+	}
+}
+
+impl<F: Field, const N: usize> Module<F> for Vector<F, N> {
+	fn scale(&self, r: &F) -> Self {
+		Vector::scale(self, r)
+	}
+}
+
+// MARK: Inner Products and Norms
+
+/// The dot product, which is the standard inner product over an ordered field
+impl<F: Field, const N: usize> InnerProductSpace<F> for Vector<F, N> {
+	default fn inner_product(&self, other: Self) -> F {
+		self.dot(&other)
+	}
+}
+
+/// The Hermitian inner product sum a_i conj(b_i), which is linear in the first argument
+impl<F: Field, const N: usize> InnerProductSpace<Complex<F>> for Vector<Complex<F>, N> {
+	fn inner_product(&self, other: Self) -> Complex<F> {
+		self.entries.inner_product(other.entries)
+	}
+}
+
+/// The squared Euclidean norm, which stays exact over ordered fields like the rationals, where the
+/// norm itself is usually irrational. Over f32 and f64 this is the Euclidean norm.
+impl<F: PoField, const N: usize> NormSpace for Vector<F, N> {
+	type NormType = F;
+
+	default fn norm(&self) -> F {
+		self.norm_squared()
+	}
+}
+
+impl<const N: usize> NormSpace for Vector<f64, N> {
+	fn norm(&self) -> f64 {
+		self.norm_squared().sqrt()
+	}
+}
+
+impl<const N: usize> NormSpace for Vector<f32, N> {
+	fn norm(&self) -> f32 {
+		self.norm_squared().sqrt()
+	}
+}
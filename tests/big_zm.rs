@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use algebra_kit::algebra::*;
+use algebra_kit::big_zm::{BigZM, P256, Secp256k1};
+
+type FP256 = BigZM<P256, 4>;
+type FSecp256k1 = BigZM<Secp256k1, 4>;
+
+fn p256(limbs: [u64; 4]) -> FP256 {
+	FP256::from_limbs(limbs).unwrap()
+}
+
+fn secp256k1(limbs: [u64; 4]) -> FSecp256k1 {
+	FSecp256k1::from_limbs(limbs).unwrap()
+}
+
+// The coordinates of each curve's standard base point, least significant limb first
+
+const P256_GX: [u64; 4] = [0xF4A13945D898C296, 0x77037D812DEB33A0, 0xF8BCE6E563A440F2, 0x6B17D1F2E12C4247];
+const P256_GY: [u64; 4] = [0xCBB6406837BF51F5, 0x2BCE33576B315ECE, 0x8EE7EB4A7C0F9E16, 0x4FE342E2FE1A7F9B];
+const P256_B: [u64; 4] = [0x3BCE3C3E27D2604B, 0x651D06B0CC53B0F6, 0xB3EBBD55769886BC, 0x5AC635D8AA3A93E7];
+
+const SECP256K1_GX: [u64; 4] = [0x59F2815B16F81798, 0x029BFCDB2DCE28D9, 0x55A06295CE870B07, 0x79BE667EF9DCBBAC];
+const SECP256K1_GY: [u64; 4] = [0x9C47D08FFB10D4B8, 0xFD17B448A6855419, 0x5DA4FBFC0E1108A8, 0x483ADA7726A3C465];
+
+#[test]
+fn base_points_are_on_their_curves() {
+	let (x, y) = (p256(P256_GX), p256(P256_GY));
+	assert_eq!(y * y, x * x * x - FP256::from_u64(3) * x + p256(P256_B));
+
+	let (x, y) = (secp256k1(SECP256K1_GX), secp256k1(SECP256K1_GY));
+	assert_eq!(y * y, x * x * x + FSecp256k1::from_u64(7));
+}
+
+#[test]
+fn montgomery_products() {
+	assert_eq!(
+		(p256(P256_GX) * p256(P256_GY)).to_limbs(),
+		[0xF713EBBBFACE98BE, 0xD183E554C6A08622, 0x33565064513A6B2B, 0x823CD15F6DD3C719]
+	);
+	assert_eq!(
+		(secp256k1(SECP256K1_GX) * secp256k1(SECP256K1_GY)).to_limbs(),
+		[0x56D7E1CE0179FD9B, 0x72324AA9DFD3428A, 0x9D166034CF3C1A5A, 0xFD3DC529C6EB60FB]
+	);
+}
+
+#[test]
+fn inverses() {
+	let x = p256(P256_GX);
+	assert_eq!(x.inverse().to_limbs(), [0x664C49E577F35238, 0x707D656273744B65, 0x24936933B69B16AB, 0xE060CBB088706D5D]);
+	assert_eq!(x * x.inverse(), FP256::one());
+
+	let x = secp256k1(SECP256K1_GX);
+	assert_eq!(x.inverse().to_limbs(), [0xE61D003687CA9EF6, 0x67B8E794ABFB076B, 0x870AAEB8AD77626A, 0x237AFDF1D2938D86]);
+	assert_eq!(x * x.inverse(), FSecp256k1::one());
+
+	assert_eq!(FP256::zero().try_inverse(), None);
+}
+
+#[test]
+fn reduction() {
+	// 2^256 and 2^512 reduce to the small differences between the moduli and powers of two
+	let two = FSecp256k1::from_u64(2);
+	assert_eq!(two.pow_limbs(&[256]).to_limbs(), [0x00000001000003D1, 0, 0, 0]);
+	assert_eq!(two.pow_limbs(&[512]).to_limbs(), [0x000007A2000E90A1, 1, 0, 0]);
+	assert_eq!(
+		FP256::from_u64(2).pow_limbs(&[256]).to_limbs(),
+		[0x0000000000000001, 0xFFFFFFFF00000000, 0xFFFFFFFFFFFFFFFF, 0x00000000FFFFFFFE]
+	);
+
+	// Only representatives below the modulus are accepted, and p - 1 is -1
+	assert_eq!(FP256::from_limbs(FP256::modulus()), None);
+	let mut p_minus_one = FP256::modulus();
+	p_minus_one[0] -= 1;
+	assert_eq!(p256(p_minus_one), -FP256::one());
+	assert_eq!(FSecp256k1::from_u64(u64::MAX).to_limbs(), [u64::MAX, 0, 0, 0]);
+}
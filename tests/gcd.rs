@@ -0,0 +1,170 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+
+use algebra_kit::algebra::*;
+
+/// The integers, but with division done by a single subtraction, so that the Euclidean
+/// algorithm on (n, 1) takes about n steps. This stands in for long division chains like
+/// those of high degree polynomials.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Subtractive(i64);
+
+impl Add for Subtractive {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self { Subtractive(self.0 + rhs.0) }
+}
+
+impl AddAssign for Subtractive {
+	fn add_assign(&mut self, rhs: Self) { self.0 += rhs.0 }
+}
+
+impl Neg for Subtractive {
+	type Output = Self;
+	fn neg(self) -> Self { Subtractive(-self.0) }
+}
+
+impl Sub for Subtractive {
+	type Output = Self;
+	fn sub(self, rhs: Self) -> Self { Subtractive(self.0 - rhs.0) }
+}
+
+impl SubAssign for Subtractive {
+	fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0 }
+}
+
+impl Mul for Subtractive {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self { Subtractive(self.0 * rhs.0) }
+}
+
+impl MulAssign for Subtractive {
+	fn mul_assign(&mut self, rhs: Self) { self.0 *= rhs.0 }
+}
+
+impl Div for Subtractive {
+	type Output = Self;
+	fn div(self, rhs: Self) -> Self { self.quotient_and_remainder(&rhs).0 }
+}
+
+impl DivAssign for Subtractive {
+	fn div_assign(&mut self, rhs: Self) { *self = *self / rhs }
+}
+
+impl Rem for Subtractive {
+	type Output = Self;
+	fn rem(self, rhs: Self) -> Self { self.quotient_and_remainder(&rhs).1 }
+}
+
+impl RemAssign for Subtractive {
+	fn rem_assign(&mut self, rhs: Self) { *self = *self % rhs }
+}
+
+impl Semiring for Subtractive {
+	fn one() -> Self { Subtractive(1) }
+	fn zero() -> Self { Subtractive(0) }
+	fn is_zero(&self) -> bool { self.0 == 0 }
+}
+
+impl Ring for Subtractive {
+	fn power(&self, n: i64) -> Self { Subtractive(self.0.pow(n as u32)) }
+}
+
+impl CommutativeRing for Subtractive { }
+
+impl IntegralDomain for Subtractive {
+	fn divide_exact(&self, divisor: &Self) -> Option<Self> {
+		(divisor.0 != 0 && self.0 % divisor.0 == 0).then(|| Subtractive(self.0 / divisor.0))
+	}
+}
+
+impl UniqueFactorizationDomain for Subtractive {
+	fn gcd(&self, other: &Self) -> Self { euclidean_gcd(self, other) }
+}
+
+impl PrincipalIdealDomain for Subtractive { }
+
+impl EuclideanDomain for Subtractive {
+	type SizeType = i64;
+
+	fn euc_size(&self) -> i64 {
+		self.0.abs()
+	}
+
+	fn quotient_and_remainder(&self, divisor: &Self) -> (Self, Self) {
+		if self.0 >= divisor.0 {
+			(Subtractive(1), Subtractive(self.0 - divisor.0))
+		} else {
+			(Subtractive(0), *self)
+		}
+	}
+}
+
+const DEPTH: i64 = 1_000_000;
+
+#[test]
+fn gcd_survives_deep_division_chains() {
+	assert_eq!(gcd(&Subtractive(DEPTH), &Subtractive(1)), Subtractive(1));
+	assert_eq!(gcd(&Subtractive(1), &Subtractive(DEPTH)), Subtractive(1));
+}
+
+#[test]
+fn ext_gcd_survives_deep_division_chains() {
+	let (a, b) = (Subtractive(1), Subtractive(DEPTH));
+	let (g, x, y) = ext_gcd(&a, &b);
+
+	assert_eq!(g, Subtractive(1));
+	assert_eq!(a * x + b * y, g);
+}
+
+#[test]
+fn ext_gcd_on_consecutive_fibonacci_numbers() {
+	let (mut a, mut b) = (1i64, 1i64);
+	while let Some(c) = a.checked_add(b) {
+		(a, b) = (b, c);
+	}
+
+	let (g, x, y) = ext_gcd(&a, &b);
+	assert_eq!(g, 1);
+	assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, 1);
+	assert_eq!(gcd(&a, &b), 1);
+}
+
+#[test]
+fn ext_gcd_bezout_identity() {
+	for a in -50i64..50 {
+		for b in -50i64..50 {
+			let (g, x, y) = ext_gcd(&a, &b);
+			assert_eq!(a * x + b * y, g);
+			assert_eq!(g.abs(), gcd(&a, &b).abs());
+		}
+	}
+}
+
+#[test]
+fn in_place_gcd_matches_gcd() {
+	let (mut a, mut b) = (Subtractive(DEPTH), Subtractive(1));
+	euclidean_gcd_assign(&mut a, &mut b);
+	assert_eq!((a, b), (Subtractive(1), Subtractive(0)));
+
+	for x in -30i64..30 {
+		for y in -30i64..30 {
+			let mut g = x;
+			gcd_assign(&mut g, &y);
+			assert_eq!(g, gcd(&x, &y));
+			assert_eq!(euclidean_gcd(&x, &y).abs(), g);
+		}
+	}
+}
+
+#[test]
+fn gcd_of_extreme_integers() {
+	assert_eq!(gcd(&i64::MIN, &3), 1);
+	assert_eq!(gcd(&i64::MIN, &(1 << 40)), 1 << 40);
+	assert_eq!(gcd(&i64::MAX, &0), i64::MAX);
+	assert_eq!(gcd(&i8::MIN, &-96), 32);
+}
+
+#[test]
+#[should_panic(expected = "does not fit")]
+fn gcd_too_large_to_represent_panics() {
+	gcd(&i64::MIN, &0);
+}
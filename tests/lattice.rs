@@ -0,0 +1,51 @@
+#![cfg(feature = "bigint")]
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use algebra_kit::lattice::{is_lll_reduced, lll};
+use algebra_kit::algebra::*;
+use algebra_kit::matrix::Matrix;
+
+fn big(rows: &[&[i64]]) -> Matrix<BigInt> {
+	Matrix::from_rows(rows.iter().map(|row| row.iter().map(|&x| BigInt::from(x)).collect()).collect())
+}
+
+fn three_quarters() -> BigRational {
+	BigRational::new(3.into(), 4.into())
+}
+
+/// Whether or not two bases span the same lattice, meaning their Hermite normal forms agree
+fn same_lattice(a: &Matrix<BigInt>, b: &Matrix<BigInt>) -> bool {
+	a.hermite_normal_form().1 == b.hermite_normal_form().1
+}
+
+#[test]
+fn lll_of_known_basis() {
+	let basis = big(&[&[1, 1, 1], &[-1, 0, 2], &[3, 5, 6]]);
+	assert!(!is_lll_reduced(&basis, &three_quarters()));
+
+	let reduced = lll(&basis);
+	assert_eq!(reduced, big(&[&[0, 1, 0], &[1, 0, 1], &[-1, 0, 2]]));
+	assert!(is_lll_reduced(&reduced, &three_quarters()));
+	assert!(same_lattice(&basis, &reduced));
+}
+
+#[test]
+fn lll_of_random_bases() {
+	let mut rng = StdRng::seed_from_u64(554);
+	for _ in 0..30 {
+		let n = rng.gen_range(2..6);
+		let noise: Vec<i64> = (0..n * n).map(|_| rng.gen_range(-50..=50)).collect();
+		let basis = Matrix::from_fn(n, n, |i, j| BigInt::from(if i == j { 1000 } else { 0 } + noise[i * n + j]));
+		if basis.determinant().is_zero() {
+			continue
+		}
+
+		let reduced = lll(&basis);
+		assert!(is_lll_reduced(&reduced, &three_quarters()));
+		assert!(same_lattice(&basis, &reduced));
+	}
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use algebra_kit::matrix::Matrix;
+
+fn random_matrix(rows: usize, cols: usize, rng: &mut StdRng) -> Matrix<i64> {
+	let entries: Vec<i64> = (0..rows * cols).map(|_| rng.gen_range(-9..=9)).collect();
+	Matrix::new(rows, cols, entries)
+}
+
+fn is_unimodular(m: &Matrix<i64>) -> bool {
+	m.determinant().abs() == 1
+}
+
+fn check_smith(m: &Matrix<i64>) -> Vec<i64> {
+	let (u, d, v) = m.smith_normal_form();
+	assert!(is_unimodular(&u) && is_unimodular(&v));
+	assert_eq!(u.mul_ref(m).mul_ref(&v), d);
+
+	for i in 0..d.rows() {
+		for j in 0..d.cols() {
+			assert!(i == j || d[(i, j)] == 0, "D is not diagonal: {:?}", d);
+		}
+	}
+
+	// Positive entries first, each dividing the next
+	let diagonal: Vec<i64> = (0..d.rows().min(d.cols())).map(|i| d[(i, i)]).collect();
+	let r = diagonal.iter().take_while(|&&x| x != 0).count();
+	assert!(diagonal[r..].iter().all(|&x| x == 0));
+	assert!(diagonal[..r].iter().all(|&x| x > 0));
+	assert!(diagonal[..r].windows(2).all(|w| w[1] % w[0] == 0));
+	diagonal
+}
+
+fn check_hermite(m: &Matrix<i64>) {
+	let (u, h) = m.hermite_normal_form();
+	assert!(is_unimodular(&u));
+	assert_eq!(u.mul_ref(m), h);
+
+	// Row echelon form with positive pivots and the entries above each pivot reduced modulo it
+	let mut last_pivot = None;
+	for i in 0..h.rows() {
+		let Some(p) = (0..h.cols()).find(|&j| h[(i, j)] != 0) else {
+			assert!((i..h.rows()).all(|k| (0..h.cols()).all(|j| h[(k, j)] == 0)), "zero rows must come last");
+			break
+		};
+		assert!(last_pivot.is_none_or(|q| p > q));
+		assert!(h[(i, p)] > 0);
+		assert!((0..i).all(|k| (0..h[(i, p)]).contains(&h[(k, p)])));
+		last_pivot = Some(p);
+	}
+}
+
+#[test]
+fn smith_normal_form_of_known_matrix() {
+	let m = Matrix::from_rows(vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]]);
+	assert_eq!(check_smith(&m), vec![2, 6, 12]);
+}
+
+#[test]
+fn hermite_normal_form_of_known_matrix() {
+	let m = Matrix::from_rows(vec![vec![3, 3, 1, 4], vec![0, 1, 0, 0], vec![0, 0, 19, 16], vec![0, 0, 0, 3]]);
+	check_hermite(&m);
+	let (_, h) = m.hermite_normal_form();
+	assert_eq!(h, Matrix::from_rows(vec![vec![3, 0, 1, 1], vec![0, 1, 0, 0], vec![0, 0, 19, 1], vec![0, 0, 0, 3]]));
+}
+
+#[test]
+fn normal_forms_of_random_matrices() {
+	let mut rng = StdRng::seed_from_u64(556);
+	for _ in 0..100 {
+		let (rows, cols) = (rng.gen_range(1..5), rng.gen_range(1..5));
+		let m = random_matrix(rows, cols, &mut rng);
+		check_smith(&m);
+		check_hermite(&m);
+	}
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "std")]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use algebra_kit::algebra::*;
+use algebra_kit::polynomial::{half_gcd, half_gcd_gcd, Polynomial};
+use algebra_kit::std_impls::ZM;
+
+type P = Polynomial<ZM<101>>;
+
+fn poly(coefficients: &[i64]) -> P {
+	Polynomial::new(coefficients.iter().map(|&c| ZM::from_int(c)).collect())
+}
+
+/// The product of the factors raised to their multiplicities
+fn expand(factors: &[(P, usize)]) -> P {
+	factors.iter().fold(P::one(), |product, (f, m)| product * f.power(*m as i64))
+}
+
+fn check_factorization(f: &P) -> Vec<(P, usize)> {
+	let factors = f.factor_with_rng(&mut StdRng::seed_from_u64(542));
+	assert_eq!(expand(&factors), f.monic());
+	for (g, _) in &factors {
+		assert!(g.is_monic() && g.is_irreducible(), "{:?} is not a monic irreducible factor", g);
+	}
+	factors
+}
+
+#[test]
+fn factor_x12_plus_1() {
+	let mut coefficients = vec![0; 13];
+	coefficients[0] = 1;
+	coefficients[12] = 1;
+	let factors = check_factorization(&poly(&coefficients));
+	assert!(factors.iter().all(|(_, m)| *m == 1));
+}
+
+#[test]
+fn factor_with_repeated_factors() {
+	// x^2 (x + 1)^3 (x^2 + 2), where -2 is not a square modulo 101
+	let x = poly(&[0, 1]);
+	let f = x.power(2) * poly(&[1, 1]).power(3) * poly(&[2, 0, 1]);
+	let mut factors = check_factorization(&f.scale(&ZM::from_int(7)));
+	factors.sort_by_key(|(g, _)| (g.degree(), g.coefficient(0).val));
+	assert_eq!(factors, vec![(x, 2), (poly(&[1, 1]), 3), (poly(&[2, 0, 1]), 1)]);
+}
+
+#[test]
+fn irreducibility() {
+	assert!(poly(&[2, 0, 1]).is_irreducible());
+	assert!(!poly(&[1, 0, 1]).is_irreducible());
+	assert!(!poly(&[3]).is_irreducible());
+	assert!(poly(&[3, 1]).is_irreducible());
+}
+
+#[test]
+fn roots_mod_p() {
+	// (x - 3)(x - 5)^2 (x^2 + 2)
+	let f = poly(&[-3, 1]) * poly(&[-5, 1]).power(2) * poly(&[2, 0, 1]);
+	let roots = f.roots_mod_p_with_rng(&mut StdRng::seed_from_u64(542));
+	assert_eq!(roots, vec![ZM::from_int(3), ZM::from_int(5)]);
+
+	// x^4 - 1 splits modulo 101 since 4 divides 100, and x^4 + 1 has no roots since 8 does not
+	let roots = poly(&[-1, 0, 0, 0, 1]).roots_mod_p_with_rng(&mut StdRng::seed_from_u64(542));
+	assert_eq!(roots, [1, 10, 91, 100].map(ZM::from_int));
+	assert!(poly(&[1, 0, 0, 0, 1]).roots_mod_p_with_rng(&mut StdRng::seed_from_u64(542)).is_empty());
+}
+
+type Big = Polynomial<ZM<1000003>>;
+
+#[test]
+fn half_gcd_gcd_matches_euclid() {
+	let mut rng = StdRng::seed_from_u64(518);
+	for _ in 0..50 {
+		// A common factor, so the gcd is usually c itself, at degrees past the half-GCD threshold
+		let c = Big::random_with_rng(rng.gen_range(0..40), &mut rng);
+		let a = Big::random_with_rng(rng.gen_range(30..80), &mut rng) * c.clone();
+		let b = Big::random_with_rng(rng.gen_range(30..80), &mut rng) * c;
+		assert_eq!(half_gcd_gcd(&a, &b), gcd(&a, &b).monic());
+	}
+}
+
+#[test]
+fn half_gcd_halves_the_degree() {
+	let mut rng = StdRng::seed_from_u64(518);
+	for _ in 0..50 {
+		let n = rng.gen_range(2..150);
+		let a = Big::random_with_rng(n, &mut rng);
+		let b = Big::random_with_rng(rng.gen_range(0..n), &mut rng);
+
+		let m = half_gcd(&a, &b);
+		let c = m[0][0].clone() * a.clone() + m[0][1].clone() * b.clone();
+		let d = m[1][0].clone() * a.clone() + m[1][1].clone() * b.clone();
+
+		// deg c >= ceil(deg a / 2) > deg d, with d possibly zero
+		assert!(c.degree() >= n.div_ceil(2));
+		assert!(d.is_zero() || d.degree() < n.div_ceil(2));
+
+		// The determinant is a unit, so the gcd is unchanged
+		let determinant = m[0][0].clone() * m[1][1].clone() - m[0][1].clone() * m[1][0].clone();
+		assert!(determinant.degree() == 0 && !determinant.is_zero());
+		assert_eq!(gcd(&c, &d).monic(), gcd(&a, &b).monic());
+	}
+}